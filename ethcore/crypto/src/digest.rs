@@ -54,6 +54,7 @@ pub fn ripemd160(data: &[u8]) -> Digest<Ripemd160> {
 	hasher.finish()
 }
 
+pub enum Sha1 {}
 pub enum Sha256 {}
 pub enum Sha512 {}
 pub enum Ripemd160 {}
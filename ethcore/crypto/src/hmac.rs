@@ -15,7 +15,7 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 use digest;
-use ring::digest::{SHA256, SHA512};
+use ring::digest::{SHA1, SHA256, SHA512};
 use ring::hmac::{self, SigningContext};
 use std::marker::PhantomData;
 use std::ops::Deref;
@@ -33,6 +33,13 @@ impl<T> Deref for Signature<T> {
 /// HMAC signing key.
 pub struct SigKey<T>(hmac::SigningKey, PhantomData<T>);
 
+impl SigKey<digest::Sha1> {
+	/// HMAC-SHA1 key, only meant for legacy protocols such as TOTP (RFC 6238).
+	pub fn sha1(key: &[u8]) -> SigKey<digest::Sha1> {
+		SigKey(hmac::SigningKey::new(&SHA1, key), PhantomData)
+	}
+}
+
 impl SigKey<digest::Sha256> {
 	pub fn sha256(key: &[u8]) -> SigKey<digest::Sha256> {
 		SigKey(hmac::SigningKey::new(&SHA256, key), PhantomData)
@@ -17,113 +17,142 @@
 //! benchmarking for EVM
 //! should be started with:
 //! ```bash
-//! multirust run nightly cargo bench
+//! rustup run nightly cargo bench --features benches
 //! ```
+//!
+//! Every benchmark runs twice: with basic block execution and with
+//! per-instruction gas and stack checks (forced by enabling tracing).
 
 extern crate test;
 
+use std::str::FromStr;
+use std::sync::Arc;
 use self::test::{Bencher, black_box};
 
-use bigint::prelude::U256;
-use bigint::hash::H256;
-use util::*;
+use ethereum_types::{U256, Address};
+use rustc_hex::FromHex;
 use vm::ActionParams;
-use evm::{self, Factory, VMType};
-use evm::tests::FakeExt;
+use vm::tests::FakeExt;
+use factory::Factory;
+use vmtype::VMType;
+use vm::GasLeft;
 
 #[bench]
 fn simple_loop_log0_usize(b: &mut Bencher) {
-	simple_loop_log0(U256::from(::std::usize::MAX), b)
+	simple_loop_log0(U256::from(::std::usize::MAX), false, b)
 }
 
 #[bench]
 fn simple_loop_log0_u256(b: &mut Bencher) {
-	simple_loop_log0(!U256::zero(), b)
+	simple_loop_log0(!U256::zero(), false, b)
 }
 
-fn simple_loop_log0(gas: U256, b: &mut Bencher) {
-	let mut vm = Factory::new(VMType::Interpreter).create(gas);
-	let mut ext = FakeExt::new();
+#[bench]
+fn simple_loop_log0_usize_per_instruction(b: &mut Bencher) {
+	simple_loop_log0(U256::from(::std::usize::MAX), true, b)
+}
 
-	let address = Address::from_str("0f572e5295c57f15886f9b263e2f6d2d6c7b5ec6").unwrap();
+fn simple_loop_log0(gas: U256, per_instruction: bool, b: &mut Bencher) {
 	let code = black_box(
 		"62ffffff5b600190036000600fa0600357".from_hex().unwrap()
 	);
 
-	b.iter(|| {
-		let mut params = ActionParams::default();
-		params.address = address.clone();
-		params.gas = gas;
-		params.code = Some(code.clone());
-
-		result(vm.exec(params, &mut ext))
-	});
+	run(&code, gas, per_instruction, b)
 }
 
 #[bench]
 fn mem_gas_calculation_same_usize(b: &mut Bencher) {
-	mem_gas_calculation_same(U256::from(::std::usize::MAX), b)
+	mem_gas_calculation_same(U256::from(::std::usize::MAX), false, b)
 }
 
 #[bench]
 fn mem_gas_calculation_same_u256(b: &mut Bencher) {
-	mem_gas_calculation_same(!U256::zero(), b)
+	mem_gas_calculation_same(!U256::zero(), false, b)
 }
 
-fn mem_gas_calculation_same(gas: U256, b: &mut Bencher) {
-	let mut vm = Factory::new(VMType::Interpreter).create(gas);
-	let mut ext = FakeExt::new();
-
-	let address = Address::from_str("0f572e5295c57f15886f9b263e2f6d2d6c7b5ec6").unwrap();
-
-	b.iter(|| {
-		let code = black_box(
-			"6110006001556001546000555b610fff805560016000540380600055600c57".from_hex().unwrap()
-		);
+#[bench]
+fn mem_gas_calculation_same_usize_per_instruction(b: &mut Bencher) {
+	mem_gas_calculation_same(U256::from(::std::usize::MAX), true, b)
+}
 
-		let mut params = ActionParams::default();
-		params.address = address.clone();
-		params.gas = gas;
-		params.code = Some(code.clone());
+fn mem_gas_calculation_same(gas: U256, per_instruction: bool, b: &mut Bencher) {
+	let code = black_box(
+		"6110006001556001546000555b610fff805560016000540380600055600c57".from_hex().unwrap()
+	);
 
-		result(vm.exec(params, &mut ext))
-	});
+	run(&code, gas, per_instruction, b)
 }
 
 #[bench]
 fn mem_gas_calculation_increasing_usize(b: &mut Bencher) {
-	mem_gas_calculation_increasing(U256::from(::std::usize::MAX), b)
+	mem_gas_calculation_increasing(U256::from(::std::usize::MAX), false, b)
 }
 
 #[bench]
 fn mem_gas_calculation_increasing_u256(b: &mut Bencher) {
-	mem_gas_calculation_increasing(!U256::zero(), b)
+	mem_gas_calculation_increasing(!U256::zero(), false, b)
+}
+
+#[bench]
+fn mem_gas_calculation_increasing_usize_per_instruction(b: &mut Bencher) {
+	mem_gas_calculation_increasing(U256::from(::std::usize::MAX), true, b)
+}
+
+fn mem_gas_calculation_increasing(gas: U256, per_instruction: bool, b: &mut Bencher) {
+	let code = black_box(
+		"6110006001556001546000555b610fff60005401805560016000540380600055600c57".from_hex().unwrap()
+	);
+
+	run(&code, gas, per_instruction, b)
 }
 
-fn mem_gas_calculation_increasing(gas: U256, b: &mut Bencher) {
-	let mut vm = Factory::new(VMType::Interpreter).create(gas);
-	let mut ext = FakeExt::new();
+#[bench]
+fn arithmetic_loop_usize(b: &mut Bencher) {
+	arithmetic_loop(U256::from(::std::usize::MAX), false, b)
+}
+
+#[bench]
+fn arithmetic_loop_u256(b: &mut Bencher) {
+	arithmetic_loop(!U256::zero(), false, b)
+}
+
+#[bench]
+fn arithmetic_loop_usize_per_instruction(b: &mut Bencher) {
+	arithmetic_loop(U256::from(::std::usize::MAX), true, b)
+}
+
+/// Long straight-line blocks of stack arithmetic, executed in a loop of 0xffff iterations.
+fn arithmetic_loop(gas: U256, per_instruction: bool, b: &mut Bencher) {
+	let code = black_box(
+		"61ffff5b6001600201600302600403806005019003506001900380600357".from_hex().unwrap()
+	);
+
+	run(&code, gas, per_instruction, b)
+}
 
+fn run(code: &[u8], gas: U256, per_instruction: bool, b: &mut Bencher) {
+	let factory = Factory::new(VMType::Interpreter, 1024 * 32);
 	let address = Address::from_str("0f572e5295c57f15886f9b263e2f6d2d6c7b5ec6").unwrap();
+	let code = Arc::new(code.to_vec());
 
 	b.iter(|| {
-		let code = black_box(
-			"6110006001556001546000555b610fff60005401805560016000540380600055600c57".from_hex().unwrap()
-		);
+		let mut ext = FakeExt::new();
+		ext.tracing = per_instruction;
 
 		let mut params = ActionParams::default();
 		params.address = address.clone();
 		params.gas = gas;
 		params.code = Some(code.clone());
 
+		let mut vm = factory.create(&gas);
 		result(vm.exec(params, &mut ext))
 	});
 }
 
-fn result(r: evm::Result<evm::GasLeft>) -> U256 {
+fn result(r: ::vm::Result<GasLeft>) -> U256 {
 	match r {
-		Ok(evm::GasLeft::Known(v)) => v,
-		Ok(evm::GasLeft::NeedsReturn(v, _)) => v,
+		Ok(GasLeft::Known(v)) => v,
+		Ok(GasLeft::NeedsReturn { gas_left: v, .. }) => v,
 		_ => U256::zero(),
 	}
 }
@@ -43,7 +43,7 @@ impl Factory {
 	}
 
 	/// Create new instance of specific `VMType` factory, with a size in bytes
-	/// for caching jump destinations.
+	/// for caching code analysis.
 	pub fn new(evm: VMType, cache_size: usize) -> Self {
 		Factory {
 			evm: evm,
@@ -0,0 +1,250 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Static bytecode analysis: jump destinations and basic blocks.
+
+use std::{cmp, mem};
+use std::sync::Arc;
+use bit_set::BitSet;
+use heapsize::HeapSizeOf;
+use instructions::{self, Instruction, InstructionInfo, GasPriceTier};
+use vm::Schedule;
+
+/// Number of gas tiers which have a fixed cost in `Schedule::tier_step_gas`.
+const STATIC_TIERS: usize = 7;
+
+/// Straight-line run of instructions with statically known gas cost and stack effect.
+///
+/// Blocks never contain instructions which alter control flow, touch memory,
+/// read the remaining gas or have a dynamic cost, so charging the whole block
+/// upfront is indistinguishable from charging it instruction by instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicBlock {
+	/// Position of the first instruction.
+	pub start: usize,
+	/// Position right after the last instruction.
+	pub end: usize,
+	/// Number of instructions in each fixed gas tier.
+	pub tier_counts: [u32; STATIC_TIERS],
+	/// Gas not covered by tiers (`JUMPDEST`).
+	pub extra_gas: usize,
+	/// Minimal stack size required when entering the block.
+	pub stack_required: usize,
+	/// Maximal stack growth within the block, relative to the entry size.
+	pub stack_max_growth: usize,
+}
+
+impl BasicBlock {
+	fn new(start: usize) -> Self {
+		BasicBlock {
+			start: start,
+			end: start,
+			tier_counts: [0; STATIC_TIERS],
+			extra_gas: 0,
+			stack_required: 0,
+			stack_max_growth: 0,
+		}
+	}
+
+	/// Total gas cost of the block under given schedule.
+	pub fn gas_cost(&self, schedule: &Schedule) -> usize {
+		self.tier_counts.iter()
+			.zip(schedule.tier_step_gas.iter())
+			.fold(self.extra_gas, |acc, (count, gas)| acc + *count as usize * *gas)
+	}
+}
+
+/// Result of analysing contract code.
+pub struct CodeAnalysis {
+	/// Valid jump destinations.
+	pub jump_destinations: Arc<BitSet>,
+	/// Start positions of basic blocks.
+	block_starts: BitSet,
+	/// Basic blocks ordered by start position.
+	blocks: Vec<BasicBlock>,
+}
+
+impl CodeAnalysis {
+	/// Analyse given code.
+	pub fn new(code: &[u8]) -> Self {
+		let infos = &*instructions::INSTRUCTIONS;
+		let mut jump_dests = BitSet::with_capacity(code.len());
+		let mut block_starts = BitSet::with_capacity(code.len());
+		let mut blocks = Vec::new();
+
+		let mut current: Option<(BasicBlock, isize)> = None;
+		let mut position = 0;
+
+		while position < code.len() {
+			let instruction = code[position];
+			let info = &infos[instruction as usize];
+
+			if instruction == instructions::JUMPDEST {
+				jump_dests.insert(position);
+				if let Some((block, _)) = current.take() {
+					blocks.push(block);
+				}
+				let mut block = BasicBlock::new(position);
+				block.extra_gas = 1;
+				block.end = position + 1;
+				current = Some((block, 0));
+			} else if is_static(instruction, info) {
+				let (mut block, mut height) = current.take().unwrap_or_else(|| (BasicBlock::new(position), 0));
+				block.tier_counts[instructions::get_tier_idx(info.tier)] += 1;
+				block.stack_required = cmp::max(block.stack_required as isize, info.args as isize - height) as usize;
+				height += info.ret as isize - info.args as isize;
+				block.stack_max_growth = cmp::max(block.stack_max_growth as isize, height) as usize;
+				if instructions::is_push(instruction) {
+					position += instructions::get_push_bytes(instruction);
+				}
+				block.end = cmp::min(position + 1, code.len());
+				current = Some((block, height));
+			} else if let Some((block, _)) = current.take() {
+				blocks.push(block);
+			}
+
+			position += 1;
+		}
+
+		if let Some((block, _)) = current.take() {
+			blocks.push(block);
+		}
+
+		for block in &blocks {
+			block_starts.insert(block.start);
+		}
+
+		jump_dests.shrink_to_fit();
+		block_starts.shrink_to_fit();
+		blocks.shrink_to_fit();
+
+		CodeAnalysis {
+			jump_destinations: Arc::new(jump_dests),
+			block_starts: block_starts,
+			blocks: blocks,
+		}
+	}
+
+	/// Get basic block starting at given position.
+	pub fn block_at(&self, position: usize) -> Option<&BasicBlock> {
+		if !self.block_starts.contains(position) {
+			return None;
+		}
+
+		self.blocks.binary_search_by_key(&position, |block| block.start)
+			.ok()
+			.map(|index| &self.blocks[index])
+	}
+}
+
+impl HeapSizeOf for CodeAnalysis {
+	fn heap_size_of_children(&self) -> usize {
+		// dealing in bits here
+		self.jump_destinations.capacity() * 8
+			+ self.block_starts.capacity() * 8
+			+ self.blocks.capacity() * mem::size_of::<BasicBlock>()
+	}
+}
+
+/// Returns true if instruction has a fixed, schedule-independent stack effect and tier cost
+/// and can be executed without checking anything but the stack and gas.
+fn is_static(instruction: Instruction, info: &InstructionInfo) -> bool {
+	match info.tier {
+		GasPriceTier::Special | GasPriceTier::Invalid => return false,
+		_ => {},
+	}
+
+	match instruction {
+		// control flow
+		instructions::STOP | instructions::JUMP | instructions::JUMPI |
+		instructions::RETURN | instructions::REVERT |
+		// depends on remaining gas
+		instructions::GAS |
+		// gas not taken from the tier
+		instructions::BLOCKHASH |
+		// memory expansion
+		instructions::MLOAD | instructions::MSTORE | instructions::MSTORE8 |
+		instructions::CALLDATACOPY | instructions::CODECOPY | instructions::RETURNDATACOPY |
+		// availability depends on schedule
+		instructions::RETURNDATASIZE | instructions::SHL | instructions::SHR | instructions::SAR => false,
+		_ => true,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rustc_hex::FromHex;
+	use vm::Schedule;
+	use super::*;
+
+	#[test]
+	fn should_find_jump_destinations() {
+		// given
+		let code = "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff5b01600055".from_hex().unwrap();
+
+		// when
+		let analysis = CodeAnalysis::new(&code);
+
+		// then
+		assert!(analysis.jump_destinations.contains(66));
+	}
+
+	#[test]
+	fn should_split_code_into_basic_blocks() {
+		// given
+		// PUSH1 1 PUSH1 2 ADD JUMPDEST DUP1 PUSH1 0 SSTORE POP
+		let code = "60016002015b8060005550".from_hex().unwrap();
+
+		// when
+		let analysis = CodeAnalysis::new(&code);
+
+		// then
+		let first = analysis.block_at(0).unwrap();
+		assert_eq!(first.end, 5);
+		assert_eq!(first.stack_required, 0);
+		assert_eq!(first.stack_max_growth, 2);
+		assert_eq!(first.gas_cost(&Schedule::default()), 9);
+
+		let second = analysis.block_at(5).unwrap();
+		assert_eq!(second.end, 9);
+		assert_eq!(second.stack_required, 1);
+		assert_eq!(second.stack_max_growth, 2);
+		assert_eq!(second.gas_cost(&Schedule::default()), 7);
+
+		let third = analysis.block_at(10).unwrap();
+		assert_eq!(third.end, 11);
+		assert_eq!(third.stack_required, 1);
+		assert_eq!(third.stack_max_growth, 0);
+
+		assert!(analysis.block_at(9).is_none());
+		assert!(analysis.block_at(1).is_none());
+	}
+
+	#[test]
+	fn should_not_include_gas_dependent_instructions() {
+		// given
+		// PUSH1 1 GAS PUSH1 2
+		let code = "60015a6002".from_hex().unwrap();
+
+		// when
+		let analysis = CodeAnalysis::new(&code);
+
+		// then
+		assert_eq!(analysis.block_at(0).unwrap().end, 2);
+		assert!(analysis.block_at(2).is_none());
+		assert_eq!(analysis.block_at(3).unwrap().end, 5);
+	}
+}
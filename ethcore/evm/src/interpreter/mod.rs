@@ -22,6 +22,7 @@ mod gasometer;
 mod stack;
 mod memory;
 mod shared_cache;
+mod analysis;
 
 use std::marker::PhantomData;
 use std::{cmp, mem};
@@ -41,6 +42,7 @@ use self::gasometer::Gasometer;
 use self::stack::{Stack, VecStack};
 use self::memory::Memory;
 pub use self::shared_cache::SharedCache;
+pub use self::analysis::{CodeAnalysis, BasicBlock};
 
 use bit_set::BitSet;
 
@@ -119,7 +121,9 @@ impl<Cost: CostType> vm::Vm for Interpreter<Cost> {
 		let mut do_trace = true;
 
 		let code = &params.code.as_ref().expect("exec always called with code; qed");
-		let mut valid_jump_destinations = None;
+		// init code runs once, so it's not worth analysing up front
+		let is_init_code = params.params_type == vm::ParamsType::Embedded;
+		let mut analysis = None;
 
 		let mut gasometer = Gasometer::<Cost>::new(Cost::from_u256(params.gas)?);
 		let mut stack = VecStack::with_capacity(ext.schedule().stack_limit, U256::zero());
@@ -128,13 +132,24 @@ impl<Cost: CostType> vm::Vm for Interpreter<Cost> {
 
 		while reader.position < code.len() {
 			let instruction = code[reader.position];
-			reader.position += 1;
 
 			// TODO: make compile-time removable if too much of a performance hit.
 			do_trace = do_trace && ext.trace_next_instruction(
-				reader.position - 1, instruction, gasometer.current_gas.as_u256(),
+				reader.position, instruction, gasometer.current_gas.as_u256(),
 			);
 
+			// Without tracing, straight-line code is charged and validated once per basic block.
+			if !do_trace && !is_init_code && !cfg!(feature = "evm-debug") {
+				let analysis = analysis.get_or_insert_with(|| self.code_analysis(&params, code));
+				if let Some(block) = analysis.block_at(reader.position) {
+					if self.exec_block(block, &mut gasometer, &params, ext, &mut reader, &mut stack)? {
+						continue;
+					}
+				}
+			}
+
+			reader.position += 1;
+
 			let info = &infos[instruction as usize];
 			self.verify_instruction(ext, instruction, info, &stack)?;
 
@@ -179,12 +194,11 @@ impl<Cost: CostType> vm::Vm for Interpreter<Cost> {
 			// Advance
 			match result {
 				InstructionResult::JumpToPosition(position) => {
-					if valid_jump_destinations.is_none() {
-						let code_hash = params.code_hash.clone().unwrap_or_else(|| keccak(code.as_ref()));
-						valid_jump_destinations = Some(self.cache.jump_destinations(&code_hash, code));
+					if analysis.is_none() {
+						analysis = Some(self.code_analysis(&params, code));
 					}
-					let jump_destinations = valid_jump_destinations.as_ref().expect("jump_destinations are initialized on first jump; qed");
-					let pos = self.verify_jump(position, jump_destinations)?;
+					let analysis = analysis.as_ref().expect("analysis is initialized on first jump; qed");
+					let pos = self.verify_jump(position, &analysis.jump_destinations)?;
 					reader.position = pos;
 				},
 				InstructionResult::StopExecutionNeedsReturn {gas, init_off, init_size, apply} => {
@@ -216,6 +230,55 @@ impl<Cost: CostType> Interpreter<Cost> {
 		}
 	}
 
+	fn code_analysis(&self, params: &ActionParams, code: &[u8]) -> Arc<CodeAnalysis> {
+		if params.params_type == vm::ParamsType::Embedded {
+			return Arc::new(CodeAnalysis::new(code));
+		}
+
+		let code_hash = params.code_hash.clone().unwrap_or_else(|| keccak(code));
+		self.cache.analysis(&code_hash, code)
+	}
+
+	/// Execute whole basic block, charging its gas and validating the stack once.
+	///
+	/// Returns `false` without touching any state if the block would fail;
+	/// the caller then falls back to executing instructions one by one,
+	/// so that the exact failure is reported.
+	fn exec_block(
+		&mut self,
+		block: &BasicBlock,
+		gasometer: &mut Gasometer<Cost>,
+		params: &ActionParams,
+		ext: &mut vm::Ext,
+		reader: &mut CodeReader,
+		stack: &mut Stack<U256>,
+	) -> vm::Result<bool> {
+		let (gas_cost, stack_limit) = {
+			let schedule = ext.schedule();
+			(Cost::from(block.gas_cost(schedule)), schedule.stack_limit)
+		};
+
+		if gasometer.current_gas < gas_cost ||
+			stack.size() < block.stack_required ||
+			stack.size() + block.stack_max_growth > stack_limit {
+			return Ok(false);
+		}
+
+		gasometer.current_gas = gasometer.current_gas - gas_cost;
+
+		while reader.position < block.end {
+			let instruction = reader.code[reader.position];
+			reader.position += 1;
+
+			match self.exec_instruction(gasometer.current_gas, params, ext, instruction, reader, stack, None)? {
+				InstructionResult::Ok => {},
+				_ => return Err(vm::Error::Internal(format!("Instruction {} altered control flow inside a basic block", instruction))),
+			}
+		}
+
+		Ok(true)
+	}
+
 	fn verify_instruction(&self, ext: &vm::Ext, instruction: Instruction, info: &InstructionInfo, stack: &Stack<U256>) -> vm::Result<()> {
 		let schedule = ext.schedule();
 
@@ -1015,4 +1078,44 @@ mod tests {
 
 		assert_eq!(err, ::vm::Error::OutOfBounds);
 	}
+
+	#[test]
+	fn should_report_exact_error_when_basic_block_fails() {
+		// PUSH1 1 ADD
+		let code = "600101".from_hex().unwrap();
+
+		let mut params = ActionParams::default();
+		params.address = 5.into();
+		params.gas = 300_000.into();
+		params.code = Some(Arc::new(code));
+		let mut ext = FakeExt::new();
+
+		let err = {
+			let mut vm = interpreter(&params.gas);
+			test_finalize(vm.exec(params, &mut ext)).err().unwrap()
+		};
+
+		assert_eq!(err, ::vm::Error::StackUnderflow { instruction: "ADD", wanted: 2, on_stack: 1 });
+	}
+
+	#[test]
+	fn should_charge_the_same_gas_with_and_without_tracing() {
+		// PUSH1 1 PUSH1 2 ADD JUMPDEST DUP1 PUSH1 0 SSTORE POP
+		let code = "60016002015b8060005550".from_hex().unwrap();
+
+		let exec = |tracing: bool| {
+			let mut params = ActionParams::default();
+			params.address = 5.into();
+			params.gas = 300_000.into();
+			params.code = Some(Arc::new(code.clone()));
+			let mut ext = FakeExt::new();
+			ext.tracing = tracing;
+
+			let mut vm = interpreter(&params.gas);
+			test_finalize(vm.exec(params, &mut ext)).unwrap()
+		};
+
+		assert_eq!(exec(false), exec(true));
+		assert_eq!(exec(false), 279_982.into());
+	}
 }
@@ -20,68 +20,47 @@ use heapsize::HeapSizeOf;
 use ethereum_types::H256;
 use parking_lot::Mutex;
 use memory_cache::MemoryLruCache;
-use bit_set::BitSet;
-use super::super::instructions;
+use super::analysis::CodeAnalysis;
 
 const DEFAULT_CACHE_SIZE: usize = 4 * 1024 * 1024;
 
 // stub for a HeapSizeOf implementation.
-struct Bits(Arc<BitSet>);
+struct Analysis(Arc<CodeAnalysis>);
 
-impl HeapSizeOf for Bits {
+impl HeapSizeOf for Analysis {
 	fn heap_size_of_children(&self) -> usize {
-		// dealing in bits here
-		self.0.capacity() * 8
+		self.0.heap_size_of_children()
 	}
 }
 
 /// Global cache for EVM interpreter
 pub struct SharedCache {
-	jump_destinations: Mutex<MemoryLruCache<H256, Bits>>,
+	analysis: Mutex<MemoryLruCache<H256, Analysis>>,
 }
 
 impl SharedCache {
-	/// Create a jump destinations cache with a maximum size in bytes
+	/// Create a code analysis cache with a maximum size in bytes
 	/// to cache.
 	pub fn new(max_size: usize) -> Self {
 		SharedCache {
-			jump_destinations: Mutex::new(MemoryLruCache::new(max_size)),
+			analysis: Mutex::new(MemoryLruCache::new(max_size)),
 		}
 	}
 
-	/// Get jump destinations bitmap for a contract.
-	pub fn jump_destinations(&self, code_hash: &H256, code: &[u8]) -> Arc<BitSet> {
+	/// Get jump destinations and basic blocks for a contract.
+	pub fn analysis(&self, code_hash: &H256, code: &[u8]) -> Arc<CodeAnalysis> {
 		if code_hash == &KECCAK_EMPTY {
-			return Self::find_jump_destinations(code);
+			return Arc::new(CodeAnalysis::new(code));
 		}
 
-		if let Some(d) = self.jump_destinations.lock().get_mut(code_hash) {
-			return d.0.clone();
+		if let Some(a) = self.analysis.lock().get_mut(code_hash) {
+			return a.0.clone();
 		}
 
-		let d = Self::find_jump_destinations(code);
-		self.jump_destinations.lock().insert(code_hash.clone(), Bits(d.clone()));
+		let a = Arc::new(CodeAnalysis::new(code));
+		self.analysis.lock().insert(code_hash.clone(), Analysis(a.clone()));
 
-		d
-	}
-
-	fn find_jump_destinations(code: &[u8]) -> Arc<BitSet> {
-		let mut jump_dests = BitSet::with_capacity(code.len());
-		let mut position = 0;
-
-		while position < code.len() {
-			let instruction = code[position];
-
-			if instruction == instructions::JUMPDEST {
-				jump_dests.insert(position);
-			} else if instructions::is_push(instruction) {
-				position += instructions::get_push_bytes(instruction);
-			}
-			position += 1;
-		}
-
-		jump_dests.shrink_to_fit();
-		Arc::new(jump_dests)
+		a
 	}
 }
 
@@ -90,16 +69,3 @@ impl Default for SharedCache {
 		SharedCache::new(DEFAULT_CACHE_SIZE)
 	}
 }
-
-#[test]
-fn test_find_jump_destinations() {
-	use rustc_hex::FromHex;
-	// given
-	let code = "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff5b01600055".from_hex().unwrap();
-
-	// when
-	let valid_jump_destinations = SharedCache::find_jump_destinations(&code);
-
-	// then
-	assert!(valid_jump_destinations.contains(66));
-}
@@ -16,6 +16,8 @@
 
 //! Ethereum virtual machine.
 
+#![cfg_attr(feature = "benches", feature(test))]
+
 extern crate bit_set;
 extern crate ethereum_types;
 extern crate parking_lot;
@@ -722,6 +722,26 @@ fn test_jumps(factory: super::Factory) {
 	assert_eq!(gas_left, U256::from(54_117));
 }
 
+evm_test!{test_jumps_in_init_code: test_jumps_in_init_code_int}
+fn test_jumps_in_init_code(factory: super::Factory) {
+	let code = "600160015560066000555b60016000540380806000551560245760015402600155600a565b".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(150_000);
+	params.code = Some(Arc::new(code));
+	params.params_type = vm::ParamsType::Embedded;
+	let mut ext = FakeExt::new();
+
+	let gas_left = {
+		let mut vm = factory.create(&params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap()
+	};
+
+	assert_eq!(ext.sstore_clears, 1);
+	assert_store(&ext, 1, "0000000000000000000000000000000000000000000000000000000000000078");
+	assert_eq!(gas_left, U256::from(54_117));
+}
+
 evm_test!{test_calls: test_calls_int}
 fn test_calls(factory: super::Factory) {
 	let code = "600054602d57600160005560006000600060006050610998610100f160006000600060006050610998610100f25b".from_hex().unwrap();
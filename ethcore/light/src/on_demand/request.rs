@@ -23,7 +23,7 @@ use ethcore::encoded;
 use ethcore::engines::{EthEngine, StateDependentProof};
 use ethcore::machine::EthereumMachine;
 use ethcore::receipt::Receipt;
-use ethcore::state::{self, ProvedExecution};
+use ethcore::state::{self, ProvedExecution, StateOverride};
 use transaction::SignedTransaction;
 use vm::EnvInfo;
 use hash::{KECCAK_NULL_RLP, KECCAK_EMPTY, KECCAK_EMPTY_LIST_RLP, keccak};
@@ -881,6 +881,9 @@ pub struct TransactionProof {
 	pub env_info: EnvInfo,
 	/// Consensus engine.
 	pub engine: Arc<EthEngine>,
+	/// Accounts replaced before executing the transaction locally.
+	/// The peer proves the execution without them.
+	pub overrides: StateOverride,
 }
 
 impl TransactionProof {
@@ -891,13 +894,24 @@ impl TransactionProof {
 		let mut env_info = self.env_info.clone();
 		env_info.gas_limit = self.tx.gas.clone();
 
-		let proved_execution = state::check_proof(
-			state_items,
-			root,
-			&self.tx,
-			self.engine.machine(),
-			&self.env_info,
-		);
+		let proved_execution = if self.overrides.is_empty() {
+			state::check_proof(
+				state_items,
+				root,
+				&self.tx,
+				self.engine.machine(),
+				&self.env_info,
+			)
+		} else {
+			state::check_proof_with_overrides(
+				state_items,
+				root,
+				&self.tx,
+				&self.overrides,
+				self.engine.machine(),
+				&self.env_info,
+			)
+		};
 
 		match proved_execution {
 			ProvedExecution::BadProof => Err(Error::BadProof),
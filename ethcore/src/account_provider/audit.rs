@@ -0,0 +1,302 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Audit log of account operations.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ethcore_logger::RotatingFile;
+use ethereum_types::{Address, H256};
+use hash::keccak;
+use ethjson::misc::AuditEntry as JsonAuditEntry;
+
+/// Size of the audit log after which it's rotated, in bytes.
+const MAX_LOG_SIZE: u64 = 16 * 1024 * 1024;
+/// Number of rotated audit logs kept.
+const MAX_ROTATED_LOGS: usize = 8;
+
+thread_local! {
+	static ORIGIN: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Runs `f` with given request origin attached to all account operations it performs on this thread.
+pub fn with_origin<T, F: FnOnce() -> T>(origin: Option<String>, f: F) -> T {
+	struct Reset(Option<String>);
+
+	impl Drop for Reset {
+		fn drop(&mut self) {
+			let previous = self.0.take();
+			ORIGIN.with(|origin| *origin.borrow_mut() = previous);
+		}
+	}
+
+	let _reset = Reset(ORIGIN.with(|current| current.replace(origin)));
+	f()
+}
+
+/// Returns the request origin of account operations performed on this thread.
+pub fn current_origin() -> Option<String> {
+	ORIGIN.with(|origin| origin.borrow().clone())
+}
+
+/// Hash of a message signed with `\x19Ethereum Signed Message:\n` prefix and its length.
+pub fn message_hash(message: &[u8]) -> H256 {
+	let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+	prefixed.extend_from_slice(message);
+	keccak(prefixed)
+}
+
+/// Hash of EIP-712 typed data signed, given hashes of its domain separator and message.
+pub fn typed_data_hash(domain_hash: &H256, message_hash: &H256) -> H256 {
+	let mut encoded = vec![0x19, 0x01];
+	encoded.extend_from_slice(&domain_hash[..]);
+	encoded.extend_from_slice(&message_hash[..]);
+	keccak(encoded)
+}
+
+/// Audited account operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditOperation {
+	/// Account unlocked.
+	Unlock,
+	/// Message or transaction signed.
+	Sign,
+	/// Message decrypted.
+	Decrypt,
+	/// Shared key agreed.
+	Agree,
+	/// Account exported.
+	Export,
+	/// Account password changed.
+	ChangePassword,
+	/// Account encrypted again with strong parameters.
+	Reencrypt,
+	/// Account removed.
+	Remove,
+	/// Vault created.
+	CreateVault,
+	/// Vault opened.
+	OpenVault,
+	/// Vault closed.
+	CloseVault,
+	/// Vault password changed.
+	ChangeVaultPassword,
+	/// Vault encrypted again with strong parameters.
+	ReencryptVault,
+	/// Account moved to another vault.
+	ChangeVault,
+}
+
+impl AuditOperation {
+	fn as_str(&self) -> &'static str {
+		match *self {
+			AuditOperation::Unlock => "unlock",
+			AuditOperation::Sign => "sign",
+			AuditOperation::Decrypt => "decrypt",
+			AuditOperation::Agree => "agree",
+			AuditOperation::Export => "export",
+			AuditOperation::ChangePassword => "changePassword",
+			AuditOperation::Reencrypt => "reencrypt",
+			AuditOperation::Remove => "remove",
+			AuditOperation::CreateVault => "createVault",
+			AuditOperation::OpenVault => "openVault",
+			AuditOperation::CloseVault => "closeVault",
+			AuditOperation::ChangeVaultPassword => "changeVaultPassword",
+			AuditOperation::ReencryptVault => "reencryptVault",
+			AuditOperation::ChangeVault => "changeVault",
+		}
+	}
+}
+
+impl fmt::Display for AuditOperation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+impl FromStr for AuditOperation {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"unlock" => Ok(AuditOperation::Unlock),
+			"sign" => Ok(AuditOperation::Sign),
+			"decrypt" => Ok(AuditOperation::Decrypt),
+			"agree" => Ok(AuditOperation::Agree),
+			"export" => Ok(AuditOperation::Export),
+			"changePassword" => Ok(AuditOperation::ChangePassword),
+			"reencrypt" => Ok(AuditOperation::Reencrypt),
+			"remove" => Ok(AuditOperation::Remove),
+			"createVault" => Ok(AuditOperation::CreateVault),
+			"openVault" => Ok(AuditOperation::OpenVault),
+			"closeVault" => Ok(AuditOperation::CloseVault),
+			"changeVaultPassword" => Ok(AuditOperation::ChangeVaultPassword),
+			"reencryptVault" => Ok(AuditOperation::ReencryptVault),
+			"changeVault" => Ok(AuditOperation::ChangeVault),
+			other => Err(format!("Unknown audit operation: {}", other)),
+		}
+	}
+}
+
+/// Entry of the audit log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+	/// Unix timestamp of the operation.
+	pub timestamp: u64,
+	/// Performed operation.
+	pub operation: AuditOperation,
+	/// Account the operation was performed with.
+	pub account: Option<Address>,
+	/// Vault the operation was performed with.
+	pub vault: Option<String>,
+	/// Origin of the request, `None` for operations of the node itself.
+	pub origin: Option<String>,
+	/// Hash of the signed or decrypted message.
+	pub hash: Option<H256>,
+	/// Whether the operation succeeded.
+	pub success: bool,
+}
+
+impl AuditEntry {
+	fn from_json(json: JsonAuditEntry) -> Option<Self> {
+		Some(AuditEntry {
+			timestamp: json.timestamp,
+			operation: json.operation.parse().ok()?,
+			account: json.account.map(Into::into),
+			vault: json.vault,
+			origin: json.origin,
+			hash: json.hash.map(Into::into),
+			success: json.success,
+		})
+	}
+}
+
+impl Into<JsonAuditEntry> for AuditEntry {
+	fn into(self) -> JsonAuditEntry {
+		JsonAuditEntry {
+			timestamp: self.timestamp,
+			operation: self.operation.to_string(),
+			account: self.account.map(Into::into),
+			vault: self.vault,
+			origin: self.origin,
+			hash: self.hash.map(Into::into),
+			success: self.success,
+		}
+	}
+}
+
+/// Append-only log of account operations, written as JSON lines.
+pub struct AuditLog {
+	file: RotatingFile,
+}
+
+impl AuditLog {
+	/// Opens the audit log at given path.
+	pub fn open(path: &Path) -> io::Result<Self> {
+		Ok(AuditLog {
+			file: RotatingFile::open(path, MAX_LOG_SIZE, MAX_ROTATED_LOGS)?,
+		})
+	}
+
+	/// Records an operation requested from the current thread.
+	pub fn record(&self, operation: AuditOperation, account: Option<Address>, vault: Option<&str>, hash: Option<H256>, success: bool) {
+		let entry = AuditEntry {
+			timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+			operation,
+			account,
+			vault: vault.map(Into::into),
+			origin: current_origin(),
+			hash,
+			success,
+		};
+
+		let json: JsonAuditEntry = entry.into();
+		if let Err(e) = self.file.append(&json.to_line()) {
+			warn!("Error writing to the audit log: {}", e);
+		}
+	}
+
+	/// Returns up to `limit` most recent entries, optionally only of given account.
+	pub fn entries(&self, account: Option<&Address>, limit: usize) -> io::Result<Vec<AuditEntry>> {
+		let mut entries = Vec::new();
+		for path in self.file.paths() {
+			let mut lines = BufReader::new(File::open(path)?).lines().collect::<io::Result<Vec<_>>>()?;
+			lines.reverse();
+
+			let matching = lines.into_iter()
+				.filter_map(|line| JsonAuditEntry::from_line(&line).ok().and_then(AuditEntry::from_json))
+				.filter(|entry| account.map_or(true, |account| entry.account.as_ref() == Some(account)));
+
+			for entry in matching {
+				if entries.len() == limit {
+					return Ok(entries);
+				}
+				entries.push(entry);
+			}
+		}
+
+		Ok(entries)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use tempdir::TempDir;
+	use super::{with_origin, current_origin, AuditLog, AuditOperation};
+
+	#[test]
+	fn should_reset_origin() {
+		assert_eq!(current_origin(), None);
+		with_origin(Some("IPC".into()), || {
+			assert_eq!(current_origin(), Some("IPC".into()));
+			with_origin(None, || assert_eq!(current_origin(), None));
+			assert_eq!(current_origin(), Some("IPC".into()));
+		});
+		assert_eq!(current_origin(), None);
+	}
+
+	#[test]
+	fn should_return_latest_entries_of_account() {
+		// given
+		let dir = TempDir::new("audit").unwrap();
+		let log = AuditLog::open(&dir.path().join("audit.log")).unwrap();
+
+		// when
+		log.record(AuditOperation::Unlock, Some(1.into()), None, None, true);
+		with_origin(Some("IPC".into()), || log.record(AuditOperation::Sign, Some(1.into()), None, Some(5.into()), true));
+		log.record(AuditOperation::Sign, Some(2.into()), None, Some(6.into()), false);
+		log.record(AuditOperation::CreateVault, None, Some("vault"), None, true);
+
+		// then
+		let entries = log.entries(None, 3).unwrap();
+		assert_eq!(entries.iter().map(|e| e.operation).collect::<Vec<_>>(), vec![AuditOperation::CreateVault, AuditOperation::Sign, AuditOperation::Sign]);
+		assert_eq!(entries[0].vault, Some("vault".into()));
+
+		let entries = log.entries(Some(&1.into()), 10).unwrap();
+		assert_eq!(entries.len(), 2);
+		assert_eq!(entries[0].operation, AuditOperation::Sign);
+		assert_eq!(entries[0].origin, Some("IPC".into()));
+		assert_eq!(entries[0].hash, Some(5.into()));
+		assert_eq!(entries[1].operation, AuditOperation::Unlock);
+		assert_eq!(entries[1].origin, None);
+	}
+}
@@ -16,25 +16,38 @@
 
 //! Account management.
 
+mod audit;
+mod policy;
+mod spending;
 mod stores;
 
-use self::stores::{AddressBook, DappsSettingsStore, NewDappsPolicy};
+use self::stores::{AddressBook, DappsSettingsStore, NewDappsPolicy, PolicyAccountsStore, SpendingLimitsStore};
 
 use std::fmt;
 use std::collections::{HashMap, HashSet};
-use std::time::{Instant, Duration};
+use std::sync::Arc;
+use std::time::{Instant, Duration, SystemTime, UNIX_EPOCH};
 use parking_lot::RwLock;
 use ethstore::{
 	SimpleSecretStore, SecretStore, Error as SSError, EthStore, EthMultiStore,
-	random_string, SecretVaultRef, StoreAccountRef, OpaqueSecret,
+	random_string, SecretVaultRef, StoreAccountRef, OpaqueSecret, import_key_pairs, mnemonic_key_pairs, ScryptParams,
 };
-use ethstore::accounts_dir::MemoryDirectory;
-use ethstore::ethkey::{Address, Message, Public, Secret, Random, Generator};
+use ethstore::accounts_dir::{MemoryDirectory, AUDIT_LOG_FILE};
+use ethstore::ethkey::{Address, Message, Public, Secret, Random, Generator, Mnemonic};
 use ethjson::misc::AccountMeta;
+use ethereum_types::{H256, U256};
+use external_signer::{Error as ExternalError, ExternalSigner, TransactionRequest as ExternalTransaction};
 use hardware_wallet::{Error as HardwareError, HardwareWalletManager, KeyPath, TransactionInfo};
+use hash::keccak;
 use super::transaction::{Action, Transaction};
 pub use ethstore::ethkey::Signature;
 pub use ethstore::{Derivation, IndexDerivation, KeyFile};
+pub use self::audit::{with_origin as with_audit_origin, AuditEntry, AuditOperation};
+use self::audit::AuditLog;
+pub use self::policy::{PolicyAccount, PolicyApproval, SafeTransaction};
+use self::policy::PendingApprovals;
+pub use self::spending::{SpendingError, SpendingLimit};
+pub use external_signer::Protocol as ExternalSignerProtocol;
 
 /// Type of unlock.
 #[derive(Clone, PartialEq)]
@@ -64,6 +77,8 @@ pub enum SignError {
 	NotFound,
 	/// Low-level hardware device error.
 	Hardware(HardwareError),
+	/// External signing service error.
+	External(ExternalError),
 	/// Low-level error from store
 	SStore(SSError),
 }
@@ -74,6 +89,7 @@ impl fmt::Display for SignError {
 			SignError::NotUnlocked => write!(f, "Account is locked"),
 			SignError::NotFound => write!(f, "Account does not exist"),
 			SignError::Hardware(ref e) => write!(f, "{}", e),
+			SignError::External(ref e) => write!(f, "{}", e),
 			SignError::SStore(ref e) => write!(f, "{}", e),
 		}
 	}
@@ -85,6 +101,12 @@ impl From<HardwareError> for SignError {
 	}
 }
 
+impl From<ExternalError> for SignError {
+	fn from(e: ExternalError) -> Self {
+		SignError::External(e)
+	}
+}
+
 impl From<SSError> for SignError {
 	fn from(e: SSError) -> Self {
 		SignError::SStore(e)
@@ -114,6 +136,9 @@ fn transient_sstore() -> EthMultiStore {
 
 type AccountToken = String;
 
+/// Approvals of policy account transactions are discarded if they aren't updated for this long.
+const POLICY_APPROVALS_EXPIRY_SECS: u64 = 24 * 60 * 60;
+
 /// Account management.
 /// Responsible for unlocking accounts.
 pub struct AccountProvider {
@@ -125,17 +150,27 @@ pub struct AccountProvider {
 	address_book: RwLock<AddressBook>,
 	/// Dapps settings.
 	dapps_settings: RwLock<DappsSettingsStore>,
+	/// Accounts controlled by a threshold of local owners.
+	policy_accounts: RwLock<PolicyAccountsStore>,
+	/// Owners' approvals of pending policy account transactions.
+	policy_approvals: RwLock<HashMap<(Address, H256), PendingApprovals>>,
+	/// Restrictions of transactions sent from unlocked accounts and value they spent today.
+	spending_limits: RwLock<SpendingLimitsStore>,
 	/// Accounts on disk
 	sstore: Box<SecretStore>,
 	/// Accounts unlocked with rolling tokens
 	transient_sstore: EthMultiStore,
 	/// Accounts in hardware wallets.
 	hardware_store: Option<HardwareWalletManager>,
+	/// Accounts in an external signing service.
+	external_signer: Option<Arc<ExternalSigner>>,
 	/// When unlocking account permanently we additionally keep a raw secret in memory
 	/// to increase the performance of transaction signing.
 	unlock_keep_secret: bool,
 	/// Disallowed accounts.
 	blacklisted_accounts: Vec<Address>,
+	/// Log of account operations.
+	audit_log: Option<AuditLog>,
 }
 
 /// Account management settings.
@@ -144,10 +179,16 @@ pub struct AccountProviderSettings {
 	pub enable_hardware_wallets: bool,
 	/// Use the classic chain key on the hardware wallet.
 	pub hardware_wallet_classic_key: bool,
+	/// Endpoint of an external signing service, either an HTTP(S) URL or a path to a Unix domain socket.
+	pub external_signer: Option<String>,
+	/// JSON-RPC API spoken by the external signing service.
+	pub external_signer_protocol: ExternalSignerProtocol,
 	/// Store raw account secret when unlocking the account permanently.
 	pub unlock_keep_secret: bool,
 	/// Disallowed accounts.
 	pub blacklisted_accounts: Vec<Address>,
+	/// Write an audit log of account operations.
+	pub audit_log: bool,
 }
 
 impl Default for AccountProviderSettings {
@@ -155,15 +196,20 @@ impl Default for AccountProviderSettings {
 		AccountProviderSettings {
 			enable_hardware_wallets: false,
 			hardware_wallet_classic_key: false,
+			external_signer: None,
+			external_signer_protocol: ExternalSignerProtocol::default(),
 			unlock_keep_secret: false,
 			blacklisted_accounts: vec![],
+			audit_log: false,
 		}
 	}
 }
 
 impl AccountProvider {
 	/// Creates new account provider.
-	pub fn new(sstore: Box<SecretStore>, settings: AccountProviderSettings) -> Self {
+	///
+	/// Fails if the audit log is enabled, but can't be opened, or if spending limits can't be read.
+	pub fn new(sstore: Box<SecretStore>, settings: AccountProviderSettings) -> Result<Self, Error> {
 		let mut hardware_store = None;
 		if settings.enable_hardware_wallets {
 			match HardwareWalletManager::new() {
@@ -175,6 +221,28 @@ impl AccountProvider {
 			}
 		}
 
+		let external_signer = settings.external_signer.as_ref().and_then(|endpoint| match ExternalSigner::new(endpoint, settings.external_signer_protocol) {
+			Ok(signer) => {
+				// accounts must be known right away, e.g. to check the engine signer
+				if let Err(e) = signer.refresh_accounts() {
+					warn!("Error fetching accounts of the external signer at {}: {}", endpoint, e);
+				}
+
+				let signer = Arc::new(signer);
+				match ExternalSigner::refresh_in_background(&signer) {
+					Ok(()) => Some(signer),
+					Err(e) => {
+						warn!("Error starting external signer account discovery: {}", e);
+						None
+					},
+				}
+			},
+			Err(e) => {
+				warn!("Error initializing external signer at {}: {}", endpoint, e);
+				None
+			},
+		});
+
 		if let Ok(accounts) = sstore.accounts() {
 			for account in accounts.into_iter().filter(|a| settings.blacklisted_accounts.contains(&a.address)) {
 				warn!("Local Account {} has a blacklisted (known to be weak) address and will be ignored",
@@ -182,23 +250,35 @@ impl AccountProvider {
 			}
 		}
 
+		let audit_log = match settings.audit_log {
+			true => Some(AuditLog::open(&sstore.local_path().join(AUDIT_LOG_FILE))?),
+			false => None,
+		};
+
+		let spending_limits = SpendingLimitsStore::new(&sstore.local_path()).map_err(Error::Custom)?;
+
 		// Remove blacklisted accounts from address book.
 		let mut address_book = AddressBook::new(&sstore.local_path());
 		for addr in &settings.blacklisted_accounts {
 			address_book.remove(*addr);
 		}
 
-		AccountProvider {
+		Ok(AccountProvider {
 			unlocked_secrets: RwLock::new(HashMap::new()),
 			unlocked: RwLock::new(HashMap::new()),
 			address_book: RwLock::new(address_book),
 			dapps_settings: RwLock::new(DappsSettingsStore::new(&sstore.local_path())),
+			policy_accounts: RwLock::new(PolicyAccountsStore::new(&sstore.local_path())),
+			policy_approvals: RwLock::new(HashMap::new()),
+			spending_limits: RwLock::new(spending_limits),
 			sstore: sstore,
 			transient_sstore: transient_sstore(),
 			hardware_store: hardware_store,
+			external_signer: external_signer,
 			unlock_keep_secret: settings.unlock_keep_secret,
 			blacklisted_accounts: settings.blacklisted_accounts,
-		}
+			audit_log,
+		})
 	}
 
 	/// Creates not disk backed provider.
@@ -208,11 +288,16 @@ impl AccountProvider {
 			unlocked: RwLock::new(HashMap::new()),
 			address_book: RwLock::new(AddressBook::transient()),
 			dapps_settings: RwLock::new(DappsSettingsStore::transient()),
+			policy_accounts: RwLock::new(PolicyAccountsStore::transient()),
+			policy_approvals: RwLock::new(HashMap::new()),
+			spending_limits: RwLock::new(SpendingLimitsStore::transient()),
 			sstore: Box::new(EthStore::open(Box::new(MemoryDirectory::default())).expect("MemoryDirectory load always succeeds; qed")),
 			transient_sstore: transient_sstore(),
 			hardware_store: None,
+			external_signer: None,
 			unlock_keep_secret: false,
 			blacklisted_accounts: vec![],
+			audit_log: None,
 		}
 	}
 
@@ -241,6 +326,16 @@ impl AccountProvider {
 		Ok(account.address)
 	}
 
+	/// Derives `count` accounts from a BIP-39 mnemonic using BIP-44 paths `m/44'/60'/0'/0/i`
+	/// and stores them with given password. Returns addresses of all derived accounts.
+	pub fn insert_mnemonic_accounts(&self, mnemonic: &Mnemonic, passphrase: &str, count: u32, password: &str) -> Result<Vec<Address>, Error> {
+		let key_pairs = mnemonic_key_pairs(mnemonic, passphrase, count)?;
+		if key_pairs.iter().any(|key_pair| self.blacklisted_accounts.contains(&key_pair.address())) {
+			return Err(SSError::InvalidAccount.into());
+		}
+		Ok(import_key_pairs(&*self.sstore, SecretVaultRef::Root, &key_pairs, password)?)
+	}
+
 	/// Generates new derived account based on the existing one
 	/// If password is not provided, account must be unlocked
 	/// New account will be created with the same password (if save: true)
@@ -273,20 +368,32 @@ impl AccountProvider {
 
 	/// Checks whether an account with a given address is present.
 	pub fn has_account(&self, address: Address) -> Result<bool, Error> {
-		Ok(self.sstore.account_ref(&address).is_ok() && !self.blacklisted_accounts.contains(&address))
+		Ok((self.sstore.account_ref(&address).is_ok() || self.is_external_address(&address))
+			&& !self.blacklisted_accounts.contains(&address))
 	}
 
-	/// Returns addresses of all accounts.
+	/// Returns addresses of all accounts, including accounts in the external signer.
 	pub fn accounts(&self) -> Result<Vec<Address>, Error> {
 		let accounts = self.sstore.accounts()?;
 		Ok(accounts
 		   .into_iter()
 		   .map(|a| a.address)
+		   .chain(self.external_accounts())
 		   .filter(|address| !self.blacklisted_accounts.contains(address))
 		   .collect()
 		)
 	}
 
+	/// Returns addresses of accounts in the external signer.
+	pub fn external_accounts(&self) -> Vec<Address> {
+		self.external_signer.as_ref().map_or(Vec::new(), |s| s.accounts())
+	}
+
+	/// Returns `true` if the account is held by the external signer.
+	pub fn is_external_address(&self, address: &Address) -> bool {
+		self.external_signer.as_ref().map_or(false, |s| s.is_external(address))
+	}
+
 	/// Returns addresses of hardware accounts.
 	pub fn hardware_accounts(&self) -> Result<Vec<Address>, Error> {
 		let accounts = self.hardware_store.as_ref().map_or(Vec::new(), |h| h.list_wallets());
@@ -500,6 +607,160 @@ impl AccountProvider {
 		self.address_book.write().remove(addr)
 	}
 
+	/// Adds an account controlled by a Gnosis Safe deployed at `address`.
+	/// Transactions need to be approved by `threshold` of the `owners`, which must be local accounts.
+	pub fn add_policy_account(&self, address: Address, name: String, owners: Vec<Address>, threshold: usize) -> Result<(), Error> {
+		if threshold == 0 || threshold > owners.len() {
+			return Err(SSError::Custom(format!("Threshold has to be between 1 and the number of owners ({})", owners.len())));
+		}
+		if owners.iter().collect::<HashSet<_>>().len() != owners.len() {
+			return Err(SSError::Custom("Owners have to be unique".into()));
+		}
+		if self.sstore.account_ref(&address).is_ok() {
+			return Err(SSError::Custom("Policy account can't be a local account".into()));
+		}
+		for owner in &owners {
+			self.sstore.account_ref(owner)?;
+		}
+
+		self.policy_accounts.write().insert(address, PolicyAccount { name, owners, threshold });
+		Ok(())
+	}
+
+	/// Removes a policy account and discards approvals of its pending transactions.
+	pub fn remove_policy_account(&self, address: &Address) {
+		self.policy_accounts.write().remove(*address);
+		self.policy_approvals.write().retain(|&(ref policy, _), _| policy != address);
+	}
+
+	/// Returns each policy account.
+	pub fn policy_accounts_info(&self) -> HashMap<Address, PolicyAccount> {
+		self.policy_accounts.read().get()
+	}
+
+	/// Returns `true` if given address is a policy account.
+	pub fn is_policy_address(&self, address: &Address) -> bool {
+		self.policy_accounts.read().get().contains_key(address)
+	}
+
+	/// Approves a transaction of a policy account, identified by the hash its owners sign.
+	///
+	/// With a password the first owner it unlocks approves the transaction,
+	/// without one every unlocked owner does until the threshold is reached.
+	/// Approvals are kept until `discard_policy_approvals` is called after the transaction is executed,
+	/// so execution can be retried; approvals not updated for a day are discarded.
+	pub fn approve_policy_transaction(&self, address: Address, hash: H256, password: Option<String>) -> Result<PolicyApproval, SignError> {
+		let account = self.policy_accounts.read().get().remove(&address).ok_or(SignError::NotFound)?;
+		let now = Instant::now();
+		let mut approvals = self.policy_approvals.write();
+		approvals.retain(|_, approvals| now.duration_since(approvals.updated) < Duration::from_secs(POLICY_APPROVALS_EXPIRY_SECS));
+		let mut error = SignError::NotUnlocked;
+
+		let result = {
+			let approval = approvals.entry((address, hash)).or_insert_with(|| PendingApprovals::new(now));
+			if approval.executor.is_none() {
+				let pending: Vec<Address> = account.owners.iter()
+					.filter(|owner| approval.signatures.iter().all(|&(approved, _)| approved != **owner))
+					.cloned()
+					.collect();
+
+				let mut approved = false;
+				for owner in pending {
+					// a password always has to be checked, even if the owner's secret is kept in memory
+					let signature = match password {
+						Some(ref password) => self.audited(AuditOperation::Sign, Some(owner), None, Some(hash), || {
+							self.sstore.account_ref(&owner)
+								.and_then(|account| self.sstore.sign(&account, password, &hash))
+								.map_err(SignError::from)
+						}),
+						None if self.is_unlocked(&owner) => self.sign(owner, None, hash),
+						None => continue,
+					};
+
+					match signature {
+						Ok(signature) => {
+							approval.signatures.push((owner, signature));
+							approval.updated = now;
+							approved = true;
+							if approval.signatures.len() >= account.threshold {
+								approval.executor = Some(owner);
+								break;
+							}
+							if password.is_some() {
+								break;
+							}
+						},
+						Err(e) => error = e,
+					}
+				}
+
+				if !approved {
+					None
+				} else {
+					Some(match approval.executor {
+						Some(executor) => PolicyApproval::Complete { executor, signatures: approval.signatures.clone() },
+						None => PolicyApproval::Pending { approvals: approval.signatures.len(), threshold: account.threshold },
+					})
+				}
+			} else {
+				// the transaction is fully approved already, but its execution failed
+				approval.executor.map(|executor| PolicyApproval::Complete { executor, signatures: approval.signatures.clone() })
+			}
+		};
+
+		match result {
+			Some(result) => Ok(result),
+			None => {
+				if approvals.get(&(address, hash)).map_or(false, |approval| approval.signatures.is_empty()) {
+					approvals.remove(&(address, hash));
+				}
+				Err(error)
+			},
+		}
+	}
+
+	/// Discards approvals of an executed policy account transaction.
+	pub fn discard_policy_approvals(&self, address: &Address, hash: &H256) {
+		self.policy_approvals.write().remove(&(*address, *hash));
+	}
+
+	/// Restricts transactions sent from a local account while it's unlocked.
+	pub fn set_spending_limit(&self, address: Address, limit: SpendingLimit) -> Result<(), Error> {
+		self.sstore.account_ref(&address)?;
+		self.spending_limits.write().insert(address, limit)?;
+		Ok(())
+	}
+
+	/// Removes restrictions of transactions sent from an account.
+	pub fn remove_spending_limit(&self, address: &Address) -> Result<(), Error> {
+		self.spending_limits.write().remove(*address)?;
+		Ok(())
+	}
+
+	/// Returns spending limit of each restricted account.
+	pub fn spending_limits(&self) -> HashMap<Address, SpendingLimit> {
+		self.spending_limits.read().get()
+	}
+
+	/// Checks a transaction against the spending limit of its sender.
+	/// If it's allowed, its value counts towards the daily limit, even if it's never signed.
+	pub fn authorize_spending(&self, from: &Address, to: Option<&Address>, value: &U256, data: &[u8]) -> Result<(), SpendingError> {
+		let mut spending_limits = self.spending_limits.write();
+		let limit = match spending_limits.get().remove(from) {
+			Some(limit) => limit,
+			None => return Ok(()),
+		};
+
+		let day = SystemTime::now().duration_since(UNIX_EPOCH)
+			.map(|since_epoch| since_epoch.as_secs() / spending::DAY_SECS)
+			.unwrap_or(0);
+		let spent = spending_limits.spent(from, day);
+		limit.check(to, value, data, &spent)?;
+		// the spent value has to survive a restart, otherwise the daily limit could be exceeded
+		spending_limits.set_spent(*from, day, spent.saturating_add(*value))
+			.map_err(|e| SpendingError::Storage(e.to_string()))
+	}
+
 	/// Returns each account along with name and meta.
 	pub fn accounts_info(&self) -> Result<HashMap<Address, AccountMeta>, Error> {
 		let r = self.sstore.accounts()?
@@ -567,49 +828,68 @@ impl AccountProvider {
 
 	/// Permanently removes an account.
 	pub fn kill_account(&self, address: &Address, password: &str) -> Result<(), Error> {
-		self.sstore.remove_account(&self.sstore.account_ref(&address)?, &password)?;
-		Ok(())
+		self.audited(AuditOperation::Remove, Some(*address), None, None, || {
+			self.sstore.remove_account(&self.sstore.account_ref(&address)?, &password)?;
+			Ok(())
+		})
 	}
 
 	/// Changes the password of `account` from `password` to `new_password`. Fails if incorrect `password` given.
 	pub fn change_password(&self, address: &Address, password: String, new_password: String) -> Result<(), Error> {
-		self.sstore.change_password(&self.sstore.account_ref(address)?, &password, &new_password)
+		self.audited(AuditOperation::ChangePassword, Some(*address), None, None, || {
+			self.sstore.change_password(&self.sstore.account_ref(address)?, &password, &new_password)
+		})
+	}
+
+	/// Encrypts `account` again with strong scrypt parameters. Fails if incorrect `password` given.
+	pub fn reencrypt_account(&self, address: &Address, password: &str) -> Result<(), Error> {
+		self.audited(AuditOperation::Reencrypt, Some(*address), None, None, || {
+			self.sstore.reencrypt(&self.sstore.account_ref(address)?, password, &ScryptParams::default())
+		})
 	}
 
 	/// Exports an account for given address.
 	pub fn export_account(&self, address: &Address, password: String) -> Result<KeyFile, Error> {
-		self.sstore.export_account(&self.sstore.account_ref(address)?, &password)
+		self.audited(AuditOperation::Export, Some(*address), None, None, || {
+			self.sstore.export_account(&self.sstore.account_ref(address)?, &password)
+		})
 	}
 
 	/// Helper method used for unlocking accounts.
 	fn unlock_account(&self, address: Address, password: String, unlock: Unlock) -> Result<(), Error> {
-		let account = self.sstore.account_ref(&address)?;
+		self.audited(AuditOperation::Unlock, Some(address), None, None, || {
+			let account = self.sstore.account_ref(&address)?;
 
-		// check if account is already unlocked pernamently, if it is, do nothing
-		let mut unlocked = self.unlocked.write();
-		if let Some(data) = unlocked.get(&account) {
-			if let Unlock::Perm = data.unlock {
-				return Ok(())
+			// check if account is already unlocked pernamently, if it is, do nothing
+			let mut unlocked = self.unlocked.write();
+			if let Some(data) = unlocked.get(&account) {
+				if let Unlock::Perm = data.unlock {
+					return Ok(())
+				}
 			}
-		}
 
-		if self.unlock_keep_secret && unlock == Unlock::Perm {
-			// verify password and get the secret
-			let secret = self.sstore.raw_secret(&account, &password)?;
-			self.unlocked_secrets.write().insert(account.clone(), secret);
-		} else {
-			// verify password by signing dump message
-			// result may be discarded
-			let _ = self.sstore.sign(&account, &password, &Default::default())?;
-		}
+			if self.unlock_keep_secret && unlock == Unlock::Perm {
+				// verify password and get the secret
+				let secret = self.sstore.raw_secret(&account, &password)?;
+				self.unlocked_secrets.write().insert(account.clone(), secret);
+			} else {
+				// verify password by signing dump message
+				// result may be discarded
+				let _ = self.sstore.sign(&account, &password, &Default::default())?;
+			}
 
-		let data = AccountData {
-			unlock: unlock,
-			password: password,
-		};
+			if self.sstore.has_weak_kdf(&account).unwrap_or(false) {
+				warn!("Account {:?} is encrypted with weak key derivation parameters. Consider re-encrypting it with `parity_reencryptAccount`.", address);
+			}
 
-		unlocked.insert(account, data);
-		Ok(())
+			let data = AccountData {
+				unlock: unlock,
+				password: password,
+			};
+
+			unlocked.insert(account, data);
+			Ok(())
+		})
 	}
 
 	fn password(&self, account: &StoreAccountRef) -> Result<String, SignError> {
@@ -660,84 +940,154 @@ impl AccountProvider {
 	}
 
 	/// Signs the message. If password is not provided the account must be unlocked.
+	/// Accounts in the external signer don't need a password.
 	pub fn sign(&self, address: Address, password: Option<String>, message: Message) -> Result<Signature, SignError> {
-		let account = self.sstore.account_ref(&address)?;
-		match self.unlocked_secrets.read().get(&account) {
-			Some(secret) => {
-				Ok(self.sstore.sign_with_secret(&secret, &message)?)
-			},
-			None => {
-				let password = password.map(Ok).unwrap_or_else(|| self.password(&account))?;
-				Ok(self.sstore.sign(&account, &password, &message)?)
+		self.audited(AuditOperation::Sign, Some(address), None, Some(message), || {
+			if let Some(signer) = self.external_signer(&address) {
+				return Ok(signer.sign(&address, &message)?);
 			}
-		}
+
+			let account = self.sstore.account_ref(&address)?;
+			match self.unlocked_secrets.read().get(&account) {
+				Some(secret) => {
+					Ok(self.sstore.sign_with_secret(&secret, &message)?)
+				},
+				None => {
+					let password = password.map(Ok).unwrap_or_else(|| self.password(&account))?;
+					Ok(self.sstore.sign(&account, &password, &message)?)
+				}
+			}
+		})
 	}
 
 	/// Signs message using the derived secret. If password is not provided the account must be unlocked.
 	pub fn sign_derived(&self, address: &Address, password: Option<String>, derivation: Derivation, message: Message)
 		-> Result<Signature, SignError>
 	{
-		let account = self.sstore.account_ref(address)?;
-		let password = password.map(Ok).unwrap_or_else(|| self.password(&account))?;
-		Ok(self.sstore.sign_derived(&account, &password, derivation, &message)?)
+		self.audited(AuditOperation::Sign, Some(*address), None, Some(message), || {
+			let account = self.sstore.account_ref(address)?;
+			let password = password.map(Ok).unwrap_or_else(|| self.password(&account))?;
+			Ok(self.sstore.sign_derived(&account, &password, derivation, &message)?)
+		})
 	}
 
 	/// Signs given message with supplied token. Returns a token to use in next signing within this session.
 	pub fn sign_with_token(&self, address: Address, token: AccountToken, message: Message) -> Result<(Signature, AccountToken), SignError> {
-		let account = self.sstore.account_ref(&address)?;
-		let is_std_password = self.sstore.test_password(&account, &token)?;
-
-		let new_token = random_string(16);
-		let signature = if is_std_password {
-			// Insert to transient store
-			self.sstore.copy_account(&self.transient_sstore, SecretVaultRef::Root, &account, &token, &new_token)?;
-			// sign
-			self.sstore.sign(&account, &token, &message)?
-		} else {
-			// check transient store
-			self.transient_sstore.change_password(&account, &token, &new_token)?;
-			// and sign
-			self.transient_sstore.sign(&account, &new_token, &message)?
-		};
+		self.audited(AuditOperation::Sign, Some(address), None, Some(message), || {
+			let account = self.sstore.account_ref(&address)?;
+			let is_std_password = self.sstore.test_password(&account, &token)?;
+
+			let new_token = random_string(16);
+			let signature = if is_std_password {
+				// Insert to transient store
+				self.sstore.copy_account(&self.transient_sstore, SecretVaultRef::Root, &account, &token, &new_token)?;
+				// sign
+				self.sstore.sign(&account, &token, &message)?
+			} else {
+				// check transient store
+				self.transient_sstore.change_password(&account, &token, &new_token)?;
+				// and sign
+				self.transient_sstore.sign(&account, &new_token, &message)?
+			};
 
-		Ok((signature, new_token))
+			Ok((signature, new_token))
+		})
 	}
 
 	/// Decrypts a message with given token. Returns a token to use in next operation for this account.
 	pub fn decrypt_with_token(&self, address: Address, token: AccountToken, shared_mac: &[u8], message: &[u8])
 		-> Result<(Vec<u8>, AccountToken), SignError>
 	{
-		let account = self.sstore.account_ref(&address)?;
-		let is_std_password = self.sstore.test_password(&account, &token)?;
-
-		let new_token = random_string(16);
-		let message = if is_std_password {
-			// Insert to transient store
-			self.sstore.copy_account(&self.transient_sstore, SecretVaultRef::Root, &account, &token, &new_token)?;
-			// decrypt
-			self.sstore.decrypt(&account, &token, shared_mac, message)?
-		} else {
-			// check transient store
-			self.transient_sstore.change_password(&account, &token, &new_token)?;
-			// and decrypt
-			self.transient_sstore.decrypt(&account, &token, shared_mac, message)?
-		};
+		self.audited(AuditOperation::Decrypt, Some(address), None, Some(keccak(message)), || {
+			let account = self.sstore.account_ref(&address)?;
+			let is_std_password = self.sstore.test_password(&account, &token)?;
+
+			let new_token = random_string(16);
+			let message = if is_std_password {
+				// Insert to transient store
+				self.sstore.copy_account(&self.transient_sstore, SecretVaultRef::Root, &account, &token, &new_token)?;
+				// decrypt
+				self.sstore.decrypt(&account, &token, shared_mac, message)?
+			} else {
+				// check transient store
+				self.transient_sstore.change_password(&account, &token, &new_token)?;
+				// and decrypt
+				self.transient_sstore.decrypt(&account, &token, shared_mac, message)?
+			};
 
-		Ok((message, new_token))
+			Ok((message, new_token))
+		})
 	}
 
 	/// Decrypts a message. If password is not provided the account must be unlocked.
 	pub fn decrypt(&self, address: Address, password: Option<String>, shared_mac: &[u8], message: &[u8]) -> Result<Vec<u8>, SignError> {
-		let account = self.sstore.account_ref(&address)?;
-		let password = password.map(Ok).unwrap_or_else(|| self.password(&account))?;
-		Ok(self.sstore.decrypt(&account, &password, shared_mac, message)?)
+		self.audited(AuditOperation::Decrypt, Some(address), None, Some(keccak(message)), || {
+			if let Some(signer) = self.external_signer(&address) {
+				return Ok(signer.decrypt(&address, shared_mac, message)?);
+			}
+
+			let account = self.sstore.account_ref(&address)?;
+			let password = password.map(Ok).unwrap_or_else(|| self.password(&account))?;
+			Ok(self.sstore.decrypt(&account, &password, shared_mac, message)?)
+		})
 	}
 
 	/// Agree on shared key.
 	pub fn agree(&self, address: Address, password: Option<String>, other_public: &Public) -> Result<Secret, SignError> {
-		let account = self.sstore.account_ref(&address)?;
-		let password = password.map(Ok).unwrap_or_else(|| self.password(&account))?;
-		Ok(self.sstore.agree(&account, &password, other_public)?)
+		self.audited(AuditOperation::Agree, Some(address), None, Some(keccak(other_public)), || {
+			if let Some(signer) = self.external_signer(&address) {
+				return Ok(signer.agree(&address, other_public)?);
+			}
+
+			let account = self.sstore.account_ref(&address)?;
+			let password = password.map(Ok).unwrap_or_else(|| self.password(&account))?;
+			Ok(self.sstore.agree(&account, &password, other_public)?)
+		})
+	}
+
+	/// Signs a transaction with an account in the external signer.
+	pub fn sign_transaction_with_external(&self, address: Address, transaction: &Transaction, chain_id: Option<u64>) -> Result<Signature, SignError> {
+		self.audited(AuditOperation::Sign, Some(address), None, Some(transaction.hash(chain_id)), || {
+			let signer = self.external_signer(&address).ok_or(SignError::NotFound)?;
+			let request = ExternalTransaction {
+				nonce: transaction.nonce,
+				gas_price: transaction.gas_price,
+				gas: transaction.gas,
+				to: match transaction.action {
+					Action::Create => None,
+					Action::Call(ref to) => Some(to.clone()),
+				},
+				value: transaction.value,
+				data: transaction.data.clone(),
+				chain_id: chain_id,
+			};
+			Ok(signer.sign_transaction(&address, &request, &transaction.hash(chain_id))?)
+		})
+	}
+
+	/// Returns up to `limit` most recent entries of the audit log, optionally only of given account.
+	pub fn audit_log(&self, account: Option<&Address>, limit: usize) -> Result<Vec<AuditEntry>, Error> {
+		match self.audit_log {
+			Some(ref log) => log.entries(account, limit).map_err(Error::Io),
+			None => Err(Error::Custom("Audit log is disabled.".into())),
+		}
+	}
+
+	fn audited<T, E, F>(&self, operation: AuditOperation, account: Option<Address>, vault: Option<&str>, hash: Option<H256>, f: F) -> Result<T, E> where
+		F: FnOnce() -> Result<T, E>,
+	{
+		let result = f();
+		if let Some(ref log) = self.audit_log {
+			log.record(operation, account, vault, hash, result.is_ok());
+		}
+		result
+	}
+
+	fn external_signer(&self, address: &Address) -> Option<&ExternalSigner> {
+		match self.external_signer {
+			Some(ref signer) if !self.blacklisted_accounts.contains(address) && signer.is_external(address) => Some(&**signer),
+			_ => None,
+		}
 	}
 
 	/// Returns the underlying `SecretStore` reference if one exists.
@@ -754,20 +1104,26 @@ impl AccountProvider {
 
 	/// Create new vault.
 	pub fn create_vault(&self, name: &str, password: &str) -> Result<(), Error> {
-		self.sstore.create_vault(name, password)
-			.map_err(Into::into)
+		self.audited(AuditOperation::CreateVault, None, Some(name), None, || {
+			self.sstore.create_vault(name, password)
+				.map_err(Into::into)
+		})
 	}
 
 	/// Open existing vault.
 	pub fn open_vault(&self, name: &str, password: &str) -> Result<(), Error> {
-		self.sstore.open_vault(name, password)
-			.map_err(Into::into)
+		self.audited(AuditOperation::OpenVault, None, Some(name), None, || {
+			self.sstore.open_vault(name, password)
+				.map_err(Into::into)
+		})
 	}
 
 	/// Close previously opened vault.
 	pub fn close_vault(&self, name: &str) -> Result<(), Error> {
-		self.sstore.close_vault(name)
-			.map_err(Into::into)
+		self.audited(AuditOperation::CloseVault, None, Some(name), None, || {
+			self.sstore.close_vault(name)
+				.map_err(Into::into)
+		})
 	}
 
 	/// List all vaults
@@ -784,17 +1140,29 @@ impl AccountProvider {
 
 	/// Change vault password.
 	pub fn change_vault_password(&self, name: &str, new_password: &str) -> Result<(), Error> {
-		self.sstore.change_vault_password(name, new_password)
-			.map_err(Into::into)
+		self.audited(AuditOperation::ChangeVaultPassword, None, Some(name), None, || {
+			self.sstore.change_vault_password(name, new_password)
+				.map_err(Into::into)
+		})
+	}
+
+	/// Encrypt vault file and metadata of vault accounts again with strong scrypt parameters.
+	pub fn reencrypt_vault(&self, name: &str) -> Result<(), Error> {
+		self.audited(AuditOperation::ReencryptVault, None, Some(name), None, || {
+			self.sstore.reencrypt_vault(name, &ScryptParams::default())
+				.map_err(Into::into)
+		})
 	}
 
 	/// Change vault of the given address.
 	pub fn change_vault(&self, address: Address, new_vault: &str) -> Result<(), Error> {
-		let new_vault_ref = if new_vault.is_empty() { SecretVaultRef::Root } else { SecretVaultRef::Vault(new_vault.to_owned()) };
-		let old_account_ref = self.sstore.account_ref(&address)?;
-		self.sstore.change_account_vault(new_vault_ref, old_account_ref)
-			.map_err(Into::into)
-			.map(|_| ())
+		self.audited(AuditOperation::ChangeVault, Some(address), Some(new_vault), None, || {
+			let new_vault_ref = if new_vault.is_empty() { SecretVaultRef::Root } else { SecretVaultRef::Vault(new_vault.to_owned()) };
+			let old_account_ref = self.sstore.account_ref(&address)?;
+			self.sstore.change_account_vault(new_vault_ref, old_account_ref)
+				.map_err(Into::into)
+				.map(|_| ())
+		})
 	}
 
 	/// Get vault metadata string.
@@ -811,19 +1179,39 @@ impl AccountProvider {
 
 	/// Sign transaction with hardware wallet.
 	pub fn sign_with_hardware(&self, address: Address, transaction: &Transaction, chain_id: Option<u64>, rlp_encoded_transaction: &[u8]) -> Result<Signature, SignError> {
-		let t_info = TransactionInfo {
-			nonce: transaction.nonce,
-			gas_price: transaction.gas_price,
-			gas_limit: transaction.gas,
-			to: match transaction.action {
-				Action::Create => None,
-				Action::Call(ref to) => Some(to.clone()),
-			},
-			value: transaction.value,
-			data: transaction.data.to_vec(),
-			chain_id: chain_id,
-		};
-		match self.hardware_store.as_ref().map(|s| s.sign_transaction(&address, &t_info, rlp_encoded_transaction)) {
+		self.audited(AuditOperation::Sign, Some(address), None, Some(transaction.hash(chain_id)), || {
+			let t_info = TransactionInfo {
+				nonce: transaction.nonce,
+				gas_price: transaction.gas_price,
+				gas_limit: transaction.gas,
+				to: match transaction.action {
+					Action::Create => None,
+					Action::Call(ref to) => Some(to.clone()),
+				},
+				value: transaction.value,
+				data: transaction.data.to_vec(),
+				chain_id: chain_id,
+			};
+			Self::hardware_signature(self.hardware_store.as_ref().map(|s| s.sign_transaction(&address, &t_info, rlp_encoded_transaction)))
+		})
+	}
+
+	/// Sign a message prefixed with `\x19Ethereum Signed Message:\n` and its length with hardware wallet.
+	pub fn sign_message_with_hardware(&self, address: Address, message: &[u8]) -> Result<Signature, SignError> {
+		self.audited(AuditOperation::Sign, Some(address), None, Some(audit::message_hash(message)), || {
+			Self::hardware_signature(self.hardware_store.as_ref().map(|s| s.sign_message(&address, message)))
+		})
+	}
+
+	/// Sign EIP-712 typed data, given hashes of the domain separator and of the message, with hardware wallet.
+	pub fn sign_typed_data_with_hardware(&self, address: Address, domain_hash: &H256, message_hash: &H256) -> Result<Signature, SignError> {
+		self.audited(AuditOperation::Sign, Some(address), None, Some(audit::typed_data_hash(domain_hash, message_hash)), || {
+			Self::hardware_signature(self.hardware_store.as_ref().map(|s| s.sign_typed_data(&address, domain_hash, message_hash)))
+		})
+	}
+
+	fn hardware_signature(result: Option<Result<Signature, HardwareError>>) -> Result<Signature, SignError> {
+		match result {
 			None | Some(Err(HardwareError::KeyNotFound)) => Err(SignError::NotFound),
 			Some(Err(e)) => Err(From::from(e)),
 			Some(Ok(s)) => Ok(s),
@@ -833,10 +1221,13 @@ impl AccountProvider {
 
 #[cfg(test)]
 mod tests {
-	use super::{AccountProvider, Unlock, DappId};
+	use super::{AccountProvider, AccountProviderSettings, AuditOperation, Unlock, DappId, PolicyApproval, SpendingError, SpendingLimit};
+	use std::fs;
 	use std::time::{Duration, Instant};
-	use ethstore::ethkey::{Generator, Random, Address};
-	use ethstore::{StoreAccountRef, Derivation};
+	use tempdir::TempDir;
+	use ethstore::ethkey::{Generator, Random, Address, Mnemonic};
+	use ethstore::{EthStore, SimpleSecretStore, StoreAccountRef, SecretVaultRef, Derivation, mnemonic_key_pairs};
+	use ethstore::accounts_dir::{RootDiskDirectory, AUDIT_LOG_FILE};
 	use ethereum_types::H256;
 
 	#[test]
@@ -1062,4 +1453,135 @@ mod tests {
 		assert_eq!(ap.accounts_info().unwrap().keys().cloned().collect::<Vec<Address>>(), vec![]);
 		assert_eq!(ap.accounts().unwrap(), vec![]);
 	}
+
+	#[test]
+	fn should_not_insert_mnemonic_accounts_if_any_is_blacklisted() {
+		// given
+		let mut ap = AccountProvider::transient_provider();
+		let mnemonic = Mnemonic::from_phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+		let key_pairs = mnemonic_key_pairs(&mnemonic, "", 2).unwrap();
+		ap.sstore.insert_account(SecretVaultRef::Root, key_pairs[1].secret().clone(), "test").unwrap();
+		ap.blacklisted_accounts = vec![key_pairs[1].address()];
+
+		// when
+		assert!(ap.insert_mnemonic_accounts(&mnemonic, "", 2, "test").is_err());
+
+		// then
+		let stored: Vec<_> = ap.sstore.accounts().unwrap().into_iter().map(|a| a.address).collect();
+		assert_eq!(stored, vec![key_pairs[1].address()]);
+	}
+
+	#[test]
+	fn should_collect_policy_approvals() {
+		// given
+		let ap = AccountProvider::transient_provider();
+		let owner1 = ap.new_account("one").unwrap();
+		let owner2 = ap.new_account("two").unwrap();
+		let owner3 = ap.new_account("three").unwrap();
+		let policy: Address = 5.into();
+		let hash = H256::from(1);
+		assert!(ap.add_policy_account(policy, "Treasury".into(), vec![owner1, owner2], 3).is_err());
+		assert!(ap.add_policy_account(policy, "Treasury".into(), vec![owner1, 6.into()], 1).is_err());
+		ap.add_policy_account(policy, "Treasury".into(), vec![owner1, owner2, owner3], 2).unwrap();
+		assert!(ap.is_policy_address(&policy));
+
+		// when
+		assert_eq!(ap.approve_policy_transaction(policy, hash, Some("one".into())).unwrap(), PolicyApproval::Pending {
+			approvals: 1,
+			threshold: 2,
+		});
+		// the same owner can't approve twice
+		assert!(ap.approve_policy_transaction(policy, hash, Some("one".into())).is_err());
+		ap.unlock_account_permanently(owner3, "three".into()).unwrap();
+		let approval = ap.approve_policy_transaction(policy, hash, None).unwrap();
+
+		// then
+		match approval {
+			PolicyApproval::Complete { executor, signatures } => {
+				assert_eq!(executor, owner3);
+				assert_eq!(signatures.iter().map(|&(owner, _)| owner).collect::<Vec<_>>(), vec![owner1, owner3]);
+			},
+			_ => panic!("Expected the transaction to be approved."),
+		}
+		// approvals are kept until the execution is dispatched
+		match ap.approve_policy_transaction(policy, hash, Some("two".into())).unwrap() {
+			PolicyApproval::Complete { executor, .. } => assert_eq!(executor, owner3),
+			_ => panic!("Expected the approvals to be kept."),
+		}
+		ap.discard_policy_approvals(&policy, &hash);
+		assert_eq!(ap.approve_policy_transaction(policy, hash, Some("two".into())).unwrap(), PolicyApproval::Pending {
+			approvals: 1,
+			threshold: 2,
+		});
+	}
+
+	#[test]
+	fn should_audit_policy_approvals() {
+		// given
+		let dir = TempDir::new("accounts").unwrap();
+		let sstore = EthStore::open(Box::new(RootDiskDirectory::create(dir.path()).unwrap())).unwrap();
+		let settings = AccountProviderSettings { audit_log: true, ..Default::default() };
+		let ap = AccountProvider::new(Box::new(sstore), settings).unwrap();
+		let owner1 = ap.new_account("one").unwrap();
+		let owner2 = ap.new_account("two").unwrap();
+		let policy: Address = 5.into();
+		let hash = H256::from(1);
+		ap.add_policy_account(policy, "Treasury".into(), vec![owner1, owner2], 2).unwrap();
+
+		// when
+		ap.approve_policy_transaction(policy, hash, Some("one".into())).unwrap();
+		assert!(ap.approve_policy_transaction(policy, hash, Some("wrong".into())).is_err());
+
+		// then
+		let entries = ap.audit_log(Some(&owner1), 10).unwrap();
+		assert_eq!(entries.len(), 1);
+		assert_eq!(entries[0].operation, AuditOperation::Sign);
+		assert_eq!(entries[0].hash, Some(hash));
+		assert!(entries[0].success);
+		let entries = ap.audit_log(Some(&owner2), 10).unwrap();
+		assert_eq!(entries.len(), 1);
+		assert!(!entries[0].success);
+	}
+
+	#[test]
+	fn should_not_start_if_audit_log_cant_be_opened() {
+		// given
+		let dir = TempDir::new("accounts").unwrap();
+		fs::create_dir(dir.path().join(AUDIT_LOG_FILE)).unwrap();
+		let sstore = EthStore::open(Box::new(RootDiskDirectory::create(dir.path()).unwrap())).unwrap();
+		let settings = AccountProviderSettings { audit_log: true, ..Default::default() };
+
+		// when
+		let ap = AccountProvider::new(Box::new(sstore), settings);
+
+		// then
+		assert!(ap.is_err());
+	}
+
+	#[test]
+	fn should_track_daily_spending() {
+		// given
+		let ap = AccountProvider::transient_provider();
+		let address = ap.new_account("test").unwrap();
+		let to = Address::from(1);
+		assert!(ap.set_spending_limit(2.into(), SpendingLimit::default()).is_err());
+		ap.set_spending_limit(address, SpendingLimit {
+			daily_limit: Some(10.into()),
+			..Default::default()
+		}).unwrap();
+
+		// when
+		assert_eq!(ap.authorize_spending(&address, Some(&to), &6.into(), &[]), Ok(()));
+		let exceeded = ap.authorize_spending(&address, Some(&to), &6.into(), &[]);
+
+		// then
+		assert_eq!(exceeded, Err(SpendingError::DailyLimitExceeded {
+			spent: 6.into(),
+			value: 6.into(),
+			limit: 10.into(),
+		}));
+		assert_eq!(ap.authorize_spending(&address, Some(&to), &4.into(), &[]), Ok(()));
+		ap.remove_spending_limit(&address).unwrap();
+		assert_eq!(ap.authorize_spending(&address, Some(&to), &100.into(), &[]), Ok(()));
+	}
 }
@@ -0,0 +1,201 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Policy accounts: Gnosis Safe contracts controlled by a threshold of local owners.
+
+use std::time::Instant;
+use bytes::Bytes;
+use ethereum_types::{Address, H256, U256};
+use ethjson::misc::PolicyAccount as JsonPolicyAccount;
+use ethstore::ethkey::Signature;
+
+use_contract!(gnosis_safe, "GnosisSafe", "res/contracts/gnosis_safe.json");
+
+/// Account controlled by a threshold of local owner accounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyAccount {
+	/// Account name.
+	pub name: String,
+	/// Local accounts owning the policy account.
+	pub owners: Vec<Address>,
+	/// Number of owners required to approve a transaction.
+	pub threshold: usize,
+}
+
+impl From<JsonPolicyAccount> for PolicyAccount {
+	fn from(a: JsonPolicyAccount) -> Self {
+		PolicyAccount {
+			name: a.name,
+			owners: a.owners.into_iter().map(Into::into).collect(),
+			threshold: a.threshold,
+		}
+	}
+}
+
+impl From<PolicyAccount> for JsonPolicyAccount {
+	fn from(a: PolicyAccount) -> Self {
+		JsonPolicyAccount {
+			name: a.name,
+			owners: a.owners.into_iter().map(Into::into).collect(),
+			threshold: a.threshold,
+		}
+	}
+}
+
+/// State of a policy account transaction after it has been approved by an owner.
+#[derive(Debug, PartialEq)]
+pub enum PolicyApproval {
+	/// More owners need to approve the transaction.
+	Pending {
+		/// Number of owners who already approved the transaction.
+		approvals: usize,
+		/// Number of approvals required.
+		threshold: usize,
+	},
+	/// Enough owners approved the transaction.
+	Complete {
+		/// Owner who approved the transaction last and is expected to submit it.
+		executor: Address,
+		/// Signatures of the approving owners.
+		signatures: Vec<(Address, Signature)>,
+	},
+}
+
+/// Approvals of a policy account transaction collected so far.
+#[derive(Debug)]
+pub struct PendingApprovals {
+	/// Signatures of the approving owners.
+	pub signatures: Vec<(Address, Signature)>,
+	/// Owner who completed the approvals, set once the threshold is reached.
+	pub executor: Option<Address>,
+	/// Time of the last approval.
+	pub updated: Instant,
+}
+
+impl PendingApprovals {
+	/// Creates empty approvals.
+	pub fn new(now: Instant) -> Self {
+		PendingApprovals {
+			signatures: Vec::new(),
+			executor: None,
+			updated: now,
+		}
+	}
+}
+
+/// Call executed by the Safe contract on behalf of its owners.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafeTransaction {
+	to: Address,
+	value: U256,
+	data: Bytes,
+}
+
+impl SafeTransaction {
+	/// Create a call of `to` with given value and data.
+	pub fn new(to: Address, value: U256, data: Bytes) -> Self {
+		SafeTransaction { to, value, data }
+	}
+
+	/// Input of a call returning the current nonce of the Safe.
+	pub fn nonce_input() -> Bytes {
+		gnosis_safe::GnosisSafe::default().functions().nonce().input()
+	}
+
+	/// Decode output of the nonce call.
+	pub fn decode_nonce(output: &[u8]) -> Result<U256, String> {
+		gnosis_safe::GnosisSafe::default().functions().nonce().output(output).map_err(|e| e.to_string())
+	}
+
+	/// Input of a call returning the hash which owners sign to approve this transaction.
+	/// Delegate calls, gas refunds and explicit gas limits are never used.
+	pub fn hash_input(&self, nonce: U256) -> Bytes {
+		gnosis_safe::GnosisSafe::default().functions().get_transaction_hash().input(
+			self.to,
+			self.value,
+			self.data.clone(),
+			U256::zero(),
+			U256::zero(),
+			U256::zero(),
+			U256::zero(),
+			Address::default(),
+			Address::default(),
+			nonce,
+		)
+	}
+
+	/// Decode output of the hash call.
+	pub fn decode_hash(output: &[u8]) -> Result<H256, String> {
+		gnosis_safe::GnosisSafe::default().functions().get_transaction_hash().output(output).map_err(|e| e.to_string())
+	}
+
+	/// Input of a transaction executing this call with given owners' approvals.
+	pub fn exec_input(&self, signatures: &[(Address, Signature)]) -> Bytes {
+		// the contract expects signatures ordered by owner address
+		let mut signatures = signatures.to_vec();
+		signatures.sort_by_key(|&(owner, _)| owner);
+		let packed: Bytes = signatures.into_iter()
+			.flat_map(|(_, signature)| signature.into_electrum().to_vec())
+			.collect();
+
+		gnosis_safe::GnosisSafe::default().functions().exec_transaction().input(
+			self.to,
+			self.value,
+			self.data.clone(),
+			U256::zero(),
+			U256::zero(),
+			U256::zero(),
+			U256::zero(),
+			Address::default(),
+			Address::default(),
+			packed,
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rustc_hex::FromHex;
+	use super::*;
+
+	#[test]
+	fn should_encode_safe_calls() {
+		assert_eq!(SafeTransaction::nonce_input(), "affed0e0".from_hex().unwrap());
+
+		let tx = SafeTransaction::new(1.into(), 2.into(), vec![0xff]);
+		let hash_input = tx.hash_input(5.into());
+		// getTransactionHash(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,uint256)
+		assert_eq!(&hash_input[..4], &"d8d11f78".from_hex().unwrap()[..]);
+		// nonce is the last static argument
+		assert_eq!(hash_input[4 + 9 * 32 + 31], 5);
+	}
+
+	#[test]
+	fn should_pack_signatures_ordered_by_owner() {
+		let tx = SafeTransaction::new(1.into(), 0.into(), vec![]);
+		let first = Signature::from_rsv(&1.into(), &1.into(), 0);
+		let second = Signature::from_rsv(&2.into(), &2.into(), 1);
+		let input = tx.exec_input(&[(2.into(), second), (1.into(), first)]);
+
+		// signatures are the last dynamic argument: length followed by padded 65-byte entries
+		let signatures = &input[input.len() - 192..];
+		assert_eq!(signatures[31], 130);
+		assert_eq!(signatures[32 + 31], 1);
+		assert_eq!(signatures[32 + 64], 27);
+		assert_eq!(signatures[32 + 65 + 31], 2);
+		assert_eq!(signatures[32 + 65 + 64], 28);
+	}
+}
@@ -0,0 +1,238 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Spending limits of unlocked accounts.
+
+use std::fmt;
+
+use ethereum_types::{Address, U256};
+use ethjson::misc::{SpendingLimit as JsonSpendingLimit, DailySpending as JsonDailySpending};
+use ethjson::uint::Uint;
+use rustc_hex::{FromHex, ToHex};
+
+/// Length of a period tracked by the daily limit, in seconds.
+pub const DAY_SECS: u64 = 24 * 60 * 60;
+
+/// Restrictions of transactions sent from an unlocked account.
+/// Unset fields don't restrict anything.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SpendingLimit {
+	/// Maximal value of a single transaction.
+	pub max_value: Option<U256>,
+	/// Maximal value of all transactions within a day.
+	pub daily_limit: Option<U256>,
+	/// Addresses the account is allowed to send transactions to.
+	/// Contract creation is not allowed if set.
+	pub allowed_recipients: Option<Vec<Address>>,
+	/// Selectors of the contract methods the account is allowed to call.
+	/// Plain value transfers without data are always allowed.
+	pub allowed_methods: Option<Vec<[u8; 4]>>,
+}
+
+impl SpendingLimit {
+	/// Check if a transaction is allowed given the value already spent today.
+	pub fn check(&self, to: Option<&Address>, value: &U256, data: &[u8], spent_today: &U256) -> Result<(), SpendingError> {
+		if let Some(ref limit) = self.max_value {
+			if value > limit {
+				return Err(SpendingError::ValueTooHigh { value: *value, limit: *limit });
+			}
+		}
+
+		if let Some(ref limit) = self.daily_limit {
+			let (total, overflow) = spent_today.overflowing_add(*value);
+			if overflow || total > *limit {
+				return Err(SpendingError::DailyLimitExceeded { spent: *spent_today, value: *value, limit: *limit });
+			}
+		}
+
+		if let Some(ref recipients) = self.allowed_recipients {
+			match to {
+				Some(to) if recipients.contains(to) => {},
+				_ => return Err(SpendingError::RecipientNotAllowed(to.cloned())),
+			}
+		}
+
+		if let Some(ref methods) = self.allowed_methods {
+			if !data.is_empty() && !methods.iter().any(|method| data.starts_with(method)) {
+				return Err(SpendingError::MethodNotAllowed(data.iter().take(4).cloned().collect()));
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl SpendingLimit {
+	/// Convert from the JSON representation. Fails on malformed method selectors,
+	/// since dropping them would allow calls the limit is meant to forbid.
+	pub fn from_json(l: JsonSpendingLimit) -> Result<Self, String> {
+		let allowed_methods = match l.allowed_methods {
+			Some(methods) => Some(methods.iter().map(|method| {
+				match method.trim_left_matches("0x").from_hex() {
+					Ok(ref selector) if selector.len() == 4 => Ok([selector[0], selector[1], selector[2], selector[3]]),
+					_ => Err(format!("Invalid method selector: {}", method)),
+				}
+			}).collect::<Result<Vec<_>, _>>()?),
+			None => None,
+		};
+
+		Ok(SpendingLimit {
+			max_value: l.max_value.map(Into::into),
+			daily_limit: l.daily_limit.map(Into::into),
+			allowed_recipients: l.allowed_recipients.map(|r| r.into_iter().map(Into::into).collect()),
+			allowed_methods,
+		})
+	}
+}
+
+impl From<SpendingLimit> for JsonSpendingLimit {
+	fn from(l: SpendingLimit) -> Self {
+		JsonSpendingLimit {
+			max_value: l.max_value.map(Uint),
+			daily_limit: l.daily_limit.map(Uint),
+			allowed_recipients: l.allowed_recipients.map(|r| r.into_iter().map(Into::into).collect()),
+			allowed_methods: l.allowed_methods.map(|methods| methods.iter()
+				.map(|method| format!("0x{}", method.to_hex()))
+				.collect()
+			),
+		}
+	}
+}
+
+/// Value spent by a limited account on a day.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DailySpending {
+	/// Number of the day since the Unix epoch.
+	pub day: u64,
+	/// Value spent on the day.
+	pub spent: U256,
+}
+
+impl From<JsonDailySpending> for DailySpending {
+	fn from(s: JsonDailySpending) -> Self {
+		DailySpending {
+			day: s.day,
+			spent: s.spent.into(),
+		}
+	}
+}
+
+impl From<DailySpending> for JsonDailySpending {
+	fn from(s: DailySpending) -> Self {
+		JsonDailySpending {
+			day: s.day,
+			spent: Uint(s.spent),
+		}
+	}
+}
+
+/// Transaction violating a spending limit.
+#[derive(Debug, PartialEq)]
+pub enum SpendingError {
+	/// Value of the transaction is above the per-transaction limit.
+	ValueTooHigh {
+		/// Value of the transaction.
+		value: U256,
+		/// Maximal value.
+		limit: U256,
+	},
+	/// Value of the transaction would exceed the daily limit.
+	DailyLimitExceeded {
+		/// Value already spent today.
+		spent: U256,
+		/// Value of the transaction.
+		value: U256,
+		/// Daily limit.
+		limit: U256,
+	},
+	/// Recipient is not on the allow-list (`None` for contract creation).
+	RecipientNotAllowed(Option<Address>),
+	/// Called method is not on the allow-list.
+	MethodNotAllowed(Vec<u8>),
+	/// Value spent today couldn't be saved, so the transaction isn't allowed.
+	Storage(String),
+}
+
+impl fmt::Display for SpendingError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			SpendingError::ValueTooHigh { value, limit } =>
+				write!(f, "Transaction value {} exceeds the limit of {} per transaction", value, limit),
+			SpendingError::DailyLimitExceeded { spent, value, limit } =>
+				write!(f, "Transaction value {} exceeds the daily limit of {} ({} already spent today)", value, limit, spent),
+			SpendingError::RecipientNotAllowed(Some(ref to)) =>
+				write!(f, "Recipient 0x{:x} is not allowed", to),
+			SpendingError::RecipientNotAllowed(None) =>
+				write!(f, "Contract creation is not allowed"),
+			SpendingError::MethodNotAllowed(ref selector) =>
+				write!(f, "Method 0x{} is not allowed", selector.to_hex()),
+			SpendingError::Storage(ref e) =>
+				write!(f, "Value spent today couldn't be saved: {}", e),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_check_spending_limit() {
+		let limit = SpendingLimit {
+			max_value: Some(10.into()),
+			daily_limit: Some(15.into()),
+			allowed_recipients: Some(vec![1.into()]),
+			allowed_methods: Some(vec![[0xa9, 0x05, 0x9c, 0xbb]]),
+		};
+		let to = Address::from(1);
+
+		assert_eq!(limit.check(Some(&to), &10.into(), &[], &5.into()), Ok(()));
+		assert_eq!(limit.check(Some(&to), &0.into(), &[0xa9, 0x05, 0x9c, 0xbb, 0x01], &0.into()), Ok(()));
+		assert_eq!(
+			limit.check(Some(&to), &11.into(), &[], &0.into()),
+			Err(SpendingError::ValueTooHigh { value: 11.into(), limit: 10.into() })
+		);
+		assert_eq!(
+			limit.check(Some(&to), &10.into(), &[], &6.into()),
+			Err(SpendingError::DailyLimitExceeded { spent: 6.into(), value: 10.into(), limit: 15.into() })
+		);
+		assert_eq!(limit.check(Some(&2.into()), &1.into(), &[], &0.into()), Err(SpendingError::RecipientNotAllowed(Some(2.into()))));
+		assert_eq!(limit.check(None, &1.into(), &[], &0.into()), Err(SpendingError::RecipientNotAllowed(None)));
+		assert_eq!(
+			limit.check(Some(&to), &0.into(), &[0x09, 0x5e, 0xa7, 0xb3], &0.into()),
+			Err(SpendingError::MethodNotAllowed(vec![0x09, 0x5e, 0xa7, 0xb3]))
+		);
+	}
+
+	#[test]
+	fn should_convert_to_and_from_json() {
+		let limit = SpendingLimit {
+			max_value: None,
+			daily_limit: Some(15.into()),
+			allowed_recipients: None,
+			allowed_methods: Some(vec![[0xa9, 0x05, 0x9c, 0xbb]]),
+		};
+
+		let mut json: JsonSpendingLimit = limit.clone().into();
+		assert_eq!(json.allowed_methods, Some(vec!["0xa9059cbb".to_owned()]));
+		assert_eq!(SpendingLimit::from_json(json.clone()), Ok(limit));
+
+		json.allowed_methods = Some(vec!["0xa9059cbb".to_owned(), "0xa9059c".to_owned()]);
+		assert!(SpendingLimit::from_json(json.clone()).is_err());
+		json.allowed_methods = Some(vec!["transfer".to_owned()]);
+		assert!(SpendingLimit::from_json(json).is_err());
+	}
+}
@@ -14,21 +14,27 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Address Book and Dapps Settings Store
+//! Address Book, Dapps Settings, Policy Accounts and Spending Limits Store
 
-use std::{fs, fmt, hash, ops};
+use std::{fs, fmt, hash, io, ops};
 use std::sync::atomic::{self, AtomicUsize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use ethereum_types::U256;
 use ethstore::ethkey::Address;
 use ethjson::misc::{
 	AccountMeta,
 	DappsSettings as JsonSettings,
 	DappsHistory as JsonDappsHistory,
 	NewDappsPolicy as JsonNewDappsPolicy,
+	PolicyAccount as JsonPolicyAccount,
+	SpendingLimit as JsonSpendingLimit,
+	DailySpending as JsonDailySpending,
 };
 use account_provider::DappId;
+use account_provider::policy::PolicyAccount;
+use account_provider::spending::{DailySpending, SpendingLimit};
 
 /// Disk-backed map from Address to String. Uses JSON.
 pub struct AddressBook {
@@ -88,6 +94,137 @@ impl AddressBook {
 	}
 }
 
+/// Disk-backed map from Address to policy account. Uses JSON.
+pub struct PolicyAccountsStore {
+	cache: DiskMap<Address, PolicyAccount>,
+}
+
+impl PolicyAccountsStore {
+	/// Creates new store at given directory.
+	pub fn new(path: &Path) -> Self {
+		let mut r = PolicyAccountsStore {
+			cache: DiskMap::new(path, "policy_accounts.json")
+		};
+		r.cache.revert(JsonPolicyAccount::read);
+		r
+	}
+
+	/// Creates transient store (no changes are saved to disk).
+	pub fn transient() -> Self {
+		PolicyAccountsStore {
+			cache: DiskMap::transient()
+		}
+	}
+
+	/// Get all policy accounts.
+	pub fn get(&self) -> HashMap<Address, PolicyAccount> {
+		self.cache.clone()
+	}
+
+	/// Inserts or replaces a policy account.
+	pub fn insert(&mut self, a: Address, account: PolicyAccount) {
+		self.cache.insert(a, account);
+		self.cache.save(JsonPolicyAccount::write);
+	}
+
+	/// Removes a policy account.
+	pub fn remove(&mut self, a: Address) {
+		self.cache.remove(&a);
+		self.cache.save(JsonPolicyAccount::write);
+	}
+}
+
+/// Disk-backed maps from Address to SpendingLimit and to the value spent today. Uses JSON.
+pub struct SpendingLimitsStore {
+	cache: DiskMap<Address, SpendingLimit>,
+	spent: DiskMap<Address, DailySpending>,
+}
+
+impl SpendingLimitsStore {
+	/// Creates new store at given directory.
+	/// Fails if existing files can't be read, since ignoring them would lift the limits.
+	pub fn new(path: &Path) -> Result<Self, String> {
+		let mut r = SpendingLimitsStore {
+			cache: DiskMap::new(path, "spending_limits.json"),
+			spent: DiskMap::new(path, "daily_spending.json"),
+		};
+		r.cache.try_revert(|file| JsonSpendingLimit::read(file).map(|limits: HashMap<Address, JsonSpendingLimit>| {
+			limits.into_iter().map(|(address, limit)| {
+				let limit = SpendingLimit::from_json(limit).unwrap_or_else(|e| {
+					warn!("Malformed spending limit of {:?}: {}. All its transactions will be rejected.", address, e);
+					// no recipient is allowed
+					SpendingLimit { allowed_recipients: Some(Vec::new()), ..Default::default() }
+				});
+				(address, limit)
+			}).collect()
+		}))?;
+		r.spent.try_revert(JsonDailySpending::read)?;
+		Ok(r)
+	}
+
+	/// Creates transient store (no changes are saved to disk).
+	pub fn transient() -> Self {
+		SpendingLimitsStore {
+			cache: DiskMap::transient(),
+			spent: DiskMap::transient(),
+		}
+	}
+
+	/// Get all spending limits.
+	pub fn get(&self) -> HashMap<Address, SpendingLimit> {
+		self.cache.clone()
+	}
+
+	/// Inserts or replaces spending limit of an account.
+	/// The previous limit is kept if the change can't be saved.
+	pub fn insert(&mut self, a: Address, limit: SpendingLimit) -> io::Result<()> {
+		let previous = self.cache.insert(a, limit);
+		self.cache.try_save(JsonSpendingLimit::write).map_err(|e| {
+			match previous {
+				Some(previous) => self.cache.insert(a, previous),
+				None => self.cache.remove(&a),
+			};
+			e
+		})
+	}
+
+	/// Removes spending limit of an account along with the value it spent today.
+	/// The limit is kept if the change can't be saved.
+	pub fn remove(&mut self, a: Address) -> io::Result<()> {
+		if let Some(previous) = self.cache.remove(&a) {
+			if let Err(e) = self.cache.try_save(JsonSpendingLimit::write) {
+				self.cache.insert(a, previous);
+				return Err(e);
+			}
+		}
+		if self.spent.remove(&a).is_some() {
+			self.spent.try_save(JsonDailySpending::write)?;
+		}
+		Ok(())
+	}
+
+	/// Returns the value an account spent on given day.
+	pub fn spent(&self, a: &Address, day: u64) -> U256 {
+		match self.spent.get(a) {
+			Some(spending) if spending.day == day => spending.spent,
+			_ => U256::zero(),
+		}
+	}
+
+	/// Sets the value an account spent on given day.
+	/// The previous value is kept if the change can't be saved.
+	pub fn set_spent(&mut self, a: Address, day: u64, spent: U256) -> io::Result<()> {
+		let previous = self.spent.insert(a, DailySpending { day, spent });
+		self.spent.try_save(JsonDailySpending::write).map_err(|e| {
+			match previous {
+				Some(previous) => self.spent.insert(a, previous),
+				None => self.spent.remove(&a),
+			};
+			e
+		})
+	}
+}
+
 /// Dapps user settings
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
 pub struct DappsSettings {
@@ -350,25 +487,63 @@ impl<K: hash::Hash + Eq, V> DiskMap<K, V> {
 			});
 	}
 
+	/// Like `revert`, but fails if an existing file can't be read.
+	fn try_revert<F, E>(&mut self, read: F) -> Result<(), String> where
+		F: Fn(fs::File) -> Result<HashMap<K, V>, E>,
+		E: fmt::Display,
+	{
+		if self.transient { return Ok(()); }
+		trace!(target: "diskmap", "revert {:?}", self.path);
+		let file = match fs::File::open(&self.path) {
+			Ok(file) => file,
+			Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+			Err(e) => return Err(format!("Couldn't open {}: {}", self.path.display(), e)),
+		};
+		self.cache = read(file).map_err(|e| format!("Couldn't read {}: {}", self.path.display(), e))?;
+		Ok(())
+	}
+
 	fn save<F, E>(&self, write: F) where
 		F: Fn(&HashMap<K, V>, &mut fs::File) -> Result<(), E>,
 		E: fmt::Display,
 	{
-		if self.transient { return; }
+		if let Err(e) = self.try_save(write) {
+			warn!(target: "diskmap", "Couldn't save disk map: {}", e);
+		}
+	}
+
+	/// Like `save`, but returns the error.
+	fn try_save<F, E>(&self, write: F) -> io::Result<()> where
+		F: Fn(&HashMap<K, V>, &mut fs::File) -> Result<(), E>,
+		E: fmt::Display,
+	{
+		if self.transient { return Ok(()); }
 		trace!(target: "diskmap", "save {:?}", self.path);
-		let _ = fs::File::create(self.path.clone())
-			.map_err(|e| warn!(target: "diskmap", "Couldn't open disk map for writing: {}", e))
-			.and_then(|mut f| {
-				write(&self.cache, &mut f).map_err(|e| warn!(target: "diskmap", "Couldn't write to disk map: {}", e))
-			});
+		// write to a hidden temporary file first and then rename it,
+		// so that an interrupted write never leaves a truncated map behind
+		let file_name = self.path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+		let temp_path = self.path.with_file_name(format!(".{}.tmp", file_name));
+		let result = fs::File::create(&temp_path)
+			.and_then(|mut file| {
+				write(&self.cache, &mut file).map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+				file.sync_all()
+			})
+			.and_then(|_| fs::rename(&temp_path, &self.path));
+		if result.is_err() {
+			let _ = fs::remove_file(&temp_path);
+		}
+		result
 	}
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{AddressBook, DappsSettingsStore, DappsSettings, NewDappsPolicy};
+	use super::{AddressBook, DappsSettingsStore, DappsSettings, NewDappsPolicy, PolicyAccountsStore, SpendingLimitsStore};
 	use account_provider::DappId;
+	use account_provider::policy::PolicyAccount;
+	use account_provider::spending::SpendingLimit;
 	use std::collections::HashMap;
+	use std::fs;
 	use ethjson::misc::AccountMeta;
 	use tempdir::TempDir;
 
@@ -399,6 +574,92 @@ mod tests {
 		]);
 	}
 
+	#[test]
+	fn should_save_and_reload_policy_accounts() {
+		let tempdir = TempDir::new("").unwrap();
+		let mut store = PolicyAccountsStore::new(tempdir.path());
+		let account = PolicyAccount {
+			name: "Treasury".to_owned(),
+			owners: vec![2.into(), 3.into()],
+			threshold: 2,
+		};
+		store.insert(1.into(), account.clone());
+		store.insert(4.into(), account.clone());
+		store.remove(4.into());
+
+		let store = PolicyAccountsStore::new(tempdir.path());
+		assert_eq!(store.get(), hash_map![1.into() => account]);
+	}
+
+	#[test]
+	fn should_save_and_reload_spending_limits() {
+		let tempdir = TempDir::new("").unwrap();
+		let mut store = SpendingLimitsStore::new(tempdir.path()).unwrap();
+		let limit = SpendingLimit {
+			max_value: Some(1_000.into()),
+			daily_limit: None,
+			allowed_recipients: Some(vec![2.into()]),
+			allowed_methods: Some(vec![[0xa9, 0x05, 0x9c, 0xbb]]),
+		};
+		store.insert(1.into(), limit.clone()).unwrap();
+		store.insert(3.into(), limit.clone()).unwrap();
+		store.set_spent(1.into(), 7, 10.into()).unwrap();
+		store.set_spent(3.into(), 7, 20.into()).unwrap();
+		store.remove(3.into()).unwrap();
+
+		let store = SpendingLimitsStore::new(tempdir.path()).unwrap();
+		assert_eq!(store.get(), hash_map![1.into() => limit]);
+		assert_eq!(store.spent(&1.into(), 7), 10.into());
+		assert_eq!(store.spent(&1.into(), 8), 0.into());
+		assert_eq!(store.spent(&3.into(), 7), 0.into());
+		// temporary files are renamed
+		assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 2);
+	}
+
+	#[test]
+	fn should_not_open_unreadable_spending_limits() {
+		let tempdir = TempDir::new("").unwrap();
+		fs::write(tempdir.path().join("spending_limits.json"), r#"{"0x0000000000000000000000000000000000000001":"#).unwrap();
+		assert!(SpendingLimitsStore::new(tempdir.path()).is_err());
+
+		let tempdir = TempDir::new("").unwrap();
+		fs::write(tempdir.path().join("daily_spending.json"), "[]").unwrap();
+		assert!(SpendingLimitsStore::new(tempdir.path()).is_err());
+	}
+
+	#[test]
+	fn should_keep_previous_spending_limit_if_it_cant_be_saved() {
+		// given
+		let tempdir = TempDir::new("").unwrap();
+		let mut store = SpendingLimitsStore::new(tempdir.path()).unwrap();
+		let limit = SpendingLimit { max_value: Some(1.into()), ..Default::default() };
+		store.insert(1.into(), limit.clone()).unwrap();
+		fs::remove_file(tempdir.path().join("spending_limits.json")).unwrap();
+		fs::create_dir(tempdir.path().join("spending_limits.json")).unwrap();
+
+		// when
+		let result = store.insert(1.into(), SpendingLimit::default());
+
+		// then
+		assert!(result.is_err());
+		assert_eq!(store.get(), hash_map![1.into() => limit]);
+		assert!(store.remove(1.into()).is_err());
+		assert_eq!(store.get().len(), 1);
+	}
+
+	#[test]
+	fn should_reject_everything_for_malformed_spending_limits() {
+		let tempdir = TempDir::new("").unwrap();
+		let path = tempdir.path().join("spending_limits.json");
+		fs::write(&path, r#"{"0x0000000000000000000000000000000000000001":{"allowedMethods":["0xa9059c"]}}"#).unwrap();
+
+		let store = SpendingLimitsStore::new(tempdir.path()).unwrap();
+		assert_eq!(store.get(), hash_map![1.into() => SpendingLimit {
+			allowed_recipients: Some(Vec::new()),
+			..Default::default()
+		}]);
+	}
+
 	#[test]
 	fn should_save_and_reload_dapps_settings() {
 		// given
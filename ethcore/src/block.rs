@@ -239,6 +239,58 @@ pub struct OpenBlock<'x> {
 	engine: &'x EthEngine,
 }
 
+/// Outcome of a transaction executed against a scratch copy of block state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedTransaction {
+	/// Gas actually used by the transaction.
+	pub gas_used: U256,
+	/// Increase of the block author's balance caused by the transaction.
+	pub profit: U256,
+	/// `true` if the transaction reverted or ran out of gas.
+	pub failed: bool,
+}
+
+/// A scratch copy of `OpenBlock` state. Transactions applied here are never included in the block.
+pub struct BlockSimulation<'x> {
+	state: State<StateDB>,
+	env_info: EnvInfo,
+	engine: &'x EthEngine,
+}
+
+impl<'x> BlockSimulation<'x> {
+	/// Apply a transaction to the scratch state and return its outcome.
+	pub fn apply(&mut self, t: &SignedTransaction) -> Result<SimulatedTransaction, Error> {
+		let author = self.env_info.author;
+		let balance_before = self.state.balance(&author)?;
+		let executed = self.state.apply_uncommitted(&self.env_info, self.engine.machine(), t)?;
+		let balance_after = self.state.balance(&author)?;
+
+		let gas_used = executed.cumulative_gas_used - self.env_info.gas_used;
+		self.env_info.gas_used = executed.cumulative_gas_used;
+
+		Ok(SimulatedTransaction {
+			gas_used,
+			profit: if balance_after > balance_before { balance_after - balance_before } else { U256::zero() },
+			failed: executed.exception.is_some(),
+		})
+	}
+
+	/// Run `f` against the scratch state and undo everything it applied afterwards.
+	pub fn dry_run<T, F>(&mut self, f: F) -> T where F: FnOnce(&mut Self) -> T {
+		let gas_used = self.env_info.gas_used;
+		self.state.checkpoint();
+		let result = f(self);
+		self.state.revert_to_checkpoint();
+		self.env_info.gas_used = gas_used;
+		result
+	}
+
+	/// Gas used by transactions in the block so far, including the simulated ones.
+	pub fn gas_used(&self) -> U256 {
+		self.env_info.gas_used
+	}
+}
+
 /// Just like `OpenBlock`, except that we've applied `Engine::on_close_block`, finished up the non-seal header fields,
 /// and collected the uncles.
 ///
@@ -363,6 +415,38 @@ impl<'x> OpenBlock<'x> {
 		Ok(self.block.receipts.last().expect("receipt just pushed; qed"))
 	}
 
+	/// Execute a transaction on top of the current block state without including it in the block.
+	pub fn simulate_transaction(&self, t: &SignedTransaction) -> Result<SimulatedTransaction, Error> {
+		if self.block.transactions_set.contains(&t.hash()) {
+			return Err(TransactionError::AlreadyImported.into());
+		}
+
+		self.simulation().apply(t)
+	}
+
+	/// Push transactions onto the block one after another.
+	///
+	/// Either all of them are pushed or, if any of them fails, none of them and the block is left unchanged.
+	pub fn push_transactions_atomically(&mut self, transactions: Vec<SignedTransaction>) -> Result<(), Error> {
+		let backup = self.block.clone();
+		for t in transactions {
+			if let Err(e) = self.push_transaction(t, None) {
+				self.block = backup;
+				return Err(e);
+			}
+		}
+		Ok(())
+	}
+
+	/// Create a scratch copy of the current block state for dry-running a sequence of transactions.
+	pub fn simulation(&self) -> BlockSimulation<'x> {
+		BlockSimulation {
+			state: self.block.state.clone(),
+			env_info: self.env_info(),
+			engine: self.engine,
+		}
+	}
+
 	/// Push transactions onto the block.
 	#[cfg(not(feature = "slow-blocks"))]
 	fn push_transactions(&mut self, transactions: Vec<SignedTransaction>) -> Result<(), Error> {
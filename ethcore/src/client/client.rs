@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::cmp;
 use std::collections::{HashSet, BTreeMap, BTreeSet, VecDeque};
 use std::fmt;
 use std::str::FromStr;
@@ -31,7 +32,7 @@ use kvdb::{DBValue, KeyValueDB, DBTransaction};
 use util_error::UtilError;
 
 // other
-use ethereum_types::{H256, Address, U256};
+use ethereum_types::{H256, Address, U256, U512};
 use block::{IsBlock, LockedBlock, Drain, ClosedBlock, OpenBlock, enact_verified, SealedBlock};
 use blockchain::{BlockChain, BlockProvider, TreeRoute, ImportRoute, TransactionAddress, ExtrasInsert};
 use client::ancient_import::AncientVerifier;
@@ -52,7 +53,7 @@ use client::{
 use encoded;
 use engines::{EthEngine, EpochTransition, ForkChoice};
 use error::{ImportErrorKind, BlockImportErrorKind, ExecutionError, CallError, BlockError, ImportResult, Error as EthcoreError};
-use vm::{EnvInfo, LastHashes};
+use vm::{self, EnvInfo, LastHashes};
 use evm::Schedule;
 use executive::{Executive, Executed, TransactOptions, contract_address};
 use factory::{Factories, VmFactory};
@@ -73,6 +74,7 @@ use trace::{TraceDB, ImportRequest as TraceImportRequest, LocalizedTrace, Databa
 use transaction::{self, LocalizedTransaction, UnverifiedTransaction, SignedTransaction, Transaction, Action};
 use types::filter::Filter;
 use types::ancestry_action::AncestryAction;
+use types::state_diff::StateDiff;
 use verification;
 use verification::{PreverifiedBlock, Verifier};
 use verification::queue::BlockQueue;
@@ -1215,6 +1217,16 @@ impl Client {
 		}.fake_sign(from)
 	}
 
+	/// Applies `overrides` to `state`.
+	/// The global account cache still holds the original storage of accounts whose storage
+	/// is replaced as a whole, so in that case the state is swapped for a clone which bypasses it.
+	fn apply_overrides(state: &mut State<StateDB>, overrides: &state::StateOverride) -> Result<(), CallError> {
+		if overrides.values().any(|account| account.reset_storage) {
+			*state = state.clone();
+		}
+		state.apply_overrides(overrides).map_err(|e| ExecutionError::from(e).into())
+	}
+
 	fn do_virtual_call(
 		machine: &::machine::EthereumMachine,
 		env_info: &EnvInfo,
@@ -1248,6 +1260,15 @@ impl Client {
 
 		let state_diff = analytics.state_diffing;
 
+		if analytics.vm_profiling {
+			let executed = if analytics.transaction_tracing {
+				call(state, env_info, machine, state_diff, t, TransactOptions::new(trace::ExecutiveTracer::default(), trace::ProfilingVMTracer::toplevel()))
+			} else {
+				call(state, env_info, machine, state_diff, t, TransactOptions::new(trace::NoopTracer, trace::ProfilingVMTracer::toplevel()))
+			};
+			return executed.map(Executed::into_profiled);
+		}
+
 		match (analytics.transaction_tracing, analytics.vm_tracing) {
 			(true, true) => call(state, env_info, machine, state_diff, t, TransactOptions::with_tracing_and_vm_tracing()),
 			(true, false) => call(state, env_info, machine, state_diff, t, TransactOptions::with_tracing()),
@@ -1382,7 +1403,7 @@ impl CallContract for Client {
 
 		let transaction = self.contract_call_tx(block_id, address, data);
 
-		self.call(&transaction, Default::default(), state, &header)
+		self.call(&transaction, Default::default(), &Default::default(), state, &header)
 			.map_err(|e| format!("{:?}", e))
 			.map(|executed| executed.output)
 	}
@@ -1424,7 +1445,8 @@ impl StateClient for Client {
 impl Call for Client {
 	type State = State<::state_db::StateDB>;
 
-	fn call(&self, transaction: &SignedTransaction, analytics: CallAnalytics, state: &mut Self::State, header: &Header) -> Result<Executed, CallError> {
+	fn call(&self, transaction: &SignedTransaction, analytics: CallAnalytics, overrides: &state::StateOverride, state: &mut Self::State, header: &Header) -> Result<Executed, CallError> {
+		Self::apply_overrides(state, overrides)?;
 		let env_info = EnvInfo {
 			number: header.number(),
 			author: header.author().clone(),
@@ -1462,7 +1484,21 @@ impl Call for Client {
 		Ok(results)
 	}
 
-	fn estimate_gas(&self, t: &SignedTransaction, state: &Self::State, header: &Header) -> Result<U256, CallError> {
+	fn simulate(&self, transactions: &[(SignedTransaction, CallAnalytics)], overrides: &state::StateOverride, state: &mut Self::State, header: &Header)
+		-> Result<(Vec<Executed>, StateDiff), CallError>
+	{
+		Self::apply_overrides(state, overrides)?;
+		let original = state.clone();
+		let results = self.call_many(transactions, state, header)?;
+		let diff = state.diff_from(original).map_err(ExecutionError::from)?;
+
+		Ok((results, diff))
+	}
+
+	fn estimate_gas(&self, t: &SignedTransaction, overrides: &state::StateOverride, state: &Self::State, header: &Header) -> Result<U256, CallError> {
+		let mut state = state.clone();
+		Self::apply_overrides(&mut state, overrides)?;
+
 		let (mut upper, max_upper, env_info) = {
 			let init = *header.gas_limit();
 			let max = init * U256::from(10);
@@ -1481,29 +1517,57 @@ impl Call for Client {
 		};
 
 		let sender = t.sender();
-		let options = || TransactOptions::with_tracing().dont_check_nonce();
+		let options = || TransactOptions::with_tracing();
+
+		// with a gas price given the sender has to be able to pay for the gas
+		let balance = state.balance(&sender).map_err(ExecutionError::from)?;
+		let allowance = match t.gas_price.is_zero() {
+			true => max_upper,
+			false if balance < t.value => {
+				return Err(ExecutionError::NotEnoughCash { required: t.value.into(), got: balance.into() }.into())
+			},
+			false => cmp::min(max_upper, (balance - t.value) / t.gas_price),
+		};
+		let lower: U256 = t.gas_required(&self.engine.schedule(env_info.number)).into();
+		if allowance < lower {
+			return Err(ExecutionError::NotEnoughCash {
+				required: U512::from(t.value) + U512::from(t.gas_price) * U512::from(lower),
+				got: balance.into(),
+			}.into())
+		}
+		upper = cmp::min(upper, allowance);
 
-		let cond = |gas| {
+		let exec = |gas| {
 			let mut tx = t.as_unsigned().clone();
 			tx.gas = gas;
 			let tx = tx.fake_sign(sender);
 
 			let mut clone = state.clone();
-			Ok(Executive::new(&mut clone, &env_info, self.engine.machine())
+			Executive::new(&mut clone, &env_info, self.engine.machine())
 				.transact_virtual(&tx, options())
-				.map(|r| r.exception.is_none())
-				.unwrap_or(false))
+				.map_err(CallError::from)
 		};
+		let cond = |gas| exec(gas).map(|r| r.exception.is_none());
 
 		if !cond(upper)? {
-			upper = max_upper;
-			if !cond(upper)? {
-				trace!(target: "estimate_gas", "estimate_gas failed with {}", upper);
-				let err = ExecutionError::Internal(format!("Requires higher than upper limit of {}", upper));
-				return Err(err.into())
+			upper = allowance;
+			let executed = exec(upper)?;
+			if let Some(exception) = executed.exception {
+				trace!(target: "estimate_gas", "estimate_gas failed with {} at {}", exception, upper);
+				return Err(match exception {
+					vm::Error::Reverted => CallError::Reverted(executed.output),
+					vm::Error::OutOfGas if allowance < max_upper && cond(max_upper)? => {
+						let required = binary_chop(allowance, max_upper, &cond)?;
+						ExecutionError::NotEnoughCash {
+							required: U512::from(t.value) + U512::from(t.gas_price) * U512::from(required),
+							got: balance.into(),
+						}.into()
+					},
+					vm::Error::OutOfGas => CallError::OutOfGas(max_upper),
+					_ => CallError::Exceptional,
+				});
 			}
 		}
-		let lower = t.gas_required(&self.engine.schedule(env_info.number)).into();
 		if cond(lower)? {
 			trace!(target: "estimate_gas", "estimate_gas succeeded with {}", lower);
 			return Ok(lower)
@@ -1960,6 +2024,14 @@ impl BlockChainClient for Client {
 		self.importer.miner.ready_transactions(self)
 	}
 
+	fn transaction_spam_peers(&self) -> Vec<usize> {
+		self.importer.miner.spam_peers()
+	}
+
+	fn remove_transaction_peer(&self, peer_id: usize) {
+		self.importer.miner.remove_peer(&peer_id)
+	}
+
 	fn signing_chain_id(&self) -> Option<u64> {
 		self.engine.signing_chain_id(&self.latest_env_info())
 	}
@@ -2027,7 +2099,7 @@ impl IoClient for Client {
 				notify.transactions_received(&txs, peer_id);
 			});
 
-			client.importer.miner.import_external_transactions(client, txs);
+			client.importer.miner.import_peer_transactions(client, peer_id, txs);
 		}).unwrap_or_else(|e| {
 			debug!(target: "client", "Ignoring {} transactions: {}", len, e);
 		});
@@ -17,12 +17,21 @@
 //! Simple Client used for EVM tests.
 
 use std::fmt;
+use std::collections::BTreeMap;
 use std::sync::Arc;
-use ethereum_types::{H256, U256, H160};
-use {factory, journaldb, trie, kvdb_memorydb, bytes};
+use ethereum_types::{H256, U256, H160, Address};
+use hash::{keccak, KECCAK_EMPTY};
+use {factory, journaldb, trie, kvdb_memorydb, bytes, rlp};
 use kvdb::{self, KeyValueDB};
-use {state, state_db, client, executive, trace, transaction, db, spec, pod_state, log_entry, receipt};
+use {state, state_db, client, executive, trace, transaction, db, spec, pod_state, log_entry, receipt, encoded};
+use block::Block;
+use pod_account::PodAccount;
+use trie::Trie;
+use types::basic_account::BasicAccount;
+use client::{Client, ClientConfig, BlockChainClient, ImportBlock};
 use factory::Factories;
+use io::IoChannel;
+use miner::Miner;
 use evm::{VMType, FinalizationResult};
 use vm::{self, ActionParams};
 
@@ -62,6 +71,7 @@ impl fmt::Display for EvmTestError {
 }
 
 use ethereum;
+use ethjson;
 use ethjson::state::test::ForkSpec;
 
 lazy_static! {
@@ -77,6 +87,7 @@ lazy_static! {
 pub struct EvmTestClient<'a> {
 	state: state::State<state_db::StateDB>,
 	spec: &'a spec::Spec,
+	factories: Factories,
 }
 
 impl<'a> fmt::Debug for EvmTestClient<'a> {
@@ -103,32 +114,177 @@ impl<'a> EvmTestClient<'a> {
 		}
 	}
 
+	/// Creates a spec for a json blockchain test, with genesis header and state taken from the test.
+	/// Returns `None` if the test network is not supported.
+	pub fn spec_from_blockchain_test(test: &ethjson::blockchain::BlockChain) -> Result<Option<spec::Spec>, EvmTestError> {
+		let mut spec = match Self::spec_from_json(&test.network) {
+			Some(spec) => spec.clone(),
+			None => return Ok(None),
+		};
+
+		spec.set_genesis_state(test.pre_state.clone().into())?;
+		spec.overwrite_genesis_params(spec::Genesis::from(test.genesis()));
+		if !spec.is_state_root_valid() {
+			return Err(EvmTestError::PostCondition("Invalid genesis state root".into()));
+		}
+
+		Ok(Some(spec))
+	}
+
+	/// Imports given blocks into a fresh client with in-memory DB and returns the best block header.
+	/// Invalid blocks are skipped, like they would be during sync.
+	pub fn import_blocks(spec: &spec::Spec, blocks: Vec<bytes::Bytes>) -> Result<encoded::Header, EvmTestError> {
+		let db = Arc::new(kvdb_memorydb::create(db::NUM_COLUMNS.expect("We use column-based DB; qed")));
+		let mut config = ClientConfig::default();
+		config.history = 8;
+		let client = Client::new(
+			config,
+			spec,
+			db,
+			Arc::new(Miner::new_for_tests(spec, None)),
+			IoChannel::disconnected(),
+		)?;
+
+		for block in blocks {
+			if Block::is_good(&block) {
+				let _ = client.import_block(block);
+				client.flush_queue();
+				client.import_verified_blocks();
+			}
+		}
+
+		client.block_header(client::BlockId::Latest)
+			.ok_or_else(|| EvmTestError::PostCondition("Missing best block header".into()))
+	}
+
+	/// Returns the fork which rules validate the transaction of a json transaction test at given block.
+	pub fn transaction_test_fork(block_number: Option<u64>) -> ForkSpec {
+		match block_number {
+			Some(x) if x >= 3_000_000 => ForkSpec::Byzantium,
+			Some(x) if x >= 1_150_000 => ForkSpec::Homestead,
+			_ => ForkSpec::Frontier,
+		}
+	}
+
+	/// Returns the schedule used to validate the transaction of a json transaction test at given block.
+	pub fn transaction_test_schedule(block_number: Option<u64>) -> vm::Schedule {
+		match Self::transaction_test_fork(block_number) {
+			ForkSpec::Byzantium => vm::Schedule::new_byzantium(),
+			ForkSpec::Homestead => vm::Schedule::new_homestead(),
+			_ => vm::Schedule::new_frontier(),
+		}
+	}
+
+	/// Decodes and validates the transaction of a json transaction test and compares it with the test expectations.
+	/// Returns `None` if the transaction is invalid, as expected by the test.
+	pub fn check_transaction_test(test: ethjson::transaction::TransactionTest) -> Result<Option<transaction::SignedTransaction>, EvmTestError> {
+		let number: Option<u64> = test.block_number.map(Into::into);
+		let schedule = Self::transaction_test_schedule(number);
+		let allow_chain_id_of_one = number.map_or(false, |n| n >= 2_675_000);
+		let allow_unsigned = number.map_or(false, |n| n >= 3_000_000);
+
+		let rlp: Vec<u8> = test.rlp.into();
+		let result = rlp::Rlp::new(&rlp)
+			.as_val()
+			.map_err(::error::Error::from)
+			.and_then(|t: transaction::UnverifiedTransaction| {
+				let chain_id = if allow_chain_id_of_one { Some(1) } else { None };
+				t.verify_basic(schedule.have_delegate_call, chain_id, allow_unsigned)?;
+				if !allow_unsigned || !t.is_unsigned() {
+					t.recover_public()?;
+				}
+				let gas_required = U256::from(t.gas_required(&schedule));
+				if t.gas < gas_required {
+					let bounds = ::unexpected::OutOfBounds { min: Some(gas_required), max: None, found: t.gas };
+					return Err(transaction::Error::InvalidGasLimit(bounds).into());
+				}
+				Ok(t)
+			});
+
+		let (t, expected) = match (result, test.transaction) {
+			(Err(_), None) => return Ok(None),
+			(Err(err), Some(_)) => return Err(EvmTestError::PostCondition(format!("Unexpected transaction error: {}", err))),
+			(Ok(_), None) => return Err(EvmTestError::PostCondition("Transaction is expected to be invalid".into())),
+			(Ok(t), Some(expected)) => (transaction::SignedTransaction::new(t)?, expected),
+		};
+
+		// the transaction is only compared when the test specifies its sender
+		let sender: Address = match test.sender {
+			Some(sender) => sender.into(),
+			None => return Ok(Some(t)),
+		};
+		let mismatch = |what: &str| Err(EvmTestError::PostCondition(format!("{} mismatch", what)));
+
+		if t.sender() != sender {
+			return Err(EvmTestError::PostCondition(format!("Sender mismatch (got: {}, expected: {})", t.sender(), sender)));
+		}
+		match t.chain_id() {
+			None => {},
+			Some(1) if allow_chain_id_of_one => {},
+			Some(chain_id) => return Err(EvmTestError::PostCondition(format!("Unacceptable chain id {}", chain_id))),
+		}
+		let data: Vec<u8> = expected.data.into();
+		if t.data != data {
+			return mismatch("Data");
+		}
+		if t.gas_price != expected.gas_price.into() {
+			return mismatch("Gas price");
+		}
+		if t.nonce != expected.nonce.into() {
+			return mismatch("Nonce");
+		}
+		if t.value != expected.value.into() {
+			return mismatch("Value");
+		}
+		let to: Option<ethjson::hash::Address> = expected.to.into();
+		let to: Option<Address> = to.map(Into::into);
+		match t.action {
+			transaction::Action::Call(dest) if Some(dest) == to => {},
+			transaction::Action::Create if to.is_none() => {},
+			_ => return mismatch("Destination"),
+		}
+
+		Ok(Some(t))
+	}
+
 	/// Creates new EVM test client with in-memory DB initialized with genesis of given Spec.
 	pub fn new(spec: &'a spec::Spec) -> Result<Self, EvmTestError> {
-		let factories = Self::factories();
+		let factories = Self::factories(trie::TrieSpec::Secure);
 		let state =	Self::state_from_spec(spec, &factories)?;
 
 		Ok(EvmTestClient {
 			state,
 			spec,
+			factories,
 		})
 	}
 
 	/// Creates new EVM test client with in-memory DB initialized with given PodState.
 	pub fn from_pod_state(spec: &'a spec::Spec, pod_state: pod_state::PodState) -> Result<Self, EvmTestError> {
-		let factories = Self::factories();
+		Self::with_pod_state(spec, pod_state, trie::TrieSpec::Secure)
+	}
+
+	/// Creates new EVM test client with in-memory DB initialized with given PodState,
+	/// keeping the preimages of trie keys so that the resulting state can be listed with `pod_state`.
+	pub fn transition_from_pod_state(spec: &'a spec::Spec, pod_state: pod_state::PodState) -> Result<Self, EvmTestError> {
+		Self::with_pod_state(spec, pod_state, trie::TrieSpec::Fat)
+	}
+
+	fn with_pod_state(spec: &'a spec::Spec, pod_state: pod_state::PodState, trie_spec: trie::TrieSpec) -> Result<Self, EvmTestError> {
+		let factories = Self::factories(trie_spec);
 		let state =	Self::state_from_pod(spec, &factories, pod_state)?;
 
 		Ok(EvmTestClient {
 			state,
 			spec,
+			factories,
 		})
 	}
 
-	fn factories() -> Factories {
+	fn factories(trie_spec: trie::TrieSpec) -> Factories {
 		Factories {
 			vm: factory::VmFactory::new(VMType::Interpreter, 5 * 1024),
-			trie: trie::TrieFactory::new(trie::TrieSpec::Secure),
+			trie: trie::TrieFactory::new(trie_spec),
 			accountdb: Default::default(),
 		}
 	}
@@ -174,6 +330,51 @@ impl<'a> EvmTestClient<'a> {
 		&self.state
 	}
 
+	/// Consume the client and return all accounts of the committed state, including storage.
+	/// Only clients created with `transition_from_pod_state` can list their state.
+	pub fn pod_state(self) -> Result<pod_state::PodState, EvmTestError> {
+		if !self.factories.trie.is_fat() {
+			return Err(EvmTestError::PostCondition("Listing the state requires a fat trie".into()));
+		}
+
+		let factories = self.factories;
+		let (root, db) = self.state.drop();
+		let state_trie = factories.trie.readonly(db.as_hashdb(), &root).map_err(|e| EvmTestError::Trie(*e))?;
+
+		let mut accounts = BTreeMap::new();
+		for item in state_trie.iter().map_err(|e| EvmTestError::Trie(*e))? {
+			let (address, account) = item.map_err(|e| EvmTestError::Trie(*e))?;
+			let address = Address::from_slice(&address);
+			let account: BasicAccount = rlp::decode(&account)
+				.map_err(|e| EvmTestError::PostCondition(format!("Invalid account {}: {}", address, e)))?;
+
+			let account_db = factories.accountdb.readonly(db.as_hashdb(), keccak(&address));
+			let code = match account.code_hash {
+				KECCAK_EMPTY => Some(vec![]),
+				ref code_hash => account_db.get(code_hash).map(|code| code.to_vec()),
+			};
+
+			let mut storage = BTreeMap::new();
+			let storage_trie = factories.trie.readonly(account_db.as_hashdb(), &account.storage_root)
+				.map_err(|e| EvmTestError::Trie(*e))?;
+			for item in storage_trie.iter().map_err(|e| EvmTestError::Trie(*e))? {
+				let (key, value) = item.map_err(|e| EvmTestError::Trie(*e))?;
+				let value: U256 = rlp::decode(&value)
+					.map_err(|e| EvmTestError::PostCondition(format!("Invalid storage of {}: {}", address, e)))?;
+				storage.insert(H256::from_slice(&key), value.into());
+			}
+
+			accounts.insert(address, PodAccount {
+				balance: account.balance,
+				nonce: account.nonce,
+				code,
+				storage,
+			});
+		}
+
+		Ok(pod_state::PodState::from(accounts))
+	}
+
 	/// Execute the VM given ActionParams and tracer.
 	/// Returns amount of gas left and the output.
 	pub fn call<T: trace::Tracer, V: trace::VMTracer>(
@@ -233,7 +434,7 @@ impl<'a> EvmTestClient<'a> {
 				self.state.commit().ok();
 				TransactResult::Ok {
 					state_root: *self.state.root(),
-					gas_left: initial_gas - result.receipt.gas_used,
+					gas_left: initial_gas - (result.receipt.gas_used - env_info.gas_used),
 					outcome: result.receipt.outcome,
 					output: result.output,
 					trace: result.trace,
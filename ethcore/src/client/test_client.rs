@@ -52,6 +52,7 @@ use miner::{Miner, MinerService};
 use spec::Spec;
 use types::basic_account::BasicAccount;
 use types::pruning_info::PruningInfo;
+use types::state_diff::StateDiff;
 
 use verification::queue::QueueInfo;
 use block::{OpenBlock, SealedBlock, ClosedBlock};
@@ -63,7 +64,7 @@ use header::Header;
 use encoded;
 use engines::EthEngine;
 use trie;
-use state::StateInfo;
+use state::{StateInfo, StateOverride};
 use views::BlockView;
 
 /// Test client.
@@ -563,21 +564,28 @@ impl Call for TestBlockChainClient {
 	// State will not be used by test client anyway, since all methods that accept state are mocked
 	type State = ();
 
-	fn call(&self, _t: &SignedTransaction, _analytics: CallAnalytics, _state: &mut Self::State, _header: &Header) -> Result<Executed, CallError> {
+	fn call(&self, _t: &SignedTransaction, _analytics: CallAnalytics, _overrides: &StateOverride, _state: &mut Self::State, _header: &Header) -> Result<Executed, CallError> {
 		self.execution_result.read().clone().unwrap()
 	}
 
 	fn call_many(&self, txs: &[(SignedTransaction, CallAnalytics)], state: &mut Self::State, header: &Header) -> Result<Vec<Executed>, CallError> {
 		let mut res = Vec::with_capacity(txs.len());
 		for &(ref tx, analytics) in txs {
-			res.push(self.call(tx, analytics, state, header)?);
+			res.push(self.call(tx, analytics, &Default::default(), state, header)?);
 		}
 		Ok(res)
 	}
 
-	fn estimate_gas(&self, _t: &SignedTransaction, _state: &Self::State, _header: &Header) -> Result<U256, CallError> {
+	fn estimate_gas(&self, _t: &SignedTransaction, _overrides: &StateOverride, _state: &Self::State, _header: &Header) -> Result<U256, CallError> {
 		Ok(21000.into())
 	}
+
+	fn simulate(&self, txs: &[(SignedTransaction, CallAnalytics)], _overrides: &StateOverride, state: &mut Self::State, header: &Header)
+		-> Result<(Vec<Executed>, StateDiff), CallError>
+	{
+		let results = self.call_many(txs, state, header)?;
+		Ok((results, StateDiff { raw: Default::default() }))
+	}
 }
 
 impl StateInfo for () {
@@ -810,6 +818,14 @@ impl BlockChainClient for TestBlockChainClient {
 		self.miner.ready_transactions(self)
 	}
 
+	fn transaction_spam_peers(&self) -> Vec<usize> {
+		self.miner.spam_peers()
+	}
+
+	fn remove_transaction_peer(&self, peer_id: usize) {
+		self.miner.remove_peer(&peer_id)
+	}
+
 	fn signing_chain_id(&self) -> Option<u64> { None }
 
 	fn mode(&self) -> Mode { Mode::Active }
@@ -851,10 +867,10 @@ impl BlockChainClient for TestBlockChainClient {
 }
 
 impl IoClient for TestBlockChainClient {
-	fn queue_transactions(&self, transactions: Vec<Bytes>, _peer_id: usize) {
+	fn queue_transactions(&self, transactions: Vec<Bytes>, peer_id: usize) {
 		// import right here
 		let txs = transactions.into_iter().filter_map(|bytes| Rlp::new(&bytes).as_val().ok()).collect();
-		self.miner.import_external_transactions(self, txs);
+		self.miner.import_peer_transactions(self, peer_id, txs);
 	}
 
 	fn queue_ancient_block(&self, b: Bytes, _r: Bytes) -> Result<H256, BlockImportError> {
@@ -34,7 +34,7 @@ use receipt::LocalizedReceipt;
 use trace::LocalizedTrace;
 use transaction::{self, LocalizedTransaction, SignedTransaction};
 use verification::queue::QueueInfo as BlockQueueInfo;
-use state::StateInfo;
+use state::{StateInfo, StateOverride};
 use header::Header;
 use engines::EthEngine;
 
@@ -50,6 +50,7 @@ use types::call_analytics::CallAnalytics;
 use types::blockchain_info::BlockChainInfo;
 use types::block_status::BlockStatus;
 use types::pruning_info::PruningInfo;
+use types::state_diff::StateDiff;
 
 /// State information to be used during client query
 pub enum StateOrBlock {
@@ -181,15 +182,20 @@ pub trait Call {
 	/// Type representing chain state
 	type State: StateInfo;
 
-	/// Makes a non-persistent transaction call.
-	fn call(&self, tx: &SignedTransaction, analytics: CallAnalytics, state: &mut Self::State, header: &Header) -> Result<Executed, CallError>;
+	/// Makes a non-persistent transaction call on top of `state` with `overrides` applied.
+	fn call(&self, tx: &SignedTransaction, analytics: CallAnalytics, overrides: &StateOverride, state: &mut Self::State, header: &Header) -> Result<Executed, CallError>;
 
 	/// Makes multiple non-persistent but dependent transaction calls.
 	/// Returns a vector of successes or a failure if any of the transaction fails.
 	fn call_many(&self, txs: &[(SignedTransaction, CallAnalytics)], state: &mut Self::State, header: &Header) -> Result<Vec<Executed>, CallError>;
 
-	/// Estimates how much gas will be necessary for a call.
-	fn estimate_gas(&self, t: &SignedTransaction, state: &Self::State, header: &Header) -> Result<U256, CallError>;
+	/// Estimates how much gas will be necessary for a call on top of `state` with `overrides` applied.
+	fn estimate_gas(&self, t: &SignedTransaction, overrides: &StateOverride, state: &Self::State, header: &Header) -> Result<U256, CallError>;
+
+	/// Makes multiple non-persistent but dependent transaction calls on top of `state` with `overrides` applied.
+	/// Returns results of all calls and a combined diff of the state they produced.
+	fn simulate(&self, txs: &[(SignedTransaction, CallAnalytics)], overrides: &StateOverride, state: &mut Self::State, header: &Header)
+		-> Result<(Vec<Executed>, StateDiff), CallError>;
 }
 
 /// Provides `engine` method
@@ -323,6 +329,12 @@ pub trait BlockChainClient : Sync + Send + AccountData + BlockChain + CallContra
 	/// List all transactions that are allowed into the next block.
 	fn ready_transactions(&self) -> Vec<Arc<VerifiedTransaction>>;
 
+	/// List network peers which keep relaying transactions that never get mined.
+	fn transaction_spam_peers(&self) -> Vec<usize>;
+
+	/// Forget transaction statistics of a disconnected network peer.
+	fn remove_transaction_peer(&self, peer_id: usize);
+
 	/// Sorted list of transaction gas prices from at least last sample_size blocks.
 	fn gas_price_corpus(&self, sample_size: usize) -> ::stats::Corpus<U256> {
 		let mut h = self.chain_info().best_block_hash;
@@ -20,7 +20,7 @@ use ethereum_types::{U256, U512, Address};
 use bytes::Bytes;
 use trie;
 use vm;
-use trace::{VMTrace, FlatTrace};
+use trace::{VMTrace, VMProfile, FlatTrace};
 use log_entry::LogEntry;
 use state_diff::StateDiff;
 
@@ -67,6 +67,28 @@ pub struct Executed<T = FlatTrace, V = VMTrace> {
 	pub vm_trace: Option<V>,
 	/// The state diff, if we traced it.
 	pub state_diff: Option<StateDiff>,
+	/// The instruction coverage and gas profile, if we profiled it.
+	pub vm_profile: Option<VMProfile>,
+}
+
+impl<T> Executed<T, VMProfile> {
+	/// Move the profile recorded by `ProfilingVMTracer` out of the VM trace.
+	pub fn into_profiled<V>(self) -> Executed<T, V> {
+		Executed {
+			exception: self.exception,
+			gas: self.gas,
+			gas_used: self.gas_used,
+			refunded: self.refunded,
+			cumulative_gas_used: self.cumulative_gas_used,
+			logs: self.logs,
+			contracts_created: self.contracts_created,
+			output: self.output,
+			trace: self.trace,
+			vm_trace: None,
+			state_diff: self.state_diff,
+			vm_profile: self.vm_trace,
+		}
+	}
 }
 
 /// Result of executing the transaction.
@@ -163,6 +185,10 @@ pub enum CallError {
 	StatePruned,
 	/// Couldn't find an amount of gas that didn't result in an exception.
 	Exceptional,
+	/// Ran out of gas even with the highest gas limit allowed.
+	OutOfGas(U256),
+	/// Execution was reverted. Contains the returned output.
+	Reverted(Bytes),
 	/// Corrupt state.
 	StateCorrupt,
 	/// Error executing.
@@ -183,6 +209,8 @@ impl fmt::Display for CallError {
 			TransactionNotFound => "Transaction couldn't be found in the chain".into(),
 			StatePruned => "Couldn't find the transaction block's state in the chain".into(),
 			Exceptional => "An exception happened in the execution".into(),
+			OutOfGas(ref limit) => format!("Out of gas with gas limit of {}", limit),
+			Reverted(_) => "Execution reverted".into(),
 			StateCorrupt => "Stored state found to be corrupted.".into(),
 			Execution(ref e) => format!("{}", e),
 		};
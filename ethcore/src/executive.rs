@@ -658,6 +658,7 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 					trace: trace,
 					vm_trace: vm_trace,
 					state_diff: None,
+					vm_profile: None,
 				})
 			},
 			Ok(r) => {
@@ -673,6 +674,7 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 					trace: trace,
 					vm_trace: vm_trace,
 					state_diff: None,
+					vm_profile: None,
 				})
 			},
 		}
@@ -720,6 +722,7 @@ mod tests {
 	use trace::trace;
 	use trace::{FlatTrace, Tracer, NoopTracer, ExecutiveTracer};
 	use trace::{VMTrace, VMOperation, VMExecutedOperation, MemoryDiff, StorageDiff, VMTracer, NoopVMTracer, ExecutiveVMTracer};
+	use trace::{ProfilingVMTracer, InstructionProfile};
 	use transaction::{Action, Transaction};
 
 	fn make_frontier_machine(max_depth: usize) -> EthereumMachine {
@@ -895,6 +898,66 @@ mod tests {
 		}]);
 	}
 
+	#[test]
+	fn test_profiling_vm_tracer() {
+		// outer code:
+		//
+		// 60 00 - push 0 out size
+		// 60 00 - push 0 out offset
+		// 60 00 - push 0 in size
+		// 60 00 - push 0 in offset
+		// 60 00 - push 0 value
+		// 60 10 - push 0x10 to
+		// 61 ffff - push ffff gas
+		// f1 - CALL
+		//
+		// inner code:
+		//
+		// 60 01 - push 1
+		// 60 00 - push 0
+		// 55 - sstore
+
+		let code = "60006000600060006000601061fffff1".from_hex().unwrap();
+		let inner_code = "6001600055".from_hex().unwrap();
+		let sender = Address::from_str("4444444444444444444444444444444444444444").unwrap();
+		let address = Address::from_str("5555555555555555555555555555555555555555").unwrap();
+		let inner_address = Address::from_str("0000000000000000000000000000000000000010").unwrap();
+
+		let mut params = ActionParams::default();
+		params.address = address.clone();
+		params.code_address = address.clone();
+		params.sender = sender.clone();
+		params.origin = sender.clone();
+		params.gas = U256::from(100_000);
+		params.code = Some(Arc::new(code.clone()));
+		params.value = ActionValue::Transfer(U256::zero());
+		params.call_type = CallType::Call;
+		let mut state = get_temp_state();
+		state.init_code(&inner_address, inner_code.clone()).unwrap();
+		let info = EnvInfo::default();
+		let machine = make_byzantium_machine(5);
+		let mut substate = Substate::new();
+		let mut vm_tracer = ProfilingVMTracer::toplevel();
+
+		let mut ex = Executive::new(&mut state, &info, &machine);
+		let output = BytesRef::Fixed(&mut[0u8;0]);
+		ex.call(params, &mut substate, output, &mut NoopTracer, &mut vm_tracer).unwrap();
+
+		let profile = vm_tracer.drain().unwrap();
+		let profile_of = |gas: usize| InstructionProfile { count: 1, gas_used: gas.into() };
+
+		let outer = &profile.codes[&keccak(&code)];
+		assert_eq!(outer.keys().cloned().collect::<Vec<_>>(), vec![0, 2, 4, 6, 8, 10, 12, 15]);
+		assert_eq!(outer[&12], profile_of(3));
+		// gas consumed by the callee is not attributed to CALL
+		assert_eq!(outer[&15], profile_of(700));
+
+		let inner = &profile.codes[&keccak(&inner_code)];
+		assert_eq!(inner.len(), 3);
+		assert_eq!(inner[&0], profile_of(3));
+		assert_eq!(inner[&4], profile_of(20000));
+	}
+
 	#[test]
 	// Tracing is not suported in JIT
 	fn test_call_to_create() {
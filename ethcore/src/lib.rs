@@ -75,6 +75,7 @@ extern crate ethcore_transaction as transaction;
 extern crate ethereum_types;
 extern crate ethjson;
 extern crate ethkey;
+extern crate external_signer;
 extern crate hardware_wallet;
 extern crate hashdb;
 extern crate itertools;
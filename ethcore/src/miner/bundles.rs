@@ -0,0 +1,257 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bundles of transactions included atomically at the top of a block.
+//!
+//! Bundles are kept apart from the transaction pool: they are never propagated
+//! and their transactions are either all included back-to-back or not at all.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use ethereum_types::H256;
+use hash::keccak;
+use transaction::{self, SignedTransaction};
+
+use header::BlockNumber;
+
+/// Maximal number of bundles waiting for inclusion.
+pub const MAX_BUNDLES: usize = 256;
+/// Maximal number of transactions in a single bundle.
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 64;
+
+/// Reasons for rejecting a bundle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BundleError {
+	/// Bundle does not contain any transactions.
+	Empty,
+	/// Bundle contains more than `MAX_BUNDLE_TRANSACTIONS` transactions.
+	TooManyTransactions(usize),
+	/// Minimal block of the bundle is above its maximal block.
+	InvalidRange {
+		/// Minimal block number.
+		min_block: BlockNumber,
+		/// Maximal block number.
+		max_block: BlockNumber,
+	},
+	/// Maximal block of the bundle is already mined.
+	Expired {
+		/// Maximal block number.
+		max_block: BlockNumber,
+		/// Current best block number.
+		best_block: BlockNumber,
+	},
+	/// There are already `MAX_BUNDLES` bundles waiting.
+	LimitReached,
+	/// One of the transactions is invalid.
+	Transaction(transaction::Error),
+}
+
+impl fmt::Display for BundleError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			BundleError::Empty => write!(f, "Bundle does not contain any transactions."),
+			BundleError::TooManyTransactions(count) =>
+				write!(f, "Bundle contains {} transactions, at most {} are allowed.", count, MAX_BUNDLE_TRANSACTIONS),
+			BundleError::InvalidRange { min_block, max_block } =>
+				write!(f, "Invalid block range: {} is above {}.", min_block, max_block),
+			BundleError::Expired { max_block, best_block } =>
+				write!(f, "Bundle expired: block {} is already mined (best block: {}).", max_block, best_block),
+			BundleError::LimitReached => write!(f, "Too many bundles waiting for inclusion."),
+			BundleError::Transaction(ref e) => write!(f, "Invalid transaction: {}", e),
+		}
+	}
+}
+
+impl From<transaction::Error> for BundleError {
+	fn from(e: transaction::Error) -> Self {
+		BundleError::Transaction(e)
+	}
+}
+
+/// Transactions to be included together in a block within given range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+	/// Hash of the bundle.
+	pub hash: H256,
+	/// Transactions in order of inclusion.
+	pub transactions: Vec<SignedTransaction>,
+	/// First block the bundle can be included in.
+	pub min_block: BlockNumber,
+	/// Last block the bundle can be included in.
+	pub max_block: BlockNumber,
+}
+
+impl Bundle {
+	/// Create a new bundle. The hash is computed from the hashes of all transactions.
+	pub fn new(transactions: Vec<SignedTransaction>, min_block: BlockNumber, max_block: BlockNumber) -> Self {
+		let hashes: Vec<u8> = transactions.iter().flat_map(|tx| tx.hash().to_vec()).collect();
+		Bundle {
+			hash: keccak(&hashes),
+			transactions,
+			min_block,
+			max_block,
+		}
+	}
+
+	/// Returns `true` if the bundle can be included in block with given number.
+	pub fn is_valid_for(&self, block_number: BlockNumber) -> bool {
+		self.min_block <= block_number && block_number <= self.max_block
+	}
+}
+
+/// Bundles waiting for inclusion, in order of submission.
+#[derive(Debug)]
+pub struct Bundles {
+	bundles: Vec<Bundle>,
+	limit: usize,
+}
+
+impl Default for Bundles {
+	fn default() -> Self {
+		Bundles::new(MAX_BUNDLES)
+	}
+}
+
+impl Bundles {
+	/// Create an empty set of bundles holding at most `limit` bundles.
+	pub fn new(limit: usize) -> Self {
+		Bundles {
+			bundles: Vec::new(),
+			limit,
+		}
+	}
+
+	/// Add a bundle. Submitting the same bundle again updates its block range.
+	pub fn insert(&mut self, bundle: Bundle) -> Result<H256, BundleError> {
+		let hash = bundle.hash;
+		if let Some(existing) = self.bundles.iter_mut().find(|b| b.hash == hash) {
+			*existing = bundle;
+			return Ok(hash);
+		}
+
+		if self.bundles.len() >= self.limit {
+			return Err(BundleError::LimitReached);
+		}
+
+		self.bundles.push(bundle);
+		Ok(hash)
+	}
+
+	/// Remove bundle with given hash.
+	pub fn remove(&mut self, hash: &H256) -> bool {
+		let len = self.bundles.len();
+		self.bundles.retain(|b| &b.hash != hash);
+		self.bundles.len() != len
+	}
+
+	/// Remove bundles with any of their transactions among `included`,
+	/// they are either in the chain already or can't be included anymore.
+	pub fn remove_included(&mut self, included: &HashSet<H256>) {
+		self.bundles.retain(|b| !b.transactions.iter().any(|tx| included.contains(&tx.hash())));
+	}
+
+	/// Bundles which can be included in block with given number.
+	pub fn for_block(&self, block_number: BlockNumber) -> Vec<Bundle> {
+		self.bundles.iter().filter(|b| b.is_valid_for(block_number)).cloned().collect()
+	}
+
+	/// Drop bundles which can't be included in any block after `best_block`.
+	pub fn cull(&mut self, best_block: BlockNumber) {
+		self.bundles.retain(|b| b.max_block > best_block);
+	}
+
+	/// Number of bundles waiting.
+	pub fn len(&self) -> usize {
+		self.bundles.len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethkey::{Generator, Random};
+	use transaction::{Action, Transaction};
+
+	fn bundle(nonce: u64, min_block: BlockNumber, max_block: BlockNumber) -> Bundle {
+		let keypair = Random.generate().unwrap();
+		let tx = Transaction {
+			action: Action::Create,
+			value: 0.into(),
+			data: vec![],
+			gas: 100_000.into(),
+			gas_price: 1.into(),
+			nonce: nonce.into(),
+		}.sign(keypair.secret(), None);
+
+		Bundle::new(vec![tx], min_block, max_block)
+	}
+
+	#[test]
+	fn should_return_bundles_valid_for_block() {
+		// given
+		let mut bundles = Bundles::default();
+		let (b1, b2) = (bundle(0, 1, 2), bundle(0, 2, 5));
+		bundles.insert(b1.clone()).unwrap();
+		bundles.insert(b2.clone()).unwrap();
+
+		// then
+		assert_eq!(bundles.for_block(1), vec![b1.clone()]);
+		assert_eq!(bundles.for_block(2), vec![b1, b2.clone()]);
+		assert_eq!(bundles.for_block(6), vec![]);
+
+		// when
+		bundles.cull(2);
+
+		// then
+		assert_eq!(bundles.len(), 1);
+		assert_eq!(bundles.for_block(3), vec![b2]);
+	}
+
+	#[test]
+	fn should_remove_included_bundles() {
+		// given
+		let mut bundles = Bundles::default();
+		let (b1, b2) = (bundle(0, 1, 5), bundle(0, 1, 5));
+		bundles.insert(b1.clone()).unwrap();
+		bundles.insert(b2.clone()).unwrap();
+
+		// when
+		bundles.remove_included(&b1.transactions.iter().map(|tx| tx.hash()).collect());
+
+		// then
+		assert_eq!(bundles.for_block(2), vec![b2]);
+	}
+
+	#[test]
+	fn should_replace_resubmitted_bundle() {
+		// given
+		let mut bundles = Bundles::new(1);
+		let b1 = bundle(0, 1, 2);
+		let mut b1_extended = b1.clone();
+		b1_extended.max_block = 10;
+
+		// when
+		let hash = bundles.insert(b1).unwrap();
+
+		// then
+		assert_eq!(bundles.insert(b1_extended), Ok(hash));
+		assert_eq!(bundles.for_block(5).len(), 1);
+		assert_eq!(bundles.insert(bundle(1, 1, 2)), Err(BundleError::LimitReached));
+		assert!(bundles.remove(&hash));
+		assert_eq!(bundles.len(), 0);
+	}
+}
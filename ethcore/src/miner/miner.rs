@@ -38,7 +38,7 @@ use transaction::{
 use using_queue::{UsingQueue, GetAction};
 
 use account_provider::{AccountProvider, SignError as AccountError};
-use block::{ClosedBlock, IsBlock, Block, SealedBlock};
+use block::{ClosedBlock, OpenBlock, IsBlock, Block, SealedBlock};
 use client::{
 	BlockChain, ChainInfo, CallContract, BlockProducer, SealedBlockImporter, Nonce
 };
@@ -46,7 +46,9 @@ use client::BlockId;
 use executive::contract_address;
 use header::{Header, BlockNumber};
 use miner;
+use miner::bundles::{Bundle, Bundles, BundleError, MAX_BUNDLE_TRANSACTIONS};
 use miner::pool_client::{PoolClient, CachedNonceClient};
+use miner::profit::{self, ProfitStats, ProfitTracker};
 use receipt::{Receipt, RichReceipt};
 use spec::Spec;
 use state::State;
@@ -204,6 +206,8 @@ pub struct Miner {
 	transaction_queue: Arc<TransactionQueue>,
 	engine: Arc<EthEngine>,
 	accounts: Option<Arc<AccountProvider>>,
+	profit_tracker: Mutex<ProfitTracker>,
+	bundles: RwLock<Bundles>,
 }
 
 impl Miner {
@@ -218,6 +222,11 @@ impl Miner {
 		self.transaction_queue.add_listener(f);
 	}
 
+	/// Set a callback to be notified about transaction pool events.
+	pub fn add_transaction_events_listener(&self, f: Box<Fn(&[pool::TransactionEvent]) + Send + Sync>) {
+		self.transaction_queue.add_event_listener(f);
+	}
+
 	/// Creates new instance of miner Arc.
 	pub fn new(options: MinerOptions, gas_pricer: GasPricer, spec: &Spec, accounts: Option<Arc<AccountProvider>>) -> Self {
 		let limits = options.pool_limits.clone();
@@ -241,6 +250,8 @@ impl Miner {
 			transaction_queue: Arc::new(TransactionQueue::new(limits, verifier_options, tx_queue_strategy)),
 			accounts,
 			engine: spec.engine.clone(),
+			profit_tracker: Mutex::new(ProfitTracker::default()),
+			bundles: RwLock::new(Bundles::default()),
 		}
 	}
 
@@ -298,6 +309,67 @@ impl Miner {
 			})
 	}
 
+	/// Re-import transactions restored from a persisted queue.
+	///
+	/// Transactions are verified against the current state again and keep the priority
+	/// implied by their variant. Insertion order follows the order of `transactions`.
+	pub fn import_restored_transactions<C: miner::BlockChainClient>(
+		&self,
+		chain: &C,
+		transactions: Vec<pool::verifier::Transaction>,
+	) -> Vec<Result<(), transaction::Error>> {
+		trace!(target: "miner", "Importing {} restored transactions", transactions.len());
+		let client = self.pool_client(chain);
+		self.transaction_queue.import(client, transactions)
+	}
+
+	/// Import transactions received from given network peer.
+	///
+	/// Outcomes of these transactions are used to score the peer, see `spam_peers`.
+	pub fn import_peer_transactions<C: miner::BlockChainClient>(
+		&self,
+		chain: &C,
+		peer_id: pool::PeerId,
+		transactions: Vec<UnverifiedTransaction>,
+	) -> Vec<Result<(), transaction::Error>> {
+		self.import_external(chain, transactions, Some(peer_id))
+	}
+
+	/// Returns network peers which keep relaying transactions that never get mined.
+	pub fn spam_peers(&self) -> Vec<pool::PeerId> {
+		self.transaction_queue.spam_peers()
+	}
+
+	/// Forget transaction score of given network peer.
+	pub fn remove_peer(&self, peer_id: &pool::PeerId) {
+		self.transaction_queue.remove_peer(peer_id)
+	}
+
+	fn import_external<C: miner::BlockChainClient>(
+		&self,
+		chain: &C,
+		transactions: Vec<UnverifiedTransaction>,
+		peer_id: Option<pool::PeerId>,
+	) -> Vec<Result<(), transaction::Error>> {
+		trace!(target: "external_tx", "Importing external transactions");
+		let client = self.pool_client(chain);
+		let transactions = transactions.into_iter().map(pool::verifier::Transaction::Unverified).collect();
+		let results = match peer_id {
+			Some(peer_id) => self.transaction_queue.import_from_peer(client, peer_id, transactions),
+			None => self.transaction_queue.import(client, transactions),
+		};
+
+		if !results.is_empty() && self.options.reseal_on_external_tx &&	self.sealing.lock().reseal_allowed() {
+			// --------------------------------------------------------------------------
+			// | NOTE Code below requires sealing locks.                                |
+			// | Make sure to release the locks before calling that method.             |
+			// --------------------------------------------------------------------------
+			self.update_sealing(chain);
+		}
+
+		results
+	}
+
 	fn pool_client<'a, C: 'a>(&'a self, chain: &'a C) -> PoolClient<'a, C> where
 		C: BlockChain + CallContract,
 	{
@@ -318,7 +390,7 @@ impl Miner {
 		let chain_info = chain.chain_info();
 
 		// Open block
-		let (mut open_block, original_work_hash) = {
+		let (mut open_block, original_work_hash, reopened_hash) = {
 			let mut sealing = self.sealing.lock();
 			let last_work_hash = sealing.queue.peek_last_ref().map(|pb| pb.block().header().hash());
 			let best_hash = chain_info.best_block_hash;
@@ -329,9 +401,11 @@ impl Miner {
 			//   if at least one was pushed successfully, close and enqueue new ClosedBlock;
 			//   otherwise, leave everything alone.
 			// otherwise, author a fresh block.
+			let mut reopened_hash = None;
 			let mut open_block = match sealing.queue.pop_if(|b| b.block().header().parent_hash() == &best_hash) {
 				Some(old_block) => {
 					trace!(target: "miner", "prepare_block: Already have previous work; updating and returning");
+					reopened_hash = Some(old_block.header().bare_hash());
 					// add transactions to old_block
 					chain.reopen_block(old_block)
 				}
@@ -351,7 +425,7 @@ impl Miner {
 				open_block.remove_gas_limit();
 			}
 
-			(open_block, last_work_hash)
+			(open_block, last_work_hash, reopened_hash)
 		};
 
 		let mut invalid_transactions = HashSet::new();
@@ -371,6 +445,23 @@ impl Miner {
 			None
 		};
 
+		// Bundles go to the top of the block, so only a fresh block can include them.
+		if open_block.transactions().is_empty() {
+			for bundle in self.bundles.read().for_block(block_number) {
+				if !self.bundle_succeeds(&open_block, &client, &bundle) {
+					trace!(target: "miner", "Skipping bundle {:?}: not all transactions succeed.", bundle.hash);
+					continue;
+				}
+
+				debug!(target: "miner", "Adding bundle {:?} with {} transactions.", bundle.hash, bundle.transactions.len());
+				let count = bundle.transactions.len();
+				match open_block.push_transactions_atomically(bundle.transactions) {
+					Ok(()) => tx_count += count,
+					Err(e) => warn!(target: "miner", "Error adding bundle {:?} after successful simulation: {:?}", bundle.hash, e),
+				}
+			}
+		}
+
 		let pending: Vec<Arc<_>> = self.transaction_queue.pending(
 			client.clone(),
 			chain_info.best_block_number,
@@ -378,6 +469,17 @@ impl Miner {
 			nonce_cap,
 		);
 
+		let mut expected_profit = None;
+		let pending = match self.options.tx_queue_strategy {
+			PrioritizationStrategy::GasPriceOnly => pending,
+			PrioritizationStrategy::Profit => {
+				let ordering = profit::order_by_profit(&open_block, pending);
+				debug!(target: "miner", "Profit strategy expects {} in fees, {} in pool order.", ordering.profit, ordering.baseline_profit);
+				expected_profit = Some((ordering.profit, ordering.baseline_profit));
+				ordering.transactions
+			},
+		};
+
 		let took_ms = |elapsed: &Duration| {
 			elapsed.as_secs() * 1000 + elapsed.subsec_nanos() as u64 / 1_000_000
 		};
@@ -462,6 +564,11 @@ impl Miner {
 
 		let block = open_block.close();
 
+		if let Some((profit, baseline_profit)) = expected_profit {
+			let header = block.header();
+			self.profit_tracker.lock().prepared(reopened_hash.as_ref(), header.bare_hash(), header.number(), profit, baseline_profit);
+		}
+
 		{
 			self.transaction_queue.remove(invalid_transactions.iter(), true);
 			self.transaction_queue.remove(not_allowed_transactions.iter(), false);
@@ -471,6 +578,25 @@ impl Miner {
 		(block, original_work_hash)
 	}
 
+	/// Returns `true` if every transaction of the bundle succeeds on top of `open_block`.
+	fn bundle_succeeds<C>(&self, open_block: &OpenBlock, client: &PoolClient<C>, bundle: &Bundle) -> bool where
+		C: BlockChain + CallContract,
+	{
+		let included: HashSet<H256> = open_block.transactions().iter().map(|tx| tx.hash()).collect();
+		let mut hashes = HashSet::new();
+		if !bundle.transactions.iter().all(|tx| !included.contains(&tx.hash()) && hashes.insert(tx.hash())) {
+			return false;
+		}
+
+		let mut simulation = open_block.simulation();
+		bundle.transactions.iter().all(|transaction| {
+			client.verify_signed(transaction).is_ok() && match simulation.apply(transaction) {
+				Ok(outcome) => !outcome.failed,
+				Err(_) => false,
+			}
+		})
+	}
+
 	/// Returns `true` if we should create pending block even if some other conditions are not met.
 	///
 	/// In general we always seal iff:
@@ -747,22 +873,7 @@ impl miner::MinerService for Miner {
 		chain: &C,
 		transactions: Vec<UnverifiedTransaction>
 	) -> Vec<Result<(), transaction::Error>> {
-		trace!(target: "external_tx", "Importing external transactions");
-		let client = self.pool_client(chain);
-		let results = self.transaction_queue.import(
-			client,
-			transactions.into_iter().map(pool::verifier::Transaction::Unverified).collect(),
-		);
-
-		if !results.is_empty() && self.options.reseal_on_external_tx &&	self.sealing.lock().reseal_allowed() {
-			// --------------------------------------------------------------------------
-			// | NOTE Code below requires sealing locks.                                |
-			// | Make sure to release the locks before calling that method.             |
-			// --------------------------------------------------------------------------
-			self.update_sealing(chain);
-		}
-
-		results
+		self.import_external(chain, transactions, None)
 	}
 
 	fn import_own_transaction<C: miner::BlockChainClient>(
@@ -797,6 +908,52 @@ impl miner::MinerService for Miner {
 		imported
 	}
 
+	fn submit_bundle<C: miner::BlockChainClient>(
+		&self,
+		chain: &C,
+		transactions: Vec<UnverifiedTransaction>,
+		min_block: Option<BlockNumber>,
+		max_block: BlockNumber,
+	) -> Result<H256, BundleError> {
+		use ethcore_miner::pool::client::Client;
+
+		let best_block = chain.chain_info().best_block_number;
+		let min_block = min_block.unwrap_or(best_block + 1);
+
+		if transactions.is_empty() {
+			return Err(BundleError::Empty);
+		}
+		if transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+			return Err(BundleError::TooManyTransactions(transactions.len()));
+		}
+		if min_block > max_block {
+			return Err(BundleError::InvalidRange { min_block, max_block });
+		}
+		if max_block <= best_block {
+			return Err(BundleError::Expired { max_block, best_block });
+		}
+
+		let client = self.pool_client(chain);
+		let transactions = transactions.into_iter()
+			.map(|tx| client.verify_transaction(tx))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let hash = self.bundles.write().insert(Bundle::new(transactions, min_block, max_block))?;
+		debug!(target: "miner", "Bundle {:?} accepted for blocks {}-{}.", hash, min_block, max_block);
+
+		// --------------------------------------------------------------------------
+		// | NOTE Code below requires sealing locks.                                |
+		// | Make sure to release the locks before calling that method.             |
+		// --------------------------------------------------------------------------
+		if min_block <= best_block + 1 && self.sealing.lock().enabled {
+			// Bundles are only included in fresh blocks, so don't reopen the current one.
+			self.sealing.lock().queue.pop_if(|_| true);
+			self.update_sealing(chain);
+		}
+
+		Ok(hash)
+	}
+
 	fn local_transactions(&self) -> BTreeMap<H256, pool::local_transactions::Status> {
 		self.transaction_queue.local_transactions()
 	}
@@ -866,6 +1023,10 @@ impl miner::MinerService for Miner {
 		self.transaction_queue.status()
 	}
 
+	fn profit_stats(&self) -> ProfitStats {
+		self.profit_tracker.lock().stats()
+	}
+
 	fn pending_receipt(&self, best_block: BlockNumber, hash: &H256) -> Option<RichReceipt> {
 		self.map_existing_pending_block(|pending| {
 			let txs = pending.transactions();
@@ -1038,6 +1199,30 @@ impl miner::MinerService for Miner {
 
 		// ...and at the end remove the old ones
 		self.transaction_queue.cull(client);
+		{
+			let mut bundles = self.bundles.write();
+			if bundles.len() > 0 {
+				let included: HashSet<H256> = enacted.iter()
+					.filter_map(|hash| chain.block_body(BlockId::Hash(*hash)))
+					.flat_map(|body| body.transaction_hashes())
+					.collect();
+				bundles.remove_included(&included);
+			}
+			bundles.cull(chain.chain_info().best_block_number);
+		}
+
+		{
+			// count the fees of our blocks once they make it to the chain.
+			let mut profit_tracker = self.profit_tracker.lock();
+			if !profit_tracker.is_empty() {
+				for hash in enacted {
+					if let Some(header) = chain.block_header(BlockId::Hash(*hash)).and_then(|h| h.decode().ok()) {
+						profit_tracker.sealed(&header.bare_hash());
+					}
+				}
+				profit_tracker.cull(chain.chain_info().best_block_number);
+			}
+		}
 
 		if enacted.len() > 0 || (imported.len() > 0 && self.options.reseal_on_uncle) {
 			// Reset `next_allowed_reseal` in case a block is imported.
@@ -1080,7 +1265,7 @@ mod tests {
 	use header::BlockNumber;
 	use rustc_hex::FromHex;
 
-	use client::{TestBlockChainClient, EachBlockWith, ChainInfo, ImportSealedBlock};
+	use client::{TestBlockChainClient, EachBlockWith, ChainInfo, ImportBlock, ImportSealedBlock};
 	use miner::MinerService;
 	use test_helpers::{generate_dummy_client, generate_dummy_client_with_spec_and_accounts};
 	use transaction::{Transaction};
@@ -1182,6 +1367,102 @@ mod tests {
 		assert!(!miner.prepare_pending_block(&client));
 	}
 
+	#[test]
+	fn should_make_pending_block_with_profit_strategy() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = Miner::new(MinerOptions {
+			tx_queue_strategy: PrioritizationStrategy::Profit,
+			..miner().options
+		}, GasPricer::new_fixed(0u64.into()), &Spec::new_test(), None);
+		let best_block = 0;
+
+		// when
+		let res = miner.import_own_transaction(&client, PendingTransaction::new(transaction(), None));
+
+		// then
+		assert_eq!(res.unwrap(), ());
+		assert_eq!(miner.pending_transactions(best_block).unwrap().len(), 1);
+		// the block is not sealed yet
+		assert_eq!(miner.profit_stats(), ProfitStats::default());
+	}
+
+	#[test]
+	fn should_count_profit_of_sealed_blocks() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = Miner::new(MinerOptions {
+			tx_queue_strategy: PrioritizationStrategy::Profit,
+			..miner().options
+		}, GasPricer::new_fixed(0u64.into()), &Spec::new_test(), None);
+		miner.import_own_transaction(&client, PendingTransaction::new(transaction(), None)).unwrap();
+		let hash = miner.work_package(&client).unwrap().0;
+
+		// when
+		let block = miner.submit_seal(hash, vec![]).unwrap();
+		let block_hash = block.header().hash();
+		client.import_block(block.rlp_bytes()).unwrap();
+		miner.chain_new_blocks(&client, &[block_hash], &[], &[block_hash], &[], false);
+
+		// then
+		assert_eq!(miner.profit_stats().blocks, 1);
+	}
+
+	#[test]
+	fn should_include_bundle_at_the_top_of_pending_block() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+		let (bundled, own) = (transaction(), transaction());
+		let best_block = 0;
+
+		// when
+		let hash = miner.submit_bundle(&client, vec![bundled.clone().into()], None, 1);
+		let res = miner.import_own_transaction(&client, PendingTransaction::new(own.clone(), None));
+
+		// then
+		assert!(hash.is_ok());
+		assert_eq!(res.unwrap(), ());
+		assert_eq!(miner.queue_status().status.transaction_count, 1);
+		assert_eq!(miner.pending_transactions(best_block), Some(vec![bundled, own]));
+	}
+
+	#[test]
+	fn should_remove_bundles_included_in_the_chain() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+		miner.submit_bundle(&client, vec![transaction().into()], None, 5).unwrap();
+		let hash = miner.work_package(&client).unwrap().0;
+
+		// when
+		let block = miner.submit_seal(hash, vec![]).unwrap();
+		let block_hash = block.header().hash();
+		client.import_block(block.rlp_bytes()).unwrap();
+		miner.chain_new_blocks(&client, &[block_hash], &[], &[block_hash], &[], false);
+
+		// then
+		assert_eq!(miner.bundles.read().len(), 0);
+	}
+
+	#[test]
+	fn should_reject_invalid_bundles() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+
+		// then
+		assert_eq!(miner.submit_bundle(&client, vec![], None, 1), Err(BundleError::Empty));
+		assert_eq!(
+			miner.submit_bundle(&client, vec![transaction().into()], Some(3), 2),
+			Err(BundleError::InvalidRange { min_block: 3, max_block: 2 })
+		);
+		assert_eq!(
+			miner.submit_bundle(&client, vec![transaction().into()], None, 0),
+			Err(BundleError::Expired { max_block: 0, best_block: 0 })
+		);
+	}
+
 	#[test]
 	fn should_not_use_pending_block_if_best_block_is_higher() {
 		// given
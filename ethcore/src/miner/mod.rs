@@ -19,13 +19,17 @@
 //! Miner module
 //! Keeps track of transactions and currently sealed pending block.
 
+mod bundles;
 mod miner;
+mod profit;
 mod service_transaction_checker;
 
 pub mod pool_client;
 pub mod stratum;
 
 pub use self::miner::{Miner, MinerOptions, Penalization, PendingSet, AuthoringParams};
+pub use self::bundles::{Bundle, BundleError};
+pub use self::profit::ProfitStats;
 
 use std::sync::Arc;
 use std::collections::BTreeMap;
@@ -137,6 +141,15 @@ pub trait MinerService : Send + Sync {
 		-> Result<(), transaction::Error>
 		where C: BlockChainClient;
 
+	/// Submits a bundle of transactions to be included back-to-back at the top of a block.
+	///
+	/// The bundle is kept apart from the transaction pool and included in one of the blocks
+	/// `min_block..=max_block` (`min_block` defaults to the next block) only if every transaction succeeds.
+	/// Returns the hash of the bundle.
+	fn submit_bundle<C>(&self, chain: &C, transactions: Vec<UnverifiedTransaction>, min_block: Option<BlockNumber>, max_block: BlockNumber)
+		-> Result<H256, BundleError>
+		where C: BlockChainClient;
+
 	/// Removes transaction from the pool.
 	///
 	/// Attempts to "cancel" a transaction. If it was not propagated yet (or not accepted by other peers)
@@ -173,6 +186,9 @@ pub trait MinerService : Send + Sync {
 	/// Status includes verification thresholds and current pool utilization and limits.
 	fn queue_status(&self) -> QueueStatus;
 
+	/// Get fees of blocks sealed with the profit-based block building strategy.
+	fn profit_stats(&self) -> ProfitStats;
+
 	// Misc
 
 	/// Suggested gas price.
@@ -0,0 +1,407 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Profit-based ordering of block transactions.
+//!
+//! Transactions are simulated against the pending block state and ordered by the fee
+//! the block author actually receives per unit of gas used. Only the first transaction
+//! of every sender is a candidate at any time, so nonce chains are never reordered.
+//! Once the block is nearly full the leftover gas is filled by solving a knapsack
+//! over the remaining candidates.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use ethcore_miner::pool::VerifiedTransaction;
+use ethereum_types::{Address, H256, U256};
+
+use block::{OpenBlock, IsBlock, BlockSimulation, SimulatedTransaction};
+use error::{Error, ErrorKind, ExecutionError};
+use header::BlockNumber;
+
+/// Maximal number of candidates considered when filling leftover gas.
+const MAX_KNAPSACK_ITEMS: usize = 256;
+/// Number of units the leftover gas is divided into when filling it.
+const KNAPSACK_RESOLUTION: u64 = 1024;
+
+/// Cumulative statistics of blocks sealed with the profit-based strategy.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProfitStats {
+	/// Number of sealed blocks.
+	pub blocks: u64,
+	/// Total fees expected from the sealed blocks.
+	pub profit: U256,
+	/// Total fees expected from the same blocks when filled in pool order.
+	pub baseline_profit: U256,
+}
+
+impl ProfitStats {
+	/// Fees earned over the plain gas price strategy.
+	pub fn gain(&self) -> U256 {
+		if self.profit > self.baseline_profit {
+			self.profit - self.baseline_profit
+		} else {
+			U256::zero()
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PreparedBlock {
+	number: BlockNumber,
+	profit: U256,
+	baseline_profit: U256,
+}
+
+/// Keeps expected fees of prepared blocks until they are sealed or outdated.
+/// Blocks are identified by their bare hash, which doesn't change when the seal is added.
+#[derive(Debug, Default)]
+pub struct ProfitTracker {
+	stats: ProfitStats,
+	prepared: HashMap<H256, PreparedBlock>,
+}
+
+impl ProfitTracker {
+	/// Note a prepared block, optionally created by reopening a previously prepared one.
+	pub fn prepared(&mut self, reopened: Option<&H256>, hash: H256, number: BlockNumber, profit: U256, baseline_profit: U256) {
+		let mut block = PreparedBlock { number, profit, baseline_profit };
+		if let Some(previous) = reopened.and_then(|hash| self.prepared.get(hash)) {
+			block.profit = block.profit + previous.profit;
+			block.baseline_profit = block.baseline_profit + previous.baseline_profit;
+		}
+		self.prepared.insert(hash, block);
+	}
+
+	/// Note a block imported to the chain. Returns `true` if it was prepared by us.
+	pub fn sealed(&mut self, hash: &H256) -> bool {
+		match self.prepared.remove(hash) {
+			Some(block) => {
+				self.stats.blocks += 1;
+				self.stats.profit = self.stats.profit + block.profit;
+				self.stats.baseline_profit = self.stats.baseline_profit + block.baseline_profit;
+				true
+			},
+			None => false,
+		}
+	}
+
+	/// Forget prepared blocks which can't be sealed on top of the best block anymore.
+	pub fn cull(&mut self, best_block: BlockNumber) {
+		self.prepared.retain(|_, block| block.number > best_block);
+	}
+
+	/// Returns `true` if there are no prepared blocks awaiting a seal.
+	pub fn is_empty(&self) -> bool {
+		self.prepared.is_empty()
+	}
+
+	/// Statistics of sealed blocks.
+	pub fn stats(&self) -> ProfitStats {
+		self.stats.clone()
+	}
+}
+
+/// Transactions ordered for inclusion in a block.
+pub struct ProfitOrdering {
+	/// Transactions in the order they should be pushed to the block.
+	pub transactions: Vec<Arc<VerifiedTransaction>>,
+	/// Fees expected from the transactions.
+	pub profit: U256,
+	/// Fees expected when pushing the same transactions in pool order.
+	pub baseline_profit: U256,
+}
+
+struct Candidate {
+	transaction: Arc<VerifiedTransaction>,
+	outcome: SimulatedTransaction,
+}
+
+impl Ord for Candidate {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// compare profit per gas without losing precision
+		let a = self.outcome.profit.full_mul(other.outcome.gas_used);
+		let b = other.outcome.profit.full_mul(self.outcome.gas_used);
+		a.cmp(&b).then_with(|| other.transaction.insertion_id().cmp(&self.transaction.insertion_id()))
+	}
+}
+
+impl PartialOrd for Candidate {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl PartialEq for Candidate {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for Candidate {}
+
+enum Simulated {
+	Ok(Candidate),
+	GasLimitReached(Arc<VerifiedTransaction>),
+	Invalid(Arc<VerifiedTransaction>),
+}
+
+fn simulate(simulation: &mut BlockSimulation, transaction: Arc<VerifiedTransaction>) -> Simulated {
+	match simulation.dry_run(|simulation| simulation.apply(transaction.signed())) {
+		Ok(outcome) => Simulated::Ok(Candidate { transaction, outcome }),
+		Err(Error(ErrorKind::Execution(ExecutionError::BlockGasLimitReached { .. }), _)) => Simulated::GasLimitReached(transaction),
+		Err(_) => Simulated::Invalid(transaction),
+	}
+}
+
+struct Chains {
+	chains: HashMap<Address, VecDeque<Arc<VerifiedTransaction>>>,
+	// transactions left for the block to decide on
+	rest: Vec<Arc<VerifiedTransaction>>,
+}
+
+impl Chains {
+	fn new(pending: Vec<Arc<VerifiedTransaction>>) -> Self {
+		let mut chains = HashMap::new();
+		for tx in pending {
+			chains.entry(tx.signed().sender()).or_insert_with(VecDeque::new).push_back(tx);
+		}
+
+		Chains { chains, rest: Vec::new() }
+	}
+
+	fn senders(&self) -> Vec<Address> {
+		self.chains.keys().cloned().collect()
+	}
+
+	// simulate next transaction of given sender and push it to the heap if it fits.
+	fn advance(&mut self, sender: &Address, simulation: &mut BlockSimulation, heap: &mut BinaryHeap<Candidate>) {
+		let next = self.chains.get_mut(sender).and_then(VecDeque::pop_front);
+		match next.map(|tx| simulate(simulation, tx)) {
+			Some(Simulated::Ok(candidate)) => heap.push(candidate),
+			Some(Simulated::GasLimitReached(tx)) | Some(Simulated::Invalid(tx)) => self.skip(sender, tx),
+			None => {},
+		}
+	}
+
+	// give up on the rest of sender's chain.
+	fn skip(&mut self, sender: &Address, tx: Arc<VerifiedTransaction>) {
+		self.rest.push(tx);
+		if let Some(chain) = self.chains.remove(sender) {
+			self.rest.extend(chain);
+		}
+	}
+
+	fn into_rest(mut self) -> Vec<Arc<VerifiedTransaction>> {
+		let mut chains: Vec<_> = self.chains.into_iter().map(|(_, chain)| chain).collect();
+		chains.sort_by_key(|chain| chain.front().map(|tx| tx.insertion_id()));
+		for chain in chains {
+			self.rest.extend(chain);
+		}
+		self.rest
+	}
+}
+
+/// Order `pending` transactions by the fee paid to the author per gas used on top of `block`.
+///
+/// Transactions which could not be simulated are left at the end of the ordering,
+/// so that pushing them to the block reports the actual errors.
+pub fn order_by_profit(block: &OpenBlock, pending: Vec<Arc<VerifiedTransaction>>) -> ProfitOrdering {
+	let included: HashSet<H256> = block.transactions().iter().map(|tx| tx.hash()).collect();
+	let pending: Vec<_> = pending.into_iter().filter(|tx| !included.contains(&tx.signed().hash())).collect();
+
+	// a single scratch state is shared by all simulations below, trial runs are reverted with checkpoints.
+	let mut simulation = block.simulation();
+	let baseline_profit = simulation.dry_run(|simulation| pending.iter().fold(U256::zero(), |profit, tx| {
+		match simulation.apply(tx.signed()) {
+			Ok(outcome) => profit + outcome.profit,
+			Err(_) => profit,
+		}
+	}));
+
+	let gas_limit = *block.header().gas_limit();
+	let mut chains = Chains::new(pending);
+	let mut heap = BinaryHeap::new();
+	let mut transactions = Vec::new();
+	let mut profit = U256::zero();
+
+	for sender in chains.senders() {
+		chains.advance(&sender, &mut simulation, &mut heap);
+	}
+
+	// first pass: take the most profitable candidate until one does not fit.
+	while let Some(candidate) = heap.pop() {
+		let tx = candidate.transaction;
+		let sender = tx.signed().sender();
+		match simulation.apply(tx.signed()) {
+			Ok(outcome) => {
+				profit = profit + outcome.profit;
+				transactions.push(tx);
+				chains.advance(&sender, &mut simulation, &mut heap);
+			},
+			Err(Error(ErrorKind::Execution(ExecutionError::BlockGasLimitReached { .. }), _)) => {
+				chains.skip(&sender, tx);
+				break;
+			},
+			Err(_) => chains.skip(&sender, tx),
+		}
+	}
+
+	// second pass: fill leftover gas with the best subset of remaining candidates.
+	let gas_left = if gas_limit > simulation.gas_used() { gas_limit - simulation.gas_used() } else { U256::zero() };
+	let mut candidates = Vec::new();
+	for candidate in heap.into_sorted_vec().into_iter().rev() {
+		let tx = candidate.transaction;
+		let sender = tx.signed().sender();
+		match simulate(&mut simulation, tx) {
+			Simulated::Ok(candidate) => {
+				if candidates.len() < MAX_KNAPSACK_ITEMS && candidate.transaction.signed().gas <= gas_left {
+					candidates.push(candidate);
+				} else {
+					chains.skip(&sender, candidate.transaction);
+				}
+			},
+			Simulated::GasLimitReached(tx) | Simulated::Invalid(tx) => chains.skip(&sender, tx),
+		}
+	}
+
+	let items: Vec<_> = candidates.iter().map(|c| (c.transaction.signed().gas, c.outcome.profit)).collect();
+	let chosen = knapsack(&items, gas_left);
+	for (index, candidate) in candidates.into_iter().enumerate() {
+		let tx = candidate.transaction;
+		let sender = tx.signed().sender();
+		if !chosen.contains(&index) {
+			chains.skip(&sender, tx);
+			continue;
+		}
+
+		match simulation.apply(tx.signed()) {
+			Ok(outcome) => {
+				profit = profit + outcome.profit;
+				transactions.push(tx);
+			},
+			Err(_) => chains.skip(&sender, tx),
+		}
+	}
+
+	transactions.extend(chains.into_rest());
+
+	ProfitOrdering {
+		transactions,
+		profit,
+		baseline_profit,
+	}
+}
+
+/// Pick the subset of `(weight, value)` items with the highest total value whose total weight
+/// does not exceed `capacity`. Weights are rounded up to `capacity / KNAPSACK_RESOLUTION`,
+/// so the result never exceeds the capacity but might miss the exact optimum.
+fn knapsack(items: &[(U256, U256)], capacity: U256) -> HashSet<usize> {
+	if items.is_empty() || capacity.is_zero() {
+		return HashSet::new();
+	}
+
+	let unit = ::std::cmp::max(capacity / KNAPSACK_RESOLUTION.into(), U256::one());
+	let slots = (capacity / unit).low_u64() as usize;
+	let weights: Vec<usize> = items.iter()
+		.map(|&(weight, _)| {
+			let units = (weight + unit - U256::one()) / unit;
+			if units > U256::from(slots) { slots + 1 } else { units.low_u64() as usize }
+		})
+		.collect();
+
+	// best[i][w] - best value using first `i` items with total weight at most `w`
+	let mut best = vec![vec![U256::zero(); slots + 1]; items.len() + 1];
+	for (i, &(_, value)) in items.iter().enumerate() {
+		for w in 0..slots + 1 {
+			best[i + 1][w] = best[i][w];
+			if weights[i] <= w {
+				let with_item = best[i][w - weights[i]] + value;
+				if with_item > best[i + 1][w] {
+					best[i + 1][w] = with_item;
+				}
+			}
+		}
+	}
+
+	let mut chosen = HashSet::new();
+	let mut w = slots;
+	for i in (0..items.len()).rev() {
+		if best[i + 1][w] != best[i][w] {
+			chosen.insert(i);
+			w -= weights[i];
+		}
+	}
+	chosen
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn items(items: &[(u64, u64)]) -> Vec<(U256, U256)> {
+		items.iter().map(|&(w, v)| (w.into(), v.into())).collect()
+	}
+
+	#[test]
+	fn knapsack_should_prefer_better_subset_over_best_ratio() {
+		// first item has the best ratio, but the other two yield more together.
+		let items = items(&[(60_000, 600), (50_000, 450), (50_000, 450)]);
+
+		let chosen = knapsack(&items, 101_000.into());
+
+		assert_eq!(chosen, vec![1, 2].into_iter().collect());
+	}
+
+	#[test]
+	fn knapsack_should_never_exceed_capacity() {
+		let items = items(&[(21_000, 10), (21_001, 11), (30_000, 20), (40_000, 5)]);
+
+		let chosen = knapsack(&items, 60_000.into());
+
+		let weight = chosen.iter().fold(U256::zero(), |acc, i| acc + items[*i].0);
+		assert!(weight <= 60_000.into());
+		assert_eq!(chosen, vec![1, 2].into_iter().collect());
+	}
+
+	#[test]
+	fn tracker_should_count_only_sealed_blocks() {
+		let mut tracker = ProfitTracker::default();
+		tracker.prepared(None, 1.into(), 1, 10.into(), 8.into());
+		tracker.prepared(Some(&1.into()), 2.into(), 1, 5.into(), 5.into());
+		tracker.prepared(None, 3.into(), 1, 100.into(), 0.into());
+		assert_eq!(tracker.stats(), ProfitStats::default());
+
+		assert!(tracker.sealed(&2.into()));
+		assert!(!tracker.sealed(&4.into()));
+		tracker.cull(1);
+
+		assert!(tracker.is_empty());
+		assert_eq!(tracker.stats(), ProfitStats {
+			blocks: 1,
+			profit: 15.into(),
+			baseline_profit: 13.into(),
+		});
+		assert_eq!(tracker.stats().gain(), 2.into());
+	}
+
+	#[test]
+	fn knapsack_should_handle_empty_input() {
+		assert!(knapsack(&[], 100.into()).is_empty());
+		assert!(knapsack(&items(&[(10, 10)]), 0.into()).is_empty());
+		assert!(knapsack(&items(&[(10, 10)]), 5.into()).is_empty());
+	}
+}
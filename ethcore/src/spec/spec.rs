@@ -123,6 +123,8 @@ pub struct CommonParams {
 	pub remove_dust_contracts: bool,
 	/// Wasm activation blocknumber, if any disabled initially.
 	pub wasm_activation_transition: BlockNumber,
+	/// Wasm schedule changes, applied in order on top of the default wasm costs.
+	pub wasm_schedule_transitions: BTreeMap<BlockNumber, ::vm::WasmCostsChanges>,
 	/// Gas limit bound divisor (how much gas limit can change per block)
 	pub gas_limit_bound_divisor: U256,
 	/// Registrar contract address.
@@ -184,7 +186,11 @@ impl CommonParams {
 			};
 		}
 		if block_number >= self.wasm_activation_transition {
-			schedule.wasm = Some(Default::default());
+			let mut wasm = ::vm::WasmCosts::default();
+			for (_, changes) in self.wasm_schedule_transitions.iter().take_while(|&(block, _)| *block <= block_number) {
+				changes.apply(&mut wasm);
+			}
+			schedule.wasm = Some(wasm);
 		}
 	}
 
@@ -279,10 +285,46 @@ impl From<ethjson::spec::Params> for CommonParams {
 				BlockNumber::max_value,
 				Into::into
 			),
+			wasm_schedule_transitions: p.wasm_schedule_transitions.map_or_else(
+				BTreeMap::new,
+				|transitions| transitions.into_iter().map(|(block, changes)| (block.into(), wasm_costs_changes(changes))).collect(),
+			),
 		}
 	}
 }
 
+/// Convert a wasm schedule transition of the spec.
+/// Costs are checked to fit in 32 bits when the spec is deserialized.
+fn wasm_costs_changes(changes: ethjson::spec::WasmSchedule) -> ::vm::WasmCostsChanges {
+	fn cost(value: Option<ethjson::uint::Uint>) -> Option<u32> {
+		value.map(|value| {
+			let value: U256 = value.into();
+			value.low_u32()
+		})
+	}
+
+	let functions = changes.host_functions.unwrap_or_default();
+	::vm::WasmCostsChanges {
+		regular: cost(changes.regular),
+		div: cost(changes.div),
+		mul: cost(changes.mul),
+		mem: cost(changes.mem),
+		static_u256: cost(changes.static_u256),
+		static_address: cost(changes.static_address),
+		initial_mem: cost(changes.initial_mem),
+		grow_mem: cost(changes.grow_mem),
+		memcpy: cost(changes.memcpy),
+		max_stack_height: cost(changes.max_stack_height),
+		opcodes_mul: cost(changes.opcodes_mul),
+		opcodes_div: cost(changes.opcodes_div),
+		keccak256: cost(changes.keccak256),
+		keccak256_word: cost(changes.keccak256_word),
+		ecrecover: cost(changes.ecrecover),
+		enable_keccak256: functions.contains(&ethjson::spec::WasmHostFunction::Keccak256),
+		enable_ecrecover: functions.contains(&ethjson::spec::WasmHostFunction::Ecrecover),
+	}
+}
+
 /// Runtime parameters for the spec that are related to how the software should run the chain,
 /// rather than integral properties of the chain itself.
 #[derive(Debug, Clone, Copy)]
@@ -984,4 +1026,78 @@ mod tests {
 		assert_eq!(state.storage_at(&address, &H256::zero()).unwrap(), expected);
 		assert_eq!(state.balance(&address).unwrap(), 1.into());
 	}
+
+	#[test]
+	fn wasm_schedule_transitions() {
+		let tempdir = TempDir::new("").unwrap();
+		let spec = Spec::load(&tempdir.path(), r#"{
+			"name": "Wasm",
+			"engine": { "null": { "params": {} } },
+			"params": {
+				"gasLimitBoundDivisor": "0x0400",
+				"accountStartNonce": "0x0",
+				"maximumExtraDataSize": "0x20",
+				"minGasLimit": "0x1388",
+				"networkID" : "0x2",
+				"wasmActivationTransition": "0x10",
+				"wasmScheduleTransitions": {
+					"0x20": { "keccak256": "0x40", "hostFunctions": ["keccak256"] },
+					"0x30": { "opcodesDiv": "0x10", "hostFunctions": ["ecrecover"] }
+				}
+			},
+			"genesis": {
+				"seal": { "generic": "0x" },
+				"difficulty": "0x20000",
+				"gasLimit": "0x2fefd8"
+			},
+			"accounts": {}
+		}"#.as_bytes()).unwrap();
+
+		let params = spec.params();
+		assert!(params.schedule(0x0f).wasm.is_none());
+
+		let schedule = params.schedule(0x10);
+		assert_eq!(schedule.wasm().keccak256, 80);
+		assert!(!schedule.wasm().have_keccak256);
+
+		let schedule = params.schedule(0x20);
+		assert_eq!(schedule.wasm().keccak256, 0x40);
+		assert!(schedule.wasm().have_keccak256);
+		assert!(!schedule.wasm().have_ecrecover);
+		assert_eq!(schedule.wasm().opcodes_div, 8);
+
+		let schedule = params.schedule(0x30);
+		assert_eq!(schedule.wasm().keccak256, 0x40);
+		assert!(schedule.wasm().have_keccak256);
+		assert!(schedule.wasm().have_ecrecover);
+		assert_eq!(schedule.wasm().opcodes_div, 0x10);
+	}
+
+	#[test]
+	fn wasm_schedule_costs_out_of_range_are_rejected() {
+		let tempdir = TempDir::new("").unwrap();
+		let spec = Spec::load(&tempdir.path(), r#"{
+			"name": "Wasm",
+			"engine": { "null": { "params": {} } },
+			"params": {
+				"gasLimitBoundDivisor": "0x0400",
+				"accountStartNonce": "0x0",
+				"maximumExtraDataSize": "0x20",
+				"minGasLimit": "0x1388",
+				"networkID" : "0x2",
+				"wasmActivationTransition": "0x10",
+				"wasmScheduleTransitions": {
+					"0x20": { "keccak256": "0x10000000000000000" }
+				}
+			},
+			"genesis": {
+				"seal": { "generic": "0x" },
+				"difficulty": "0x20000",
+				"gasLimit": "0x2fefd8"
+			},
+			"accounts": {}
+		}"#.as_bytes());
+
+		assert!(spec.is_err());
+	}
 }
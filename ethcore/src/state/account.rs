@@ -192,6 +192,13 @@ impl Account {
 		self.storage_changes = storage;
 	}
 
+	/// Replace all storage of this account with given values.
+	pub fn reset_storage(&mut self, storage: HashMap<H256, H256>) {
+		self.storage_root = KECCAK_NULL_RLP;
+		self.storage_cache = Self::empty_storage_cache();
+		self.storage_changes = storage;
+	}
+
 	/// Set (and cache) the contents of the trie's storage at `key` to `value`.
 	pub fn set_storage(&mut self, key: H256, value: H256) {
 		self.storage_changes.insert(key, value);
@@ -361,6 +368,11 @@ impl Account {
 		self.nonce = self.nonce + U256::from(1u8);
 	}
 
+	/// Set the nonce of the account.
+	pub fn set_nonce(&mut self, nonce: U256) {
+		self.nonce = nonce;
+	}
+
 	/// Increase account balance.
 	pub fn add_balance(&mut self, x: &U256) {
 		self.balance = self.balance + *x;
@@ -222,6 +222,44 @@ pub fn check_proof(
 	}
 }
 
+/// Check the given proof of execution of `transaction` without overrides and execute
+/// it on top of the proved state with `overrides` applied.
+/// The overridden execution may need state which is not part of the proof, this is
+/// reported as `ProvedExecution::Failed` since the proof itself is fine.
+pub fn check_proof_with_overrides(
+	proof: &[DBValue],
+	root: H256,
+	transaction: &SignedTransaction,
+	overrides: &StateOverride,
+	machine: &Machine,
+	env_info: &EnvInfo,
+) -> ProvedExecution {
+	if let ProvedExecution::BadProof = check_proof(proof, root, transaction, machine, env_info) {
+		return ProvedExecution::BadProof;
+	}
+
+	let backend = self::backend::ProofCheck::new(proof);
+	let mut factories = Factories::default();
+	factories.accountdb = ::account_db::Factory::Plain;
+
+	let mut state = match State::from_existing(backend, root, machine.account_start_nonce(env_info.number), factories) {
+		Ok(state) => state,
+		Err(_) => return ProvedExecution::BadProof,
+	};
+
+	let missing_state = || ExecutionError::Internal("State required by the call with overrides applied is not part of the proof".into());
+	if state.apply_overrides(overrides).is_err() {
+		return ProvedExecution::Failed(missing_state());
+	}
+
+	let options = TransactOptions::with_no_tracing().save_output_from_contract();
+	match state.execute(env_info, machine, transaction, options, true) {
+		Ok(executed) => ProvedExecution::Complete(executed),
+		Err(ExecutionError::Internal(_)) => ProvedExecution::Failed(missing_state()),
+		Err(e) => ProvedExecution::Failed(e),
+	}
+}
+
 /// Prove a transaction on the given state.
 /// Returns `None` when the transacion could not be proved,
 /// and a proof otherwise.
@@ -333,6 +371,24 @@ pub enum CleanupMode<'a> {
 	TrackTouched(&'a mut HashSet<Address>),
 }
 
+/// Values of an account replaced before executing calls.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AccountOverride {
+	/// Balance of the account.
+	pub balance: Option<U256>,
+	/// Nonce of the account.
+	pub nonce: Option<U256>,
+	/// Code of the account.
+	pub code: Option<Bytes>,
+	/// Storage slots of the account.
+	pub storage: BTreeMap<H256, H256>,
+	/// Whether slots not listed in `storage` are cleared instead of left untouched.
+	pub reset_storage: bool,
+}
+
+/// Accounts replaced before executing calls.
+pub type StateOverride = BTreeMap<Address, AccountOverride>;
+
 /// Provides subset of `State` methods to query state information
 pub trait StateInfo {
 	/// Get the nonce of account `a`.
@@ -681,6 +737,43 @@ impl<B: Backend> State<B> {
 		Ok(())
 	}
 
+	/// Replace all storage of an account. Creates account if it does not exist.
+	/// NOTE: Storage reads go through the global account cache first, so this must only
+	/// be used on a state that bypasses it (see `Clone for State<StateDB>`).
+	pub fn reset_storage(&mut self, a: &Address, storage: HashMap<H256, H256>) -> trie::Result<()> {
+		self.require(a, false)?.reset_storage(storage);
+		Ok(())
+	}
+
+	/// Replace balance, nonce, code and storage of accounts as described by `overrides`.
+	pub fn apply_overrides(&mut self, overrides: &StateOverride) -> trie::Result<()> {
+		for (address, account) in overrides {
+			if let Some(balance) = account.balance {
+				let current = self.balance(address)?;
+				if balance >= current {
+					self.add_balance(address, &(balance - current), CleanupMode::ForceCreate)?;
+				} else {
+					self.sub_balance(address, &(current - balance), &mut CleanupMode::NoEmpty)?;
+				}
+			}
+			if let Some(nonce) = account.nonce {
+				self.require(address, false)?.set_nonce(nonce);
+			}
+			if let Some(ref code) = account.code {
+				self.reset_code(address, code.clone())?;
+			}
+			if account.reset_storage {
+				self.reset_storage(address, account.storage.iter().map(|(k, v)| (*k, *v)).collect())?;
+			} else {
+				for (key, value) in &account.storage {
+					self.set_storage(address, *key, *value)?;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
 	/// Execute a given transaction, producing a receipt and an optional trace.
 	/// This will change the state accordingly.
 	pub fn apply(&mut self, env_info: &EnvInfo, machine: &Machine, t: &SignedTransaction, tracing: bool) -> ApplyResult<FlatTrace, VMTrace> {
@@ -738,6 +831,12 @@ impl<B: Backend> State<B> {
 		})
 	}
 
+	/// Execute a given transaction without committing changes to the trie,
+	/// so that they can be undone with `revert_to_checkpoint`.
+	pub fn apply_uncommitted(&mut self, env_info: &EnvInfo, machine: &Machine, t: &SignedTransaction) -> Result<Executed, ExecutionError> {
+		self.execute(env_info, machine, t, TransactOptions::with_no_tracing(), false)
+	}
+
 	// Execute a given transaction without committing changes.
 	//
 	// `virt` signals that we are executing outside of a block set and restrictions like
@@ -2131,6 +2230,51 @@ mod tests {
 		assert_eq!(state.nonce(&a).unwrap(), U256::from(3u64));
 	}
 
+	#[test]
+	fn apply_overrides() {
+		let mut state = get_temp_state();
+		let (a, b) = (Address::zero(), Address::from(1));
+		state.add_balance(&a, &U256::from(69u64), CleanupMode::NoEmpty).unwrap();
+		state.commit().unwrap();
+
+		let mut overrides = StateOverride::new();
+		overrides.insert(a, AccountOverride { balance: Some(42.into()), nonce: Some(5.into()), ..Default::default() });
+		overrides.insert(b, AccountOverride {
+			balance: Some(100.into()),
+			code: Some(vec![0x60, 0x00]),
+			storage: vec![(H256::from(1), H256::from(2))].into_iter().collect(),
+			..Default::default()
+		});
+		state.apply_overrides(&overrides).unwrap();
+
+		assert_eq!(state.balance(&a).unwrap(), U256::from(42u64));
+		assert_eq!(state.nonce(&a).unwrap(), U256::from(5u64));
+		assert_eq!(state.balance(&b).unwrap(), U256::from(100u64));
+		assert_eq!(state.code(&b).unwrap(), Some(Arc::new(vec![0x60, 0x00])));
+		assert_eq!(state.storage_at(&b, &H256::from(1)).unwrap(), H256::from(2));
+	}
+
+	#[test]
+	fn apply_overrides_resetting_storage() {
+		let mut state = get_temp_state();
+		let a = Address::zero();
+		state.set_storage(&a, H256::from(1), H256::from(1)).unwrap();
+		state.set_storage(&a, H256::from(2), H256::from(2)).unwrap();
+		state.commit().unwrap();
+		state.clear();
+
+		let mut overrides = StateOverride::new();
+		overrides.insert(a, AccountOverride {
+			storage: vec![(H256::from(2), H256::from(3))].into_iter().collect(),
+			reset_storage: true,
+			..Default::default()
+		});
+		state.apply_overrides(&overrides).unwrap();
+
+		assert_eq!(state.storage_at(&a, &H256::from(1)).unwrap(), H256::new());
+		assert_eq!(state.storage_at(&a, &H256::from(2)).unwrap(), H256::from(3));
+	}
+
 	#[test]
 	fn balance_nonce() {
 		let mut state = get_temp_state();
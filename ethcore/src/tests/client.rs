@@ -389,3 +389,86 @@ fn transaction_proof() {
 	assert_eq!(state.balance(&Address::default()).unwrap(), 5.into());
 	assert_eq!(state.balance(&address).unwrap(), 95.into());
 }
+
+#[test]
+fn call_with_state_overrides() {
+	use client::Call;
+	use ethereum_types::H256;
+	use rustc_hex::FromHex;
+
+	let client = generate_dummy_client(0);
+	let contract = Address::random();
+	let sender = Address::random();
+
+	// returns the value stored at slot 0
+	let code = "60005460005260206000f3".from_hex().unwrap();
+	let mut overrides = state::StateOverride::new();
+	overrides.insert(contract, state::AccountOverride {
+		code: Some(code),
+		storage: vec![(H256::zero(), H256::from(42))].into_iter().collect(),
+		..Default::default()
+	});
+
+	let transaction = Transaction {
+		nonce: 0.into(),
+		gas_price: 1.into(),
+		gas: 100_000.into(),
+		action: Action::Call(contract),
+		value: 0.into(),
+		data: Vec::new(),
+	}.fake_sign(sender);
+	let header = client.best_block_header();
+
+	let mut state = client.latest_state();
+	let executed = client.call(&transaction, Default::default(), &Default::default(), &mut state, &header).unwrap();
+	assert!(executed.output.is_empty());
+
+	let mut state = client.latest_state();
+	let executed = client.call(&transaction, Default::default(), &overrides, &mut state, &header).unwrap();
+	assert_eq!(executed.output, H256::from(42).to_vec());
+	assert!(client.estimate_gas(&transaction, &overrides, &client.latest_state(), &header).is_ok());
+}
+
+#[test]
+fn estimate_gas_failure_reasons() {
+	use client::Call;
+	use error::CallError;
+	use executed::ExecutionError;
+	use rustc_hex::FromHex;
+
+	let client = generate_dummy_client(0);
+	let contract = Address::random();
+	let sender = Address::random();
+	let header = client.best_block_header();
+
+	let mut overrides = state::StateOverride::new();
+	overrides.insert(contract, state::AccountOverride {
+		// revert(0, 0)
+		code: Some("60006000fd".from_hex().unwrap()),
+		..Default::default()
+	});
+
+	let mut tx = Transaction {
+		nonce: 0.into(),
+		gas_price: 0.into(),
+		gas: 100_000.into(),
+		action: Action::Call(contract),
+		value: 0.into(),
+		data: Vec::new(),
+	};
+
+	let res = client.estimate_gas(&tx.clone().fake_sign(sender), &overrides, &client.latest_state(), &header);
+	assert_eq!(res, Err(CallError::Reverted(vec![])));
+
+	tx.gas_price = 1.into();
+	let res = client.estimate_gas(&tx.clone().fake_sign(sender), &overrides, &client.latest_state(), &header);
+	match res {
+		Err(CallError::Execution(ExecutionError::NotEnoughCash { .. })) => {},
+		other => panic!("Unexpected result: {:?}", other),
+	}
+
+	tx.gas_price = 0.into();
+	tx.nonce = 1.into();
+	let res = client.estimate_gas(&tx.fake_sign(sender), &overrides, &client.latest_state(), &header);
+	assert_eq!(res, Err(CallError::Execution(ExecutionError::InvalidNonce { expected: 0.into(), got: 1.into() })));
+}
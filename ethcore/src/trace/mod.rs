@@ -21,18 +21,20 @@ mod db;
 mod executive_tracer;
 mod import;
 mod noop_tracer;
+mod profiling_tracer;
 mod types;
 
 pub use self::config::Config;
 pub use self::db::TraceDB;
 pub use self::noop_tracer::{NoopTracer, NoopVMTracer};
 pub use self::executive_tracer::{ExecutiveTracer, ExecutiveVMTracer};
+pub use self::profiling_tracer::ProfilingVMTracer;
 pub use self::import::ImportRequest;
 pub use self::localized::LocalizedTrace;
 
 pub use self::types::{filter, flat, localized, trace, Tracing};
 pub use self::types::error::Error as TraceError;
-pub use self::types::trace::{VMTrace, VMOperation, VMExecutedOperation, MemoryDiff, StorageDiff, RewardType};
+pub use self::types::trace::{VMTrace, VMOperation, VMExecutedOperation, MemoryDiff, StorageDiff, RewardType, VMProfile, InstructionProfile};
 pub use self::types::flat::{FlatTrace, FlatTransactionTraces, FlatBlockTraces};
 pub use self::types::filter::{Filter, AddressesFilter};
 
@@ -0,0 +1,86 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Coverage and gas profiling VM tracer.
+
+use ethereum_types::{H256, U256};
+use hash::keccak;
+use trace::VMTracer;
+use trace::trace::VMProfile;
+
+/// VM tracer recording which instructions were executed and how much gas they consumed.
+///
+/// Records are keyed by the hash of the executing code, so that they can be mapped back
+/// to the source with the compiler's source maps.
+pub struct ProfilingVMTracer {
+	code_hash: H256,
+	profile: VMProfile,
+	pc: usize,
+	gas_before: U256,
+	/// Gas consumed by calls made by the current instruction.
+	subcalls_gas: U256,
+	/// Gas consumed by all instructions of this frame, including calls.
+	gas_used: U256,
+}
+
+impl ProfilingVMTracer {
+	/// Create a new top-level instance.
+	pub fn toplevel() -> Self {
+		ProfilingVMTracer::with_code_hash(H256::zero())
+	}
+
+	fn with_code_hash(code_hash: H256) -> Self {
+		ProfilingVMTracer {
+			code_hash: code_hash,
+			profile: VMProfile::default(),
+			pc: 0,
+			gas_before: U256::zero(),
+			subcalls_gas: U256::zero(),
+			gas_used: U256::zero(),
+		}
+	}
+}
+
+impl VMTracer for ProfilingVMTracer {
+	type Output = VMProfile;
+
+	fn trace_next_instruction(&mut self, _pc: usize, _instruction: u8, current_gas: U256) -> bool {
+		self.gas_before = current_gas;
+		true
+	}
+
+	fn trace_prepare_execute(&mut self, pc: usize, _instruction: u8, _gas_cost: U256) {
+		self.pc = pc;
+		self.subcalls_gas = U256::zero();
+	}
+
+	fn trace_executed(&mut self, gas_left: U256, _stack_push: &[U256], _mem_diff: Option<(usize, &[u8])>, _store_diff: Option<(U256, U256)>) {
+		let gas_used = self.gas_before.saturating_sub(gas_left);
+		self.gas_used = self.gas_used + gas_used;
+		self.profile.record(self.code_hash, self.pc, gas_used.saturating_sub(self.subcalls_gas));
+	}
+
+	fn prepare_subtrace(&self, code: &[u8]) -> Self {
+		ProfilingVMTracer::with_code_hash(keccak(code))
+	}
+
+	fn done_subtrace(&mut self, sub: Self) {
+		self.subcalls_gas = self.subcalls_gas + sub.gas_used;
+		self.profile.merge(sub.profile);
+	}
+
+	fn drain(self) -> Option<VMProfile> { Some(self.profile) }
+}
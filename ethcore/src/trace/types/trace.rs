@@ -16,7 +16,8 @@
 
 //! Tracing datatypes.
 
-use ethereum_types::{U256, Address, Bloom, BloomInput};
+use std::collections::BTreeMap;
+use ethereum_types::{H256, U256, Address, Bloom, BloomInput};
 use bytes::Bytes;
 use rlp::{Rlp, RlpStream, Encodable, DecoderError, Decodable};
 
@@ -429,3 +430,40 @@ pub struct VMTrace {
 	/// Thre is a 1:1 correspondance between these and a CALL/CREATE/CALLCODE/DELEGATECALL instruction.
 	pub subs: Vec<VMTrace>,
 }
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// Number of executions and gas consumed by a single instruction.
+pub struct InstructionProfile {
+	/// How many times the instruction was executed.
+	pub count: u64,
+	/// Gas consumed by the instruction, excluding gas consumed by the calls it made.
+	pub gas_used: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// Instruction coverage and gas consumption of all code executed by a CALL/CREATE.
+pub struct VMProfile {
+	/// Profiles of executed instructions, keyed by code hash and program counter.
+	pub codes: BTreeMap<H256, BTreeMap<usize, InstructionProfile>>,
+}
+
+impl VMProfile {
+	/// Record a single execution of the instruction at `pc`.
+	pub fn record(&mut self, code_hash: H256, pc: usize, gas_used: U256) {
+		let instruction = self.codes.entry(code_hash).or_insert_with(BTreeMap::new).entry(pc).or_insert_with(Default::default);
+		instruction.count += 1;
+		instruction.gas_used = instruction.gas_used + gas_used;
+	}
+
+	/// Add all records of other profile to this one.
+	pub fn merge(&mut self, other: VMProfile) {
+		for (code_hash, instructions) in other.codes {
+			let code = self.codes.entry(code_hash).or_insert_with(BTreeMap::new);
+			for (pc, other) in instructions {
+				let instruction = code.entry(pc).or_insert_with(Default::default);
+				instruction.count += other.count;
+				instruction.gas_used = instruction.gas_used + other.gas_used;
+			}
+		}
+	}
+}
@@ -105,6 +105,10 @@ pub struct SyncConfig {
 	pub warp_sync: WarpSync,
 	/// Enable light client server.
 	pub serve_light: bool,
+	/// Max number of transactions accepted from a single peer per second.
+	pub max_peer_transactions_per_second: Option<usize>,
+	/// Max number of transactions accepted from all peers per second.
+	pub max_transactions_per_second: Option<usize>,
 }
 
 impl Default for SyncConfig {
@@ -118,6 +122,8 @@ impl Default for SyncConfig {
 			fork_block: None,
 			warp_sync: WarpSync::Disabled,
 			serve_light: false,
+			max_peer_transactions_per_second: None,
+			max_transactions_per_second: None,
 		}
 	}
 }
@@ -24,6 +24,7 @@ use ethcore::snapshot::{ManifestData, RestorationStatus};
 use ethereum_types::{H256, U256};
 use hash::keccak;
 use network::PeerId;
+use rate_limit::RateLimiter;
 use rlp::Rlp;
 use snapshot::ChunkType;
 use std::cmp;
@@ -108,6 +109,7 @@ impl SyncHandler {
 			sync.clear_peer_download(peer_id);
 			sync.peers.remove(&peer_id);
 			sync.active_peers.remove(&peer_id);
+			io.chain().remove_transaction_peer(peer_id);
 
 			if sync.state == SyncState::SnapshotManifest {
 				// Check if we are asking other peers for
@@ -632,6 +634,7 @@ impl SyncHandler {
 			snapshot_hash: if warp_protocol { Some(r.val_at(5)?) } else { None },
 			snapshot_number: if warp_protocol { Some(r.val_at(6)?) } else { None },
 			block_set: None,
+			transactions_rate: RateLimiter::new(sync.peer_transactions_rate_limit),
 		};
 
 		trace!(target: "sync", "New peer {} (protocol: {}, network: {:?}, difficulty: {:?}, latest:{}, genesis:{}, snapshot:{:?})",
@@ -704,8 +707,20 @@ impl SyncHandler {
 
 		let item_count = r.item_count()?;
 		trace!(target: "sync", "{:02} -> Transactions ({} entries)", peer_id, item_count);
-		let mut transactions = Vec::with_capacity(item_count);
-		for i in 0 .. item_count {
+		let now = Instant::now();
+		let accepted = {
+			let peer = sync.peers.get_mut(&peer_id).expect("peer presence checked above; qed");
+			let accepted = cmp::min(item_count, cmp::min(peer.transactions_rate.available(now), sync.transactions_rate.available(now)));
+			peer.transactions_rate.consume(accepted);
+			accepted
+		};
+		sync.transactions_rate.consume(accepted);
+		if accepted < item_count {
+			trace!(target: "sync", "{:02} -> Dropping {} transactions over the rate limit", peer_id, item_count - accepted);
+		}
+
+		let mut transactions = Vec::with_capacity(accepted);
+		for i in 0 .. accepted {
 			let rlp = r.at(i)?;
 			let tx = rlp.as_raw().to_vec();
 			transactions.push(tx);
@@ -747,8 +762,11 @@ impl SyncHandler {
 #[cfg(test)]
 mod tests {
 	use ethcore::client::{ChainInfo, EachBlockWith, TestBlockChainClient};
+	use ethcore::miner::MinerService;
+	use ethkey::{Generator, Random};
 	use parking_lot::RwLock;
-	use rlp::{Rlp};
+	use rlp::{Rlp, RlpStream};
+	use transaction::{Action, Transaction};
 	use std::collections::{VecDeque};
 	use tests::helpers::{TestIo};
 	use tests::snapshot::TestSnapshotService;
@@ -850,4 +868,36 @@ mod tests {
 
 		assert!(result.is_ok());
 	}
+
+	#[test]
+	fn limits_transactions_accepted_from_peers() {
+		let mut client = TestBlockChainClient::new();
+		client.add_blocks(10, EachBlockWith::Uncle);
+		let keypair = Random.generate().unwrap();
+		client.set_balance(keypair.address(), 10_000_000_000_000_000_000u64.into());
+		let queue = RwLock::new(VecDeque::new());
+		let mut sync = dummy_sync_with_peer(client.block_hash_delta_minus(1), &client);
+		sync.transactions_rate = RateLimiter::new(Some(2));
+		let ss = TestSnapshotService::new();
+		let mut io = TestIo::new(&mut client, &ss, &queue, None);
+
+		let mut packet = RlpStream::new_list(3);
+		for nonce in 0..3u64 {
+			let tx = Transaction {
+				action: Action::Create,
+				value: 0.into(),
+				data: vec![],
+				gas: 100_000.into(),
+				gas_price: 20_000_000_000u64.into(),
+				nonce: nonce.into(),
+			}.sign(keypair.secret(), None);
+			packet.append(&tx);
+		}
+		let data = packet.out();
+
+		let result = SyncHandler::on_peer_transactions(&mut sync, &mut io, 0, &Rlp::new(&data));
+
+		assert!(result.is_ok());
+		assert_eq!(io.chain.miner.queue_status().status.transaction_count, 2);
+	}
 }
@@ -115,6 +115,7 @@ use snapshot::{Snapshot};
 use api::{EthProtocolInfo as PeerInfoDigest, WARP_SYNC_PROTOCOL_ID};
 use private_tx::PrivateTxHandler;
 use transactions_stats::{TransactionsStats, Stats as TransactionStats};
+use rate_limit::RateLimiter;
 use transaction::UnverifiedTransaction;
 
 use self::handler::SyncHandler;
@@ -335,6 +336,8 @@ pub struct PeerInfo {
 	snapshot_number: Option<BlockNumber>,
 	/// Block set requested
 	block_set: Option<BlockSet>,
+	/// Transactions accepted from this peer in the current window
+	transactions_rate: RateLimiter,
 }
 
 impl PeerInfo {
@@ -408,6 +411,10 @@ pub struct ChainSync {
 	private_tx_handler: Arc<PrivateTxHandler>,
 	/// Enable warp sync.
 	warp_sync: WarpSync,
+	/// Transactions accepted from all peers in the current window
+	transactions_rate: RateLimiter,
+	/// Max number of transactions accepted from a single peer per second
+	peer_transactions_rate_limit: Option<usize>,
 }
 
 impl ChainSync {
@@ -435,6 +442,8 @@ impl ChainSync {
 			transactions_stats: TransactionsStats::default(),
 			private_tx_handler,
 			warp_sync: config.warp_sync,
+			transactions_rate: RateLimiter::new(config.max_transactions_per_second),
+			peer_transactions_rate_limit: config.max_peer_transactions_per_second,
 		};
 		sync.update_targets(chain);
 		sync
@@ -1252,6 +1261,7 @@ pub mod tests {
 				snapshot_hash: None,
 				asking_snapshot_data: None,
 				block_set: None,
+				transactions_rate: RateLimiter::new(None),
 			});
 
 	}
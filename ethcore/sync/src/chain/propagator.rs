@@ -119,7 +119,10 @@ impl SyncPropagator {
 			return 0;
 		}
 
+		// don't relay transactions received from peers spamming the pool
+		let spam_peers: HashSet<_> = io.chain().transaction_spam_peers().into_iter().collect();
 		let (transactions, service_transactions): (Vec<_>, Vec<_>) = transactions.iter()
+			.filter(|tx| tx.peer_id().map_or(true, |peer_id| !spam_peers.contains(&peer_id)))
 			.map(|tx| tx.signed())
 			.partition(|tx| !tx.gas_price.is_zero());
 
@@ -429,6 +432,7 @@ mod tests {
 				snapshot_hash: None,
 				asking_snapshot_data: None,
 				block_set: None,
+				transactions_rate: RateLimiter::new(None),
 			});
 		let ss = TestSnapshotService::new();
 		let mut io = TestIo::new(&mut client, &ss, &queue, None);
@@ -64,6 +64,7 @@ mod sync_io;
 mod private_tx;
 mod snapshot;
 mod transactions_stats;
+mod rate_limit;
 
 pub mod light_sync;
 
@@ -0,0 +1,89 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Rate limiting of items received from the network.
+
+use std::cmp;
+use std::time::{Duration, Instant};
+
+/// Length of the rate limiting window.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Fixed-window rate limiter allowing `limit` items per second.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+	limit: Option<usize>,
+	window_start: Instant,
+	used: usize,
+}
+
+impl RateLimiter {
+	/// Create a new limiter. `None` means no limit.
+	pub fn new(limit: Option<usize>) -> Self {
+		RateLimiter {
+			limit,
+			window_start: Instant::now(),
+			used: 0,
+		}
+	}
+
+	/// Number of items still allowed in the current window.
+	pub fn available(&mut self, now: Instant) -> usize {
+		let limit = match self.limit {
+			Some(limit) => limit,
+			None => return usize::max_value(),
+		};
+
+		if now.duration_since(self.window_start) >= WINDOW {
+			self.window_start = now;
+			self.used = 0;
+		}
+
+		limit - self.used
+	}
+
+	/// Account for `count` items received in the current window.
+	pub fn consume(&mut self, count: usize) {
+		if let Some(limit) = self.limit {
+			self.used = cmp::min(limit, self.used + count);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_limit_items_within_window() {
+		let start = Instant::now();
+		let mut limiter = RateLimiter::new(Some(10));
+
+		assert_eq!(limiter.available(start), 10);
+		limiter.consume(7);
+		assert_eq!(limiter.available(start), 3);
+		limiter.consume(5);
+		assert_eq!(limiter.available(start + Duration::from_millis(500)), 0);
+		assert_eq!(limiter.available(start + Duration::from_secs(2)), 10);
+	}
+
+	#[test]
+	fn should_not_limit_without_limit() {
+		let mut limiter = RateLimiter::new(None);
+		limiter.consume(1_000_000);
+		assert_eq!(limiter.available(Instant::now()), usize::max_value());
+	}
+}
@@ -25,4 +25,6 @@ pub struct CallAnalytics {
 	pub vm_tracing: bool,
 	/// Make a diff.
 	pub state_diffing: bool,
+	/// Make an instruction coverage and gas profile. Takes precedence over `vm_tracing`.
+	pub vm_profiling: bool,
 }
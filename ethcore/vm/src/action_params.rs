@@ -34,7 +34,7 @@ pub enum ActionValue {
 }
 
 /// Type of the way parameters encoded
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ParamsType {
 	/// Parameters are included in code
 	Embedded,
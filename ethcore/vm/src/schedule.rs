@@ -145,6 +145,16 @@ pub struct WasmCosts {
 	pub opcodes_mul: u32,
 	/// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` / `opcodes_div`
 	pub opcodes_div: u32,
+	/// Base cost of the `keccak256` host function
+	pub keccak256: u32,
+	/// Cost of the `keccak256` host function, per 32-byte word of input
+	pub keccak256_word: u32,
+	/// Cost of the `ecrecover` host function
+	pub ecrecover: u32,
+	/// `keccak256` host function enabled
+	pub have_keccak256: bool,
+	/// `ecrecover` host function enabled
+	pub have_ecrecover: bool,
 }
 
 impl Default for WasmCosts {
@@ -162,10 +172,83 @@ impl Default for WasmCosts {
 			max_stack_height: 64*1024,
 			opcodes_mul: 3,
 			opcodes_div: 8,
+			keccak256: 80,
+			keccak256_word: 16,
+			ecrecover: 8000,
+			have_keccak256: false,
+			have_ecrecover: false,
 		}
 	}
 }
 
+/// Changes to `WasmCosts` made by a chain at some block. `None` keeps the previous value.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WasmCostsChanges {
+	/// See `WasmCosts::regular`.
+	pub regular: Option<u32>,
+	/// See `WasmCosts::div`.
+	pub div: Option<u32>,
+	/// See `WasmCosts::mul`.
+	pub mul: Option<u32>,
+	/// See `WasmCosts::mem`.
+	pub mem: Option<u32>,
+	/// See `WasmCosts::static_u256`.
+	pub static_u256: Option<u32>,
+	/// See `WasmCosts::static_address`.
+	pub static_address: Option<u32>,
+	/// See `WasmCosts::initial_mem`.
+	pub initial_mem: Option<u32>,
+	/// See `WasmCosts::grow_mem`.
+	pub grow_mem: Option<u32>,
+	/// See `WasmCosts::memcpy`.
+	pub memcpy: Option<u32>,
+	/// See `WasmCosts::max_stack_height`.
+	pub max_stack_height: Option<u32>,
+	/// See `WasmCosts::opcodes_mul`.
+	pub opcodes_mul: Option<u32>,
+	/// See `WasmCosts::opcodes_div`.
+	pub opcodes_div: Option<u32>,
+	/// See `WasmCosts::keccak256`.
+	pub keccak256: Option<u32>,
+	/// See `WasmCosts::keccak256_word`.
+	pub keccak256_word: Option<u32>,
+	/// See `WasmCosts::ecrecover`.
+	pub ecrecover: Option<u32>,
+	/// Enable the `keccak256` host function.
+	pub enable_keccak256: bool,
+	/// Enable the `ecrecover` host function.
+	pub enable_ecrecover: bool,
+}
+
+impl WasmCostsChanges {
+	/// Overwrite given costs with the changed values.
+	pub fn apply(&self, costs: &mut WasmCosts) {
+		fn set(cost: &mut u32, value: Option<u32>) {
+			if let Some(value) = value {
+				*cost = value;
+			}
+		}
+
+		set(&mut costs.regular, self.regular);
+		set(&mut costs.div, self.div);
+		set(&mut costs.mul, self.mul);
+		set(&mut costs.mem, self.mem);
+		set(&mut costs.static_u256, self.static_u256);
+		set(&mut costs.static_address, self.static_address);
+		set(&mut costs.initial_mem, self.initial_mem);
+		set(&mut costs.grow_mem, self.grow_mem);
+		set(&mut costs.memcpy, self.memcpy);
+		set(&mut costs.max_stack_height, self.max_stack_height);
+		set(&mut costs.opcodes_mul, self.opcodes_mul);
+		set(&mut costs.opcodes_div, self.opcodes_div);
+		set(&mut costs.keccak256, self.keccak256);
+		set(&mut costs.keccak256_word, self.keccak256_word);
+		set(&mut costs.ecrecover, self.ecrecover);
+		costs.have_keccak256 |= self.enable_keccak256;
+		costs.have_ecrecover |= self.enable_ecrecover;
+	}
+}
+
 /// Dust accounts cleanup mode.
 #[derive(PartialEq, Eq)]
 pub enum CleanDustMode {
@@ -17,6 +17,7 @@
 //! Env module glue for wasmi interpreter
 
 use std::cell::RefCell;
+use vm::WasmCosts;
 use wasmi::{
 	self, Signature, Error, FuncRef, FuncInstance, MemoryDescriptor,
 	MemoryRef, MemoryInstance, memory_units,
@@ -47,6 +48,8 @@ pub mod ids {
 	pub const SENDER_FUNC: usize = 190;
 	pub const ORIGIN_FUNC: usize = 200;
 	pub const ELOG_FUNC: usize = 210;
+	pub const KECCAK256_FUNC: usize = 220;
+	pub const ECRECOVER_FUNC: usize = 230;
 
 	pub const PANIC_FUNC: usize = 1000;
 	pub const DEBUG_FUNC: usize = 1010;
@@ -180,6 +183,16 @@ pub mod signatures {
 		None,
 	);
 
+	pub const KECCAK256: StaticSignature = StaticSignature(
+		&[I32, I32, I32],
+		None,
+	);
+
+	pub const ECRECOVER: StaticSignature = StaticSignature(
+		&[I32, I32, I32, I32, I32],
+		Some(I32),
+	);
+
 	impl Into<wasmi::Signature> for StaticSignature {
 		fn into(self) -> wasmi::Signature {
 			wasmi::Signature::new(self.0, self.1)
@@ -195,18 +208,24 @@ fn host(signature: signatures::StaticSignature, idx: usize) -> FuncRef {
 /// Maps all functions that runtime support to the corresponding contract import
 /// entries.
 /// Also manages initial memory request from the runtime.
+/// Optional host functions are only resolved if enabled by the schedule.
 #[derive(Default)]
 pub struct ImportResolver {
 	max_memory: u32,
 	memory: RefCell<Option<MemoryRef>>,
+	have_keccak256: bool,
+	have_ecrecover: bool,
 }
 
 impl ImportResolver {
 	/// New import resolver with specifed maximum amount of inital memory (in wasm pages = 64kb)
-	pub fn with_limit(max_memory: u32) -> ImportResolver {
+	/// and host functions enabled in the given schedule
+	pub fn with_limit(max_memory: u32, schedule: &WasmCosts) -> ImportResolver {
 		ImportResolver {
 			max_memory: max_memory,
 			memory: RefCell::new(None),
+			have_keccak256: schedule.have_keccak256,
+			have_ecrecover: schedule.have_ecrecover,
 		}
 	}
 
@@ -263,6 +282,8 @@ impl wasmi::ModuleImportResolver for ImportResolver {
 			"sender" => host(signatures::SENDER, ids::SENDER_FUNC),
 			"origin" => host(signatures::ORIGIN, ids::ORIGIN_FUNC),
 			"elog" => host(signatures::ELOG, ids::ELOG_FUNC),
+			"keccak256" if self.have_keccak256 => host(signatures::KECCAK256, ids::KECCAK256_FUNC),
+			"ecrecover" if self.have_ecrecover => host(signatures::ECRECOVER, ids::ECRECOVER_FUNC),
 			_ => {
 				return Err(wasmi::Error::Instantiation(
 					format!("Export {} not found", field_name),
@@ -296,3 +317,26 @@ impl wasmi::ModuleImportResolver for ImportResolver {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use vm::WasmCosts;
+	use wasmi::{ModuleImportResolver, Signature};
+	use super::ImportResolver;
+
+	#[test]
+	fn should_resolve_optional_functions_only_if_enabled() {
+		let signature = Signature::new(&[][..], None);
+		let mut schedule = WasmCosts::default();
+
+		let resolver = ImportResolver::with_limit(16, &schedule);
+		assert!(resolver.resolve_func("ret", &signature).is_ok());
+		assert!(resolver.resolve_func("keccak256", &signature).is_err());
+		assert!(resolver.resolve_func("ecrecover", &signature).is_err());
+
+		schedule.have_keccak256 = true;
+		let resolver = ImportResolver::with_limit(16, &schedule);
+		assert!(resolver.resolve_func("keccak256", &signature).is_ok());
+		assert!(resolver.resolve_func("ecrecover", &signature).is_err());
+	}
+}
@@ -19,6 +19,8 @@
 extern crate byteorder;
 extern crate ethcore_logger;
 extern crate ethereum_types;
+extern crate ethkey;
+extern crate keccak_hash as hash;
 #[macro_use] extern crate log;
 extern crate libc;
 extern crate parity_wasm;
@@ -90,7 +92,7 @@ impl vm::Vm for WasmInterpreter {
 
 		let loaded_module = wasmi::Module::from_parity_wasm_module(module).map_err(Error::Interpreter)?;
 
-		let instantiation_resolver = env::ImportResolver::with_limit(16);
+		let instantiation_resolver = env::ImportResolver::with_limit(16, ext.schedule().wasm());
 
 		let module_instance = wasmi::ModuleInstance::new(
 			&loaded_module,
@@ -15,6 +15,8 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 use ethereum_types::{U256, H256, Address};
+use ethkey::{self, Signature};
+use hash::keccak;
 use vm::{self, CallType};
 use wasmi::{self, MemoryRef, RuntimeArgs, RuntimeValue, Error as InterpreterError, Trap, TrapKind};
 use super::panic_payload;
@@ -694,6 +696,57 @@ impl<'a> Runtime<'a> {
 
 		Ok(())
 	}
+
+	///	Signature: `fn keccak256(input_ptr: *const u8, input_len: u32, result_ptr: *mut u8)`
+	pub fn keccak256(&mut self, args: RuntimeArgs) -> Result<()> {
+		let input_ptr: u32 = args.nth_checked(0)?;
+		let input_len: u32 = args.nth_checked(1)?;
+		let result_ptr: u32 = args.nth_checked(2)?;
+
+		self.overflow_charge(|schedule|
+			(schedule.wasm().keccak256_word as u64)
+				.checked_mul((input_len as u64 + 31) / 32)
+				.and_then(|words_gas| words_gas.checked_add(schedule.wasm().keccak256 as u64))
+		)?;
+
+		let hash = keccak(self.memory.get(input_ptr, input_len as usize)?);
+		self.memory.set(result_ptr, &*hash)?;
+
+		Ok(())
+	}
+
+	///	Signature: `fn ecrecover(hash_ptr: *const u8, v: u32, r_ptr: *const u8, s_ptr: *const u8, result_ptr: *mut u8) -> i32`
+	///
+	/// Writes the signer address and returns 0, or returns -1 if the signature is invalid.
+	/// `v` is expected to be 27 or 28.
+	pub fn ecrecover(&mut self, args: RuntimeArgs) -> Result<RuntimeValue> {
+		let hash = self.h256_at(args.nth_checked(0)?)?;
+		let v: u32 = args.nth_checked(1)?;
+		let r = self.h256_at(args.nth_checked(2)?)?;
+		let s = self.h256_at(args.nth_checked(3)?)?;
+		let result_ptr: u32 = args.nth_checked(4)?;
+
+		self.charge(|schedule| schedule.wasm().ecrecover as u64)?;
+
+		let bit = match v {
+			27 | 28 => (v - 27) as u8,
+			_ => return Ok((-1i32).into()),
+		};
+
+		let signature = Signature::from_rsv(&r, &s, bit);
+		if !signature.is_valid() {
+			return Ok((-1i32).into());
+		}
+
+		match ethkey::recover(&signature, &hash) {
+			Ok(public) => {
+				let address = ethkey::public_to_address(&public);
+				self.memory.set(result_ptr, &*address)?;
+				Ok(0i32.into())
+			},
+			Err(_) => Ok((-1i32).into()),
+		}
+	}
 }
 
 mod ext_impl {
@@ -744,6 +797,8 @@ mod ext_impl {
 				SENDER_FUNC => void!(self.sender(args)),
 				ORIGIN_FUNC => void!(self.origin(args)),
 				ELOG_FUNC => void!(self.elog(args)),
+				KECCAK256_FUNC => void!(self.keccak256(args)),
+				ECRECOVER_FUNC => some!(self.ecrecover(args)),
 				_ => panic!("env module doesn't provide function at index {}", index),
 			}
 		}
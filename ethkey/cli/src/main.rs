@@ -17,6 +17,7 @@
 extern crate docopt;
 extern crate env_logger;
 extern crate ethkey;
+extern crate num_cpus;
 extern crate panic_hook;
 extern crate parity_wordlist;
 extern crate rustc_hex;
@@ -28,10 +29,11 @@ extern crate serde_derive;
 
 use std::num::ParseIntError;
 use std::{env, fmt, process, io, sync};
+use std::time::Duration;
 
 use docopt::Docopt;
-use ethkey::{KeyPair, Random, Brain, BrainPrefix, Prefix, Error as EthkeyError, Generator, sign, verify_public, verify_address, brain_recover};
-use rustc_hex::{FromHex, FromHexError};
+use ethkey::{KeyPair, Random, Brain, BrainPrefix, Prefix, Vanity, VanityMatcher, VanityPattern, VanityProgress, Mnemonic, MnemonicError, DerivationPath, Error as EthkeyError, Generator, sign, verify_public, verify_address, brain_recover};
+use rustc_hex::{FromHex, FromHexError, ToHex};
 
 const USAGE: &'static str = r#"
 Ethereum keys generator.
@@ -41,6 +43,8 @@ Usage:
     ethkey info <secret-or-phrase> [options]
     ethkey generate random [options]
     ethkey generate prefix <prefix> [options]
+    ethkey generate vanity <pattern> [options]
+    ethkey generate mnemonic [options]
     ethkey sign <secret> <message>
     ethkey verify public <public> <signature> <message>
     ethkey verify address <address> <signature> <message>
@@ -53,11 +57,22 @@ Options:
     -p, --public       Display only the public.
     -a, --address      Display only the address.
     -b, --brain        Use parity brain wallet algorithm.
+    -m, --mnemonic     Use BIP-39 mnemonic and BIP-32 derivation.
+    --words WORDS      Number of words of generated mnemonic [default: 12].
+    --passphrase PASS  BIP-39 passphrase of the mnemonic.
+    --path PATH        BIP-32 derivation path of the key [default: m/44'/60'/0'/0/0].
+    --suffix           Vanity pattern must match the end of the address.
+    --regex            Vanity pattern is a regular expression matched against the hex address.
+    --checksum         Match vanity pattern against EIP-55 checksummed address (case-sensitive).
+    --contract         Match the address of the first contract deployed by the key.
+    --threads NUM      Number of threads used for vanity generation, 0 for number of CPUs [default: 0].
 
 Commands:
     info               Display public and address of the secret.
     generate random    Generates new random ethereum key.
     generate prefix    Random generation, but address must start with a prefix.
+    generate vanity    Multithreaded random generation, address must match given pattern.
+    generate mnemonic  Generates new BIP-39 mnemonic and derives the key from it.
     sign               Sign message using secret.
     verify             Verify signer of the signature.
     recover            Try to find brain phrase matching given address from partial phrase.
@@ -69,12 +84,15 @@ struct Args {
 	cmd_generate: bool,
 	cmd_random: bool,
 	cmd_prefix: bool,
+	cmd_vanity: bool,
+	cmd_mnemonic: bool,
 	cmd_sign: bool,
 	cmd_verify: bool,
 	cmd_public: bool,
 	cmd_address: bool,
 	cmd_recover: bool,
 	arg_prefix: String,
+	arg_pattern: String,
 	arg_secret: String,
 	arg_secret_or_phrase: String,
 	arg_known_phrase: String,
@@ -86,6 +104,15 @@ struct Args {
 	flag_public: bool,
 	flag_address: bool,
 	flag_brain: bool,
+	flag_mnemonic: bool,
+	flag_words: usize,
+	flag_passphrase: String,
+	flag_path: String,
+	flag_suffix: bool,
+	flag_regex: bool,
+	flag_checksum: bool,
+	flag_contract: bool,
+	flag_threads: usize,
 }
 
 #[derive(Debug)]
@@ -193,7 +220,10 @@ fn execute<S, I>(command: I) -> Result<String, Error> where I: IntoIterator<Item
 	return if args.cmd_info {
 		let display_mode = DisplayMode::new(&args);
 
-		let result = if args.flag_brain {
+		let result = if args.flag_mnemonic {
+			let keypair = mnemonic_keypair(&Mnemonic::from_phrase(&args.arg_secret_or_phrase).map_err(mnemonic_error)?, &args)?;
+			(keypair, None)
+		} else if args.flag_brain {
 			let phrase = args.arg_secret_or_phrase;
 			let phrase_info = validate_phrase(&phrase);
 			let keypair = Brain::new(phrase).generate().expect("Brain wallet generator is infallible; qed");
@@ -235,6 +265,13 @@ fn execute<S, I>(command: I) -> Result<String, Error> where I: IntoIterator<Item
 					Ok(res.map(Some).unwrap_or(None))
 				}
 			})?
+		} else if args.cmd_vanity {
+			vanity(&args)?
+		} else if args.cmd_mnemonic {
+			let mnemonic = Mnemonic::new(args.flag_words).map_err(mnemonic_error)?;
+			let keypair = mnemonic_keypair(&mnemonic, &args)?;
+			let phrase = format!("mnemonic: {}", mnemonic.phrase());
+			(keypair, Some(phrase))
 		} else {
 			return Ok(format!("{}", USAGE))
 		};
@@ -288,6 +325,60 @@ fn execute<S, I>(command: I) -> Result<String, Error> where I: IntoIterator<Item
 }
 
 const BRAIN_WORDS: usize = 12;
+const VANITY_PROGRESS_INTERVAL_SECS: u64 = 5;
+
+fn vanity(args: &Args) -> Result<(KeyPair, Option<String>), EthkeyError> {
+	let pattern = if args.flag_regex {
+		VanityPattern::regex(&args.arg_pattern)?
+	} else if args.flag_suffix {
+		VanityPattern::Suffix(args.arg_pattern.trim_left_matches("0x").into())
+	} else {
+		VanityPattern::Prefix(args.arg_pattern.trim_left_matches("0x").into())
+	};
+	let matcher = VanityMatcher::new(pattern, args.flag_checksum, args.flag_contract)?;
+	let difficulty = matcher.difficulty();
+	let threads = match args.flag_threads {
+		0 => num_cpus::get(),
+		threads => threads,
+	};
+
+	if let Some(difficulty) = difficulty {
+		eprintln!("Difficulty: {:.0}, using {} threads.", difficulty, threads);
+	}
+
+	let report = |progress: &VanityProgress| match difficulty {
+		Some(difficulty) => eprintln!(
+			"Generated {} keys ({:.0} keys/s), {:.2}% probability of a match so far, expected time: {}s.",
+			progress.attempts,
+			progress.rate(),
+			progress.probability(difficulty) * 100f64,
+			progress.expected_time(difficulty).map_or("unknown".into(), |t| t.as_secs().to_string())
+		),
+		None => eprintln!("Generated {} keys ({:.0} keys/s).", progress.attempts, progress.rate()),
+	};
+
+	let keypair = Vanity::new(matcher.clone(), threads)
+		.generate_with_progress(Duration::from_secs(VANITY_PROGRESS_INTERVAL_SECS), report)?;
+
+	let target = matcher.target(&keypair);
+	let extra = match (args.flag_contract, args.flag_checksum) {
+		(true, true) => Some(format!("contract: {}", ethkey::checksum_address(&target))),
+		(true, false) => Some(format!("contract: {}", target.to_hex())),
+		(false, true) => Some(format!("checksum address: {}", ethkey::checksum_address(&target))),
+		(false, false) => None,
+	};
+
+	Ok((keypair, extra))
+}
+
+fn mnemonic_error(err: MnemonicError) -> EthkeyError {
+	EthkeyError::Custom(format!("{}", err))
+}
+
+fn mnemonic_keypair(mnemonic: &Mnemonic, args: &Args) -> Result<KeyPair, EthkeyError> {
+	let path: DerivationPath = args.flag_path.parse().map_err(|_| EthkeyError::Custom(format!("Invalid derivation path: {}", args.flag_path)))?;
+	mnemonic.derive(&args.flag_passphrase, &path).map_err(|e| EthkeyError::Custom(format!("{:?}", e)))
+}
 
 fn validate_phrase(phrase: &str) -> String {
 	match Brain::validate_phrase(phrase, BRAIN_WORDS) {
@@ -355,6 +446,18 @@ address: 26d1ec50b4e62c1d1a40d16e7cacc6a6580757d5".to_owned();
 		assert_eq!(execute(command).unwrap(), expected);
 	}
 
+	#[test]
+	fn vanity_suffix() {
+		let command = vec!["ethkey", "generate", "vanity", "a", "--suffix", "--threads", "2", "--address"]
+			.into_iter()
+			.map(Into::into)
+			.collect::<Vec<String>>();
+
+		let address = execute(command).unwrap();
+		assert_eq!(address.len(), 40);
+		assert!(address.ends_with("a"));
+	}
+
 	#[test]
 	fn brain() {
 		let command = vec!["ethkey", "info", "--brain", "this is sparta"]
@@ -437,6 +540,28 @@ address: 006e27b6a72e1f34c626762f3c4761547aff1421".to_owned();
 		assert_eq!(execute(command).unwrap(), expected);
 	}
 
+	#[test]
+	fn mnemonic() {
+		let command = vec!["ethkey", "info", "--mnemonic", "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", "--secret"]
+			.into_iter()
+			.map(Into::into)
+			.collect::<Vec<String>>();
+
+		let expected = "1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727".to_owned();
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn mnemonic_with_path() {
+		let command = vec!["ethkey", "info", "-m", "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", "--path", "m/44'/60'/0'/0/1", "--secret"]
+			.into_iter()
+			.map(Into::into)
+			.collect::<Vec<String>>();
+
+		let expected = "9a983cb3d832fbde5ab49d692b7a8bf5b5d232479c99333d0fc8e1d21f1b55b6".to_owned();
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
 	#[test]
 	fn verify_invalid() {
 		let command = vec!["ethkey", "verify", "public", "689268c0ff57a20cd299fa60d3fb374862aff565b20b5f1767906a99e6e09f3ff04ca2b2a5cd22f62941db103c0356df1a8ed20ce322cab2483db67685afd124", "c1878cf60417151c766a712653d26ef350c8c75393458b7a9be715f053215af63dfd3b02c2ae65a8677917a8efa3172acb71cb90196e42106953ea0363c5aaf200", "bd50b7370c3f96733b31744c6c45079e7ae6c8d299613246d28ebcef507ec986"]
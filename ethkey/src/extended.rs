@@ -16,11 +16,17 @@
 
 //! Extended keys
 
+use std::fmt;
+use std::str::FromStr;
+
 use secret::Secret;
 use Public;
 use ethereum_types::H256;
 pub use self::derivation::Error as DerivationError;
 
+/// Index bit marking hardened derivation in BIP-32 paths.
+pub const HARDENED: u32 = 1 << 31;
+
 /// Represents label that can be stored as a part of key derivation
 pub trait Label {
 	/// Length of the data that label occupies
@@ -60,6 +66,57 @@ impl From<u32> for Derivation<u32> {
 	}
 }
 
+/// BIP-32 derivation path, e.g. `m/44'/60'/0'/0/0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+	/// BIP-44 path of the Ethereum account with given index: `m/44'/60'/0'/0/index`.
+	pub fn ethereum(index: u32) -> Self {
+		DerivationPath(vec![44 | HARDENED, 60 | HARDENED, HARDENED, 0, index])
+	}
+
+	/// Indices of subsequent derivations, hardened ones have the `HARDENED` bit set.
+	pub fn indices(&self) -> &[u32] {
+		&self.0
+	}
+}
+
+impl FromStr for DerivationPath {
+	type Err = DerivationError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut parts = s.split('/');
+		if parts.next() != Some("m") {
+			return Err(DerivationError::InvalidPath);
+		}
+
+		parts.map(|part| {
+			let (index, hardened) = match part.ends_with('\'') {
+				true => (&part[..part.len() - 1], true),
+				false => (part, false),
+			};
+			match index.parse::<u32>() {
+				Ok(index) if index < HARDENED => Ok(if hardened { index | HARDENED } else { index }),
+				_ => Err(DerivationError::InvalidPath),
+			}
+		}).collect::<Result<_, _>>().map(DerivationPath)
+	}
+}
+
+impl fmt::Display for DerivationPath {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "m")?;
+		for index in &self.0 {
+			match *index >= HARDENED {
+				true => write!(f, "/{}'", index - HARDENED)?,
+				false => write!(f, "/{}", index)?,
+			}
+		}
+		Ok(())
+	}
+}
+
 impl Label for H256 {
 	fn len() -> usize { 32 }
 
@@ -201,6 +258,19 @@ impl ExtendedKeyPair {
 			secret: derived,
 		})
 	}
+
+	/// Derive key pair following all steps of the BIP-32 path.
+	pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, DerivationError> {
+		let secret = path.indices().iter()
+			.fold(ExtendedSecret::with_code(self.secret.secret.clone(), self.secret.chain_code), |secret, index| {
+				secret.derive((*index).into())
+			});
+
+		Ok(ExtendedKeyPair {
+			public: ExtendedPublic::from_secret(&secret)?,
+			secret: secret,
+		})
+	}
 }
 
 // Derivation functions for private and public keys
@@ -221,6 +291,7 @@ mod derivation {
 		InvalidPoint,
 		MissingIndex,
 		InvalidSeed,
+		InvalidPath,
 	}
 
 	// Deterministic derivation of the key using secp256k1 elliptic curve.
@@ -375,7 +446,7 @@ mod derivation {
 
 #[cfg(test)]
 mod tests {
-	use super::{ExtendedSecret, ExtendedPublic, ExtendedKeyPair};
+	use super::{ExtendedSecret, ExtendedPublic, ExtendedKeyPair, DerivationPath};
 	use secret::Secret;
 	use std::str::FromStr;
 	use ethereum_types::{H128, H256};
@@ -477,6 +548,29 @@ mod tests {
 		assert_eq!(private_seed, test_private);
 	}
 
+	#[test]
+	fn derivation_path() {
+		let path: DerivationPath = "m/44'/60'/0'/0/7".parse().unwrap();
+
+		assert_eq!(path, DerivationPath::ethereum(7));
+		assert_eq!(path.to_string(), "m/44'/60'/0'/0/7");
+		assert!("44'/60'".parse::<DerivationPath>().is_err());
+		assert!("m/2147483648".parse::<DerivationPath>().is_err());
+		assert!("m/x'".parse::<DerivationPath>().is_err());
+	}
+
+	#[test]
+	fn derive_path_matches_subsequent_derivations() {
+		let (private_seed, chain_code) = master_chain_basic();
+		let keypair = ExtendedKeyPair::with_secret(Secret::from(private_seed.0), chain_code);
+		let derived = keypair.derive_path(&"m/0'/1".parse().unwrap()).unwrap();
+
+		assert_eq!(
+			**derived.secret().as_raw(),
+			H256::from_str("3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368").unwrap()
+		);
+	}
+
 	#[test]
 	fn test_vector_1() {
 		// xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7
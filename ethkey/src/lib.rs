@@ -25,9 +25,11 @@ extern crate parity_wordlist;
 #[macro_use]
 extern crate quick_error;
 extern crate rand;
+extern crate regex;
 extern crate rustc_hex;
 extern crate secp256k1;
 extern crate tiny_keccak;
+extern crate unicode_normalization;
 
 #[macro_use]
 extern crate lazy_static;
@@ -44,6 +46,8 @@ mod random;
 mod signature;
 mod secret;
 mod extended;
+mod mnemonic;
+mod vanity;
 
 pub mod brain_recover;
 pub mod crypto;
@@ -59,7 +63,9 @@ pub use self::prefix::Prefix;
 pub use self::random::Random;
 pub use self::signature::{sign, verify_public, verify_address, recover, Signature};
 pub use self::secret::Secret;
-pub use self::extended::{ExtendedPublic, ExtendedSecret, ExtendedKeyPair, DerivationError, Derivation};
+pub use self::extended::{ExtendedPublic, ExtendedSecret, ExtendedKeyPair, DerivationError, Derivation, DerivationPath, HARDENED};
+pub use self::mnemonic::{Mnemonic, MnemonicError};
+pub use self::vanity::{Vanity, VanityMatcher, VanityPattern, Progress as VanityProgress, checksum_address, contract_address};
 
 use ethereum_types::H256;
 
@@ -0,0 +1,233 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! BIP-39 mnemonic phrases.
+//! https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+
+use std::{fmt, error};
+
+use ethcore_crypto::{digest, pbkdf2};
+use mem::Memzero;
+use rand::Rng;
+use rand::os::OsRng;
+use unicode_normalization::UnicodeNormalization;
+
+use ethereum_types::H512;
+use extended::{ExtendedKeyPair, DerivationError, DerivationPath};
+use KeyPair;
+
+/// Number of PBKDF2 rounds used to stretch the phrase into a seed.
+const PBKDF2_ROUNDS: u32 = 2048;
+
+lazy_static! {
+	static ref WORDS: Vec<&'static str> = include_str!("./bip39_english.txt").split_whitespace().collect();
+}
+
+/// Mnemonic error.
+#[derive(Debug, PartialEq)]
+pub enum MnemonicError {
+	/// Number of words is not one of 12, 15, 18, 21 or 24.
+	InvalidWordCount(usize),
+	/// Entropy is not one of 16, 20, 24, 28 or 32 bytes long.
+	InvalidEntropyLength(usize),
+	/// Word is not in the BIP-39 english wordlist.
+	UnknownWord(String),
+	/// Checksum of the phrase doesn't match.
+	InvalidChecksum,
+}
+
+impl fmt::Display for MnemonicError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			MnemonicError::InvalidWordCount(count) => write!(f, "Invalid number of words: {}", count),
+			MnemonicError::InvalidEntropyLength(len) => write!(f, "Invalid entropy length: {} bytes", len),
+			MnemonicError::UnknownWord(ref word) => write!(f, "The word '{}' does not come from the BIP-39 wordlist", word),
+			MnemonicError::InvalidChecksum => write!(f, "Invalid checksum"),
+		}
+	}
+}
+
+impl error::Error for MnemonicError {
+	fn description(&self) -> &str {
+		"Mnemonic error"
+	}
+}
+
+/// BIP-39 mnemonic phrase, encoding 128 to 256 bits of entropy.
+pub struct Mnemonic {
+	entropy: Memzero<Vec<u8>>,
+}
+
+impl Mnemonic {
+	/// Generate new random mnemonic with given number of words.
+	pub fn new(words: usize) -> Result<Self, MnemonicError> {
+		if words < 12 || words > 24 || words % 3 != 0 {
+			return Err(MnemonicError::InvalidWordCount(words));
+		}
+
+		let mut entropy = Memzero::from(vec![0u8; words / 3 * 4]);
+		OsRng::new()
+			.expect("Not able to operate without random source.")
+			.fill_bytes(&mut entropy);
+		Mnemonic::from_entropy(&entropy)
+	}
+
+	/// Create mnemonic encoding given entropy.
+	pub fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError> {
+		if entropy.len() < 16 || entropy.len() > 32 || entropy.len() % 4 != 0 {
+			return Err(MnemonicError::InvalidEntropyLength(entropy.len()));
+		}
+
+		Ok(Mnemonic {
+			entropy: Memzero::from(entropy.to_vec()),
+		})
+	}
+
+	/// Parse and validate the phrase.
+	pub fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
+		let words: Vec<_> = phrase.split_whitespace().collect();
+		if words.len() < 12 || words.len() > 24 || words.len() % 3 != 0 {
+			return Err(MnemonicError::InvalidWordCount(words.len()));
+		}
+
+		// 11 bits per word, the last `words / 3` bits are the checksum.
+		let mut data = Memzero::from(vec![0u8; (words.len() * 11 + 7) / 8]);
+		for (i, word) in words.iter().enumerate() {
+			let index = WORDS.binary_search(word).map_err(|_| MnemonicError::UnknownWord(word.to_string()))?;
+			for bit in 0..11 {
+				if index & (1 << (10 - bit)) != 0 {
+					let position = i * 11 + bit;
+					data[position / 8] |= 1 << (7 - position % 8);
+				}
+			}
+		}
+
+		let mnemonic = Mnemonic::from_entropy(&data[..words.len() / 3 * 4])?;
+		if mnemonic.words() != words {
+			return Err(MnemonicError::InvalidChecksum);
+		}
+		Ok(mnemonic)
+	}
+
+	fn words(&self) -> Vec<&'static str> {
+		let checksum = digest::sha256(&self.entropy)[0];
+		let bit = |position: usize| match self.entropy.get(position / 8) {
+			Some(byte) => byte >> (7 - position % 8) & 1,
+			None => checksum >> (7 - position % 8) & 1,
+		};
+
+		(0..self.entropy.len() * 3 / 4)
+			.map(|i| (0..11).fold(0, |index, b| index << 1 | bit(i * 11 + b) as usize))
+			.map(|index| WORDS[index])
+			.collect()
+	}
+
+	/// Space-separated phrase.
+	pub fn phrase(&self) -> String {
+		self.words().join(" ")
+	}
+
+	/// Stretch the phrase with given passphrase into a 512-bit seed.
+	/// Both are NFKD-normalized first, as required by BIP-39.
+	pub fn seed(&self, passphrase: &str) -> Memzero<H512> {
+		let phrase = Memzero::from(self.phrase().nfkd().collect::<String>().into_bytes());
+		let salt = Memzero::from(format!("mnemonic{}", passphrase).nfkd().collect::<String>().into_bytes());
+		let mut seed = Memzero::from(H512::default());
+		pbkdf2::sha512(PBKDF2_ROUNDS, pbkdf2::Salt(&salt), pbkdf2::Secret(&phrase), &mut seed.0);
+		seed
+	}
+
+	/// Derive key pair at given BIP-32 path from the seed of this mnemonic.
+	pub fn derive(&self, passphrase: &str, path: &DerivationPath) -> Result<KeyPair, DerivationError> {
+		let master = ExtendedKeyPair::with_seed(&self.seed(passphrase))?;
+		let derived = master.derive_path(path)?;
+		KeyPair::from_secret(derived.secret().as_raw().clone()).map_err(|_| DerivationError::InvalidPoint)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rustc_hex::{FromHex, ToHex};
+	use super::*;
+
+	#[test]
+	fn should_encode_entropy_test_vectors() {
+		assert_eq!(
+			Mnemonic::from_entropy(&[0u8; 16]).unwrap().phrase(),
+			"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+		);
+		assert_eq!(
+			Mnemonic::from_entropy(&[0x7f; 16]).unwrap().phrase(),
+			"legal winner thank year wave sausage worth useful legal winner thank yellow"
+		);
+		assert_eq!(
+			Mnemonic::from_entropy(&"9e885d952ad362caeb4efe34a8e91bd2".from_hex().unwrap()).unwrap().phrase(),
+			"ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic"
+		);
+		assert_eq!(Mnemonic::from_entropy(&[0u8; 15]).err(), Some(MnemonicError::InvalidEntropyLength(15)));
+	}
+
+	#[test]
+	fn should_validate_phrase() {
+		let phrase = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+		assert_eq!(&*Mnemonic::from_phrase(phrase).unwrap().entropy, &vec![0x7f; 16]);
+
+		let wrong_checksum = "legal winner thank year wave sausage worth useful legal winner thank thank";
+		assert_eq!(Mnemonic::from_phrase(wrong_checksum).err(), Some(MnemonicError::InvalidChecksum));
+		assert_eq!(Mnemonic::from_phrase("legal winner").err(), Some(MnemonicError::InvalidWordCount(2)));
+		assert_eq!(
+			Mnemonic::from_phrase("legal winner thank year wave sausage worth useful legal winner thank parity").err(),
+			Some(MnemonicError::UnknownWord("parity".into()))
+		);
+	}
+
+	#[test]
+	fn should_generate_valid_phrase() {
+		let mnemonic = Mnemonic::new(24).unwrap();
+		let phrase = mnemonic.phrase();
+
+		assert_eq!(phrase.split(' ').count(), 24);
+		assert_eq!(Mnemonic::from_phrase(&phrase).unwrap().phrase(), phrase);
+		assert!(Mnemonic::new(13).is_err());
+	}
+
+	#[test]
+	fn should_derive_seed_and_keys() {
+		let mnemonic = Mnemonic::from_entropy(&[0u8; 16]).unwrap();
+
+		assert_eq!(
+			mnemonic.seed("TREZOR").to_hex(),
+			"c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+		);
+		assert_eq!(
+			format!("{:x}", mnemonic.derive("", &DerivationPath::ethereum(0)).unwrap().secret()),
+			"1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727"
+		);
+		assert_eq!(
+			format!("{:x}", mnemonic.derive("", &DerivationPath::ethereum(1)).unwrap().secret()),
+			"9a983cb3d832fbde5ab49d692b7a8bf5b5d232479c99333d0fc8e1d21f1b55b6"
+		);
+	}
+
+	#[test]
+	fn should_normalize_passphrase() {
+		let mnemonic = Mnemonic::from_entropy(&[0u8; 16]).unwrap();
+
+		// precomposed and decomposed forms of the same passphrase
+		assert_eq!(*mnemonic.seed("caf\u{e9}"), *mnemonic.seed("cafe\u{301}"));
+		assert!(*mnemonic.seed("caf\u{e9}") != *mnemonic.seed("cafe"));
+	}
+}
@@ -0,0 +1,313 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Multithreaded vanity address generation.
+
+use std::sync::{atomic, mpsc, Arc};
+use std::time::{Duration, Instant};
+use std::thread;
+use regex::Regex;
+use rustc_hex::ToHex;
+use keccak::Keccak256;
+use super::{Random, Generator, KeyPair, Address, Error};
+
+/// Pattern the vanity address has to match.
+#[derive(Debug, Clone)]
+pub enum VanityPattern {
+	/// Hex address starts with given string.
+	Prefix(String),
+	/// Hex address ends with given string.
+	Suffix(String),
+	/// Hex address (without `0x`) matches given regular expression.
+	Regex(Regex),
+}
+
+impl VanityPattern {
+	/// Creates a pattern from a regular expression.
+	pub fn regex(pattern: &str) -> Result<Self, Error> {
+		Regex::new(pattern)
+			.map(VanityPattern::Regex)
+			.map_err(|e| Error::Custom(format!("Invalid regular expression: {}", e)))
+	}
+}
+
+/// Checks whether keypair matches the vanity requirements.
+#[derive(Debug, Clone)]
+pub struct VanityMatcher {
+	pattern: VanityPattern,
+	checksum: bool,
+	contract: bool,
+}
+
+impl VanityMatcher {
+	/// Creates new matcher.
+	/// If `checksum` is true the pattern is matched against EIP-55 checksummed address (case-sensitive).
+	/// If `contract` is true the pattern is matched against the address of the first contract created by the key.
+	pub fn new(pattern: VanityPattern, checksum: bool, contract: bool) -> Result<Self, Error> {
+		let pattern = match pattern {
+			VanityPattern::Prefix(hex) => VanityPattern::Prefix(Self::validate_hex(hex, checksum)?),
+			VanityPattern::Suffix(hex) => VanityPattern::Suffix(Self::validate_hex(hex, checksum)?),
+			regex => regex,
+		};
+
+		Ok(VanityMatcher { pattern, checksum, contract })
+	}
+
+	fn validate_hex(hex: String, checksum: bool) -> Result<String, Error> {
+		if hex.len() > 40 || !hex.chars().all(|c| c.is_digit(16)) {
+			return Err(Error::Custom(format!("Invalid hex pattern: {}", hex)));
+		}
+
+		Ok(if checksum { hex } else { hex.to_lowercase() })
+	}
+
+	/// Returns the address the pattern is matched against.
+	pub fn target(&self, keypair: &KeyPair) -> Address {
+		if self.contract {
+			contract_address(&keypair.address())
+		} else {
+			keypair.address()
+		}
+	}
+
+	/// Returns true if given keypair matches.
+	pub fn matches(&self, keypair: &KeyPair) -> bool {
+		let address = self.target(keypair);
+		let hex = if self.checksum {
+			checksum_address(&address)
+		} else {
+			address.to_hex()
+		};
+
+		match self.pattern {
+			VanityPattern::Prefix(ref prefix) => hex.starts_with(prefix.as_str()),
+			VanityPattern::Suffix(ref suffix) => hex.ends_with(suffix.as_str()),
+			VanityPattern::Regex(ref regex) => regex.is_match(&hex),
+		}
+	}
+
+	/// Expected number of keys to generate before finding a match.
+	/// Unknown for regular expressions.
+	pub fn difficulty(&self) -> Option<f64> {
+		let hex = match self.pattern {
+			VanityPattern::Prefix(ref hex) | VanityPattern::Suffix(ref hex) => hex,
+			VanityPattern::Regex(_) => return None,
+		};
+
+		// every letter has 1/2 chance to be in the right case
+		let letters = match self.checksum {
+			true => hex.chars().filter(|c| c.is_alphabetic()).count(),
+			false => 0,
+		};
+
+		Some(16f64.powi(hex.len() as i32) * 2f64.powi(letters as i32))
+	}
+}
+
+/// Returns EIP-55 checksummed hex representation of the address (without `0x`).
+pub fn checksum_address(address: &Address) -> String {
+	let hex = address.to_hex();
+	let hash = hex.as_bytes().keccak256();
+
+	hex.chars().enumerate().map(|(i, c)| {
+		let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+		if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+	}).collect()
+}
+
+/// Returns the address of the first contract created by given sender (nonce 0).
+pub fn contract_address(sender: &Address) -> Address {
+	// rlp([sender, 0])
+	let mut stream = [0u8; 23];
+	stream[0] = 0xc0 + 22;
+	stream[1] = 0x80 + 20;
+	stream[2..22].copy_from_slice(&sender[..]);
+	stream[22] = 0x80;
+
+	let hash = stream[..].keccak256();
+	let mut result = Address::default();
+	result.copy_from_slice(&hash[12..]);
+	result
+}
+
+/// Progress of vanity generation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progress {
+	/// Number of generated keys.
+	pub attempts: u64,
+	/// Time elapsed since generation started.
+	pub elapsed: Duration,
+}
+
+impl Progress {
+	/// Number of keys generated per second.
+	pub fn rate(&self) -> f64 {
+		let secs = self.elapsed.as_secs() as f64 + self.elapsed.subsec_nanos() as f64 / 1_000_000_000f64;
+		if secs > 0f64 { self.attempts as f64 / secs } else { 0f64 }
+	}
+
+	/// Probability that a match would have been found by now.
+	pub fn probability(&self, difficulty: f64) -> f64 {
+		1f64 - (1f64 - 1f64 / difficulty).powf(self.attempts as f64)
+	}
+
+	/// Expected time to find a match given current rate.
+	pub fn expected_time(&self, difficulty: f64) -> Option<Duration> {
+		let rate = self.rate();
+		if rate > 0f64 {
+			Some(Duration::from_secs((difficulty / rate) as u64))
+		} else {
+			None
+		}
+	}
+}
+
+/// Generates keypairs matching `VanityMatcher` using multiple threads.
+pub struct Vanity {
+	matcher: Arc<VanityMatcher>,
+	threads: usize,
+}
+
+impl Vanity {
+	/// Creates new generator running on given number of threads.
+	pub fn new(matcher: VanityMatcher, threads: usize) -> Self {
+		Vanity {
+			matcher: Arc::new(matcher),
+			threads: ::std::cmp::max(threads, 1),
+		}
+	}
+
+	/// Generates a matching keypair, invoking `on_progress` every `interval`.
+	pub fn generate_with_progress<F>(&self, interval: Duration, mut on_progress: F) -> Result<KeyPair, Error> where
+		F: FnMut(&Progress),
+	{
+		let started = Instant::now();
+		let (tx, rx) = mpsc::channel();
+		let is_done = Arc::new(atomic::AtomicBool::default());
+		let attempts = Arc::new(atomic::AtomicUsize::default());
+
+		let handles = (0..self.threads).map(|_| {
+			let tx = tx.clone();
+			let is_done = is_done.clone();
+			let attempts = attempts.clone();
+			let matcher = self.matcher.clone();
+			thread::spawn(move || {
+				while !is_done.load(atomic::Ordering::Relaxed) {
+					let keypair = match Random.generate() {
+						Ok(keypair) => keypair,
+						Err(err) => {
+							let _ = tx.send(Err(Error::from(err)));
+							return;
+						},
+					};
+					attempts.fetch_add(1, atomic::Ordering::Relaxed);
+
+					if matcher.matches(&keypair) {
+						let _ = tx.send(Ok(keypair));
+						return;
+					}
+				}
+			})
+		}).collect::<Vec<_>>();
+		drop(tx);
+
+		let result = loop {
+			match rx.recv_timeout(interval) {
+				Ok(result) => break result,
+				Err(mpsc::RecvTimeoutError::Timeout) => on_progress(&Progress {
+					attempts: attempts.load(atomic::Ordering::Relaxed) as u64,
+					elapsed: started.elapsed(),
+				}),
+				Err(mpsc::RecvTimeoutError::Disconnected) => break Err(Error::Custom("Could not find keypair".into())),
+			}
+		};
+
+		is_done.store(true, atomic::Ordering::Relaxed);
+		for handle in handles {
+			let _ = handle.join();
+		}
+
+		result
+	}
+}
+
+impl Generator for Vanity {
+	type Error = Error;
+
+	fn generate(&mut self) -> Result<KeyPair, Error> {
+		self.generate_with_progress(Duration::from_secs(1), |_| {})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+	use {Address, Generator};
+	use super::*;
+
+	#[test]
+	fn should_compute_checksum_address() {
+		let address = Address::from_str("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+		assert_eq!(checksum_address(&address), "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+	}
+
+	#[test]
+	fn should_compute_contract_address() {
+		let sender = Address::from_str("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0").unwrap();
+		let expected = Address::from_str("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d").unwrap();
+		assert_eq!(contract_address(&sender), expected);
+	}
+
+	#[test]
+	fn should_compute_difficulty() {
+		let prefix = VanityMatcher::new(VanityPattern::Prefix("00".into()), false, false).unwrap();
+		let checksum = VanityMatcher::new(VanityPattern::Suffix("aB".into()), true, false).unwrap();
+		let regex = VanityMatcher::new(VanityPattern::regex("^0+").unwrap(), false, false).unwrap();
+
+		assert_eq!(prefix.difficulty(), Some(256f64));
+		assert_eq!(checksum.difficulty(), Some(1024f64));
+		assert_eq!(regex.difficulty(), None);
+	}
+
+	#[test]
+	fn should_reject_invalid_patterns() {
+		assert!(VanityMatcher::new(VanityPattern::Prefix("xyz".into()), false, false).is_err());
+		assert!(VanityPattern::regex("(").is_err());
+	}
+
+	#[test]
+	fn should_generate_matching_keypairs() {
+		let suffix = VanityMatcher::new(VanityPattern::Suffix("F".into()), false, false).unwrap();
+		let keypair = Vanity::new(suffix, 2).generate().unwrap();
+		assert!(keypair.address().to_hex().ends_with("f"));
+
+		let contract = VanityMatcher::new(VanityPattern::regex("^[0-9]{2}").unwrap(), false, true).unwrap();
+		let keypair = Vanity::new(contract, 2).generate().unwrap();
+		assert!(contract_address(&keypair.address()).to_hex()[..2].chars().all(|c| c.is_digit(10)));
+	}
+
+	#[test]
+	fn should_estimate_progress() {
+		let progress = Progress {
+			attempts: 1000,
+			elapsed: Duration::from_secs(2),
+		};
+
+		assert_eq!(progress.rate(), 500f64);
+		assert_eq!(progress.expected_time(5000f64), Some(Duration::from_secs(10)));
+		assert!(progress.probability(1000f64) > 0.63);
+	}
+}
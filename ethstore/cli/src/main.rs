@@ -32,8 +32,8 @@ use std::{env, process, fs, fmt};
 
 use docopt::Docopt;
 use ethstore::accounts_dir::{KeyDirectory, RootDiskDirectory};
-use ethstore::ethkey::Address;
-use ethstore::{EthStore, SimpleSecretStore, SecretStore, import_accounts, PresaleWallet, SecretVaultRef, StoreAccountRef};
+use ethstore::ethkey::{Address, Mnemonic};
+use ethstore::{EthStore, SimpleSecretStore, SecretStore, import_accounts, import_mnemonic, PresaleWallet, SecretVaultRef, StoreAccountRef, ScryptParams};
 
 mod crack;
 
@@ -44,9 +44,11 @@ Ethereum key management.
 Usage:
     ethstore insert <secret> <password> [--dir DIR] [--vault VAULT] [--vault-pwd VAULTPWD]
     ethstore change-pwd <address> <old-pwd> <new-pwd> [--dir DIR] [--vault VAULT] [--vault-pwd VAULTPWD]
+    ethstore reencrypt <address> <password> [--dir DIR] [--vault VAULT] [--vault-pwd VAULTPWD]
     ethstore list [--dir DIR] [--vault VAULT] [--vault-pwd VAULTPWD]
     ethstore import [--src DIR] [--dir DIR]
     ethstore import-wallet <path> <password> [--dir DIR] [--vault VAULT] [--vault-pwd VAULTPWD]
+    ethstore import-mnemonic <mnemonic> <password> [--count COUNT] [--passphrase PASS] [--dir DIR] [--vault VAULT] [--vault-pwd VAULTPWD]
     ethstore find-wallet-pass <path> <password>
     ethstore remove <address> <password> [--dir DIR] [--vault VAULT] [--vault-pwd VAULTPWD]
     ethstore sign <address> <password> <message> [--dir DIR] [--vault VAULT] [--vault-pwd VAULTPWD]
@@ -54,6 +56,7 @@ Usage:
     ethstore list-vaults [--dir DIR]
     ethstore create-vault <vault> <password> [--dir DIR]
     ethstore change-vault-pwd <vault> <old-pwd> <new-pwd> [--dir DIR]
+    ethstore reencrypt-vault <vault> <password> [--dir DIR]
     ethstore move-to-vault <address> <vault> <password> [--dir DIR] [--vault VAULT] [--vault-pwd VAULTPWD]
     ethstore move-from-vault <address> <vault> <password> [--dir DIR]
     ethstore [-h | --help]
@@ -70,13 +73,18 @@ Options:
     --src DIR                Specify import source. It may be either
                              parity, parity-(chain), get, geth-test
                              or a path [default: geth].
+    --count COUNT            Number of accounts to derive from the mnemonic [default: 1].
+    --passphrase PASS        Specify file containing BIP-39 passphrase of the mnemonic.
 
 Commands:
     insert             Save account with password.
     change-pwd         Change password.
+    reencrypt          Encrypt account again using scrypt with strong parameters.
     list               List accounts.
     import             Import accounts from src.
     import-wallet      Import presale wallet.
+    import-mnemonic    Import accounts derived from BIP-39 mnemonic stored in a file,
+                       using BIP-44 paths m/44'/60'/0'/0/i.
     find-wallet-pass   Tries to open a wallet with list of passwords given.
     remove             Remove account.
     sign               Sign message.
@@ -84,6 +92,8 @@ Commands:
     list-vaults        List vaults.
     create-vault       Create new vault.
     change-vault-pwd   Change vault password.
+    reencrypt-vault    Encrypt vault file and metadata of vault accounts again
+                       using scrypt with strong parameters.
     move-to-vault      Move account to vault from another vault/root directory.
     move-from-vault    Move account to root directory from given vault.
 "#;
@@ -92,9 +102,11 @@ Commands:
 struct Args {
 	cmd_insert: bool,
 	cmd_change_pwd: bool,
+	cmd_reencrypt: bool,
 	cmd_list: bool,
 	cmd_import: bool,
 	cmd_import_wallet: bool,
+	cmd_import_mnemonic: bool,
 	cmd_find_wallet_pass: bool,
 	cmd_remove: bool,
 	cmd_sign: bool,
@@ -102,6 +114,7 @@ struct Args {
 	cmd_list_vaults: bool,
 	cmd_create_vault: bool,
 	cmd_change_vault_pwd: bool,
+	cmd_reencrypt_vault: bool,
 	cmd_move_to_vault: bool,
 	cmd_move_from_vault: bool,
 	arg_secret: String,
@@ -112,10 +125,13 @@ struct Args {
 	arg_message: String,
 	arg_path: String,
 	arg_vault: String,
+	arg_mnemonic: String,
 	flag_src: String,
 	flag_dir: String,
 	flag_vault: String,
 	flag_vault_pwd: String,
+	flag_count: u32,
+	flag_passphrase: String,
 }
 
 enum Error {
@@ -228,6 +244,12 @@ fn execute<S, I>(command: I) -> Result<String, Error> where I: IntoIterator<Item
 		let account_ref = open_args_vault_account(&store, address, &args)?;
 		let ok = store.change_password(&account_ref, &old_pwd, &new_pwd).is_ok();
 		Ok(format!("{}", ok))
+	} else if args.cmd_reencrypt {
+		let address = args.arg_address.parse().map_err(|_| ethstore::Error::InvalidAccount)?;
+		let password = load_password(&args.arg_password)?;
+		let account_ref = open_args_vault_account(&store, address, &args)?;
+		let ok = store.reencrypt(&account_ref, &password, &ScryptParams::default()).is_ok();
+		Ok(format!("{}", ok))
 	} else if args.cmd_list {
 		let vault_ref = open_args_vault(&store, &args)?;
 		let accounts = store.accounts()?;
@@ -249,6 +271,17 @@ fn execute<S, I>(command: I) -> Result<String, Error> where I: IntoIterator<Item
 		let vault_ref = open_args_vault(&store, &args)?;
 		let account_ref = store.insert_account(vault_ref, kp.secret().clone(), &password)?;
 		Ok(format!("0x{:x}", account_ref.address))
+	} else if args.cmd_import_mnemonic {
+		let phrase = load_password(&args.arg_mnemonic)?;
+		let mnemonic = Mnemonic::from_phrase(&phrase).map_err(|e| ethstore::Error::Custom(e.to_string()))?;
+		let passphrase = match args.flag_passphrase.is_empty() {
+			true => String::new(),
+			false => load_password(&args.flag_passphrase)?,
+		};
+		let password = load_password(&args.arg_password)?;
+		let vault_ref = open_args_vault(&store, &args)?;
+		let accounts = import_mnemonic(&store, vault_ref, &mnemonic, &passphrase, args.flag_count, &password)?;
+		Ok(format_accounts(&accounts))
 	} else if args.cmd_find_wallet_pass {
 		let passwords = load_password(&args.arg_password)?;
 		let passwords = passwords.lines().map(str::to_owned).collect::<VecDeque<_>>();
@@ -286,6 +319,11 @@ fn execute<S, I>(command: I) -> Result<String, Error> where I: IntoIterator<Item
 		store.open_vault(&args.arg_vault, &old_pwd)?;
 		store.change_vault_password(&args.arg_vault, &new_pwd)?;
 		Ok("OK".to_owned())
+	} else if args.cmd_reencrypt_vault {
+		let password = load_password(&args.arg_password)?;
+		store.open_vault(&args.arg_vault, &password)?;
+		store.reencrypt_vault(&args.arg_vault, &ScryptParams::default())?;
+		Ok("OK".to_owned())
 	} else if args.cmd_move_to_vault {
 		let address = args.arg_address.parse().map_err(|_| ethstore::Error::InvalidAccount)?;
 		let password = load_password(&args.arg_password)?;
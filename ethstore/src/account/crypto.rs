@@ -20,7 +20,7 @@ use {json, Error, crypto};
 use crypto::Keccak256;
 use random::Random;
 use smallvec::SmallVec;
-use account::{Cipher, Kdf, Aes128Ctr, Pbkdf2, Prf};
+use account::{Cipher, Kdf, Aes128Ctr, Pbkdf2, Prf, Scrypt, ScryptParams};
 
 /// Encrypted data
 #[derive(Debug, PartialEq, Clone)]
@@ -77,37 +77,66 @@ impl Crypto {
 		Crypto::with_plain(&*secret, password, iterations)
 	}
 
+	/// Encrypt account secret using scrypt
+	pub fn with_secret_scrypt(secret: &Secret, password: &str, params: &ScryptParams) -> Result<Self, crypto::Error> {
+		Crypto::with_plain_scrypt(&*secret, password, params)
+	}
+
 	/// Encrypt custom plain data
 	pub fn with_plain(plain: &[u8], password: &str, iterations: u32) -> Result<Self, crypto::Error> {
 		let salt: [u8; 32] = Random::random();
-		let iv: [u8; 16] = Random::random();
 
 		// two parts of derived key
 		// DK = [ DK[0..15] DK[16..31] ] = [derived_left_bits, derived_right_bits]
 		let (derived_left_bits, derived_right_bits) = crypto::derive_key_iterations(password, &salt, iterations);
 
+		Crypto::encrypt(plain, &derived_left_bits, &derived_right_bits, Kdf::Pbkdf2(Pbkdf2 {
+			dklen: crypto::KEY_LENGTH as u32,
+			salt: salt,
+			c: iterations,
+			prf: Prf::HmacSha256,
+		}))
+	}
+
+	/// Encrypt custom plain data using scrypt
+	pub fn with_plain_scrypt(plain: &[u8], password: &str, params: &ScryptParams) -> Result<Self, crypto::Error> {
+		// scrypt silently rounds `n` down to a power of two
+		if params.n < 2 || !params.n.is_power_of_two() {
+			return Err(crypto::error::ScryptError::InvalidN.into());
+		}
+
+		let salt: [u8; 32] = Random::random();
+		let (derived_left_bits, derived_right_bits) = crypto::scrypt::derive_key(password, &salt, params.n, params.p, params.r)?;
+
+		Crypto::encrypt(plain, &derived_left_bits, &derived_right_bits, Kdf::Scrypt(Scrypt {
+			dklen: crypto::KEY_LENGTH as u32,
+			salt: salt,
+			n: params.n,
+			r: params.r,
+			p: params.p,
+		}))
+	}
+
+	fn encrypt(plain: &[u8], derived_left_bits: &[u8], derived_right_bits: &[u8], kdf: Kdf) -> Result<Self, crypto::Error> {
+		let iv: [u8; 16] = Random::random();
+
 		// preallocated (on-stack in case of `Secret`) buffer to hold cipher
 		// length = length(plain) as we are using CTR-approach
 		let plain_len = plain.len();
 		let mut ciphertext: SmallVec<[u8; 32]> = SmallVec::from_vec(vec![0; plain_len]);
 
 		// aes-128-ctr with initial vector of iv
-		crypto::aes::encrypt_128_ctr(&derived_left_bits, &iv, plain, &mut *ciphertext)?;
+		crypto::aes::encrypt_128_ctr(derived_left_bits, &iv, plain, &mut *ciphertext)?;
 
 		// KECCAK(DK[16..31] ++ <ciphertext>), where DK[16..31] - derived_right_bits
-		let mac = crypto::derive_mac(&derived_right_bits, &*ciphertext).keccak256();
+		let mac = crypto::derive_mac(derived_right_bits, &*ciphertext).keccak256();
 
 		Ok(Crypto {
 			cipher: Cipher::Aes128Ctr(Aes128Ctr {
 				iv: iv,
 			}),
 			ciphertext: ciphertext.into_vec(),
-			kdf: Kdf::Pbkdf2(Pbkdf2 {
-				dklen: crypto::KEY_LENGTH as u32,
-				salt: salt,
-				c: iterations,
-				prf: Prf::HmacSha256,
-			}),
+			kdf: kdf,
 			mac: mac,
 		})
 	}
@@ -158,6 +187,7 @@ impl Crypto {
 #[cfg(test)]
 mod tests {
 	use ethkey::{Generator, Random};
+	use account::{Kdf, Scrypt, ScryptParams};
 	use super::{Crypto, Error};
 
 	#[test]
@@ -175,6 +205,17 @@ mod tests {
 		assert_matches!(crypto.secret("this is sparta!"), Err(Error::InvalidPassword))
 	}
 
+	#[test]
+	fn crypto_with_secret_scrypt() {
+		let keypair = Random.generate().unwrap();
+		let params = ScryptParams { n: 1 << 10, r: 8, p: 1 };
+		let crypto = Crypto::with_secret_scrypt(keypair.secret(), "this is sparta", &params).unwrap();
+		assert_matches!(crypto.kdf, Kdf::Scrypt(Scrypt { n: 1024, r: 8, p: 1, .. }));
+		assert_eq!(keypair.secret(), &crypto.secret("this is sparta").unwrap());
+		assert_matches!(crypto.secret("this is sparta!"), Err(Error::InvalidPassword));
+		assert!(Crypto::with_secret_scrypt(keypair.secret(), "this is sparta", &ScryptParams { n: 1000, r: 8, p: 1 }).is_err());
+	}
+
 	#[test]
 	fn crypto_with_null_plain_data() {
 		let original_data = b"";
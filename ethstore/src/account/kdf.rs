@@ -44,6 +44,56 @@ pub enum Kdf {
 	Scrypt(Scrypt),
 }
 
+/// Cost parameters of scrypt used to encrypt keys.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ScryptParams {
+	/// CPU/memory cost, must be a power of two.
+	pub n: u32,
+	/// Block size.
+	pub r: u32,
+	/// Parallelization.
+	pub p: u32,
+}
+
+impl Default for ScryptParams {
+	fn default() -> Self {
+		// same as "standard" parameters of geth
+		ScryptParams {
+			n: 1 << 18,
+			r: 8,
+			p: 1,
+		}
+	}
+}
+
+/// Minimal strength of key derivation function parameters.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct KdfPolicy {
+	/// Minimal number of PBKDF2 iterations.
+	pub min_pbkdf2_iterations: u32,
+	/// Minimal scrypt CPU/memory cost.
+	pub min_scrypt_n: u32,
+}
+
+impl Default for KdfPolicy {
+	fn default() -> Self {
+		KdfPolicy {
+			min_pbkdf2_iterations: 10240,
+			min_scrypt_n: 1 << 12,
+		}
+	}
+}
+
+impl KdfPolicy {
+	/// Returns `true` if given key derivation function parameters are below the policy.
+	pub fn is_weak(&self, kdf: &Kdf) -> bool {
+		match *kdf {
+			Kdf::Pbkdf2(ref params) => params.c < self.min_pbkdf2_iterations,
+			Kdf::Scrypt(ref params) => params.n < self.min_scrypt_n,
+		}
+	}
+}
+
 impl From<json::Prf> for Prf {
 	fn from(json: json::Prf) -> Self {
 		match json {
@@ -123,3 +173,20 @@ impl Into<json::Kdf> for Kdf {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_detect_weak_kdf() {
+		let policy = KdfPolicy::default();
+		let pbkdf2 = |c| Kdf::Pbkdf2(Pbkdf2 { c, dklen: 32, prf: Prf::HmacSha256, salt: [0u8; 32] });
+		let scrypt = |n| Kdf::Scrypt(Scrypt { n, r: 8, p: 1, dklen: 32, salt: [0u8; 32] });
+
+		assert!(policy.is_weak(&pbkdf2(1024)));
+		assert!(!policy.is_weak(&pbkdf2(10240)));
+		assert!(policy.is_weak(&scrypt(1 << 10)));
+		assert!(!policy.is_weak(&scrypt(1 << 18)));
+	}
+}
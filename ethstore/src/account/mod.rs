@@ -22,6 +22,6 @@ mod version;
 
 pub use self::cipher::{Cipher, Aes128Ctr};
 pub use self::crypto::Crypto;
-pub use self::kdf::{Kdf, Pbkdf2, Scrypt, Prf};
+pub use self::kdf::{Kdf, KdfPolicy, Pbkdf2, Scrypt, ScryptParams, Prf};
 pub use self::safe_account::SafeAccount;
 pub use self::version::Version;
@@ -17,7 +17,8 @@
 use ethkey::{self, KeyPair, sign, Address, Signature, Message, Public, Secret};
 use ethkey::crypto::ecdh::agree;
 use {json, Error};
-use account::Version;
+use account::{Version, ScryptParams};
+use accounts_dir::VaultKey;
 use crypto;
 use super::crypto::Crypto;
 
@@ -108,14 +109,14 @@ impl SafeAccount {
 	}
 
 	/// Create a new `VaultKeyFile` from the given `self`
-	pub fn into_vault_file(self, iterations: u32, password: &str) -> Result<json::VaultKeyFile, Error> {
+	pub fn into_vault_file(self, key: &VaultKey) -> Result<json::VaultKeyFile, Error> {
 		let meta_plain = json::VaultKeyMeta {
 			address: self.address.into(),
 			name: Some(self.name),
 			meta: Some(self.meta),
 		};
 		let meta_plain = meta_plain.write().map_err(|e| Error::Custom(format!("{:?}", e)))?;
-		let meta_crypto = Crypto::with_plain(&meta_plain, password, iterations)?;
+		let meta_crypto = key.encrypt(&meta_plain)?;
 
 		Ok(json::VaultKeyFile {
 			id: self.id.into(),
@@ -164,6 +165,15 @@ impl SafeAccount {
 		Ok(result)
 	}
 
+	/// Encrypt account's secret again using scrypt with given parameters.
+	pub fn reencrypt(&self, password: &str, params: &ScryptParams) -> Result<Self, Error> {
+		let secret = self.crypto.secret(password)?;
+		Ok(SafeAccount {
+			crypto: Crypto::with_secret_scrypt(&secret, password, params)?,
+			..self.clone()
+		})
+	}
+
 	/// Check if password matches the account.
 	pub fn check_password(&self, password: &str) -> bool {
 		self.crypto.secret(password).is_ok()
@@ -173,6 +183,7 @@ impl SafeAccount {
 #[cfg(test)]
 mod tests {
 	use ethkey::{Generator, Random, verify_public, Message};
+	use account::ScryptParams;
 	use super::SafeAccount;
 
 	#[test]
@@ -199,4 +210,17 @@ mod tests {
 		assert!(new_account.sign(first_password, &message).is_err());
 		assert!(new_account.sign(sec_password, &message).is_ok());
 	}
+
+	#[test]
+	fn reencrypt() {
+		let keypair = Random.generate().unwrap();
+		let password = "hello world";
+		let message = Message::default();
+		let account = SafeAccount::create(&keypair, [0u8; 16], password, 1024, "Test".to_owned(), "{}".to_owned()).unwrap();
+		let new_account = account.reencrypt(password, &ScryptParams { n: 1024, r: 8, p: 1 }).unwrap();
+		assert!(account.reencrypt("wrong password", &Default::default()).is_err());
+		assert_eq!(new_account.id, account.id);
+		assert!(new_account.crypto != account.crypto);
+		assert!(new_account.sign(password, &message).is_ok());
+	}
 }
@@ -0,0 +1,239 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Encrypted backups of the keys directory.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use serde_json;
+use account::{Crypto, ScryptParams};
+use {json, Error};
+use super::disk::{is_audit_log, restrict_permissions_to_owner};
+
+/// Current version of the backup format
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Encrypts all files under `root` (key files, vaults, address book, dapps settings) with `password`.
+/// The audit log is not part of the backup.
+pub fn backup_directory(root: &Path, password: &str, params: &ScryptParams) -> Result<json::BackupFile, Error> {
+	let mut files = Vec::new();
+	collect_files(root, root, &mut files)?;
+
+	let plain = serde_json::to_vec(&json::BackupContents { files })
+		.map_err(|e| Error::Custom(format!("Could not serialize backup: {}", e)))?;
+	let crypto = Crypto::with_plain_scrypt(&plain, password, params)?;
+
+	Ok(json::BackupFile {
+		version: BACKUP_VERSION,
+		crypto: crypto.into(),
+	})
+}
+
+/// Decrypts the backup and writes its files under `root`, replacing existing files with the same path.
+/// Audit logs found in the backup are skipped. Returns number of restored files.
+pub fn restore_directory(backup: json::BackupFile, password: &str, root: &Path) -> Result<usize, Error> {
+	if backup.version != BACKUP_VERSION {
+		return Err(Error::Custom(format!("Unsupported backup version: {}", backup.version)));
+	}
+
+	let plain = Crypto::from(backup.crypto).decrypt(password)?;
+	let contents: json::BackupContents = serde_json::from_slice(&plain)
+		.map_err(|e| Error::Custom(format!("Invalid backup contents: {}", e)))?;
+
+	// validate all paths upfront, so that nothing is written if the backup is malformed
+	let paths = contents.files.iter()
+		.map(|entry| entry_path(root, &entry.path))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut restored = 0;
+	for (path, entry) in paths.iter().zip(contents.files.iter()) {
+		let name = path.file_name().expect("entry paths end with a normal component; qed").to_string_lossy().into_owned();
+		if is_audit_log(&name) {
+			continue;
+		}
+
+		let parent = path.parent().expect("entry paths are inside of root; qed");
+		fs::create_dir_all(parent)?;
+
+		// write to a hidden temporary file first and then rename it,
+		// so that an interrupted restore never leaves a truncated file behind
+		let temp_path = parent.join(format!(".{}.tmp", name));
+		if let Err(err) = write_file(&temp_path, &entry.content) {
+			let _ = fs::remove_file(&temp_path);
+			return Err(err);
+		}
+		fs::rename(&temp_path, path)?;
+		restored += 1;
+	}
+
+	Ok(restored)
+}
+
+fn write_file(path: &Path, content: &[u8]) -> Result<(), Error> {
+	let mut file = fs::File::create(path)?;
+	if let Err(_) = restrict_permissions_to_owner(path) {
+		return Err(Error::Io(io::Error::last_os_error()));
+	}
+
+	file.write_all(content)?;
+	file.flush()?;
+	file.sync_all()?;
+	Ok(())
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<json::BackupEntry>) -> Result<(), Error> {
+	let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+	entries.sort_by_key(|entry| entry.path());
+
+	for entry in entries {
+		let path = entry.path();
+		let file_type = entry.file_type()?;
+		if file_type.is_dir() {
+			collect_files(root, &path, files)?;
+		} else if file_type.is_file() && !is_audit_log(&entry.file_name().to_string_lossy()) {
+			let relative = path.strip_prefix(root)
+				.expect("all collected paths are inside of root; qed")
+				.components()
+				.map(|c| c.as_os_str().to_string_lossy().into_owned())
+				.collect::<Vec<_>>()
+				.join("/");
+
+			let mut content = Vec::new();
+			fs::File::open(&path)?.read_to_end(&mut content)?;
+			files.push(json::BackupEntry {
+				path: relative,
+				content: content.into(),
+			});
+		}
+	}
+
+	Ok(())
+}
+
+/// Converts `/` separated path from the backup into a path under `root`.
+/// Rejects absolute paths and parent directory references.
+fn entry_path(root: &Path, relative: &str) -> Result<PathBuf, Error> {
+	let mut path = root.to_path_buf();
+	for part in relative.split('/') {
+		let mut components = Path::new(part).components();
+		match (components.next(), components.next()) {
+			(Some(Component::Normal(_)), None) => path.push(part),
+			_ => return Err(Error::Custom(format!("Invalid path in backup: {}", relative))),
+		}
+	}
+
+	Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+	use std::fs;
+	use std::io::{Read, Write};
+	use std::path::Path;
+	use tempdir::TempDir;
+	use account::{Crypto, ScryptParams};
+	use {json, Error};
+	use super::{backup_directory, restore_directory, BACKUP_VERSION};
+
+	fn params() -> ScryptParams {
+		ScryptParams { n: 1024, r: 8, p: 1 }
+	}
+
+	fn write(path: &Path, content: &str) {
+		fs::create_dir_all(path.parent().unwrap()).unwrap();
+		fs::File::create(path).unwrap().write_all(content.as_bytes()).unwrap();
+	}
+
+	fn read(path: &Path) -> String {
+		let mut content = String::new();
+		fs::File::open(path).unwrap().read_to_string(&mut content).unwrap();
+		content
+	}
+
+	#[test]
+	fn should_backup_and_restore_directory() {
+		// given
+		let from = TempDir::new("backup_from").unwrap();
+		let to = TempDir::new("backup_to").unwrap();
+		write(&from.path().join("UTC--key"), "{\"id\":\"key\"}");
+		write(&from.path().join("address_book.json"), "{}");
+		write(&from.path().join("dapps_accounts.json"), "[]");
+		write(&from.path().join("vault").join("vault.json"), "{\"meta\":\"{}\"}");
+		write(&from.path().join("vault").join("UTC--vault-key"), "{\"id\":\"vault-key\"}");
+		write(&from.path().join("audit.log"), "{}\n");
+		write(&from.path().join("audit.log.1"), "{}\n");
+
+		// when
+		let backup = backup_directory(from.path(), "password", &params()).unwrap();
+		let restored = restore_directory(backup, "password", to.path()).unwrap();
+
+		// then
+		assert_eq!(restored, 5);
+		assert_eq!(read(&to.path().join("UTC--key")), "{\"id\":\"key\"}");
+		assert_eq!(read(&to.path().join("address_book.json")), "{}");
+		assert_eq!(read(&to.path().join("dapps_accounts.json")), "[]");
+		assert_eq!(read(&to.path().join("vault").join("vault.json")), "{\"meta\":\"{}\"}");
+		assert_eq!(read(&to.path().join("vault").join("UTC--vault-key")), "{\"id\":\"vault-key\"}");
+		assert!(!to.path().join("audit.log").exists());
+		assert!(!to.path().join("audit.log.1").exists());
+	}
+
+	#[test]
+	fn should_not_overwrite_audit_log_on_restore() {
+		let to = TempDir::new("backup_to").unwrap();
+		write(&to.path().join("audit.log"), "current");
+		let contents = r#"{"files":[{"path":"address_book.json","content":"7b7d"},{"path":"audit.log","content":"7b7d"}]}"#;
+		let backup = json::BackupFile {
+			version: BACKUP_VERSION,
+			crypto: Crypto::with_plain_scrypt(contents.as_bytes(), "password", &params()).unwrap().into(),
+		};
+
+		assert_eq!(restore_directory(backup, "password", to.path()).unwrap(), 1);
+		assert_eq!(read(&to.path().join("address_book.json")), "{}");
+		assert_eq!(read(&to.path().join("audit.log")), "current");
+		// no temporary files are left behind
+		assert_eq!(fs::read_dir(to.path()).unwrap().count(), 2);
+	}
+
+	#[test]
+	fn should_not_restore_with_invalid_password() {
+		let from = TempDir::new("backup_from").unwrap();
+		let to = TempDir::new("backup_to").unwrap();
+		write(&from.path().join("address_book.json"), "{}");
+
+		let backup = backup_directory(from.path(), "password", &params()).unwrap();
+
+		match restore_directory(backup, "wrong", to.path()) {
+			Err(Error::InvalidPassword) => {},
+			other => panic!("Unexpected result: {:?}", other),
+		}
+		assert!(!to.path().join("address_book.json").exists());
+	}
+
+	#[test]
+	fn should_reject_paths_outside_of_directory() {
+		let to = TempDir::new("backup_to").unwrap();
+		let contents = r#"{"files":[{"path":"address_book.json","content":"7b7d"},{"path":"../evil","content":"7b7d"}]}"#;
+		let backup = json::BackupFile {
+			version: BACKUP_VERSION,
+			crypto: Crypto::with_plain_scrypt(contents.as_bytes(), "password", &params()).unwrap().into(),
+		};
+
+		assert!(restore_directory(backup, "password", to.path()).is_err());
+		assert!(!to.path().join("address_book.json").exists());
+	}
+}
@@ -31,10 +31,21 @@ const IGNORED_FILES: &'static [&'static str] = &[
 	"dapps_accounts.json",
 	"dapps_history.json",
 	"vault.json",
+	"policy_accounts.json",
+	"spending_limits.json",
+	"daily_spending.json",
 ];
 
+/// Name of the audit log of account operations. Its rotated copies get a numeric suffix.
+pub const AUDIT_LOG_FILE: &'static str = "audit.log";
+
+/// Returns true if given file name is the audit log or one of its rotated copies.
+pub fn is_audit_log(name: &str) -> bool {
+	name == AUDIT_LOG_FILE || (name.starts_with(AUDIT_LOG_FILE) && name[AUDIT_LOG_FILE.len()..].starts_with('.'))
+}
+
 #[cfg(not(windows))]
-fn restrict_permissions_to_owner(file_path: &Path) -> Result<(), i32>  {
+pub fn restrict_permissions_to_owner(file_path: &Path) -> Result<(), i32>  {
 	use std::ffi;
 	use libc;
 
@@ -47,7 +58,7 @@ fn restrict_permissions_to_owner(file_path: &Path) -> Result<(), i32>  {
 }
 
 #[cfg(windows)]
-fn restrict_permissions_to_owner(_file_path: &Path) -> Result<(), i32> {
+pub fn restrict_permissions_to_owner(_file_path: &Path) -> Result<(), i32> {
 	Ok(())
 }
 
@@ -103,7 +114,8 @@ impl<T> DiskDirectory<T> where T: KeyFileManager {
 					// hidden files
 					!name.starts_with(".") &&
 					// other ignored files
-					!IGNORED_FILES.contains(&&*name)
+					!IGNORED_FILES.contains(&&*name) &&
+					!is_audit_log(&name)
 			})
 			.map(|entry| entry.path())
 			.collect::<Vec<PathBuf>>()
@@ -166,28 +178,39 @@ impl<T> DiskDirectory<T> where T: KeyFileManager {
 			keyfile_path.set_file_name(&filename);
 		}
 
+		// write to a hidden temporary file first and then rename it,
+		// so that an interrupted write never leaves a truncated key file behind
+		let temp_path = self.path.join(format!(".{}.tmp", filename));
+
 		// update account filename
 		let original_account = account.clone();
 		let mut account = account;
 		account.filename = Some(filename);
 
-		{
-			// save the file
-			let mut file = fs::File::create(&keyfile_path)?;
+		if let Err(err) = self.write_key_file(original_account, &temp_path) {
+			let _ = fs::remove_file(&temp_path);
+			return Err(err);
+		}
+		fs::rename(&temp_path, &keyfile_path)?;
+
+		Ok(account)
+	}
 
-			// write key content
-			self.key_manager.write(original_account, &mut file).map_err(|e| Error::Custom(format!("{:?}", e)))?;
+	fn write_key_file(&self, account: SafeAccount, path: &Path) -> Result<(), Error> {
+		// save the file
+		let mut file = fs::File::create(path)?;
 
-			file.flush()?;
+		// write key content
+		self.key_manager.write(account, &mut file).map_err(|e| Error::Custom(format!("{:?}", e)))?;
 
-			if let Err(_) = restrict_permissions_to_owner(keyfile_path.as_path()) {
-				return Err(Error::Io(io::Error::last_os_error()));
-			}
+		file.flush()?;
 
-			file.sync_all()?;
+		if let Err(_) = restrict_permissions_to_owner(path) {
+			return Err(Error::Io(io::Error::last_os_error()));
 		}
 
-		Ok(account)
+		file.sync_all()?;
+		Ok(())
 	}
 
 	/// Get key file manager referece
@@ -302,7 +325,7 @@ mod test {
 	extern crate tempdir;
 
 	use std::{env, fs};
-	use super::{KeyDirectory, RootDiskDirectory, VaultKey};
+	use super::{KeyDirectory, RootDiskDirectory, VaultKey, is_audit_log};
 	use account::SafeAccount;
 	use ethkey::{Random, Generator};
 	use self::tempdir::TempDir;
@@ -409,6 +432,46 @@ mod test {
 		assert!(vaults.iter().any(|v| &*v == "vault2"));
 	}
 
+	#[test]
+	fn should_replace_key_file_on_update() {
+		// given
+		let temp_path = TempDir::new("").unwrap();
+		let directory = RootDiskDirectory::create(&temp_path).unwrap();
+		let keypair = Random.generate().unwrap();
+		let account = SafeAccount::create(&keypair, [0u8; 16], "password", 1024, "Test".to_owned(), "{}".to_owned()).unwrap();
+		let account = directory.insert(account).unwrap();
+		// leftover of an interrupted write
+		fs::write(temp_path.path().join(".leftover.tmp"), b"{").unwrap();
+
+		// when
+		let updated = account.change_password("password", "new password", 1024).unwrap();
+		directory.update(updated.clone()).unwrap();
+
+		// then
+		let files: Vec<_> = fs::read_dir(&temp_path).unwrap().map(|e| e.unwrap().file_name()).collect();
+		assert_eq!(files.len(), 2);
+		assert_eq!(directory.load().unwrap(), vec![updated]);
+	}
+
+	#[test]
+	fn should_ignore_audit_logs() {
+		// given
+		let temp_path = TempDir::new("").unwrap();
+		let directory = RootDiskDirectory::create(&temp_path).unwrap();
+		let keypair = Random.generate().unwrap();
+		let account = SafeAccount::create(&keypair, [0u8; 16], "password", 1024, "Test".to_owned(), "{}".to_owned()).unwrap();
+		let account = directory.insert(account).unwrap();
+
+		// when
+		fs::write(temp_path.path().join("audit.log"), b"{}\n").unwrap();
+		fs::write(temp_path.path().join("audit.log.1"), b"{}\n").unwrap();
+
+		// then
+		assert_eq!(directory.load().unwrap(), vec![account]);
+		assert!(is_audit_log("audit.log.12"));
+		assert!(!is_audit_log("audit.logs"));
+	}
+
 	#[test]
 	fn hash_of_files() {
 		let temp_path = TempDir::new("").unwrap();
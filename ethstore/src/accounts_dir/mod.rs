@@ -17,8 +17,10 @@
 //! Accounts Directory
 
 use std::path::{PathBuf};
-use {SafeAccount, Error};
+use account::{Crypto, ScryptParams};
+use {crypto, SafeAccount, Error};
 
+mod backup;
 mod disk;
 mod memory;
 mod vault;
@@ -41,6 +43,8 @@ pub struct VaultKey {
 	pub password: String,
 	/// Number of iterations to produce a derived key from password
 	pub iterations: u32,
+	/// Use scrypt with given parameters instead of PBKDF2 iterations
+	pub scrypt: Option<ScryptParams>,
 }
 
 /// Keys directory
@@ -89,7 +93,8 @@ pub trait VaultKeyDirectory: KeyDirectory {
 	fn set_meta(&self, meta: &str) -> Result<(), Error>;
 }
 
-pub use self::disk::{RootDiskDirectory, DiskKeyFileManager, KeyFileManager};
+pub use self::backup::{backup_directory, restore_directory, BACKUP_VERSION};
+pub use self::disk::{RootDiskDirectory, DiskKeyFileManager, KeyFileManager, AUDIT_LOG_FILE};
 pub use self::memory::MemoryDirectory;
 pub use self::vault::VaultDiskDirectory;
 
@@ -99,6 +104,24 @@ impl VaultKey {
 		VaultKey {
 			password: password.to_owned(),
 			iterations: iterations,
+			scrypt: None,
+		}
+	}
+
+	/// Create new vault key, deriving the encryption key from password with scrypt
+	pub fn with_scrypt(password: &str, params: ScryptParams) -> Self {
+		VaultKey {
+			password: password.to_owned(),
+			iterations: 0,
+			scrypt: Some(params),
+		}
+	}
+
+	/// Encrypt data with the key
+	pub fn encrypt(&self, plain: &[u8]) -> Result<Crypto, crypto::Error> {
+		match self.scrypt {
+			Some(ref params) => Crypto::with_plain_scrypt(plain, &self.password, params),
+			None => Crypto::with_plain(plain, &self.password, self.iterations),
 		}
 	}
 }
@@ -19,7 +19,7 @@ use std::path::{PathBuf, Path};
 use parking_lot::Mutex;
 use {json, SafeAccount, Error};
 use crypto::Keccak256;
-use super::super::account::Crypto;
+use super::super::account::{Crypto, Kdf, ScryptParams};
 use super::{KeyDirectory, VaultKeyDirectory, VaultKey, SetKeyError};
 use super::disk::{DiskDirectory, KeyFileManager};
 
@@ -69,6 +69,12 @@ impl VaultDiskDirectory {
 		// check that passed key matches vault file
 		let meta = read_vault_file(&vault_dir_path, Some(&key))?;
 
+		// keep using scrypt if the vault has been encrypted with it
+		let mut key = key;
+		if let Kdf::Scrypt(params) = Crypto::from(load_vault_file(&vault_dir_path)?.crypto).kdf {
+			key.scrypt = Some(ScryptParams { n: params.n, r: params.r, p: params.p });
+		}
+
 		Ok(DiskDirectory::new(vault_dir_path, VaultKeyFileManager::new(name, key, &meta)))
 	}
 
@@ -204,7 +210,7 @@ impl KeyFileManager for VaultKeyFileManager {
 		account.meta = json::remove_vault_name_from_json_meta(&account.meta)
 			.map_err(|err| Error::Custom(format!("{:?}", err)))?;
 
-		let vault_file: json::VaultKeyFile = account.into_vault_file(self.key.iterations, &self.key.password)?;
+		let vault_file: json::VaultKeyFile = account.into_vault_file(&self.key)?;
 		vault_file.write(writer).map_err(|e| Error::Custom(format!("{:?}", e)))
 	}
 }
@@ -235,7 +241,7 @@ fn check_vault_name(name: &str) -> bool {
 /// Vault can be empty, but still must be pluggable => we store vault password in separate file
 fn create_vault_file<P>(vault_dir_path: P, key: &VaultKey, meta: &str) -> Result<(), Error> where P: AsRef<Path> {
 	let password_hash = key.password.keccak256();
-	let crypto = Crypto::with_plain(&password_hash, &key.password, key.iterations)?;
+	let crypto = key.encrypt(&password_hash)?;
 
 	let mut vault_file_path: PathBuf = vault_dir_path.as_ref().into();
 	vault_file_path.push(VAULT_FILE_NAME);
@@ -250,6 +256,7 @@ fn create_vault_file<P>(vault_dir_path: P, key: &VaultKey, meta: &str) -> Result
 		meta: Some(meta.to_owned()),
 	};
 	vault_file_contents.write(&mut vault_file).map_err(|e| Error::Custom(format!("{:?}", e)))?;
+	vault_file.sync_all()?;
 	drop(vault_file);
 	fs::rename(&temp_vault_file_path, &vault_file_path)?;
 
@@ -258,13 +265,9 @@ fn create_vault_file<P>(vault_dir_path: P, key: &VaultKey, meta: &str) -> Result
 
 /// When vault is opened => we must check that password matches && read metadata
 fn read_vault_file<P>(vault_dir_path: P, key: Option<&VaultKey>) -> Result<String, Error> where P: AsRef<Path> {
-	let mut vault_file_path: PathBuf = vault_dir_path.as_ref().into();
-	vault_file_path.push(VAULT_FILE_NAME);
-
-	let vault_file = fs::File::open(vault_file_path)?;
-	let vault_file_contents = json::VaultFile::load(vault_file).map_err(|e| Error::Custom(format!("{:?}", e)))?;
-	let vault_file_meta = vault_file_contents.meta.unwrap_or("{}".to_owned());
-	let vault_file_crypto: Crypto = vault_file_contents.crypto.into();
+	let vault_file = load_vault_file(vault_dir_path)?;
+	let vault_file_meta = vault_file.meta.unwrap_or("{}".to_owned());
+	let vault_file_crypto: Crypto = vault_file.crypto.into();
 
 	if let Some(key) = key {
 		let password_bytes = vault_file_crypto.decrypt(&key.password)?;
@@ -277,6 +280,14 @@ fn read_vault_file<P>(vault_dir_path: P, key: Option<&VaultKey>) -> Result<Strin
 	Ok(vault_file_meta)
 }
 
+fn load_vault_file<P>(vault_dir_path: P) -> Result<json::VaultFile, Error> where P: AsRef<Path> {
+	let mut vault_file_path: PathBuf = vault_dir_path.as_ref().into();
+	vault_file_path.push(VAULT_FILE_NAME);
+
+	let vault_file = fs::File::open(vault_file_path)?;
+	json::VaultFile::load(vault_file).map_err(|e| Error::Custom(format!("{:?}", e)))
+}
+
 #[cfg(test)]
 mod test {
 	extern crate tempdir;
@@ -24,7 +24,7 @@ use crypto::KEY_ITERATIONS;
 use random::Random;
 use ethkey::{self, Signature, Address, Message, Secret, Public, KeyPair, ExtendedKeyPair};
 use accounts_dir::{KeyDirectory, VaultKeyDirectory, VaultKey, SetKeyError};
-use account::SafeAccount;
+use account::{SafeAccount, KdfPolicy, ScryptParams};
 use presale::PresaleWallet;
 use json::{self, Uuid, OpaqueKeyFile};
 use {import, Error, SimpleSecretStore, SecretStore, SecretVaultRef, StoreAccountRef, Derivation, OpaqueSecret};
@@ -57,6 +57,11 @@ impl EthStore {
 		self.store.set_refresh_time(time)
 	}
 
+	/// Modify minimal strength of key derivation parameters, below which keys are reported as weak.
+	pub fn set_kdf_policy(&mut self, policy: KdfPolicy) {
+		self.store.set_kdf_policy(policy)
+	}
+
 	fn get(&self, account: &StoreAccountRef) -> Result<SafeAccount, Error> {
 		let mut accounts = self.store.get_accounts(account)?.into_iter();
 		accounts.next().ok_or(Error::InvalidAccount)
@@ -90,6 +95,10 @@ impl SimpleSecretStore for EthStore {
 		self.store.change_password(account, old_password, new_password)
 	}
 
+	fn reencrypt(&self, account: &StoreAccountRef, password: &str, params: &ScryptParams) -> Result<(), Error> {
+		self.store.reencrypt(account, password, params)
+	}
+
 	fn export_account(&self, account: &StoreAccountRef, password: &str) -> Result<OpaqueKeyFile, Error> {
 		self.store.export_account(account, password)
 	}
@@ -141,6 +150,10 @@ impl SimpleSecretStore for EthStore {
 		self.store.change_vault_password(name, new_password)
 	}
 
+	fn reencrypt_vault(&self, name: &str, params: &ScryptParams) -> Result<(), Error> {
+		self.store.reencrypt_vault(name, params)
+	}
+
 	fn change_account_vault(&self, vault: SecretVaultRef, account: StoreAccountRef) -> Result<StoreAccountRef, Error> {
 		self.store.change_account_vault(vault, account)
 	}
@@ -184,6 +197,10 @@ impl SecretStore for EthStore {
 		Ok(account.check_password(password))
 	}
 
+	fn has_weak_kdf(&self, account: &StoreAccountRef) -> Result<bool, Error> {
+		self.store.has_weak_kdf(account)
+	}
+
 	fn copy_account(&self, new_store: &SimpleSecretStore, new_vault: SecretVaultRef, account: &StoreAccountRef, password: &str, new_password: &str) -> Result<(), Error> {
 		let account = self.get(account)?;
 		let secret = account.crypto.secret(password)?;
@@ -258,6 +275,7 @@ impl SecretStore for EthStore {
 pub struct EthMultiStore {
 	dir: Box<KeyDirectory>,
 	iterations: u32,
+	kdf_policy: KdfPolicy,
 	// order lock: cache, then vaults
 	cache: RwLock<BTreeMap<StoreAccountRef, Vec<SafeAccount>>>,
 	vaults: Mutex<HashMap<String, Box<VaultKeyDirectory>>>,
@@ -282,6 +300,7 @@ impl EthMultiStore {
 			dir: directory,
 			vaults: Mutex::new(HashMap::new()),
 			iterations: iterations,
+			kdf_policy: KdfPolicy::default(),
 			cache: Default::default(),
 			timestamp: Mutex::new(Timestamp {
 				dir_hash: None,
@@ -304,6 +323,18 @@ impl EthMultiStore {
 		self.timestamp.lock().refresh_time = time;
 	}
 
+	/// Modify minimal strength of key derivation parameters, below which keys are reported as weak.
+	pub fn set_kdf_policy(&mut self, policy: KdfPolicy) {
+		self.kdf_policy = policy;
+	}
+
+	/// Returns `true` if any of the accounts stored under given reference is encrypted
+	/// with key derivation parameters weaker than the policy.
+	pub fn has_weak_kdf(&self, account: &StoreAccountRef) -> Result<bool, Error> {
+		let accounts = self.get_accounts(account)?;
+		Ok(accounts.iter().any(|account| self.kdf_policy.is_weak(&account.crypto.kdf)))
+	}
+
 	fn reload_if_changed(&self) -> Result<(), Error> {
 		let mut last_timestamp = self.timestamp.lock();
 		let now = Instant::now();
@@ -436,6 +467,29 @@ impl EthMultiStore {
 		return Ok(());
 	}
 
+	fn set_vault_key(&self, name: &str, new_key: VaultKey) -> Result<(), Error> {
+		let old_key = self.vaults.lock().get(name).map(|v| v.key()).ok_or(Error::VaultNotFound)?;
+		let vault_provider = self.dir.as_vault_provider().ok_or(Error::VaultsAreNotSupported)?;
+		let vault = vault_provider.open(name, old_key)?;
+		let new_password = new_key.password.clone();
+		match vault.set_key(new_key) {
+			Ok(_) => {
+				self.close_vault(name)
+					.and_then(|_| self.open_vault(name, &new_password))
+			},
+			Err(SetKeyError::Fatal(err)) => {
+				let _ = self.close_vault(name);
+				Err(err)
+			},
+			Err(SetKeyError::NonFatalNew(err)) => {
+				let _ = self.close_vault(name)
+					.and_then(|_| self.open_vault(name, &new_password));
+				Err(err)
+			},
+			Err(SetKeyError::NonFatalOld(err)) => Err(err),
+		}
+	}
+
 	fn generate(&self, secret: Secret, derivation: Derivation) -> Result<ExtendedKeyPair, Error> {
 		let mut extended = ExtendedKeyPair::new(secret);
 		match derivation {
@@ -544,6 +598,21 @@ impl SimpleSecretStore for EthMultiStore {
 		Ok(())
 	}
 
+	fn reencrypt(&self, account_ref: &StoreAccountRef, password: &str, params: &ScryptParams) -> Result<(), Error> {
+		let accounts = self.get_matching(account_ref, password)?;
+
+		if accounts.is_empty() {
+			return Err(Error::InvalidPassword);
+		}
+
+		for account in accounts {
+			let new_account = account.reencrypt(password, params)?;
+			self.update(account_ref, account, new_account)?;
+		}
+
+		Ok(())
+	}
+
 	fn export_account(&self, account_ref: &StoreAccountRef, password: &str) -> Result<OpaqueKeyFile, Error> {
 		self.get_matching(account_ref, password)?.into_iter().nth(0).map(Into::into).ok_or(Error::InvalidPassword)
 	}
@@ -631,24 +700,17 @@ impl SimpleSecretStore for EthMultiStore {
 
 	fn change_vault_password(&self, name: &str, new_password: &str) -> Result<(), Error> {
 		let old_key = self.vaults.lock().get(name).map(|v| v.key()).ok_or(Error::VaultNotFound)?;
-		let vault_provider = self.dir.as_vault_provider().ok_or(Error::VaultsAreNotSupported)?;
-		let vault = vault_provider.open(name, old_key)?;
-		match vault.set_key(VaultKey::new(new_password, self.iterations)) {
-			Ok(_) => {
-				self.close_vault(name)
-					.and_then(|_| self.open_vault(name, new_password))
-			},
-			Err(SetKeyError::Fatal(err)) => {
-				let _ = self.close_vault(name);
-				Err(err)
-			},
-			Err(SetKeyError::NonFatalNew(err)) => {
-				let _ = self.close_vault(name)
-					.and_then(|_| self.open_vault(name, new_password));
-				Err(err)
-			},
-			Err(SetKeyError::NonFatalOld(err)) => Err(err),
-		}
+		// don't downgrade vaults already encrypted with scrypt
+		let new_key = match old_key.scrypt {
+			Some(params) => VaultKey::with_scrypt(new_password, params),
+			None => VaultKey::new(new_password, self.iterations),
+		};
+		self.set_vault_key(name, new_key)
+	}
+
+	fn reencrypt_vault(&self, name: &str, params: &ScryptParams) -> Result<(), Error> {
+		let old_key = self.vaults.lock().get(name).map(|v| v.key()).ok_or(Error::VaultNotFound)?;
+		self.set_vault_key(name, VaultKey::with_scrypt(&old_key.password, *params))
 	}
 
 	fn change_account_vault(&self, vault: SecretVaultRef, account_ref: StoreAccountRef) -> Result<StoreAccountRef, Error> {
@@ -692,6 +754,7 @@ mod tests {
 	use accounts_dir::{KeyDirectory, MemoryDirectory, RootDiskDirectory};
 	use ethkey::{Random, Generator, KeyPair};
 	use secret_store::{SimpleSecretStore, SecretStore, SecretVaultRef, StoreAccountRef, Derivation};
+	use account::ScryptParams;
 	use super::{EthStore, EthMultiStore};
 	use self::tempdir::TempDir;
 	use ethereum_types::H256;
@@ -1117,4 +1180,53 @@ mod tests {
 		// then
 		assert!(exported.is_ok(), "Should export single account: {:?}", exported);
 	}
+
+	#[test]
+	fn should_reencrypt_weak_account() {
+		// given
+		let store = EthStore::open_with_iterations(Box::new(MemoryDirectory::default()), 1024).unwrap();
+		let keypair = keypair();
+		let address = store.insert_account(SecretVaultRef::Root, keypair.secret().clone(), "test").unwrap();
+		assert!(store.has_weak_kdf(&address).unwrap());
+
+		// when
+		let params = ScryptParams { n: 1 << 12, r: 8, p: 1 };
+		assert!(store.reencrypt(&address, "wrong", &params).is_err());
+		store.reencrypt(&address, "test", &params).unwrap();
+
+		// then
+		assert!(!store.has_weak_kdf(&address).unwrap());
+		assert!(store.test_password(&address, "test").unwrap());
+		assert_eq!(store.accounts().unwrap().len(), 1);
+	}
+
+	#[test]
+	fn should_reencrypt_vault() {
+		// given
+		let mut dir = RootDiskDirectoryGuard::new();
+		let store = EthStore::open(dir.key_dir.take().unwrap()).unwrap();
+		let name = "vault"; let password = "password";
+		let keypair = keypair();
+		store.create_vault(name, password).unwrap();
+		store.set_vault_meta(name, "Meta").unwrap();
+		store.insert_account(SecretVaultRef::Vault(name.to_owned()), keypair.secret().clone(), password).unwrap();
+
+		// when
+		store.reencrypt_vault(name, &ScryptParams { n: 1 << 12, r: 8, p: 1 }).unwrap();
+		store.close_vault(name).unwrap();
+		store.open_vault(name, password).unwrap();
+
+		// then
+		assert_eq!(store.accounts().unwrap().len(), 1);
+		assert_eq!(store.get_vault_meta(name).unwrap(), "Meta".to_owned());
+
+		// and when
+		store.change_vault_password(name, "new_password").unwrap();
+		store.close_vault(name).unwrap();
+
+		// then
+		assert!(store.open_vault(name, password).is_err());
+		store.open_vault(name, "new_password").unwrap();
+		assert_eq!(store.accounts().unwrap().len(), 1);
+	}
 }
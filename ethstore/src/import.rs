@@ -18,10 +18,10 @@ use std::collections::HashSet;
 use std::path::Path;
 use std::fs;
 
-use ethkey::Address;
+use ethkey::{Address, KeyPair, Mnemonic, DerivationPath, ExtendedKeyPair};
 use accounts_dir::{KeyDirectory, RootDiskDirectory, DiskKeyFileManager, KeyFileManager};
 use dir;
-use Error;
+use {Error, SimpleSecretStore, SecretVaultRef};
 
 /// Import an account from a file.
 pub fn import_account(path: &Path, dst: &KeyDirectory) -> Result<Address, Error> {
@@ -76,3 +76,57 @@ pub fn import_geth_accounts(dst: &KeyDirectory, desired: HashSet<Address>, testn
 			Ok(address)
 		}).collect()
 }
+
+/// Derive `count` key pairs from a BIP-39 mnemonic using the BIP-44 Ethereum path (`m/44'/60'/0'/0/i`).
+pub fn mnemonic_key_pairs(mnemonic: &Mnemonic, passphrase: &str, count: u32) -> Result<Vec<KeyPair>, Error> {
+	let master = ExtendedKeyPair::with_seed(&mnemonic.seed(passphrase))?;
+
+	(0..count).map(|index| {
+		let derived = master.derive_path(&DerivationPath::ethereum(index))?;
+		Ok(KeyPair::from_secret(derived.secret().as_raw().clone())?)
+	}).collect()
+}
+
+/// Insert key pairs into the store with given password. Accounts already in the store are left untouched.
+/// If any insertion fails, accounts inserted so far are removed again.
+pub fn import_key_pairs<S: SimpleSecretStore + ?Sized>(
+	store: &S,
+	vault: SecretVaultRef,
+	key_pairs: &[KeyPair],
+	password: &str,
+) -> Result<Vec<Address>, Error> {
+	let missing: Vec<_> = key_pairs.iter()
+		.filter(|key_pair| store.account_ref(&key_pair.address()).is_err())
+		.collect();
+
+	let mut inserted = Vec::with_capacity(missing.len());
+	for key_pair in missing {
+		match store.insert_account(vault.clone(), key_pair.secret().clone(), password) {
+			Ok(account) => inserted.push(account),
+			Err(err) => {
+				for account in &inserted {
+					if let Err(e) = store.remove_account(account, password) {
+						warn!("Unable to remove partially imported account {}: {}", account.address, e);
+					}
+				}
+				return Err(err);
+			},
+		}
+	}
+
+	Ok(key_pairs.iter().map(KeyPair::address).collect())
+}
+
+/// Derive `count` accounts from a BIP-39 mnemonic using the BIP-44 Ethereum path (`m/44'/60'/0'/0/i`)
+/// and insert them into the store with given password. Accounts already in the store are left untouched.
+pub fn import_mnemonic<S: SimpleSecretStore + ?Sized>(
+	store: &S,
+	vault: SecretVaultRef,
+	mnemonic: &Mnemonic,
+	passphrase: &str,
+	count: u32,
+	password: &str,
+) -> Result<Vec<Address>, Error> {
+	let key_pairs = mnemonic_key_pairs(mnemonic, passphrase, count)?;
+	import_key_pairs(store, vault, &key_pairs, password)
+}
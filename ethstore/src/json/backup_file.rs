@@ -0,0 +1,102 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::{Read, Write};
+use serde_json;
+use super::{Bytes, Crypto};
+
+/// Encrypted backup of the keys directory
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BackupFile {
+	/// Backup format version
+	pub version: u32,
+	/// Serialized `BackupContents`, encrypted with backup password
+	pub crypto: Crypto,
+}
+
+impl BackupFile {
+	pub fn load<R>(reader: R) -> Result<Self, serde_json::Error> where R: Read {
+		serde_json::from_reader(reader)
+	}
+
+	pub fn write<W>(&self, writer: &mut W) -> Result<(), serde_json::Error> where W: Write {
+		serde_json::to_writer(writer, self)
+	}
+}
+
+/// Decrypted contents of the backup
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BackupContents {
+	/// All files of the keys directory
+	pub files: Vec<BackupEntry>,
+}
+
+/// Single file stored in the backup
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BackupEntry {
+	/// Path relative to the keys directory, components separated with `/`
+	pub path: String,
+	/// File contents
+	pub content: Bytes,
+}
+
+#[cfg(test)]
+mod test {
+	use serde_json;
+	use json::{BackupFile, BackupContents, BackupEntry, Crypto, Cipher, Aes128Ctr, Kdf, Scrypt};
+
+	#[test]
+	fn to_and_from_json() {
+		let file = BackupFile {
+			version: 1,
+			crypto: Crypto {
+				cipher: Cipher::Aes128Ctr(Aes128Ctr {
+					iv: "0155e3690be19fbfbecabcd440aa284b".into(),
+				}),
+				ciphertext: "4d6938a1f49b7782".into(),
+				kdf: Kdf::Scrypt(Scrypt {
+					dklen: 32,
+					p: 1,
+					n: 1024,
+					r: 8,
+					salt: "b6a9338a7ccd39288a86dba73bfecd9101b4f3db9c9830e7c76afdbd4f6872e5".into(),
+				}),
+				mac: "16381463ea11c6eb2239a9f339c2e780516d29d234ce30ac5f166f9080b5a262".into(),
+			},
+		};
+
+		let serialized = serde_json::to_string(&file).unwrap();
+		let deserialized = serde_json::from_str(&serialized).unwrap();
+
+		assert_eq!(file, deserialized);
+	}
+
+	#[test]
+	fn contents_to_and_from_json() {
+		let contents = BackupContents {
+			files: vec![BackupEntry {
+				path: "vault/vault.json".into(),
+				content: "7b7d".into(),
+			}],
+		};
+
+		let serialized = serde_json::to_string(&contents).unwrap();
+		assert_eq!(serialized, r#"{"files":[{"path":"vault/vault.json","content":"7b7d"}]}"#);
+		let deserialized = serde_json::from_str(&serialized).unwrap();
+
+		assert_eq!(contents, deserialized);
+	}
+}
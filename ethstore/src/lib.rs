@@ -58,11 +58,12 @@ mod presale;
 mod random;
 mod secret_store;
 
-pub use self::account::{SafeAccount, Crypto};
+pub use self::account::{SafeAccount, Crypto, KdfPolicy, ScryptParams};
 pub use self::error::Error;
 pub use self::ethstore::{EthStore, EthMultiStore};
-pub use self::import::{import_account, import_accounts, read_geth_accounts};
+pub use self::import::{import_account, import_accounts, import_key_pairs, import_mnemonic, mnemonic_key_pairs, read_geth_accounts};
 pub use self::json::OpaqueKeyFile as KeyFile;
+pub use self::json::BackupFile;
 pub use self::presale::PresaleWallet;
 pub use self::secret_store::{
 	SecretVaultRef, StoreAccountRef, SimpleSecretStore, SecretStore,
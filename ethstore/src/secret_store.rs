@@ -22,6 +22,7 @@ use Error;
 use json::{Uuid, OpaqueKeyFile};
 use ethereum_types::H256;
 use OpaqueSecret;
+use account::ScryptParams;
 
 /// Key directory reference
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -61,6 +62,8 @@ pub trait SimpleSecretStore: Send + Sync {
 	fn insert_derived(&self, vault: SecretVaultRef, account_ref: &StoreAccountRef, password: &str, derivation: Derivation) -> Result<StoreAccountRef, Error>;
 	/// Changes accounts password.
 	fn change_password(&self, account: &StoreAccountRef, old_password: &str, new_password: &str) -> Result<(), Error>;
+	/// Encrypts account with given password again, deriving the key with scrypt.
+	fn reencrypt(&self, account: &StoreAccountRef, password: &str, params: &ScryptParams) -> Result<(), Error>;
 	/// Exports key details for account.
 	fn export_account(&self, account: &StoreAccountRef, password: &str) -> Result<OpaqueKeyFile, Error>;
 	/// Entirely removes account from the store and underlying storage.
@@ -94,6 +97,9 @@ pub trait SimpleSecretStore: Send + Sync {
 	fn list_opened_vaults(&self) -> Result<Vec<String>, Error>;
 	/// Change vault password
 	fn change_vault_password(&self, name: &str, new_password: &str) -> Result<(), Error>;
+	/// Encrypts vault file and metadata of vault accounts again, deriving the key with scrypt.
+	/// Secrets of vault accounts are protected by their own passwords and have to be re-encrypted separately.
+	fn reencrypt_vault(&self, name: &str, params: &ScryptParams) -> Result<(), Error>;
 	/// Cnage account' vault
 	fn change_account_vault(&self, vault: SecretVaultRef, account: StoreAccountRef) -> Result<StoreAccountRef, Error>;
 	/// Get vault metadata string.
@@ -122,6 +128,9 @@ pub trait SecretStore: SimpleSecretStore {
 	/// Checks if password matches given account.
 	fn test_password(&self, account: &StoreAccountRef, password: &str) -> Result<bool, Error>;
 
+	/// Returns `true` if the account is encrypted with key derivation parameters weaker than the store policy.
+	fn has_weak_kdf(&self, account: &StoreAccountRef) -> Result<bool, Error>;
+
 	/// Returns a public key for given account.
 	fn public(&self, account: &StoreAccountRef, password: &str) -> Result<Public, Error>;
 
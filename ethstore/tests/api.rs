@@ -19,8 +19,8 @@ extern crate ethstore;
 
 mod util;
 
-use ethstore::{EthStore, SimpleSecretStore, SecretVaultRef, StoreAccountRef};
-use ethstore::ethkey::{Random, Generator, Secret, KeyPair, verify_address};
+use ethstore::{EthStore, SimpleSecretStore, SecretVaultRef, StoreAccountRef, import_mnemonic};
+use ethstore::ethkey::{Random, Generator, Secret, KeyPair, Mnemonic, verify_address};
 use ethstore::accounts_dir::RootDiskDirectory;
 use util::TransientDir;
 
@@ -52,6 +52,23 @@ fn secret_store_create_account() {
 	assert_eq!(store.accounts().unwrap().len(), 2);
 }
 
+#[test]
+fn secret_store_import_mnemonic() {
+	let dir = TransientDir::create().unwrap();
+	let store = EthStore::open(Box::new(dir)).unwrap();
+	let mnemonic = Mnemonic::from_phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+	let first = KeyPair::from_secret("1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727".parse().unwrap()).unwrap();
+
+	let addresses = import_mnemonic(&store, SecretVaultRef::Root, &mnemonic, "", 2, "").unwrap();
+	assert_eq!(addresses.len(), 2);
+	assert_eq!(addresses[0], first.address());
+	assert_eq!(store.accounts().unwrap().len(), 2);
+
+	// importing again doesn't duplicate accounts
+	assert_eq!(import_mnemonic(&store, SecretVaultRef::Root, &mnemonic, "", 3, "").unwrap()[..2], addresses[..]);
+	assert_eq!(store.accounts().unwrap().len(), 3);
+}
+
 #[test]
 fn secret_store_sign() {
 	let dir = TransientDir::create().unwrap();
@@ -21,6 +21,7 @@ use std::time::Duration;
 pub mod json;
 pub mod std_json;
 pub mod simple;
+pub mod profile;
 
 /// Formats duration into human readable format.
 pub fn format_time(time: &Duration) -> String {
@@ -0,0 +1,164 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Coverage and gas profile VM output.
+
+use ethereum_types::U256;
+use bytes::ToPretty;
+use ethcore::trace::{self, VMTracer, VMProfile, ProfilingVMTracer};
+
+use display;
+use info as vm;
+
+/// Coverage and gas profile informant.
+///
+/// Prints a JSON line for every executed instruction, keyed by code hash and program counter.
+pub struct Informant {
+	tracer: ProfilingVMTracer,
+}
+
+impl Default for Informant {
+	fn default() -> Self {
+		Informant {
+			tracer: ProfilingVMTracer::toplevel(),
+		}
+	}
+}
+
+impl Informant {
+	fn lines(profile: VMProfile) -> Vec<String> {
+		profile.codes.into_iter().flat_map(|(code_hash, instructions)| {
+			instructions.into_iter().map(move |(pc, instruction)| format!(
+				"{{\"codeHash\":\"0x{hash:?}\",\"pc\":{pc},\"count\":{count},\"gasUsed\":\"0x{gas:x}\"}}",
+				hash = code_hash,
+				pc = pc,
+				count = instruction.count,
+				gas = instruction.gas_used,
+			))
+		}).collect()
+	}
+}
+
+impl vm::Informant for Informant {
+	fn before_test(&mut self, name: &str, action: &str) {
+		println!(
+			"{{\"test\":\"{name}\",\"action\":\"{action}\"}}",
+			name = name,
+			action = action,
+		);
+	}
+
+	fn finish(result: vm::RunResult<Self::Output>) {
+		match result {
+			Ok(success) => {
+				for line in success.traces.map(Self::lines).unwrap_or_else(Vec::new) {
+					println!("{}", line);
+				}
+
+				println!(
+					"{{\"output\":\"0x{output}\",\"gasUsed\":\"{gas:x}\",\"time\":{time}}}",
+					output = success.output.to_hex(),
+					gas = success.gas_used,
+					time = display::as_micros(&success.time),
+				)
+			},
+			Err(failure) => {
+				for line in failure.traces.map(Self::lines).unwrap_or_else(Vec::new) {
+					println!("{}", line);
+				}
+
+				println!(
+					"{{\"error\":\"{error}\",\"gasUsed\":\"{gas:x}\",\"time\":{time}}}",
+					error = failure.error,
+					gas = failure.gas_used,
+					time = display::as_micros(&failure.time),
+				)
+			},
+		}
+	}
+}
+
+impl trace::VMTracer for Informant {
+	type Output = VMProfile;
+
+	fn trace_next_instruction(&mut self, pc: usize, instruction: u8, current_gas: U256) -> bool {
+		self.tracer.trace_next_instruction(pc, instruction, current_gas)
+	}
+
+	fn trace_prepare_execute(&mut self, pc: usize, instruction: u8, gas_cost: U256) {
+		self.tracer.trace_prepare_execute(pc, instruction, gas_cost)
+	}
+
+	fn trace_executed(&mut self, gas_used: U256, stack_push: &[U256], mem_diff: Option<(usize, &[u8])>, store_diff: Option<(U256, U256)>) {
+		self.tracer.trace_executed(gas_used, stack_push, mem_diff, store_diff)
+	}
+
+	fn prepare_subtrace(&self, code: &[u8]) -> Self where Self: Sized {
+		Informant {
+			tracer: self.tracer.prepare_subtrace(code),
+		}
+	}
+
+	fn done_subtrace(&mut self, sub: Self) {
+		self.tracer.done_subtrace(sub.tracer)
+	}
+
+	fn drain(self) -> Option<Self::Output> {
+		self.tracer.drain()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use info::tests::run_test;
+
+	fn compare_profile(profile: Option<VMProfile>, expected: &str) {
+		let profile = profile.unwrap();
+		assert_eq!(profile.codes.len(), 1);
+		let instructions = profile.codes.values().next().unwrap().iter()
+			.map(|(pc, instruction)| format!("{} {} {}", pc, instruction.count, instruction.gas_used))
+			.collect::<Vec<_>>();
+		let expected = expected.split("\n")
+			.map(|x| x.trim())
+			.filter(|x| !x.is_empty())
+			.map(|x| x.to_owned())
+			.collect::<Vec<_>>();
+		assert_eq!(instructions, expected);
+	}
+
+	#[test]
+	fn should_profile_loop() {
+		// PUSH1 3 JUMPDEST PUSH1 1 SWAP1 SUB DUP1 PUSH1 2 JUMPI STOP
+		run_test(
+			Informant::default(),
+			&compare_profile,
+			"60035b600190038060025700",
+			0xffff,
+			r#"
+0 1 3
+2 3 3
+3 3 9
+5 3 9
+6 3 9
+7 3 9
+8 3 9
+10 3 30
+11 1 0
+			"#,
+		);
+	}
+}
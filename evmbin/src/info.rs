@@ -129,6 +129,99 @@ pub fn run_transaction<T: Informant>(
 	T::finish(result)
 }
 
+/// Import blocks of a blockchain test and verify the best block hash.
+///
+/// On success the output contains the best block hash.
+pub fn run_blockchain_test<T: Informant>(
+	name: &str,
+	test: &ethjson::blockchain::BlockChain,
+	mut informant: T,
+) {
+	let spec_name = format!("{:?}", test.network).to_lowercase();
+	let test_name = format!("{}:{}", name, spec_name);
+	let start = Instant::now();
+
+	let result = match EvmTestClient::spec_from_blockchain_test(test) {
+		Ok(Some(spec)) => {
+			informant.before_test(&test_name, "starting");
+			EvmTestClient::import_blocks(&spec, test.blocks_rlp()).and_then(|best_block| {
+				let expected: H256 = test.best_block.clone().into();
+				match best_block.hash() {
+					hash if hash != expected => Err(EvmTestError::PostCondition(format!(
+						"Best block mismatch (got: {}, expected: {})",
+						hash,
+						expected,
+					))),
+					_ => Ok(best_block),
+				}
+			})
+		},
+		Ok(None) => {
+			informant.before_test(&test_name, "skipping because of missing spec");
+			return;
+		},
+		Err(error) => {
+			informant.before_test(&test_name, "starting");
+			Err(error)
+		},
+	};
+
+	let time = start.elapsed();
+	T::finish(match result {
+		Ok(best_block) => Ok(Success {
+			state_root: best_block.state_root(),
+			gas_used: best_block.gas_used(),
+			output: best_block.hash().to_vec(),
+			time,
+			traces: None,
+		}),
+		Err(error) => Err(Failure {
+			gas_used: 0.into(),
+			error,
+			time,
+			traces: None,
+		}),
+	})
+}
+
+/// Decode and validate transaction of a transaction test and compare it with expectations.
+///
+/// On success the output contains the recovered sender and gas used is the intrinsic gas
+/// of the transaction. Both are empty if the transaction is expected to be invalid.
+pub fn run_transaction_test<T: Informant>(
+	name: &str,
+	test: ethjson::transaction::TransactionTest,
+	mut informant: T,
+) {
+	let number: Option<u64> = test.block_number.map(Into::into);
+	let spec_name = format!("{:?}", EvmTestClient::transaction_test_fork(number)).to_lowercase();
+	informant.before_test(&format!("{}:{}", name, spec_name), "starting");
+
+	let schedule = EvmTestClient::transaction_test_schedule(number);
+	let start = Instant::now();
+	let result = EvmTestClient::check_transaction_test(test).map(|t| match t {
+		Some(t) => (t.sender().to_vec(), U256::from(t.gas_required(&schedule))),
+		None => (vec![], U256::zero()),
+	});
+
+	let time = start.elapsed();
+	T::finish(match result {
+		Ok((output, gas_used)) => Ok(Success {
+			state_root: H256::default(),
+			gas_used,
+			output,
+			time,
+			traces: None,
+		}),
+		Err(error) => Err(Failure {
+			gas_used: 0.into(),
+			error,
+			time,
+			traces: None,
+		}),
+	})
+}
+
 /// Execute VM with given `ActionParams`
 pub fn run<'a, F, T, X>(
 	spec: &'a spec::Spec,
@@ -204,4 +297,40 @@ pub mod tests {
 			},
 		}
 	}
+
+	fn transaction_test(sender: Option<&str>, value: &str) -> ethjson::transaction::TransactionTest {
+		let sender = sender.map_or_else(String::new, |sender| format!(r#""sender": "{}","#, sender));
+		::serde_json::from_str(&format!(r#"{{
+			"blocknumber": "0",
+			"rlp": "0xf85f800182520894095e7baea6a6c7c4c2dfeb977efac326af552d870a801ba048b55bfa915ac795c431978d8a6a992b628d557da5ff759b307d495a36649353a0efffd310ac743f371de3b9f7f9cb56c0b28ad43601b4ab949f53faa07bd2c804",
+			{}
+			"transaction": {{
+				"data": "",
+				"gasLimit": "0x5208",
+				"gasPrice": "0x01",
+				"nonce": "0x00",
+				"r": "0x48b55bfa915ac795c431978d8a6a992b628d557da5ff759b307d495a36649353",
+				"s": "0xefffd310ac743f371de3b9f7f9cb56c0b28ad43601b4ab949f53faa07bd2c804",
+				"to": "095e7baea6a6c7c4c2dfeb977efac326af552d87",
+				"v": "0x1b",
+				"value": "{}"
+			}}
+		}}"#, sender, value)).unwrap()
+	}
+
+	#[test]
+	fn should_check_transaction_test() {
+		let sender = "0f65fe9276bc9a24ae7083ae28e2660ef72df99e";
+
+		let transaction = EvmTestClient::check_transaction_test(transaction_test(Some(sender), "0x0a")).unwrap();
+		assert_eq!(transaction.unwrap().sender(), sender.into());
+
+		// without a sender only validity is checked
+		assert!(EvmTestClient::check_transaction_test(transaction_test(None, "0x0b")).unwrap().is_some());
+
+		match EvmTestClient::check_transaction_test(transaction_test(Some(sender), "0x0b")) {
+			Err(EvmTestError::PostCondition(error)) => assert_eq!(error, "Value mismatch"),
+			other => panic!("Unexpected result: {:?}", other),
+		}
+	}
 }
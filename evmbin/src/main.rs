@@ -22,6 +22,7 @@ extern crate ethcore;
 extern crate ethjson;
 extern crate rustc_hex;
 extern crate serde;
+extern crate serde_json;
 #[macro_use]
 extern crate serde_derive;
 extern crate docopt;
@@ -41,6 +42,7 @@ extern crate tempdir;
 
 use std::sync::Arc;
 use std::{fmt, fs};
+use std::io::Write;
 use std::path::PathBuf;
 use docopt::Docopt;
 use rustc_hex::FromHex;
@@ -51,6 +53,7 @@ use vm::{ActionParams, CallType};
 
 mod info;
 mod display;
+mod transition;
 
 use info::Informant;
 
@@ -59,7 +62,10 @@ EVM implementation for Parity.
   Copyright 2016, 2017 Parity Technologies (UK) Ltd
 
 Usage:
-    parity-evm state-test <file> [--json --std-json --only NAME --chain CHAIN]
+    parity-evm state-test <file> [--json --std-json --profile --only NAME --chain CHAIN]
+    parity-evm blockchain-test <file> [--json --std-json --only NAME --chain CHAIN]
+    parity-evm transaction-test <file> [--json --std-json --only NAME --chain CHAIN]
+    parity-evm transition --alloc FILE --env FILE --txs FILE [--chain CHAIN --output-dir DIR]
     parity-evm stats [options]
     parity-evm [options]
     parity-evm [-h | --help]
@@ -72,13 +78,22 @@ Transaction options:
     --gas GAS          Supplied gas as hex (without 0x).
     --gas-price WEI    Supplied gas price as hex (without 0x).
 
-State test options:
+State, blockchain and transaction test options:
     --only NAME        Runs only a single test matching the name.
     --chain CHAIN      Run only tests from specific chain.
 
+Transition options:
+    --alloc FILE       Prestate accounts in the test `pre` format.
+    --env FILE         Block environment in the VM test `env` format.
+    --txs FILE         Signed transactions in the transaction test format.
+    --output-dir DIR   Write alloc.json and result.json to the directory
+                       instead of printing them.
+
 General options:
     --json             Display verbose results in JSON.
 	--std-json         Display results in standardized JSON format.
+    --profile          Display instruction coverage and gas used per
+                       instruction in JSON, keyed by code hash.
     --chain CHAIN      Chain spec file path.
     -h, --help         Display this message and exit.
 "#;
@@ -90,10 +105,18 @@ fn main() {
 
 	if args.cmd_state_test {
 		run_state_test(args)
+	} else if args.cmd_blockchain_test {
+		run_blockchain_test(args)
+	} else if args.cmd_transaction_test {
+		run_transaction_test(args)
+	} else if args.cmd_transition {
+		run_transition(args)
 	} else if args.flag_json {
 		run_call(args, display::json::Informant::default())
 	} else if args.flag_std_json {
 		run_call(args, display::std_json::Informant::default())
+	} else if args.flag_profile {
+		run_call(args, display::profile::Informant::default())
 	} else {
 		run_call(args, display::simple::Informant::default())
 	}
@@ -138,6 +161,9 @@ fn run_state_test(args: Args) {
 				} else if args.flag_std_json {
 					let i = display::std_json::Informant::default();
 					info::run_transaction(&name, idx, &spec, &pre, post_root, &env_info, transaction, i)
+				} else if args.flag_profile {
+					let i = display::profile::Informant::default();
+					info::run_transaction(&name, idx, &spec, &pre, post_root, &env_info, transaction, i)
 				} else {
 					let i = display::simple::Informant::default();
 					info::run_transaction(&name, idx, &spec, &pre, post_root, &env_info, transaction, i)
@@ -147,6 +173,128 @@ fn run_state_test(args: Args) {
 	}
 }
 
+fn run_blockchain_test(args: Args) {
+	use ethjson::blockchain::Test;
+
+	let file = args.arg_file.expect("FILE is required");
+	let mut file = match fs::File::open(&file) {
+		Err(err) => die(format!("Unable to open: {:?}: {}", file, err)),
+		Ok(file) => file,
+	};
+	let blockchain_test = match Test::load(&mut file) {
+		Err(err) => die(format!("Unable to load the test file: {}", err)),
+		Ok(test) => test,
+	};
+	let only_test = args.flag_only.map(|s| s.to_lowercase());
+	let only_chain = args.flag_chain.map(|s| s.to_lowercase());
+
+	for (name, test) in blockchain_test {
+		if let Some(false) = only_test.as_ref().map(|only_test| &name.to_lowercase() == only_test) {
+			continue;
+		}
+
+		if let Some(false) = only_chain.as_ref().map(|only_chain| &format!("{:?}", test.network).to_lowercase() == only_chain) {
+			continue;
+		}
+
+		if args.flag_json {
+			info::run_blockchain_test(&name, &test, display::json::Informant::default())
+		} else if args.flag_std_json {
+			info::run_blockchain_test(&name, &test, display::std_json::Informant::default())
+		} else {
+			info::run_blockchain_test(&name, &test, display::simple::Informant::default())
+		}
+	}
+}
+
+fn run_transaction_test(args: Args) {
+	use ethjson::transaction::Test;
+
+	let file = args.arg_file.expect("FILE is required");
+	let mut file = match fs::File::open(&file) {
+		Err(err) => die(format!("Unable to open: {:?}: {}", file, err)),
+		Ok(file) => file,
+	};
+	let transaction_test = match Test::load(&mut file) {
+		Err(err) => die(format!("Unable to load the test file: {}", err)),
+		Ok(test) => test,
+	};
+	let only_test = args.flag_only.map(|s| s.to_lowercase());
+	let only_chain = args.flag_chain.map(|s| s.to_lowercase());
+
+	for (name, test) in transaction_test {
+		if let Some(false) = only_test.as_ref().map(|only_test| &name.to_lowercase() == only_test) {
+			continue;
+		}
+
+		let spec = ethcore::client::EvmTestClient::transaction_test_fork(test.block_number.map(Into::into));
+		if let Some(false) = only_chain.as_ref().map(|only_chain| &format!("{:?}", spec).to_lowercase() == only_chain) {
+			continue;
+		}
+
+		if args.flag_json {
+			info::run_transaction_test(&name, test, display::json::Informant::default())
+		} else if args.flag_std_json {
+			info::run_transaction_test(&name, test, display::std_json::Informant::default())
+		} else {
+			info::run_transaction_test(&name, test, display::simple::Informant::default())
+		}
+	}
+}
+
+fn run_transition(args: Args) {
+	use ethjson::state::test::ForkSpec;
+
+	fn load<T: serde::de::DeserializeOwned>(path: &str) -> T {
+		let file = match fs::File::open(path) {
+			Err(err) => die(format!("Unable to open: {:?}: {}", path, err)),
+			Ok(file) => file,
+		};
+		match serde_json::from_reader(file) {
+			Err(err) => die(format!("Unable to load {:?}: {}", path, err)),
+			Ok(value) => value,
+		}
+	}
+
+	let alloc: ethjson::blockchain::State = load(args.flag_alloc.as_ref().expect("--alloc is required"));
+	let env: ethjson::vm::Env = load(args.flag_env.as_ref().expect("--env is required"));
+	let txs: Vec<ethjson::transaction::Transaction> = load(args.flag_txs.as_ref().expect("--txs is required"));
+
+	let fork = match args.flag_chain {
+		Some(ref chain) => match serde_json::from_value::<ForkSpec>(serde_json::Value::String(chain.clone())) {
+			Ok(fork) => fork,
+			Err(_) => die(format!("Unknown chain: {}", chain)),
+		},
+		None => ForkSpec::Byzantium,
+	};
+	let spec = match ethcore::client::EvmTestClient::spec_from_json(&fork) {
+		Some(spec) => spec,
+		None => die(format!("Unsupported chain: {:?}", fork)),
+	};
+
+	let output = match transition::run(spec, transition::Input::from_json(alloc, env, txs)) {
+		Ok(output) => output,
+		Err(err) => die(format!("Transition failed: {}", err)),
+	};
+
+	match args.flag_output_dir {
+		Some(ref dir) => {
+			let dir = PathBuf::from(dir);
+			let write = |name: &str, json: String| {
+				let path = dir.join(name);
+				if let Err(err) = fs::File::create(&path).and_then(|mut file| file.write_all(json.as_bytes())) {
+					die(format!("Unable to write {:?}: {}", path, err));
+				}
+			};
+			write("alloc.json", serde_json::to_string_pretty(&output.alloc).expect("Serialization cannot fail; qed"));
+			write("result.json", serde_json::to_string_pretty(&output.result).expect("Serialization cannot fail; qed"));
+		},
+		None => {
+			println!("{}", serde_json::to_string_pretty(&output).expect("Serialization cannot fail; qed"));
+		},
+	}
+}
+
 fn run_call<T: Informant>(args: Args, informant: T) {
 	let from = arg(args.from(), "--from");
 	let to = arg(args.to(), "--to");
@@ -179,6 +327,9 @@ fn run_call<T: Informant>(args: Args, informant: T) {
 struct Args {
 	cmd_stats: bool,
 	cmd_state_test: bool,
+	cmd_blockchain_test: bool,
+	cmd_transaction_test: bool,
+	cmd_transition: bool,
 	arg_file: Option<PathBuf>,
 	flag_only: Option<String>,
 	flag_from: Option<String>,
@@ -188,8 +339,13 @@ struct Args {
 	flag_gas_price: Option<String>,
 	flag_input: Option<String>,
 	flag_chain: Option<String>,
+	flag_alloc: Option<String>,
+	flag_env: Option<String>,
+	flag_txs: Option<String>,
+	flag_output_dir: Option<String>,
 	flag_json: bool,
 	flag_std_json: bool,
+	flag_profile: bool,
 }
 
 impl Args {
@@ -276,6 +432,7 @@ mod tests {
 			"parity-evm",
 			"--json",
 			"--std-json",
+			"--profile",
 			"--gas", "1",
 			"--gas-price", "2",
 			"--from", "0000000000000000000000000000000000000003",
@@ -287,6 +444,7 @@ mod tests {
 
 		assert_eq!(args.flag_json, true);
 		assert_eq!(args.flag_std_json, true);
+		assert_eq!(args.flag_profile, true);
 		assert_eq!(args.gas(), Ok(1.into()));
 		assert_eq!(args.gas_price(), Ok(2.into()));
 		assert_eq!(args.from(), Ok(3.into()));
@@ -315,4 +473,60 @@ mod tests {
 		assert_eq!(args.flag_chain, Some("homestead".to_owned()));
 		assert_eq!(args.flag_only, Some("add11".to_owned()));
 	}
+
+	#[test]
+	fn should_parse_blockchain_test_command() {
+		let args = run(&[
+			"parity-evm",
+			"blockchain-test",
+			"./file.json",
+			"--chain", "byzantium",
+			"--only=bcValidBlockTest",
+			"--std-json"
+		]);
+
+		assert_eq!(args.cmd_blockchain_test, true);
+		assert_eq!(args.cmd_state_test, false);
+		assert!(args.arg_file.is_some());
+		assert_eq!(args.flag_std_json, true);
+		assert_eq!(args.flag_chain, Some("byzantium".to_owned()));
+		assert_eq!(args.flag_only, Some("bcValidBlockTest".to_owned()));
+	}
+
+	#[test]
+	fn should_parse_transaction_test_command() {
+		let args = run(&[
+			"parity-evm",
+			"transaction-test",
+			"./file.json",
+			"--only=AddressLessThan20",
+			"--json"
+		]);
+
+		assert_eq!(args.cmd_transaction_test, true);
+		assert!(args.arg_file.is_some());
+		assert_eq!(args.flag_json, true);
+		assert_eq!(args.flag_only, Some("AddressLessThan20".to_owned()));
+	}
+
+	#[test]
+	fn should_parse_transition_command() {
+		let args = run(&[
+			"parity-evm",
+			"transition",
+			"--alloc", "./alloc.json",
+			"--env", "./env.json",
+			"--txs", "./txs.json",
+			"--chain", "Byzantium",
+			"--output-dir", "./out",
+		]);
+
+		assert_eq!(args.cmd_transition, true);
+		assert_eq!(args.cmd_state_test, false);
+		assert_eq!(args.flag_alloc, Some("./alloc.json".to_owned()));
+		assert_eq!(args.flag_env, Some("./env.json".to_owned()));
+		assert_eq!(args.flag_txs, Some("./txs.json".to_owned()));
+		assert_eq!(args.flag_chain, Some("Byzantium".to_owned()));
+		assert_eq!(args.flag_output_dir, Some("./out".to_owned()));
+	}
 }
@@ -0,0 +1,334 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! State transition: applies a list of transactions to a prestate.
+
+use std::collections::BTreeMap;
+use ethereum_types::{H256, U256, Address, Bloom};
+use bytes::ToPretty;
+use ethcore::client::{EvmTestClient, EvmTestError, TransactResult, EnvInfo};
+use ethcore::receipt::{Receipt, TransactionOutcome};
+use ethcore::log_entry::LogEntry;
+use ethcore::{trace, spec, pod_state};
+use ethjson;
+use transaction::{SignedTransaction, UnverifiedTransaction};
+
+/// Transition input.
+pub struct Input {
+	/// Accounts before the transition.
+	pub alloc: pod_state::PodState,
+	/// Block environment.
+	pub env: EnvInfo,
+	/// Signed transactions to apply in order.
+	pub txs: Vec<UnverifiedTransaction>,
+}
+
+impl Input {
+	/// Creates the input from the files passed as `--alloc`, `--env` and `--txs`.
+	pub fn from_json(alloc: ethjson::blockchain::State, env: ethjson::vm::Env, txs: Vec<ethjson::transaction::Transaction>) -> Self {
+		Input {
+			alloc: alloc.into(),
+			env: env.into(),
+			txs: txs.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Transition output.
+#[derive(Debug, Serialize)]
+pub struct Output {
+	/// Accounts after the transition.
+	pub alloc: BTreeMap<Address, Account>,
+	/// Execution result.
+	pub result: ExecutionResult,
+}
+
+/// Account after the transition, in the same format as the input alloc.
+#[derive(Debug, Serialize)]
+pub struct Account {
+	/// Balance.
+	pub balance: U256,
+	/// Nonce.
+	pub nonce: U256,
+	/// Code as hex.
+	pub code: String,
+	/// Non-zero storage.
+	pub storage: BTreeMap<H256, H256>,
+}
+
+/// Summary of all applied transactions.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionResult {
+	/// State root after the transition.
+	pub state_root: H256,
+	/// Combined bloom of all receipts.
+	pub logs_bloom: Bloom,
+	/// Gas used by all applied transactions.
+	pub gas_used: U256,
+	/// Receipts of applied transactions.
+	pub receipts: Vec<TransactionReceipt>,
+	/// Transactions which could not be applied.
+	pub rejected: Vec<RejectedTransaction>,
+}
+
+/// Receipt of an applied transaction.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReceipt {
+	/// Transaction hash.
+	pub transaction_hash: H256,
+	/// Intermediate state root (pre-Byzantium).
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub root: Option<H256>,
+	/// Status code (Byzantium).
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub status: Option<u8>,
+	/// Gas used in the block up to and including this transaction.
+	pub cumulative_gas_used: U256,
+	/// Gas used by this transaction.
+	pub gas_used: U256,
+	/// Bloom of the logs.
+	pub logs_bloom: Bloom,
+	/// Logs.
+	pub logs: Vec<Log>,
+	/// Address of the created contract.
+	pub contract_address: Option<Address>,
+	/// Output as hex.
+	pub output: String,
+}
+
+/// Log of an applied transaction.
+#[derive(Debug, Serialize)]
+pub struct Log {
+	/// Address of the logging contract.
+	pub address: Address,
+	/// Topics.
+	pub topics: Vec<H256>,
+	/// Data as hex.
+	pub data: String,
+}
+
+impl From<LogEntry> for Log {
+	fn from(log: LogEntry) -> Self {
+		Log {
+			address: log.address,
+			topics: log.topics,
+			data: format!("0x{}", log.data.to_hex()),
+		}
+	}
+}
+
+/// Transaction which could not be applied.
+#[derive(Debug, Serialize)]
+pub struct RejectedTransaction {
+	/// Index in the input list.
+	pub index: usize,
+	/// Reason.
+	pub error: String,
+}
+
+/// Apply all transactions from the input, skipping the invalid ones.
+pub fn run(spec: &spec::Spec, input: Input) -> Result<Output, EvmTestError> {
+	let mut client = EvmTestClient::transition_from_pod_state(spec, input.alloc)?;
+	let mut env_info = input.env;
+	let mut logs_bloom = Bloom::default();
+	let mut receipts = Vec::new();
+	let mut rejected = Vec::new();
+
+	for (index, transaction) in input.txs.into_iter().enumerate() {
+		let transaction = match SignedTransaction::new(transaction) {
+			Ok(transaction) => transaction,
+			Err(err) => {
+				rejected.push(RejectedTransaction { index, error: format!("{}", err) });
+				continue;
+			},
+		};
+
+		let hash = transaction.hash();
+		let gas = transaction.gas;
+		match client.transact(&env_info, transaction, trace::NoopTracer, trace::NoopVMTracer) {
+			TransactResult::Ok { gas_left, output, logs, outcome, contract_address, .. } => {
+				let gas_used = gas - gas_left;
+				env_info.gas_used = env_info.gas_used + gas_used;
+
+				let receipt = Receipt::new(outcome, env_info.gas_used, logs);
+				logs_bloom.accrue_bloom(&receipt.log_bloom);
+
+				let (root, status) = match receipt.outcome {
+					TransactionOutcome::StateRoot(root) => (Some(root), None),
+					TransactionOutcome::StatusCode(status) => (None, Some(status)),
+					TransactionOutcome::Unknown => (None, None),
+				};
+
+				receipts.push(TransactionReceipt {
+					transaction_hash: hash,
+					root,
+					status,
+					cumulative_gas_used: receipt.gas_used,
+					gas_used,
+					logs_bloom: receipt.log_bloom,
+					logs: receipt.logs.into_iter().map(Into::into).collect(),
+					contract_address,
+					output: format!("0x{}", output.to_hex()),
+				});
+			},
+			TransactResult::Err { error, .. } => {
+				rejected.push(RejectedTransaction { index, error: format!("{}", error) });
+			},
+		}
+	}
+
+	let state_root = *client.state().root();
+	let alloc = client.pod_state()?.drain().into_iter().map(|(address, account)| {
+		(address, Account {
+			balance: account.balance,
+			nonce: account.nonce,
+			code: format!("0x{}", account.code.unwrap_or_default().to_hex()),
+			storage: account.storage,
+		})
+	}).collect();
+
+	Ok(Output {
+		alloc,
+		result: ExecutionResult {
+			state_root,
+			logs_bloom,
+			gas_used: env_info.gas_used,
+			receipts,
+			rejected,
+		},
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+	use ethereum_types::{U256, Address};
+	use ethcore::client::EvmTestClient;
+	use ethjson;
+	use ethjson::state::test::ForkSpec;
+	use transaction::SignedTransaction;
+	use super::{Input, run};
+
+	fn alloc() -> ethjson::blockchain::State {
+		::serde_json::from_str(r#"{
+			"a94f5374fce5edbc8e2a8697c15331677e6ebf0b": {
+				"balance": "0x0de0b6b3a7640000",
+				"code": "0x",
+				"nonce": "0x00",
+				"storage": {}
+			}
+		}"#).unwrap()
+	}
+
+	fn env() -> ethjson::vm::Env {
+		::serde_json::from_str(r#"{
+			"currentCoinbase": "2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+			"currentDifficulty": "0x020000",
+			"currentGasLimit": "0x7fffffffffffffff",
+			"currentNumber": "0x01",
+			"currentTimestamp": "0x03e8"
+		}"#).unwrap()
+	}
+
+	#[test]
+	fn should_apply_transactions_and_reject_invalid() {
+		// the second transaction reuses the nonce of the first one
+		let txs: Vec<ethjson::state::Transaction> = ::serde_json::from_str(r#"[{
+			"data": "",
+			"gasLimit": "0x5208",
+			"gasPrice": "0x01",
+			"nonce": "0x00",
+			"secretKey": "45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+			"to": "095e7baea6a6c7c4c2dfeb977efac326af552d87",
+			"value": "0x0a"
+		}, {
+			"data": "",
+			"gasLimit": "0x5208",
+			"gasPrice": "0x01",
+			"nonce": "0x00",
+			"secretKey": "45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+			"to": "095e7baea6a6c7c4c2dfeb977efac326af552d87",
+			"value": "0x0a"
+		}]"#).unwrap();
+
+		let spec = EvmTestClient::spec_from_json(&ForkSpec::Byzantium).unwrap();
+		let output = run(spec, Input {
+			alloc: alloc().into(),
+			env: env().into(),
+			txs: txs.into_iter().map(|tx| SignedTransaction::from(tx).into()).collect(),
+		}).unwrap();
+
+		let sender = Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
+		let recipient = Address::from_str("095e7baea6a6c7c4c2dfeb977efac326af552d87").unwrap();
+		assert_eq!(output.result.receipts.len(), 1);
+		assert_eq!(output.result.receipts[0].status, Some(1));
+		assert_eq!(output.result.gas_used, U256::from(21_000));
+		assert_eq!(output.result.rejected.len(), 1);
+		assert_eq!(output.result.rejected[0].index, 1);
+		assert_eq!(output.alloc[&sender].nonce, U256::from(1));
+		assert_eq!(output.alloc[&recipient].balance, U256::from(10));
+	}
+
+	#[test]
+	fn should_apply_signed_transactions_from_json() {
+		let signed: ethjson::state::Transaction = ::serde_json::from_str(r#"{
+			"data": "",
+			"gasLimit": "0x5208",
+			"gasPrice": "0x01",
+			"nonce": "0x00",
+			"secretKey": "45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+			"to": "095e7baea6a6c7c4c2dfeb977efac326af552d87",
+			"value": "0x0a"
+		}"#).unwrap();
+		let signed = SignedTransaction::from(signed);
+		let signature = signed.signature();
+
+		// same format as the file given with `--txs`, the second transaction has an invalid signature
+		let txs: Vec<ethjson::transaction::Transaction> = ::serde_json::from_str(&format!(r#"[{{
+			"data": "0x",
+			"gasLimit": "0x5208",
+			"gasPrice": "0x01",
+			"nonce": "0x00",
+			"to": "095e7baea6a6c7c4c2dfeb977efac326af552d87",
+			"value": "0x0a",
+			"r": "0x{:x}",
+			"s": "0x{:x}",
+			"v": "0x{:x}"
+		}}, {{
+			"data": "0x",
+			"gasLimit": "0x5208",
+			"gasPrice": "0x01",
+			"nonce": "0x01",
+			"to": "095e7baea6a6c7c4c2dfeb977efac326af552d87",
+			"value": "0x0a",
+			"r": "0x00",
+			"s": "0x00",
+			"v": "0x1b"
+		}}]"#, U256::from(signature.r()), U256::from(signature.s()), signed.original_v())).unwrap();
+
+		let spec = EvmTestClient::spec_from_json(&ForkSpec::Byzantium).unwrap();
+		let output = run(spec, Input::from_json(alloc(), env(), txs)).unwrap();
+
+		let sender = Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
+		assert_eq!(output.result.receipts.len(), 1);
+		assert_eq!(output.result.receipts[0].transaction_hash, signed.hash());
+		assert_eq!(output.result.rejected.len(), 1);
+		assert_eq!(output.result.rejected[0].index, 1);
+		assert_eq!(output.alloc[&sender].nonce, U256::from(1));
+	}
+}
@@ -0,0 +1,544 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Keys held by an external signing service.
+//!
+//! The service is reached over HTTP(S) or a Unix domain socket and speaks JSON-RPC 2.0.
+//! Over a socket every request is sent as a single line of JSON and answered with a single line.
+//! Binary data is always hex-encoded with a `0x` prefix.
+//!
+//! Methods the service has to implement:
+//!
+//! - `signer_accounts()` - addresses of all accounts managed by the service.
+//! - `signer_signHash(address, hash)` - 65-byte `r || s || v` signature of a 32-byte hash,
+//!   `v` is either `0`/`1` or `27`/`28`.
+//! - `signer_decrypt(address, mac, ciphertext)` - plaintext of an ECIES ciphertext
+//!   encrypted to the public key of the account.
+//! - `signer_agree(address, public)` - 32-byte ECDH shared secret of the account
+//!   and given 64-byte public key.
+//!
+//! The service is free to reject any request, e.g. after asking an operator, by returning
+//! a JSON-RPC error.
+//!
+//! Alternatively the service may speak the `account_*` API of Clef. It never signs arbitrary
+//! hashes, so only transactions can be signed:
+//!
+//! - `account_list()` - addresses of all accounts managed by the service.
+//! - `account_signTransaction(transaction)` - signed transaction as `{ raw, tx }`.
+
+#![warn(missing_docs)]
+
+extern crate ethereum_types;
+extern crate ethkey;
+extern crate fetch;
+extern crate futures;
+extern crate parking_lot;
+extern crate rustc_hex;
+extern crate serde;
+extern crate serde_json;
+
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_derive;
+
+mod transport;
+
+pub use transport::{Transport, Http, Ipc};
+
+use std::{fmt, io, thread};
+use std::str::FromStr;
+use std::sync::{Arc, Weak};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use ethereum_types::{H256, U256};
+use ethkey::{Address, Public, Secret, Signature};
+use parking_lot::RwLock;
+use rustc_hex::{FromHex, ToHex};
+use serde_json::Value;
+
+/// How often the list of remote accounts is fetched in the background.
+const ACCOUNTS_REFRESH_SECS: u64 = 10;
+
+/// External signer error.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+	/// Service could not be reached.
+	Transport(String),
+	/// Service rejected the request.
+	Remote {
+		/// JSON-RPC error code.
+		code: i64,
+		/// Error message.
+		message: String,
+	},
+	/// Service returned malformed response.
+	InvalidResponse(String),
+	/// Operation is not supported by the protocol of the service.
+	Unsupported(&'static str),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Error::Transport(ref e) => write!(f, "External signer is unreachable: {}", e),
+			Error::Remote { code, ref message } => write!(f, "External signer rejected the request: {} ({})", message, code),
+			Error::InvalidResponse(ref e) => write!(f, "Invalid response from external signer: {}", e),
+			Error::Unsupported(ref operation) => write!(f, "External signer does not support {}", operation),
+		}
+	}
+}
+
+/// JSON-RPC API spoken by the signing service.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Protocol {
+	/// `signer_*` methods, signing arbitrary hashes.
+	Parity,
+	/// `account_*` methods of Clef, signing whole transactions only.
+	Clef,
+}
+
+impl Default for Protocol {
+	fn default() -> Self {
+		Protocol::Parity
+	}
+}
+
+impl FromStr for Protocol {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"parity" => Ok(Protocol::Parity),
+			"clef" => Ok(Protocol::Clef),
+			other => Err(format!("Invalid external signer protocol: {}", other)),
+		}
+	}
+}
+
+/// Transaction to be signed by the service.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionRequest {
+	/// Nonce.
+	pub nonce: U256,
+	/// Gas price.
+	pub gas_price: U256,
+	/// Gas limit.
+	pub gas: U256,
+	/// Recipient, `None` for contract creation.
+	pub to: Option<Address>,
+	/// Transferred value.
+	pub value: U256,
+	/// Call data or contract code.
+	pub data: Vec<u8>,
+	/// Chain id for replay protection.
+	pub chain_id: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct Request<'a> {
+	jsonrpc: &'static str,
+	id: usize,
+	method: &'a str,
+	params: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteError {
+	code: i64,
+	message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Response {
+	result: Option<Value>,
+	error: Option<RemoteError>,
+}
+
+/// Signing service holding keys outside of the node.
+pub struct ExternalSigner {
+	transport: Box<Transport>,
+	protocol: Protocol,
+	accounts: RwLock<Vec<Address>>,
+	next_id: AtomicUsize,
+}
+
+impl ExternalSigner {
+	/// Connect to the service at given endpoint, either an HTTP(S) URL or a path to a Unix domain socket.
+	pub fn new(endpoint: &str, protocol: Protocol) -> Result<Self, Error> {
+		let transport: Box<Transport> = if endpoint.starts_with("http://") || endpoint.starts_with("https://") {
+			Box::new(Http::new(endpoint)?)
+		} else {
+			Box::new(Ipc::new(endpoint))
+		};
+
+		Ok(Self::with_transport(transport, protocol))
+	}
+
+	/// Use the service behind given transport.
+	pub fn with_transport(transport: Box<Transport>, protocol: Protocol) -> Self {
+		ExternalSigner {
+			transport,
+			protocol,
+			accounts: RwLock::new(Vec::new()),
+			next_id: AtomicUsize::new(1),
+		}
+	}
+
+	/// Keep the list of accounts up to date by fetching it every `ACCOUNTS_REFRESH_SECS` on a separate thread,
+	/// starting right away. The thread stops once the signer is dropped.
+	pub fn refresh_in_background(signer: &Arc<Self>) -> io::Result<()> {
+		let signer = Arc::downgrade(signer);
+		thread::Builder::new()
+			.name("external-signer".into())
+			.spawn(move || Self::refresh_loop(signer))
+			.map(|_| ())
+	}
+
+	fn refresh_loop(signer: Weak<Self>) {
+		while let Some(signer) = signer.upgrade() {
+			if let Err(e) = signer.refresh_accounts() {
+				warn!(target: "external_signer", "Failed to fetch the list of accounts: {}", e);
+			}
+			drop(signer);
+			thread::sleep(Duration::from_secs(ACCOUNTS_REFRESH_SECS));
+		}
+	}
+
+	/// Addresses of accounts managed by the service, as of the last refresh.
+	/// Never contacts the service.
+	pub fn accounts(&self) -> Vec<Address> {
+		self.accounts.read().clone()
+	}
+
+	/// Fetch addresses of accounts managed by the service.
+	pub fn refresh_accounts(&self) -> Result<Vec<Address>, Error> {
+		let method = match self.protocol {
+			Protocol::Parity => "signer_accounts",
+			Protocol::Clef => "account_list",
+		};
+		let addresses = match self.call(method, Vec::new())? {
+			Value::Array(addresses) => addresses.into_iter().map(address).collect::<Result<Vec<_>, _>>()?,
+			other => return Err(Error::InvalidResponse(format!("expected a list of addresses, got {}", other))),
+		};
+
+		*self.accounts.write() = addresses.clone();
+		Ok(addresses)
+	}
+
+	/// Returns `true` if the account with given address is managed by the service, as of the last refresh.
+	pub fn is_external(&self, address: &Address) -> bool {
+		self.accounts.read().contains(address)
+	}
+
+	/// Sign a hash with the account.
+	pub fn sign(&self, address: &Address, hash: &H256) -> Result<Signature, Error> {
+		if self.protocol == Protocol::Clef {
+			return Err(Error::Unsupported("signing arbitrary hashes"));
+		}
+
+		let data = bytes(self.call("signer_signHash", vec![hex(address), hex(hash)])?)?;
+		if data.len() != 65 {
+			return Err(Error::InvalidResponse(format!("expected 65-byte signature, got {} bytes", data.len())));
+		}
+
+		let v = match data[64] {
+			v @ 0...1 => v,
+			v @ 27...28 => v - 27,
+			v => return Err(Error::InvalidResponse(format!("invalid signature recovery id {}", v))),
+		};
+		verified(address, Signature::from_rsv(&H256::from_slice(&data[0..32]), &H256::from_slice(&data[32..64]), v), hash)
+	}
+
+	/// Sign a transaction with the account, given the hash of the unsigned transaction.
+	/// The signature is checked against the hash, so the service can't alter the transaction.
+	pub fn sign_transaction(&self, address: &Address, transaction: &TransactionRequest, hash: &H256) -> Result<Signature, Error> {
+		if self.protocol == Protocol::Parity {
+			return self.sign(address, hash);
+		}
+
+		let mut request = serde_json::Map::new();
+		request.insert("from".into(), hex(address));
+		if let Some(ref to) = transaction.to {
+			request.insert("to".into(), hex(to));
+		}
+		request.insert("gas".into(), quantity(&transaction.gas));
+		request.insert("gasPrice".into(), quantity(&transaction.gas_price));
+		request.insert("value".into(), quantity(&transaction.value));
+		request.insert("nonce".into(), quantity(&transaction.nonce));
+		request.insert("data".into(), hex(&transaction.data));
+		if let Some(chain_id) = transaction.chain_id {
+			request.insert("chainId".into(), quantity(&chain_id.into()));
+		}
+
+		let response = self.call("account_signTransaction", vec![Value::Object(request)])?;
+		let signed = &response["tx"];
+		let (r, s, v) = (number(&signed["r"])?, number(&signed["s"])?, number(&signed["v"])?.low_u64());
+		let v = match v {
+			0...1 => v,
+			27...28 => v - 27,
+			v if v >= 35 => (v - 35) % 2,
+			v => return Err(Error::InvalidResponse(format!("invalid signature recovery id {}", v))),
+		};
+		verified(address, Signature::from_rsv(&r.into(), &s.into(), v as u8), hash)
+	}
+
+	/// Decrypt an ECIES message encrypted to the account.
+	pub fn decrypt(&self, address: &Address, shared_mac: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+		if self.protocol == Protocol::Clef {
+			return Err(Error::Unsupported("decryption"));
+		}
+
+		bytes(self.call("signer_decrypt", vec![hex(address), hex(shared_mac), hex(message)])?)
+	}
+
+	/// Agree on a shared secret of the account and given public key.
+	pub fn agree(&self, address: &Address, other_public: &Public) -> Result<Secret, Error> {
+		if self.protocol == Protocol::Clef {
+			return Err(Error::Unsupported("key agreement"));
+		}
+
+		let data = bytes(self.call("signer_agree", vec![hex(address), hex(other_public)])?)?;
+		Secret::from_slice(&data)
+			.ok_or_else(|| Error::InvalidResponse(format!("expected 32-byte secret, got {} bytes", data.len())))
+	}
+
+	fn call(&self, method: &str, params: Vec<Value>) -> Result<Value, Error> {
+		let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+		let request = Request {
+			jsonrpc: "2.0",
+			id,
+			method,
+			params,
+		};
+		let request = serde_json::to_string(&request).expect("Request is always serializable; qed");
+		// bodies are never logged, responses may contain decrypted messages or shared secrets
+		trace!(target: "external_signer", "Request {}: {}", id, method);
+
+		let response = self.transport.send(&request)?;
+		trace!(target: "external_signer", "Response to request {}", id);
+
+		let response: Response = serde_json::from_str(&response)
+			.map_err(|e| Error::InvalidResponse(e.to_string()))?;
+		match (response.result, response.error) {
+			(_, Some(e)) => Err(Error::Remote { code: e.code, message: e.message }),
+			(Some(result), None) => Ok(result),
+			(None, None) => Err(Error::InvalidResponse("missing result".into())),
+		}
+	}
+}
+
+fn verified(address: &Address, signature: Signature, hash: &H256) -> Result<Signature, Error> {
+	match ethkey::verify_address(address, &signature, hash) {
+		Ok(true) => Ok(signature),
+		_ => Err(Error::InvalidResponse("signature does not match the account".into())),
+	}
+}
+
+fn hex(data: &[u8]) -> Value {
+	Value::String(format!("0x{}", data.to_hex()))
+}
+
+fn quantity(value: &U256) -> Value {
+	Value::String(format!("0x{:x}", value))
+}
+
+fn number(value: &Value) -> Result<U256, Error> {
+	match *value {
+		Value::String(ref s) if s.starts_with("0x") => U256::from_str(&s[2..])
+			.map_err(|e| Error::InvalidResponse(format!("invalid number {}: {:?}", s, e))),
+		ref other => Err(Error::InvalidResponse(format!("expected 0x-prefixed number, got {}", other))),
+	}
+}
+
+fn bytes(value: Value) -> Result<Vec<u8>, Error> {
+	match value {
+		Value::String(ref s) if s.starts_with("0x") => s[2..].from_hex()
+			.map_err(|e| Error::InvalidResponse(format!("invalid hex {}: {}", s, e))),
+		other => Err(Error::InvalidResponse(format!("expected 0x-prefixed hex, got {}", other))),
+	}
+}
+
+fn address(value: Value) -> Result<Address, Error> {
+	let data = bytes(value)?;
+	if data.len() != 20 {
+		return Err(Error::InvalidResponse(format!("expected 20-byte address, got {} bytes", data.len())));
+	}
+	Ok(Address::from_slice(&data))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Instant;
+	use ethkey::{self, Generator, KeyPair, Random};
+	use super::*;
+
+	/// Signing service keeping a single key in memory.
+	struct LocalSigner {
+		keypair: KeyPair,
+		requests: AtomicUsize,
+	}
+
+	impl LocalSigner {
+		fn handle(&self, method: &str, params: &[Value]) -> Result<Value, String> {
+			let account = match method {
+				"signer_accounts" | "account_list" => None,
+				"account_signTransaction" => Some(&params[0]["from"]),
+				_ => Some(&params[0]),
+			};
+			if account.map_or(false, |account| *account != hex(&self.keypair.address())) {
+				return Err("Unknown account".into());
+			}
+
+			let secret = self.keypair.secret();
+			match method {
+				"signer_accounts" | "account_list" => Ok(Value::Array(vec![hex(&self.keypair.address())])),
+				"account_signTransaction" => {
+					// pretend the transaction hashes to `TX_HASH`, signed with EIP-155 for chain 1
+					let signature = ethkey::sign(secret, &TX_HASH.into()).unwrap();
+					Ok(serde_json::from_str(&format!(
+						r#"{{"raw":"0x","tx":{{"r":"0x{:x}","s":"0x{:x}","v":"0x{:x}"}}}}"#,
+						U256::from(signature.r()), U256::from(signature.s()), 37 + signature.v()
+					)).unwrap())
+				},
+				"signer_signHash" => {
+					let hash = H256::from_slice(&bytes(params[1].clone()).unwrap());
+					Ok(hex(&ethkey::sign(secret, &hash).unwrap().into_electrum()[..]))
+				},
+				"signer_decrypt" => {
+					let mac = bytes(params[1].clone()).unwrap();
+					let message = bytes(params[2].clone()).unwrap();
+					Ok(hex(&ethkey::crypto::ecies::decrypt(secret, &mac, &message).unwrap()))
+				},
+				"signer_agree" => {
+					let public = Public::from_slice(&bytes(params[1].clone()).unwrap());
+					Ok(hex(&*ethkey::crypto::ecdh::agree(secret, &public).unwrap()))
+				},
+				_ => Err("Method not found".into()),
+			}
+		}
+	}
+
+	impl Transport for Arc<LocalSigner> {
+		fn send(&self, request: &str) -> Result<String, Error> {
+			self.requests.fetch_add(1, Ordering::SeqCst);
+			let request: Value = serde_json::from_str(request).unwrap();
+			let method = request["method"].as_str().unwrap();
+			let params = request["params"].as_array().unwrap();
+			let response = match self.handle(method, params) {
+				Ok(result) => format!(r#"{{"jsonrpc":"2.0","id":{},"result":{}}}"#, request["id"], result),
+				Err(message) => format!(r#"{{"jsonrpc":"2.0","id":{},"error":{{"code":-32000,"message":"{}"}}}}"#, request["id"], message),
+			};
+			Ok(response)
+		}
+	}
+
+	const TX_HASH: u64 = 1337;
+
+	fn setup_with_protocol(protocol: Protocol) -> (Arc<LocalSigner>, ExternalSigner) {
+		let local = Arc::new(LocalSigner {
+			keypair: Random.generate().unwrap(),
+			requests: AtomicUsize::new(0),
+		});
+		(local.clone(), ExternalSigner::with_transport(Box::new(local), protocol))
+	}
+
+	fn setup() -> (Arc<LocalSigner>, ExternalSigner) {
+		setup_with_protocol(Protocol::Parity)
+	}
+
+	#[test]
+	fn should_discover_and_cache_accounts() {
+		let (local, signer) = setup();
+		assert!(signer.accounts().is_empty());
+
+		assert_eq!(signer.refresh_accounts(), Ok(vec![local.keypair.address()]));
+		assert_eq!(signer.accounts(), vec![local.keypair.address()]);
+		assert!(signer.is_external(&local.keypair.address()));
+		assert!(!signer.is_external(&Address::default()));
+		assert_eq!(local.requests.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn should_refresh_accounts_in_background() {
+		let (local, signer) = setup();
+		let signer = Arc::new(signer);
+
+		ExternalSigner::refresh_in_background(&signer).unwrap();
+		let started = Instant::now();
+		while signer.accounts().is_empty() && started.elapsed() < Duration::from_secs(5) {
+			thread::sleep(Duration::from_millis(10));
+		}
+
+		assert_eq!(signer.accounts(), vec![local.keypair.address()]);
+		assert_eq!(local.requests.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn should_sign_decrypt_and_agree() {
+		let (local, signer) = setup();
+		let address = local.keypair.address();
+		let hash = H256::from(42);
+
+		let signature = signer.sign(&address, &hash).unwrap();
+		assert!(ethkey::verify_address(&address, &signature, &hash).unwrap());
+
+		let encrypted = ethkey::crypto::ecies::encrypt(local.keypair.public(), &[1, 2], b"hello").unwrap();
+		assert_eq!(signer.decrypt(&address, &[1, 2], &encrypted).unwrap(), b"hello".to_vec());
+
+		let other = Random.generate().unwrap();
+		assert_eq!(
+			signer.agree(&address, other.public()).unwrap(),
+			ethkey::crypto::ecdh::agree(other.secret(), local.keypair.public()).unwrap()
+		);
+	}
+
+	#[test]
+	fn should_sign_transactions_with_clef() {
+		let (local, signer) = setup_with_protocol(Protocol::Clef);
+		let address = local.keypair.address();
+		let transaction = TransactionRequest {
+			nonce: 0.into(),
+			gas_price: 1.into(),
+			gas: 21_000.into(),
+			to: Some(Address::default()),
+			value: 5.into(),
+			data: vec![],
+			chain_id: Some(1),
+		};
+
+		assert_eq!(signer.refresh_accounts(), Ok(vec![address]));
+		let signature = signer.sign_transaction(&address, &transaction, &TX_HASH.into()).unwrap();
+		assert!(ethkey::verify_address(&address, &signature, &TX_HASH.into()).unwrap());
+		// the service signed a different transaction
+		assert_eq!(
+			signer.sign_transaction(&address, &transaction, &H256::from(42)),
+			Err(Error::InvalidResponse("signature does not match the account".into()))
+		);
+		assert_eq!(signer.sign(&address, &H256::from(42)), Err(Error::Unsupported("signing arbitrary hashes")));
+	}
+
+	#[test]
+	fn should_return_remote_errors() {
+		let (_, signer) = setup();
+
+		assert_eq!(signer.sign(&Address::default(), &H256::default()), Err(Error::Remote {
+			code: -32000,
+			message: "Unknown account".into(),
+		}));
+	}
+}
@@ -0,0 +1,150 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Transports used to reach the signing service.
+
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use fetch::{self, Abort, BodyReader, Client, Fetch, Request, Url};
+use futures::Future;
+
+use Error;
+
+/// Requests are aborted if the service doesn't respond in time.
+/// The timeout is generous since the service may ask an operator for approval.
+const REQUEST_TIMEOUT_SECS: u64 = 120;
+/// Maximal size of a response.
+const MAX_RESPONSE_SIZE: usize = 1024 * 1024;
+
+/// Channel to the signing service.
+pub trait Transport: Send + Sync {
+	/// Send a serialized JSON-RPC request and return the serialized response.
+	fn send(&self, request: &str) -> Result<String, Error>;
+}
+
+impl From<io::Error> for Error {
+	fn from(e: io::Error) -> Self {
+		Error::Transport(e.to_string())
+	}
+}
+
+impl From<fetch::Error> for Error {
+	fn from(e: fetch::Error) -> Self {
+		Error::Transport(e.to_string())
+	}
+}
+
+/// Requests sent as HTTP POST.
+pub struct Http {
+	client: Client,
+	url: Url,
+}
+
+impl Http {
+	/// Create a transport posting requests to given URL.
+	pub fn new(url: &str) -> Result<Self, Error> {
+		Ok(Http {
+			client: Client::new()?,
+			url: Url::parse(url).map_err(|e| Error::Transport(format!("Invalid URL {}: {}", url, e)))?,
+		})
+	}
+}
+
+impl Transport for Http {
+	fn send(&self, request: &str) -> Result<String, Error> {
+		let mut req = Request::post(self.url.clone());
+		req.headers_mut().set_raw("Content-Type", "application/json");
+		req.set_body(request.to_owned());
+
+		let abort = Abort::default()
+			.with_max_duration(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+			.with_max_size(MAX_RESPONSE_SIZE);
+		let response = self.client.fetch(req, abort).wait()?;
+		if !response.is_success() {
+			return Err(Error::Transport(format!("Unexpected HTTP status: {}", response.status())));
+		}
+
+		let mut body = String::new();
+		BodyReader::new(response).read_to_string(&mut body)?;
+		Ok(body)
+	}
+}
+
+/// Requests sent as single lines over a Unix domain socket.
+pub struct Ipc {
+	path: PathBuf,
+}
+
+impl Ipc {
+	/// Create a transport connecting to the socket at given path.
+	pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+		Ipc {
+			path: path.into(),
+		}
+	}
+}
+
+impl Transport for Ipc {
+	#[cfg(unix)]
+	fn send(&self, request: &str) -> Result<String, Error> {
+		use std::io::{BufRead, BufReader, Write};
+		use std::os::unix::net::UnixStream;
+
+		let mut stream = UnixStream::connect(&self.path)?;
+		stream.set_read_timeout(Some(Duration::from_secs(REQUEST_TIMEOUT_SECS)))?;
+		stream.write_all(request.as_bytes())?;
+		stream.write_all(b"\n")?;
+
+		let mut response = String::new();
+		BufReader::new(stream.take(MAX_RESPONSE_SIZE as u64)).read_line(&mut response)?;
+		Ok(response)
+	}
+
+	#[cfg(not(unix))]
+	fn send(&self, _request: &str) -> Result<String, Error> {
+		Err(Error::Transport(format!("Unix domain sockets are not supported on this platform: {}", self.path.display())))
+	}
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+	use std::io::{BufRead, BufReader, Write};
+	use std::os::unix::net::UnixListener;
+	use std::thread;
+	use super::*;
+
+	#[test]
+	fn should_exchange_lines_over_socket() {
+		let dir = ::std::env::temp_dir().join(format!("external-signer-{}", ::std::process::id()));
+		let _ = ::std::fs::remove_file(&dir);
+		let listener = UnixListener::bind(&dir).unwrap();
+		let server = thread::spawn(move || {
+			let (stream, _) = listener.accept().unwrap();
+			let mut line = String::new();
+			BufReader::new(&stream).read_line(&mut line).unwrap();
+			assert_eq!(line, "{\"id\":1}\n");
+			(&stream).write_all(b"{\"result\":[]}\n").unwrap();
+		});
+
+		let response = Ipc::new(dir.clone()).send("{\"id\":1}").unwrap();
+		server.join().unwrap();
+		let _ = ::std::fs::remove_file(&dir);
+
+		assert_eq!(response, "{\"result\":[]}\n");
+	}
+}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Ledger hardware wallet module. Supports Ledger Blue and Nano S.
+//! Ledger hardware wallet module. Supports Ledger Blue, Nano S, Nano X and Nano S Plus.
 /// See https://github.com/LedgerHQ/blue-app-eth/blob/master/doc/ethapp.asc for protocol details.
 
 use std::cmp::min;
@@ -36,8 +36,11 @@ use super::{WalletInfo, KeyPath, Device, Wallet, USB_DEVICE_CLASS_DEVICE};
 
 /// Ledger vendor ID
 const LEDGER_VID: u16 = 0x2c97;
-/// Ledger product IDs: [Nano S and Blue]
-const LEDGER_PIDS: [u16; 2] = [0x0000, 0x0001];
+/// Ledger product IDs used by older firmware: [Blue, Nano S, Nano X, Nano S Plus]
+const LEDGER_PIDS: [u16; 4] = [0x0000, 0x0001, 0x0004, 0x0005];
+/// Upper byte of Ledger product IDs used by newer firmware: [Nano S, Nano X, Nano S Plus]
+/// The lower byte depends on the USB interfaces enabled on the device.
+const LEDGER_PID_PREFIXES: [u16; 3] = [0x1000, 0x4000, 0x5000];
 
 const ETH_DERIVATION_PATH_BE: [u8; 17] = [4, 0x80, 0, 0, 44, 0x80, 0, 0, 60, 0x80, 0, 0, 0, 0, 0, 0, 0]; // 44'/60'/0'/0
 const ETC_DERIVATION_PATH_BE: [u8; 21] = [5, 0x80, 0, 0, 44, 0x80, 0, 0, 60, 0x80, 0x02, 0x73, 0xd0, 0x80, 0, 0, 0, 0, 0, 0, 0]; // 44'/60'/160720'/0'/0
@@ -52,6 +55,20 @@ mod commands {
 	pub const GET_APP_CONFIGURATION: u8 = 0x06;
 	pub const GET_ETH_PUBLIC_ADDRESS: u8 = 0x02;
 	pub const SIGN_ETH_TRANSACTION: u8 = 0x04;
+	pub const SIGN_ETH_PERSONAL_MESSAGE: u8 = 0x08;
+	pub const SIGN_ETH_EIP_712: u8 = 0x0c;
+}
+
+/// Minimal app versions supporting given commands.
+mod versions {
+	pub const GET_ETH_PUBLIC_ADDRESS: (u8, u8, u8) = (1, 0, 3);
+	pub const SIGN_ETH_PERSONAL_MESSAGE: (u8, u8, u8) = (1, 0, 8);
+	pub const SIGN_ETH_EIP_712: (u8, u8, u8) = (1, 5, 0);
+}
+
+/// Returns `true` if given product ID belongs to a supported Ledger device.
+fn is_supported_product(product_id: u16) -> bool {
+	LEDGER_PIDS.contains(&product_id) || LEDGER_PID_PREFIXES.contains(&(product_id & 0xff00))
 }
 
 /// Hardware wallet error.
@@ -233,12 +250,61 @@ impl Manager {
 		Ok(message)
 	}
 
+	/// Read the version of the Ethereum app and make sure it's at least `required`.
+	fn check_app_version(handle: &hidapi::HidDevice, required: (u8, u8, u8), error: &'static str) -> Result<(), Error> {
+		let ver = Self::send_apdu(handle, commands::GET_APP_CONFIGURATION, 0, 0, &[])?;
+		if ver.len() != 4 {
+			return Err(Error::Protocol("Version packet size mismatch"));
+		}
+
+		match (ver[1], ver[2], ver[3]) < required {
+			true => Err(Error::Protocol(error)),
+			false => Ok(()),
+		}
+	}
+
+	/// Send `data` prefixed with `header` split over as many APDUs as needed and return the last response.
+	fn send_chunked_apdu(handle: &hidapi::HidDevice, command: u8, header: &[u8], data: &[u8]) -> Result<Vec<u8>, Error> {
+		const MAX_CHUNK_SIZE: usize = 255;
+		let mut chunk: [u8; MAX_CHUNK_SIZE] = [0; MAX_CHUNK_SIZE];
+		&mut chunk[0..header.len()].copy_from_slice(header);
+		let mut dest_offset = header.len();
+		let mut data_pos = 0;
+		loop {
+			let p1 = if data_pos == 0 { 0x00 } else { 0x80 };
+			let dest_left = MAX_CHUNK_SIZE - dest_offset;
+			let chunk_data_size = min(dest_left, data.len() - data_pos);
+			&mut chunk[dest_offset..][0..chunk_data_size].copy_from_slice(&data[data_pos..][0..chunk_data_size]);
+			let result = Self::send_apdu(handle, command, p1, 0, &chunk[0..(dest_offset + chunk_data_size)])?;
+			dest_offset = 0;
+			data_pos += chunk_data_size;
+			if data_pos == data.len() {
+				return Ok(result);
+			}
+		}
+	}
+
+	/// Run `f` with an open handle to the device managing `address` and the current derivation path.
+	fn with_device<T, F>(&self, address: &Address, f: F) -> Result<T, Error> where
+		F: FnOnce(&hidapi::HidDevice, &[u8]) -> Result<T, Error>,
+	{
+		let usb = self.usb.lock();
+		let devices = self.devices.read();
+		let device = devices.iter().find(|d| &d.info.address == address).ok_or(Error::KeyNotFound)?;
+		let handle = self.open_path(|| usb.open_path(&device.path))?;
+
+		match *self.key_path.read() {
+			KeyPath::Ethereum => f(&handle, &ETH_DERIVATION_PATH_BE[..]),
+			KeyPath::EthereumClassic => f(&handle, &ETC_DERIVATION_PATH_BE[..]),
+		}
+	}
+
 	fn is_valid_ledger(device: &libusb::Device) -> Result<(), Error> {
 		let desc = device.device_descriptor()?;
 		let vendor_id = desc.vendor_id();
 		let product_id = desc.product_id();
 
-		if vendor_id == LEDGER_VID && LEDGER_PIDS.contains(&product_id) {
+		if vendor_id == LEDGER_VID && is_supported_product(product_id) {
 			Ok(())
 		} else {
 			Err(Error::InvalidDevice)
@@ -246,6 +312,19 @@ impl Manager {
 	}
 }
 
+/// Decode `v || r || s` signature returned by the device, normalizing `v` with `recovery_id`.
+fn signature_from_response<F>(result: &[u8], recovery_id: F) -> Result<Signature, Error> where
+	F: FnOnce(u8) -> u8,
+{
+	if result.len() != 65 {
+		return Err(Error::Protocol("Signature packet size mismatch"));
+	}
+	let v = recovery_id(result[0]);
+	let r = H256::from_slice(&result[1..33]);
+	let s = H256::from_slice(&result[33..65]);
+	Ok(Signature::from_rsv(&r, &s, v))
+}
+
 // Try to connect to the device using polling in at most the time specified by the `timeout`
 fn try_connect_polling(ledger: Arc<Manager>, timeout: Duration) -> bool {
 	let start_time = Instant::now();
@@ -262,43 +341,34 @@ impl <'a>Wallet<'a> for Manager {
 	type Transaction = &'a [u8];
 
 	fn sign_transaction(&self, address: &Address, transaction: Self::Transaction) -> Result<Signature, Self::Error> {
-		let usb = self.usb.lock();
-		let devices = self.devices.read();
-		let device = devices.iter().find(|d| &d.info.address == address).ok_or(Error::KeyNotFound)?;
-		let handle = self.open_path(|| usb.open_path(&device.path))?;
+		self.with_device(address, |handle, derivation_path| {
+			let result = Self::send_chunked_apdu(handle, commands::SIGN_ETH_TRANSACTION, derivation_path, transaction)?;
+			signature_from_response(&result, |v| (v + 1) % 2)
+		})
+	}
 
-		let eth_path = &ETH_DERIVATION_PATH_BE[..];
-		let etc_path = &ETC_DERIVATION_PATH_BE[..];
-		let derivation_path = match *self.key_path.read() {
-			KeyPath::Ethereum => eth_path,
-			KeyPath::EthereumClassic => etc_path,
-		};
-		const MAX_CHUNK_SIZE: usize = 255;
-		let mut chunk: [u8; MAX_CHUNK_SIZE] = [0; MAX_CHUNK_SIZE];
-		&mut chunk[0..derivation_path.len()].copy_from_slice(derivation_path);
-		let mut dest_offset = derivation_path.len();
-		let mut data_pos = 0;
-		let mut result;
-		loop {
-			let p1 = if data_pos == 0 { 0x00 } else { 0x80 };
-			let dest_left = MAX_CHUNK_SIZE - dest_offset;
-			let chunk_data_size = min(dest_left, transaction.len() - data_pos);
-			&mut chunk[dest_offset..][0..chunk_data_size].copy_from_slice(&transaction[data_pos..][0..chunk_data_size]);
-			result = Self::send_apdu(&handle, commands::SIGN_ETH_TRANSACTION, p1, 0, &chunk[0..(dest_offset + chunk_data_size)])?;
-			dest_offset = 0;
-			data_pos += chunk_data_size;
-			if data_pos == transaction.len() {
-				break;
-			}
-		}
+	fn sign_message(&self, address: &Address, message: &[u8]) -> Result<Signature, Self::Error> {
+		self.with_device(address, |handle, derivation_path| {
+			Self::check_app_version(handle, versions::SIGN_ETH_PERSONAL_MESSAGE, "App version 1.0.8 is required to sign messages.")?;
 
-		if result.len() != 65 {
-			return Err(Error::Protocol("Signature packet size mismatch"));
-		}
-		let v = (result[0] + 1) % 2;
-		let r = H256::from_slice(&result[1..33]);
-		let s = H256::from_slice(&result[33..65]);
-		Ok(Signature::from_rsv(&r, &s, v))
+			let mut header = derivation_path.to_vec();
+			let len = message.len() as u32;
+			header.extend_from_slice(&[(len >> 24) as u8, (len >> 16) as u8, (len >> 8) as u8, len as u8]);
+			let result = Self::send_chunked_apdu(handle, commands::SIGN_ETH_PERSONAL_MESSAGE, &header, message)?;
+			signature_from_response(&result, |v| if v >= 27 { v - 27 } else { v })
+		})
+	}
+
+	fn sign_typed_data(&self, address: &Address, domain_hash: &H256, message_hash: &H256) -> Result<Signature, Self::Error> {
+		self.with_device(address, |handle, derivation_path| {
+			Self::check_app_version(handle, versions::SIGN_ETH_EIP_712, "App version 1.5.0 is required to sign typed data.")?;
+
+			let mut data = derivation_path.to_vec();
+			data.extend_from_slice(&**domain_hash);
+			data.extend_from_slice(&**message_hash);
+			let result = Self::send_apdu(handle, commands::SIGN_ETH_EIP_712, 0, 0, &data)?;
+			signature_from_response(&result, |v| if v >= 27 { v - 27 } else { v })
+		})
 	}
 
 	fn set_key_path(&self, key_path: KeyPath) {
@@ -313,7 +383,7 @@ impl <'a>Wallet<'a> for Manager {
 		let mut num_new_devices = 0;
 		for device in devices {
 			trace!("Checking device: {:?}", device);
-			if device.vendor_id != LEDGER_VID || !LEDGER_PIDS.contains(&device.product_id) {
+			if device.vendor_id != LEDGER_VID || !is_supported_product(device.product_id) {
 				continue;
 			}
 			match self.read_device(&usb, &device) {
@@ -369,15 +439,7 @@ impl <'a>Wallet<'a> for Manager {
 	}
 
 	fn get_address(&self, device: &hidapi::HidDevice) -> Result<Option<Address>, Self::Error> {
-		let ver = Self::send_apdu(device, commands::GET_APP_CONFIGURATION, 0, 0, &[])?;
-		if ver.len() != 4 {
-			return Err(Error::Protocol("Version packet size mismatch"));
-		}
-
-		let (major, minor, patch) = (ver[1], ver[2], ver[3]);
-		if major < 1 || (major == 1 && minor == 0 && patch < 3) {
-			return Err(Error::Protocol("App version 1.0.3 is required."));
-		}
+		Self::check_app_version(device, versions::GET_ETH_PUBLIC_ADDRESS, "App version 1.0.3 is required.")?;
 
 		let eth_path = &ETH_DERIVATION_PATH_BE[..];
 		let etc_path = &ETC_DERIVATION_PATH_BE[..];
@@ -441,6 +503,21 @@ impl libusb::Hotplug for EventHandler {
 	}
 }
 
+#[test]
+fn should_recognize_ledger_products() {
+	// Nano S with old and new firmware
+	assert!(is_supported_product(0x0001));
+	assert!(is_supported_product(0x1011));
+	// Nano X and Nano S Plus
+	assert!(is_supported_product(0x0004));
+	assert!(is_supported_product(0x4015));
+	assert!(is_supported_product(0x0005));
+	assert!(is_supported_product(0x5011));
+	// Unknown
+	assert!(!is_supported_product(0x0002));
+	assert!(!is_supported_product(0x2011));
+}
+
 #[test]
 #[ignore]
 /// This test can't be run without an actual ledger device connected
@@ -39,7 +39,7 @@ use std::fmt;
 use std::sync::Arc;
 use std::sync::atomic;
 use std::sync::atomic::AtomicBool;
-use ethereum_types::U256;
+use ethereum_types::{H256, U256};
 
 const USB_DEVICE_CLASS_DEVICE: u8 = 0;
 
@@ -58,6 +58,12 @@ pub trait Wallet<'a> {
 	/// Sign transaction data with wallet managing `address`.
 	fn sign_transaction(&self, address: &Address, transaction: Self::Transaction) -> Result<Signature, Self::Error>;
 
+	/// Sign a message prefixed with `\x19Ethereum Signed Message:\n` and its length with wallet managing `address`.
+	fn sign_message(&self, address: &Address, message: &[u8]) -> Result<Signature, Self::Error>;
+
+	/// Sign EIP-712 typed data, given hashes of the domain separator and of the message, with wallet managing `address`.
+	fn sign_typed_data(&self, address: &Address, domain_hash: &H256, message_hash: &H256) -> Result<Signature, Self::Error>;
+
 	/// Set key derivation path for a chain.
 	fn set_key_path(&self, key_path: KeyPath);
 
@@ -250,6 +256,28 @@ impl HardwareWalletManager {
 		}
 	}
 
+	/// Sign a message prefixed with `\x19Ethereum Signed Message:\n` and its length with wallet managing `address`.
+	pub fn sign_message(&self, address: &Address, message: &[u8]) -> Result<Signature, Error> {
+		if self.ledger.get_wallet(address).is_some() {
+			Ok(self.ledger.sign_message(address, message)?)
+		} else if self.trezor.get_wallet(address).is_some() {
+			Ok(self.trezor.sign_message(address, message)?)
+		} else {
+			Err(Error::KeyNotFound)
+		}
+	}
+
+	/// Sign EIP-712 typed data, given hashes of the domain separator and of the message, with wallet managing `address`.
+	pub fn sign_typed_data(&self, address: &Address, domain_hash: &H256, message_hash: &H256) -> Result<Signature, Error> {
+		if self.ledger.get_wallet(address).is_some() {
+			Ok(self.ledger.sign_typed_data(address, domain_hash, message_hash)?)
+		} else if self.trezor.get_wallet(address).is_some() {
+			Ok(self.trezor.sign_typed_data(address, domain_hash, message_hash)?)
+		} else {
+			Err(Error::KeyNotFound)
+		}
+	}
+
 	/// Send a pin to a device at a certain path to unlock it
 	/// This is only applicable to Trezor because Ledger only appears as
 	/// a device when it is unlocked
@@ -37,7 +37,10 @@ use parking_lot::{Mutex, RwLock};
 use protobuf;
 use protobuf::{Message, ProtobufEnum};
 
-use trezor_sys::messages::{EthereumAddress, PinMatrixAck, MessageType, EthereumTxRequest, EthereumSignTx, EthereumGetAddress, EthereumTxAck, ButtonAck};
+use trezor_sys::messages::{
+	EthereumAddress, PinMatrixAck, MessageType, EthereumTxRequest, EthereumSignTx, EthereumGetAddress, EthereumTxAck, ButtonAck,
+	EthereumSignMessage, EthereumMessageSignature,
+};
 
 /// Trezor v1 vendor ID
 const TREZOR_VID: u16 = 0x534c;
@@ -213,6 +216,32 @@ impl Manager {
 		}
 	}
 
+	fn message_signing_loop(&self, handle: &hidapi::HidDevice) -> Result<Signature, Error> {
+		let (resp_type, bytes) = self.read_device_response(&handle)?;
+		match resp_type {
+			MessageType::MessageType_Cancel => Err(Error::UserCancel),
+			MessageType::MessageType_ButtonRequest => {
+				self.send_device_message(handle, &MessageType::MessageType_ButtonAck, &ButtonAck::new())?;
+				self.message_signing_loop(handle)
+			}
+			MessageType::MessageType_EthereumMessageSignature => {
+				let resp: EthereumMessageSignature = protobuf::core::parse_from_bytes(&bytes)?;
+				let signature = resp.get_signature();
+				if signature.len() != 65 {
+					return Err(Error::Protocol("Signature packet size mismatch"));
+				}
+				let r = H256::from_slice(&signature[0..32]);
+				let s = H256::from_slice(&signature[32..64]);
+				// v is returned as v + 27
+				let v = signature[64];
+				let adjusted_v = if v < 27 { v } else { v - 27 };
+				Ok(Signature::from_rsv(&r, &s, adjusted_v))
+			}
+			MessageType::MessageType_Failure => Err(Error::Protocol("Last message sent to Trezor failed")),
+			_ => Err(Error::Protocol("Unexpected response from Trezor device.")),
+		}
+	}
+
 	fn send_device_message(&self, device: &hidapi::HidDevice, msg_type: &MessageType, msg: &Message) -> Result<usize, Error> {
 		let msg_id = *msg_type as u16;
 		let mut message = msg.write_to_bytes()?;
@@ -321,6 +350,29 @@ impl <'a>Wallet<'a> for Manager {
 		self.signing_loop(&handle, &t_info.chain_id, &t_info.data[first_chunk_length..])
 	}
 
+	fn sign_message(&self, address: &Address, message: &[u8]) -> Result<Signature, Error> {
+		let usb = self.usb.lock();
+		let devices = self.devices.read();
+		let device = devices.iter().find(|d| &d.info.address == address).ok_or(Error::KeyNotFound)?;
+		let handle = self.open_path(|| usb.open_path(&device.path))?;
+		let msg_type = MessageType::MessageType_EthereumSignMessage;
+		let mut msg = EthereumSignMessage::new();
+		match *self.key_path.read() {
+			KeyPath::Ethereum => msg.set_address_n(ETH_DERIVATION_PATH.to_vec()),
+			KeyPath::EthereumClassic => msg.set_address_n(ETC_DERIVATION_PATH.to_vec()),
+		}
+		msg.set_message(message.to_vec());
+
+		self.send_device_message(&handle, &msg_type, &msg)?;
+
+		self.message_signing_loop(&handle)
+	}
+
+	// Not supported by the Trezor firmware
+	fn sign_typed_data(&self, _address: &Address, _domain_hash: &H256, _message_hash: &H256) -> Result<Signature, Error> {
+		Err(Error::Protocol("Signing typed data is not supported by Trezor"))
+	}
+
 	fn set_key_path(&self, key_path: KeyPath) {
 		*self.key_path.write() = key_path;
 	}
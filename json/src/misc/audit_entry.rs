@@ -0,0 +1,66 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Account audit log de/serialization.
+
+use hash;
+use serde_json;
+
+/// Entry of the audit log of account operations, stored as a single line.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+	/// Unix timestamp of the operation.
+	pub timestamp: u64,
+	/// Name of the operation.
+	pub operation: String,
+	/// Account the operation was performed with.
+	pub account: Option<hash::Address>,
+	/// Vault the operation was performed with.
+	pub vault: Option<String>,
+	/// Origin of the request, if the operation was requested over RPC.
+	pub origin: Option<String>,
+	/// Hash of the signed or decrypted message.
+	pub hash: Option<hash::H256>,
+	/// Whether the operation succeeded.
+	pub success: bool,
+}
+
+impl AuditEntry {
+	/// Parses an entry from a line of the log.
+	pub fn from_line(line: &str) -> Result<Self, serde_json::Error> {
+		serde_json::from_str(line)
+	}
+
+	/// Serializes the entry into a line of the log.
+	pub fn to_line(&self) -> String {
+		serde_json::to_string(self).expect("Serialization of a struct with string keys never fails; qed")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::AuditEntry;
+
+	#[test]
+	fn audit_entry_roundtrip() {
+		let line = r#"{"timestamp":1530000000,"operation":"sign","account":"0x0000000000000000000000000000000000000005","vault":null,"origin":"IPC (session: 0x0000000000000000000000000000000000000000000000000000000000000001)","hash":"0x0000000000000000000000000000000000000000000000000000000000000007","success":true}"#;
+
+		let entry = AuditEntry::from_line(line).unwrap();
+		assert_eq!(entry.operation, "sign");
+		assert!(entry.success);
+		assert_eq!(entry.to_line(), line);
+	}
+}
@@ -48,7 +48,13 @@ macro_rules! impl_serialization {
 }
 
 mod account_meta;
+mod audit_entry;
 mod dapps_settings;
+mod policy_account;
+mod spending_limit;
 
 pub use self::dapps_settings::{DappsSettings, DappsHistory, NewDappsPolicy};
 pub use self::account_meta::AccountMeta;
+pub use self::audit_entry::AuditEntry;
+pub use self::policy_account::PolicyAccount;
+pub use self::spending_limit::{SpendingLimit, DailySpending};
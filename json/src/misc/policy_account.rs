@@ -0,0 +1,32 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Policy accounts de/serialization.
+
+use hash;
+
+/// Account controlled by a threshold of local owner accounts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PolicyAccount {
+	/// The name of the account.
+	pub name: String,
+	/// Local accounts owning the account.
+	pub owners: Vec<hash::Address>,
+	/// Number of owners required to approve a transaction.
+	pub threshold: usize,
+}
+
+impl_serialization!(hash::Address => PolicyAccount);
@@ -0,0 +1,47 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Spending limits de/serialization.
+
+use hash;
+use uint::Uint;
+
+/// Restrictions of transactions sent from an unlocked account.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendingLimit {
+	/// Maximal value of a single transaction.
+	pub max_value: Option<Uint>,
+	/// Maximal value of all transactions within a day.
+	pub daily_limit: Option<Uint>,
+	/// Addresses the account is allowed to send transactions to.
+	pub allowed_recipients: Option<Vec<hash::Address>>,
+	/// Hex-encoded 4-byte selectors of the contract methods the account is allowed to call.
+	pub allowed_methods: Option<Vec<String>>,
+}
+
+impl_serialization!(hash::Address => SpendingLimit);
+
+/// Value spent by a limited account on a day.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DailySpending {
+	/// Number of the day since the Unix epoch.
+	pub day: u64,
+	/// Value spent on the day.
+	pub spent: Uint,
+}
+
+impl_serialization!(hash::Address => DailySpending);
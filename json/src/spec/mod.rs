@@ -31,6 +31,7 @@ pub mod authority_round;
 pub mod tendermint;
 pub mod null_engine;
 pub mod hardcoded_sync;
+pub mod wasm;
 
 pub use self::account::Account;
 pub use self::builtin::{Builtin, Pricing, Linear};
@@ -47,3 +48,4 @@ pub use self::authority_round::{AuthorityRound, AuthorityRoundParams};
 pub use self::tendermint::{Tendermint, TendermintParams};
 pub use self::null_engine::{NullEngine, NullEngineParams};
 pub use self::hardcoded_sync::HardcodedSync;
+pub use self::wasm::{WasmSchedule, WasmHostFunction};
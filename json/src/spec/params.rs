@@ -16,9 +16,11 @@
 
 //! Spec params deserialization.
 
+use std::collections::BTreeMap;
 use uint::{self, Uint};
 use hash::{H256, Address};
 use bytes::Bytes;
+use spec::WasmSchedule;
 
 /// Spec params.
 #[derive(Debug, PartialEq, Deserialize)]
@@ -143,6 +145,9 @@ pub struct Params {
 	/// Wasm activation block height, if not activated from start
 	#[serde(rename="wasmActivationTransition")]
 	pub wasm_activation_transition: Option<Uint>,
+	/// Wasm schedule changes keyed by the block they activate at.
+	#[serde(rename="wasmScheduleTransitions")]
+	pub wasm_schedule_transitions: Option<BTreeMap<Uint, WasmSchedule>>,
 }
 
 #[cfg(test)]
@@ -163,7 +168,11 @@ mod tests {
 			"accountStartNonce": "0x01",
 			"gasLimitBoundDivisor": "0x20",
 			"maxCodeSize": "0x1000",
-			"wasmActivationTransition": "0x1010"
+			"wasmActivationTransition": "0x1010",
+			"wasmScheduleTransitions": {
+				"0x1010": { "hostFunctions": ["keccak256"] },
+				"0x2020": { "keccak256": "0x40" }
+			}
 		}"#;
 
 		let deserialized: Params = serde_json::from_str(s).unwrap();
@@ -176,6 +185,9 @@ mod tests {
 		assert_eq!(deserialized.gas_limit_bound_divisor, Uint(U256::from(0x20)));
 		assert_eq!(deserialized.max_code_size, Some(Uint(U256::from(0x1000))));
 		assert_eq!(deserialized.wasm_activation_transition, Some(Uint(U256::from(0x1010))));
+		let wasm_schedule_transitions = deserialized.wasm_schedule_transitions.unwrap();
+		assert_eq!(wasm_schedule_transitions.len(), 2);
+		assert_eq!(wasm_schedule_transitions[&Uint(U256::from(0x2020))].keccak256, Some(Uint(U256::from(0x40))));
 	}
 
 	#[test]
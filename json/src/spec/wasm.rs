@@ -0,0 +1,134 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Wasm schedule deserialization.
+
+use uint::{self, Uint};
+
+/// Optional wasm host functions.
+#[derive(Debug, PartialEq, Clone, Copy, Deserialize)]
+pub enum WasmHostFunction {
+	/// `keccak256(input_ptr, input_len, result_ptr)`
+	#[serde(rename="keccak256")]
+	Keccak256,
+	/// `ecrecover(hash_ptr, v, r_ptr, s_ptr, result_ptr) -> i32`
+	#[serde(rename="ecrecover")]
+	Ecrecover,
+}
+
+/// Changes to the wasm schedule. Missing values are inherited from the previous transition.
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+pub struct WasmSchedule {
+	/// See `WasmCosts` docs.
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub regular: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub div: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub mul: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub mem: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(rename="staticU256")]
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub static_u256: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(rename="staticAddress")]
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub static_address: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(rename="initialMem")]
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub initial_mem: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(rename="growMem")]
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub grow_mem: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub memcpy: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(rename="maxStackHeight")]
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub max_stack_height: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(rename="opcodesMul")]
+	#[serde(default, deserialize_with="uint::validate_optional_non_zero_u32")]
+	pub opcodes_mul: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(rename="opcodesDiv")]
+	#[serde(default, deserialize_with="uint::validate_optional_non_zero_u32")]
+	pub opcodes_div: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub keccak256: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(rename="keccak256Word")]
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub keccak256_word: Option<Uint>,
+	/// See `WasmCosts` docs.
+	#[serde(default, deserialize_with="uint::validate_optional_u32")]
+	pub ecrecover: Option<Uint>,
+	/// Host functions enabled from this transition on.
+	#[serde(rename="hostFunctions")]
+	pub host_functions: Option<Vec<WasmHostFunction>>,
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use uint::Uint;
+	use ethereum_types::U256;
+	use spec::wasm::{WasmSchedule, WasmHostFunction};
+
+	#[test]
+	fn wasm_schedule_deserialization() {
+		let s = r#"{
+			"opcodesDiv": "0x10",
+			"keccak256Word": 20,
+			"hostFunctions": ["keccak256", "ecrecover"]
+		}"#;
+
+		let deserialized: WasmSchedule = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.opcodes_div, Some(Uint(U256::from(0x10))));
+		assert_eq!(deserialized.keccak256_word, Some(Uint(U256::from(20))));
+		assert_eq!(deserialized.regular, None);
+		assert_eq!(deserialized.host_functions, Some(vec![WasmHostFunction::Keccak256, WasmHostFunction::Ecrecover]));
+	}
+
+	#[test]
+	#[should_panic]
+	fn wasm_schedule_cost_out_of_range() {
+		let s = r#"{
+			"keccak256": "0x100000000"
+		}"#;
+
+		let _deserialized: WasmSchedule = serde_json::from_str(s).unwrap();
+	}
+
+	#[test]
+	#[should_panic]
+	fn wasm_schedule_unknown_host_function() {
+		let s = r#"{
+			"hostFunctions": ["sha256"]
+		}"#;
+
+		let _deserialized: WasmSchedule = serde_json::from_str(s).unwrap();
+	}
+}
@@ -119,6 +119,30 @@ pub fn validate_optional_non_zero<'de, D>(d: D) -> Result<Option<Uint>, D::Error
 	Ok(value)
 }
 
+pub fn validate_optional_u32<'de, D>(d: D) -> Result<Option<Uint>, D::Error> where D: Deserializer<'de> {
+	let value: Option<Uint> = Option::deserialize(d)?;
+
+	if let Some(value) = value {
+		if value.0 > U256::from(u32::max_value()) {
+			return Err(Error::invalid_value(Unexpected::Str(&value.0.to_string()), &"a value fitting in 32 bits"))
+		}
+	}
+
+	Ok(value)
+}
+
+pub fn validate_optional_non_zero_u32<'de, D>(d: D) -> Result<Option<Uint>, D::Error> where D: Deserializer<'de> {
+	let value = validate_optional_u32(d)?;
+
+	if let Some(value) = value {
+		if value == Uint(U256::from(0)) {
+			return Err(Error::invalid_value(Unexpected::Unsigned(value.into()), &"a non-zero value"))
+		}
+	}
+
+	Ok(value)
+}
+
 #[cfg(test)]
 mod test {
 	use serde_json;
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Manages local node data: pending local transactions, sync security level
+//! Manages local node data: pending local transactions, queued transactions, sync security level
 
 use std::sync::Arc;
 use std::fmt;
@@ -49,6 +49,7 @@ extern crate ethkey;
 extern crate kvdb_memorydb;
 
 const LOCAL_TRANSACTIONS_KEY: &'static [u8] = &*b"LOCAL_TXS";
+const QUEUED_TRANSACTIONS_KEY: &'static [u8] = &*b"QUEUED_TXS";
 
 const UPDATE_TIMER: ::io::TimerToken = 0;
 const UPDATE_TIMEOUT: Duration = Duration::from_secs(15 * 60); // once every 15 minutes.
@@ -131,10 +132,55 @@ impl From<PendingTransaction> for TransactionEntry {
 	}
 }
 
+/// Priority of a transaction in the queue.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum Priority {
+	/// Transaction of local origin.
+	Local,
+	/// Transaction from a retracted block.
+	Retracted,
+	/// Transaction received from the network.
+	Regular,
+}
+
+/// Transaction from the queue together with its priority.
+#[derive(Debug, PartialEq, Clone)]
+pub struct QueuedTransaction {
+	/// The transaction.
+	pub transaction: PendingTransaction,
+	/// Priority of the transaction.
+	pub priority: Priority,
+}
+
+#[derive(Serialize, Deserialize)]
+struct QueuedTransactionEntry {
+	entry: TransactionEntry,
+	priority: Priority,
+}
+
+impl QueuedTransactionEntry {
+	fn into_queued(self) -> Option<QueuedTransaction> {
+		let priority = self.priority;
+		self.entry.into_pending().map(|transaction| QueuedTransaction { transaction, priority })
+	}
+}
+
+impl From<QueuedTransaction> for QueuedTransactionEntry {
+	fn from(queued: QueuedTransaction) -> Self {
+		QueuedTransactionEntry {
+			entry: queued.transaction.into(),
+			priority: queued.priority,
+		}
+	}
+}
+
 /// Something which can provide information about the local node.
 pub trait NodeInfo: Send + Sync {
 	/// Get all pending transactions of local origin.
 	fn pending_transactions(&self) -> Vec<PendingTransaction>;
+
+	/// Get all transactions from the queue which should be persisted, in insertion order.
+	fn queued_transactions(&self) -> Vec<QueuedTransaction> { Vec::new() }
 }
 
 /// Create a new local data store, given a database, a column to write to, and a node.
@@ -173,6 +219,21 @@ impl<T: NodeInfo> LocalDataStore<T> {
 		}
 	}
 
+	/// Attempt to read queued transactions out of the local store, in insertion order.
+	pub fn queued_transactions(&self) -> Result<Vec<QueuedTransaction>, Error> {
+		if let Some(val) = self.db.get(self.col, QUEUED_TRANSACTIONS_KEY).map_err(Error::Database)? {
+			let queued_txs: Vec<_> = ::serde_json::from_slice::<Vec<QueuedTransactionEntry>>(&val)
+				.map_err(Error::Json)?
+				.into_iter()
+				.filter_map(QueuedTransactionEntry::into_queued)
+				.collect();
+
+			Ok(queued_txs)
+		} else {
+			Ok(Vec::new())
+		}
+	}
+
 	/// Update the entries in the database.
 	pub fn update(&self) -> Result<(), Error> {
 		trace!(target: "local_store", "Updating local store entries.");
@@ -182,24 +243,33 @@ impl<T: NodeInfo> LocalDataStore<T> {
 			.map(Into::into)
 			.collect();
 
-		self.write_txs(&local_entries)
+		let queued_entries: Vec<QueuedTransactionEntry> = self.node.queued_transactions()
+			.into_iter()
+			.map(Into::into)
+			.collect();
+
+		self.write_txs(&local_entries, &queued_entries)
 	}
 
 	/// Clear data in this column.
 	pub fn clear(&self) -> Result<(), Error> {
 		trace!(target: "local_store", "Clearing local store entries.");
 
-		self.write_txs(&[])
+		self.write_txs(&[], &[])
 	}
 
-	// helper for writing a vector of transaction entries to disk.
-	fn write_txs(&self, txs: &[TransactionEntry]) -> Result<(), Error> {
+	// helper for writing vectors of transaction entries to disk.
+	fn write_txs(&self, txs: &[TransactionEntry], queued_txs: &[QueuedTransactionEntry]) -> Result<(), Error> {
 		let mut batch = self.db.transaction();
 
 		let local_json = ::serde_json::to_value(txs).map_err(Error::Json)?;
 		let json_str = format!("{}", local_json);
-
 		batch.put_vec(self.col, LOCAL_TRANSACTIONS_KEY, json_str.into_bytes());
+
+		let queued_json = ::serde_json::to_value(queued_txs).map_err(Error::Json)?;
+		let json_str = format!("{}", queued_json);
+		batch.put_vec(self.col, QUEUED_TRANSACTIONS_KEY, json_str.into_bytes());
+
 		self.db.write(batch).map_err(Error::Database)
 	}
 }
@@ -230,7 +300,7 @@ impl<T: NodeInfo> Drop for LocalDataStore<T> {
 
 #[cfg(test)]
 mod tests {
-	use super::NodeInfo;
+	use super::{NodeInfo, QueuedTransaction, Priority};
 
 	use std::sync::Arc;
 	use transaction::{Transaction, Condition, PendingTransaction};
@@ -244,6 +314,12 @@ mod tests {
 		fn pending_transactions(&self) -> Vec<PendingTransaction> { self.0.clone() }
 	}
 
+	struct DummyQueue(Vec<QueuedTransaction>);
+	impl NodeInfo for DummyQueue {
+		fn pending_transactions(&self) -> Vec<PendingTransaction> { Vec::new() }
+		fn queued_transactions(&self) -> Vec<QueuedTransaction> { self.0.clone() }
+	}
+
 	#[test]
 	fn twice_empty() {
 		let db = Arc::new(::kvdb_memorydb::create(0));
@@ -328,4 +404,42 @@ mod tests {
 			assert_eq!(loaded, transactions);
 		}
 	}
+
+	#[test]
+	fn queued_transactions_roundtrip() {
+		let keypair = Brain::new("abcd".into()).generate().unwrap();
+		let transactions: Vec<_> = (0..6u64).map(|nonce| {
+			let mut tx = Transaction::default();
+			tx.nonce = nonce.into();
+
+			let priority = match nonce % 3 {
+				0 => Priority::Local,
+				1 => Priority::Retracted,
+				_ => Priority::Regular,
+			};
+
+			QueuedTransaction {
+				transaction: PendingTransaction::new(tx.sign(keypair.secret(), None), None),
+				priority,
+			}
+		}).collect();
+
+		let db = Arc::new(::kvdb_memorydb::create(0));
+		{
+			// nothing written yet, will write queued.
+			let store = super::create(db.clone(), None, DummyQueue(transactions.clone()));
+			assert_eq!(store.queued_transactions().unwrap(), vec![])
+		}
+		{
+			// queued written in order, will write nothing.
+			let store = super::create(db.clone(), None, DummyQueue(vec![]));
+			assert_eq!(store.queued_transactions().unwrap(), transactions);
+			assert_eq!(store.pending_transactions().unwrap(), vec![]);
+		}
+		{
+			// queued removed.
+			let store = super::create(db.clone(), None, DummyQueue(vec![]));
+			assert_eq!(store.queued_transactions().unwrap(), vec![])
+		}
+	}
 }
@@ -38,7 +38,7 @@ use regex::Regex;
 use ansi_term::Colour;
 use parking_lot::Mutex;
 
-pub use rotating::{RotatingLogger, init_log};
+pub use rotating::{RotatingLogger, RotatingFile, init_log};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Config {
@@ -17,11 +17,14 @@
 //! Common log helper functions
 
 use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
 use rlog::LogLevelFilter;
 use env_logger::LogBuilder;
 use arrayvec::ArrayVec;
 
-use parking_lot::{RwLock, RwLockReadGuard};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 
 lazy_static! {
 	static ref LOG_DUMMY: () = {
@@ -85,9 +88,82 @@ impl RotatingLogger {
 
 }
 
+/// Append-only file of lines, rotated when it grows over `max_size` bytes.
+/// Rotated files are suffixed with their age, `.1` being the most recent one,
+/// and only `max_files` of them are kept.
+pub struct RotatingFile {
+	path: PathBuf,
+	max_size: u64,
+	max_files: usize,
+	file: Mutex<File>,
+}
+
+impl RotatingFile {
+	/// Opens the file at given path for appending, creating it if it doesn't exist.
+	pub fn open<P: Into<PathBuf>>(path: P, max_size: u64, max_files: usize) -> io::Result<Self> {
+		let path = path.into();
+		let file = Self::open_file(&path)?;
+		Ok(RotatingFile {
+			path,
+			max_size,
+			max_files,
+			file: Mutex::new(file),
+		})
+	}
+
+	fn open_file(path: &PathBuf) -> io::Result<File> {
+		OpenOptions::new().create(true).append(true).open(path)
+	}
+
+	fn rotated_path(&self, index: usize) -> PathBuf {
+		let mut path = self.path.clone().into_os_string();
+		path.push(format!(".{}", index));
+		path.into()
+	}
+
+	/// Appends a line to the file, rotating it afterwards if it's grown too big.
+	pub fn append(&self, line: &str) -> io::Result<()> {
+		let mut file = self.file.lock();
+		file.write_all(line.as_bytes())?;
+		file.write_all(b"\n")?;
+		file.flush()?;
+
+		if file.metadata()?.len() >= self.max_size {
+			for index in (1..self.max_files).rev() {
+				let rotated = self.rotated_path(index);
+				if rotated.exists() {
+					fs::rename(rotated, self.rotated_path(index + 1))?;
+				}
+			}
+			if self.max_files > 0 {
+				fs::rename(&self.path, self.rotated_path(1))?;
+			} else {
+				fs::remove_file(&self.path)?;
+			}
+			*file = Self::open_file(&self.path)?;
+		}
+
+		Ok(())
+	}
+
+	/// Returns paths of all existing files, starting with the current one and followed by rotated files from the most recent.
+	pub fn paths(&self) -> Vec<PathBuf> {
+		::std::iter::once(self.path.clone())
+			.chain((1..self.max_files + 1).map(|index| self.rotated_path(index)))
+			.filter(|path| path.exists())
+			.collect()
+	}
+}
+
 #[cfg(test)]
 mod test {
-	use super::RotatingLogger;
+	extern crate tempdir;
+
+	use std::fs::File;
+	use std::io::Read;
+	use std::path::Path;
+	use self::tempdir::TempDir;
+	use super::{RotatingLogger, RotatingFile};
 
 	fn logger() -> RotatingLogger {
 		RotatingLogger::new("test".to_owned())
@@ -120,4 +196,32 @@ mod test {
 		assert_eq!(logs[1], "a".to_owned());
 		assert_eq!(logs.len(), 2);
 	}
+
+	fn read(path: &Path) -> String {
+		let mut content = String::new();
+		File::open(path).unwrap().read_to_string(&mut content).unwrap();
+		content
+	}
+
+	#[test]
+	fn should_rotate_files() {
+		// given
+		let dir = TempDir::new("rotating").unwrap();
+		let path = dir.path().join("audit.log");
+		let file = RotatingFile::open(path.clone(), 4, 2).unwrap();
+
+		// when
+		file.append("a").unwrap();
+		file.append("bbbb").unwrap();
+		file.append("c").unwrap();
+		file.append("dddd").unwrap();
+		file.append("e").unwrap();
+
+		// then
+		let paths = file.paths();
+		assert_eq!(paths.len(), 3);
+		assert_eq!(read(&paths[0]), "e\n");
+		assert_eq!(read(&paths[1]), "c\ndddd\n");
+		assert_eq!(read(&paths[2]), "a\nbbbb\n");
+	}
 }
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Notifier for new transaction hashes.
+//! Notifier for new transaction hashes and transaction pool events.
 
 use std::fmt;
 use std::sync::Arc;
@@ -25,12 +25,40 @@ use txpool::{self, VerifiedTransaction};
 use pool::VerifiedTransaction as Transaction;
 
 type Listener = Box<Fn(&[H256]) + Send + Sync>;
+type EventListener = Box<Fn(&[Event]) + Send + Sync>;
 
-/// Manages notifications to pending transaction listeners.
+/// Transaction pool event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+	/// Transaction was added to the pool.
+	Added(Arc<Transaction>),
+	/// Transaction was replaced by transaction with higher gas price.
+	Replaced {
+		/// Replaced transaction.
+		old: Arc<Transaction>,
+		/// Transaction that replaced the old one.
+		new: Arc<Transaction>,
+	},
+	/// Transaction was rejected from the pool.
+	Rejected(Arc<Transaction>, String),
+	/// Transaction was pushed out from the pool because of the limit,
+	/// optionally by transaction with given hash.
+	Dropped(Arc<Transaction>, Option<H256>),
+	/// Transaction was marked as invalid by executor.
+	Invalid(Arc<Transaction>),
+	/// Transaction was canceled.
+	Canceled(Arc<Transaction>),
+	/// Transaction was mined or became stale.
+	Mined(Arc<Transaction>),
+}
+
+/// Manages notifications to pending transaction and pool event listeners.
 #[derive(Default)]
 pub struct Notifier {
 	listeners: Vec<Listener>,
 	pending: Vec<H256>,
+	event_listeners: Vec<EventListener>,
+	events: Vec<Event>,
 }
 
 impl fmt::Debug for Notifier {
@@ -38,6 +66,8 @@ impl fmt::Debug for Notifier {
 		fmt.debug_struct("Notifier")
 			.field("listeners", &self.listeners.len())
 			.field("pending", &self.pending)
+			.field("event_listeners", &self.event_listeners.len())
+			.field("events", &self.events.len())
 			.finish()
 	}
 }
@@ -48,19 +78,64 @@ impl Notifier {
 		self.listeners.push(f)
 	}
 
-	/// Notify listeners about all currently pending transactions.
+	/// Add new listener to receive pool events.
+	pub fn add_event_listener(&mut self, f: EventListener) {
+		self.event_listeners.push(f)
+	}
+
+	/// Notify listeners about all currently pending transactions and pool events.
 	pub fn notify(&mut self) {
-		for l in &self.listeners {
-			(l)(&self.pending);
+		if !self.pending.is_empty() {
+			for l in &self.listeners {
+				(l)(&self.pending);
+			}
+		}
+
+		if !self.events.is_empty() {
+			for l in &self.event_listeners {
+				(l)(&self.events);
+			}
 		}
 
 		self.pending.clear();
+		self.events.clear();
+	}
+
+	fn event(&mut self, event: Event) {
+		// Don't collect events if nobody is listening.
+		if !self.event_listeners.is_empty() {
+			self.events.push(event);
+		}
 	}
 }
 
 impl txpool::Listener<Transaction> for Notifier {
-	fn added(&mut self, tx: &Arc<Transaction>, _old: Option<&Arc<Transaction>>) {
+	fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
 		self.pending.push(*tx.hash());
+		if let Some(old) = old {
+			self.event(Event::Replaced { old: old.clone(), new: tx.clone() });
+		}
+		self.event(Event::Added(tx.clone()));
+	}
+
+	fn rejected(&mut self, tx: &Arc<Transaction>, reason: &txpool::ErrorKind) {
+		self.event(Event::Rejected(tx.clone(), format!("{}", reason)));
+	}
+
+	fn dropped(&mut self, tx: &Arc<Transaction>, by: Option<&Transaction>) {
+		self.event(Event::Dropped(tx.clone(), by.map(|tx| *tx.hash())));
+	}
+
+	fn invalid(&mut self, tx: &Arc<Transaction>) {
+		self.event(Event::Invalid(tx.clone()));
+	}
+
+	fn canceled(&mut self, tx: &Arc<Transaction>) {
+		self.event(Event::Canceled(tx.clone()));
+	}
+
+	fn mined(&mut self, tx: &Arc<Transaction>) {
+		self.event(Event::Mined(tx.clone()));
 	}
 }
 
@@ -144,6 +219,32 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn should_notify_event_listeners() {
+		// given
+		let received = Arc::new(Mutex::new(vec![]));
+		let r = received.clone();
+		let listener = Box::new(move |events: &[Event]| {
+			r.lock().extend(events.iter().cloned());
+		});
+
+		let mut tx_listener = Notifier::default();
+		tx_listener.add_event_listener(listener);
+
+		// when
+		let tx = new_tx();
+		tx_listener.added(&tx, None);
+		tx_listener.invalid(&tx);
+		assert_eq!(*received.lock(), vec![]);
+
+		// then
+		tx_listener.notify();
+		assert_eq!(*received.lock(), vec![Event::Added(tx.clone()), Event::Invalid(tx)]);
+
+		tx_listener.notify();
+		assert_eq!(received.lock().len(), 2);
+	}
+
 	fn new_tx() -> Arc<Transaction> {
 		let signed = transaction::Transaction {
 			action: transaction::Action::Create,
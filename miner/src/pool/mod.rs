@@ -22,6 +22,7 @@ use transaction;
 use txpool;
 
 mod listener;
+mod peers;
 mod queue;
 mod ready;
 mod scoring;
@@ -33,6 +34,8 @@ pub mod verifier;
 #[cfg(test)]
 mod tests;
 
+pub use self::listener::Event as TransactionEvent;
+pub use self::peers::{PeerId, PeerScore};
 pub use self::queue::{TransactionQueue, Status as QueueStatus};
 pub use self::txpool::{VerifiedTransaction as PoolVerifiedTransaction, Options};
 
@@ -43,11 +46,14 @@ pub use self::txpool::{VerifiedTransaction as PoolVerifiedTransaction, Options};
 pub enum PrioritizationStrategy {
 	/// Simple gas-price based prioritization.
 	GasPriceOnly,
+	/// Gas-price based prioritization in the pool, while blocks are filled by the fee
+	/// the author actually receives per unit of gas used, found by simulating transactions.
+	Profit,
 }
 
 /// Transaction priority.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub(crate) enum Priority {
+pub enum Priority {
 	/// Local transactions (high priority)
 	///
 	/// Transactions either from a local account or
@@ -80,6 +86,7 @@ pub struct VerifiedTransaction {
 	sender: Address,
 	priority: Priority,
 	insertion_id: usize,
+	peer_id: Option<peers::PeerId>,
 }
 
 impl VerifiedTransaction {
@@ -97,19 +104,25 @@ impl VerifiedTransaction {
 			sender,
 			priority: Priority::Retracted,
 			insertion_id: 0,
+			peer_id: None,
 		}
 	}
 
 	/// Gets transaction priority.
-	pub(crate) fn priority(&self) -> Priority {
+	pub fn priority(&self) -> Priority {
 		self.priority
 	}
 
 	/// Gets transaction insertion id.
-	pub(crate) fn insertion_id(&self) -> usize {
+	pub fn insertion_id(&self) -> usize {
 		self.insertion_id
 	}
 
+	/// Gets id of the network peer the transaction was received from.
+	pub fn peer_id(&self) -> Option<peers::PeerId> {
+		self.peer_id
+	}
+
 	/// Gets wrapped `SignedTransaction`
 	pub fn signed(&self) -> &transaction::SignedTransaction {
 		&self.transaction
@@ -0,0 +1,177 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Spam scoring of peers relaying transactions to the pool.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use txpool;
+
+use pool::VerifiedTransaction as Transaction;
+
+/// Network peer identifier.
+pub type PeerId = usize;
+
+/// Minimal number of offences before a peer can be considered a spammer.
+const MIN_OFFENCES: u64 = 64;
+/// How many offences per mined transaction a peer is allowed to have.
+const OFFENCES_PER_MINED: u64 = 4;
+
+/// Outcomes of transactions received from a single peer.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PeerScore {
+	/// Transactions added to the pool.
+	pub added: u64,
+	/// Transactions mined (or culled because of stale nonce).
+	pub mined: u64,
+	/// Transactions replaced by other transactions.
+	pub replaced: u64,
+	/// Transactions pushed out of the pool because of the limits.
+	pub dropped: u64,
+	/// Transactions rejected by the pool or marked as invalid.
+	pub invalid: u64,
+}
+
+impl PeerScore {
+	/// Number of transactions which occupied the pool without ever getting mined.
+	pub fn offences(&self) -> u64 {
+		self.replaced + self.dropped + self.invalid
+	}
+
+	/// Returns `true` if most of the transactions received from the peer never get mined.
+	pub fn is_spammer(&self) -> bool {
+		let offences = self.offences();
+		offences >= MIN_OFFENCES && offences > self.mined.saturating_mul(OFFENCES_PER_MINED)
+	}
+}
+
+/// Pool listener tracking transaction outcomes per relaying peer.
+#[derive(Debug, Default)]
+pub struct PeerScoring {
+	scores: HashMap<PeerId, PeerScore>,
+}
+
+impl PeerScoring {
+	/// Returns score of given peer.
+	pub fn score(&self, peer_id: &PeerId) -> Option<&PeerScore> {
+		self.scores.get(peer_id)
+	}
+
+	/// Returns all peers considered spammers.
+	pub fn spammers(&self) -> Vec<PeerId> {
+		self.scores.iter()
+			.filter(|&(_, score)| score.is_spammer())
+			.map(|(peer_id, _)| *peer_id)
+			.collect()
+	}
+
+	/// Forget score of a peer (e.g. after it disconnected).
+	pub fn remove(&mut self, peer_id: &PeerId) {
+		self.scores.remove(peer_id);
+	}
+
+	fn update<F: FnOnce(&mut PeerScore)>(&mut self, tx: &Transaction, f: F) {
+		if let Some(peer_id) = tx.peer_id() {
+			f(self.scores.entry(peer_id).or_insert_with(PeerScore::default));
+		}
+	}
+}
+
+impl txpool::Listener<Transaction> for PeerScoring {
+	fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
+		self.update(tx, |score| score.added += 1);
+		if let Some(old) = old {
+			self.update(old, |score| score.replaced += 1);
+		}
+	}
+
+	fn rejected(&mut self, tx: &Arc<Transaction>, _reason: &txpool::ErrorKind) {
+		self.update(tx, |score| score.invalid += 1);
+	}
+
+	fn dropped(&mut self, tx: &Arc<Transaction>, _by: Option<&Transaction>) {
+		self.update(tx, |score| score.dropped += 1);
+	}
+
+	fn invalid(&mut self, tx: &Arc<Transaction>) {
+		self.update(tx, |score| score.invalid += 1);
+	}
+
+	fn mined(&mut self, tx: &Arc<Transaction>) {
+		self.update(tx, |score| score.mined += 1);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use transaction;
+	use txpool::Listener;
+
+	fn new_tx(peer_id: Option<PeerId>) -> Arc<Transaction> {
+		let signed = transaction::Transaction {
+			action: transaction::Action::Create,
+			data: vec![1, 2, 3],
+			nonce: 5.into(),
+			gas: 21_000.into(),
+			gas_price: 5.into(),
+			value: 0.into(),
+		}.fake_sign(5.into());
+
+		let mut tx = Transaction::from_pending_block_transaction(signed);
+		tx.peer_id = peer_id;
+		Arc::new(tx)
+	}
+
+	#[test]
+	fn should_track_outcomes_per_peer() {
+		// given
+		let mut scoring = PeerScoring::default();
+		let (tx1, tx2, local) = (new_tx(Some(1)), new_tx(Some(2)), new_tx(None));
+
+		// when
+		scoring.added(&tx1, None);
+		scoring.added(&tx2, Some(&tx1));
+		scoring.added(&local, None);
+		scoring.mined(&tx2);
+
+		// then
+		assert_eq!(scoring.score(&1), Some(&PeerScore { added: 1, replaced: 1, ..Default::default() }));
+		assert_eq!(scoring.score(&2), Some(&PeerScore { added: 1, mined: 1, ..Default::default() }));
+		assert!(scoring.spammers().is_empty());
+	}
+
+	#[test]
+	fn should_mark_peer_as_spammer() {
+		// given
+		let mut scoring = PeerScoring::default();
+		let tx = new_tx(Some(1));
+		scoring.mined(&tx);
+
+		// when
+		for _ in 0..MIN_OFFENCES - 1 {
+			scoring.dropped(&tx, None);
+		}
+		assert!(scoring.spammers().is_empty());
+		scoring.dropped(&tx, None);
+
+		// then
+		assert_eq!(scoring.spammers(), vec![1]);
+		scoring.remove(&1);
+		assert!(scoring.spammers().is_empty());
+	}
+}
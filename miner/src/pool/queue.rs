@@ -28,8 +28,9 @@ use txpool::{self, Verifier};
 
 use pool::{self, scoring, verifier, client, ready, listener, PrioritizationStrategy};
 use pool::local_transactions::LocalTransactionsList;
+use pool::peers::{PeerId, PeerScore, PeerScoring};
 
-type Listener = (LocalTransactionsList, (listener::Notifier, listener::Logger));
+type Listener = (LocalTransactionsList, (listener::Notifier, (listener::Logger, PeerScoring)));
 type Pool = txpool::Pool<pool::VerifiedTransaction, scoring::NonceAndGasPrice, Listener>;
 
 /// Max cache time in milliseconds for pending transactions.
@@ -171,6 +172,27 @@ impl TransactionQueue {
 		&self,
 		client: C,
 		transactions: Vec<verifier::Transaction>,
+	) -> Vec<Result<(), transaction::Error>> {
+		self.import_with_peer(client, transactions, None)
+	}
+
+	/// Import a set of transactions received from given network peer.
+	///
+	/// Outcomes of these transactions are used to score the peer.
+	pub fn import_from_peer<C: client::Client>(
+		&self,
+		client: C,
+		peer_id: PeerId,
+		transactions: Vec<verifier::Transaction>,
+	) -> Vec<Result<(), transaction::Error>> {
+		self.import_with_peer(client, transactions, Some(peer_id))
+	}
+
+	fn import_with_peer<C: client::Client>(
+		&self,
+		client: C,
+		transactions: Vec<verifier::Transaction>,
+		peer_id: Option<PeerId>,
 	) -> Vec<Result<(), transaction::Error>> {
 		// Run verification
 		let _timer = ::trace_time::PerfTimer::new("pool::verify_and_import");
@@ -184,7 +206,10 @@ impl TransactionQueue {
 					bail!(transaction::Error::AlreadyImported)
 				}
 
-				verifier.verify_transaction(transaction)
+				verifier.verify_transaction(transaction).map(|mut verified| {
+					verified.peer_id = peer_id;
+					verified
+				})
 			})
 			.map(|result| result.and_then(|verified| {
 				self.pool.write().import(verified)
@@ -298,7 +323,12 @@ impl TransactionQueue {
 
 		let state_readiness = ready::State::new(client, stale_id, nonce_cap);
 
-		let removed = self.pool.write().cull(None, state_readiness);
+		let removed = {
+			let mut pool = self.pool.write();
+			let removed = pool.cull(None, state_readiness);
+			(pool.listener_mut().1).0.notify();
+			removed
+		};
 		debug!(target: "txqueue", "Removed {} stalled transactions. {}", removed, self.status());
 	}
 
@@ -346,10 +376,13 @@ impl TransactionQueue {
 		let results = {
 			let mut pool = self.pool.write();
 
-			hashes
+			let results = hashes
 				.into_iter()
 				.map(|hash| pool.remove(hash, is_invalid))
-				.collect::<Vec<_>>()
+				.collect::<Vec<_>>();
+
+			(pool.listener_mut().1).0.notify();
+			results
 		};
 
 		if results.iter().any(Option::is_some) {
@@ -415,6 +448,28 @@ impl TransactionQueue {
 		let mut pool = self.pool.write();
 		(pool.listener_mut().1).0.add(f);
 	}
+
+	/// Returns score of transactions received from given peer.
+	pub fn peer_score(&self, peer_id: &PeerId) -> Option<PeerScore> {
+		((self.pool.read().listener().1).1).1.score(peer_id).cloned()
+	}
+
+	/// Returns peers which keep sending transactions that never get mined.
+	pub fn spam_peers(&self) -> Vec<PeerId> {
+		((self.pool.read().listener().1).1).1.spammers()
+	}
+
+	/// Forget score of given peer.
+	pub fn remove_peer(&self, peer_id: &PeerId) {
+		((self.pool.write().listener_mut().1).1).1.remove(peer_id);
+	}
+
+	/// Add a callback to be notified about transactions being added, replaced, rejected, dropped,
+	/// marked invalid, canceled or mined.
+	pub fn add_event_listener(&self, f: Box<Fn(&[pool::TransactionEvent]) + Send + Sync>) {
+		let mut pool = self.pool.write();
+		(pool.listener_mut().1).0.add_event_listener(f);
+	}
 }
 
 fn convert_error(err: txpool::Error) -> transaction::Error {
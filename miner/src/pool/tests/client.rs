@@ -90,6 +90,7 @@ impl TestClient {
 			priority: pool::Priority::Regular,
 			transaction: tx,
 			insertion_id: 1,
+			peer_id: None,
 		}
 	}
 
@@ -830,3 +830,63 @@ fn should_avoid_verifying_transaction_already_in_pool() {
 	// then
 	assert_eq!(txq.status().status.transaction_count, 1);
 }
+
+#[test]
+fn should_notify_about_pool_events() {
+	use std::sync::Arc;
+	use parking_lot::Mutex;
+	use pool::TransactionEvent;
+	use txpool::VerifiedTransaction;
+
+	// given
+	let txq = new_queue();
+	let events = Arc::new(Mutex::new(vec![]));
+	let e = events.clone();
+	txq.add_event_listener(Box::new(move |new_events: &[TransactionEvent]| {
+		e.lock().extend(new_events.iter().map(|event| match *event {
+			TransactionEvent::Added(ref tx) => ("added", *tx.hash()),
+			TransactionEvent::Replaced { ref old, .. } => ("replaced", *old.hash()),
+			TransactionEvent::Mined(ref tx) => ("mined", *tx.hash()),
+			_ => ("other", Default::default()),
+		}));
+	}));
+	let (tx, tx2) = Tx::default().signed_replacement();
+	let (hash, hash2) = (tx.hash(), tx2.hash());
+
+	// when
+	txq.import(TestClient::new(), vec![tx].local());
+	txq.import(TestClient::new(), vec![tx2].local());
+	txq.cull(TestClient::new().with_nonce(124));
+
+	// then
+	assert_eq!(*events.lock(), vec![
+		("added", hash),
+		("replaced", hash),
+		("added", hash2),
+		("mined", hash2),
+	]);
+}
+
+#[test]
+fn should_score_peers_relaying_transactions() {
+	// given
+	let txq = new_queue();
+	let (tx, tx2) = Tx::default().signed_replacement();
+
+	// when
+	let res = txq.import_from_peer(TestClient::new(), 5, vec![tx.unverified()]);
+	let res2 = txq.import_from_peer(TestClient::new(), 7, vec![tx2.unverified()]);
+	assert_eq!(res, vec![Ok(())]);
+	assert_eq!(res2, vec![Ok(())]);
+	txq.cull(TestClient::new().with_nonce(124));
+
+	// then
+	let score = txq.peer_score(&5).unwrap();
+	assert_eq!((score.added, score.replaced, score.mined), (1, 1, 0));
+	let score = txq.peer_score(&7).unwrap();
+	assert_eq!((score.added, score.replaced, score.mined), (1, 0, 1));
+	assert!(txq.spam_peers().is_empty());
+
+	txq.remove_peer(&5);
+	assert_eq!(txq.peer_score(&5), None);
+}
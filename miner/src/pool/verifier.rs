@@ -291,6 +291,7 @@ impl<C: Client> txpool::Verifier<Transaction> for Verifier<C> {
 			hash,
 			sender,
 			insertion_id: self.id.fetch_add(1, atomic::Ordering::AcqRel),
+			peer_id: None,
 		})
 	}
 }
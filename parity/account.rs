@@ -14,12 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::path::PathBuf;
-use ethcore::ethstore::{EthStore, SecretStore, import_account, import_accounts, read_geth_accounts};
-use ethcore::ethstore::accounts_dir::RootDiskDirectory;
+use std::fs;
+use std::path::{Path, PathBuf};
+use ethcore::ethstore::{EthStore, SecretStore, BackupFile, ScryptParams, import_account, import_accounts, read_geth_accounts};
+use ethcore::ethstore::accounts_dir::{RootDiskDirectory, backup_directory, restore_directory};
 use ethcore::ethstore::SecretVaultRef;
 use ethcore::account_provider::{AccountProvider, AccountProviderSettings};
-use helpers::{password_prompt, password_from_file};
+use helpers::{password_prompt, existing_password_prompt, password_from_file};
+use path::create_owner_only;
 use params::SpecType;
 
 #[derive(Debug, PartialEq)]
@@ -27,7 +29,9 @@ pub enum AccountCmd {
 	New(NewAccount),
 	List(ListAccounts),
 	Import(ImportAccounts),
-	ImportFromGeth(ImportFromGethAccounts)
+	ImportFromGeth(ImportFromGethAccounts),
+	Backup(BackupAccounts),
+	Restore(RestoreAccounts),
 }
 
 #[derive(Debug, PartialEq)]
@@ -61,20 +65,48 @@ pub struct ImportFromGethAccounts {
 	pub spec: SpecType,
 }
 
+/// Parameters for backup of the whole keys directory
+#[derive(Debug, PartialEq)]
+pub struct BackupAccounts {
+	/// keys directory
+	pub path: String,
+	pub spec: SpecType,
+	/// archive to create
+	pub to: String,
+	pub password_file: Option<String>,
+}
+
+/// Parameters for restoring keys directory from a backup
+#[derive(Debug, PartialEq)]
+pub struct RestoreAccounts {
+	/// keys directory
+	pub path: String,
+	pub spec: SpecType,
+	/// archive to restore from
+	pub from: String,
+	pub password_file: Option<String>,
+}
+
 pub fn execute(cmd: AccountCmd) -> Result<String, String> {
 	match cmd {
 		AccountCmd::New(new_cmd) => new(new_cmd),
 		AccountCmd::List(list_cmd) => list(list_cmd),
 		AccountCmd::Import(import_cmd) => import(import_cmd),
-		AccountCmd::ImportFromGeth(import_geth_cmd) => import_geth(import_geth_cmd)
+		AccountCmd::ImportFromGeth(import_geth_cmd) => import_geth(import_geth_cmd),
+		AccountCmd::Backup(backup_cmd) => backup(backup_cmd),
+		AccountCmd::Restore(restore_cmd) => restore(restore_cmd),
 	}
 }
 
-fn keys_dir(path: String, spec: SpecType) -> Result<RootDiskDirectory, String> {
+fn keys_path(path: String, spec: SpecType) -> Result<PathBuf, String> {
 	let spec = spec.spec(&::std::env::temp_dir())?;
 	let mut path = PathBuf::from(&path);
 	path.push(spec.data_dir);
-	RootDiskDirectory::create(path).map_err(|e| format!("Could not open keys directory: {}", e))
+	Ok(path)
+}
+
+fn keys_dir(path: String, spec: SpecType) -> Result<RootDiskDirectory, String> {
+	RootDiskDirectory::create(keys_path(path, spec)?).map_err(|e| format!("Could not open keys directory: {}", e))
 }
 
 fn secret_store(dir: Box<RootDiskDirectory>, iterations: Option<u32>) -> Result<EthStore, String> {
@@ -92,7 +124,8 @@ fn new(n: NewAccount) -> Result<String, String> {
 
 	let dir = Box::new(keys_dir(n.path, n.spec)?);
 	let secret_store = Box::new(secret_store(dir, Some(n.iterations))?);
-	let acc_provider = AccountProvider::new(secret_store, AccountProviderSettings::default());
+	let acc_provider = AccountProvider::new(secret_store, AccountProviderSettings::default())
+		.map_err(|e| format!("Could not open accounts: {}", e))?;
 	let new_account = acc_provider.new_account(&password).map_err(|e| format!("Could not create new account: {}", e))?;
 	Ok(format!("0x{:x}", new_account))
 }
@@ -100,7 +133,8 @@ fn new(n: NewAccount) -> Result<String, String> {
 fn list(list_cmd: ListAccounts) -> Result<String, String> {
 	let dir = Box::new(keys_dir(list_cmd.path, list_cmd.spec)?);
 	let secret_store = Box::new(secret_store(dir, None)?);
-	let acc_provider = AccountProvider::new(secret_store, AccountProviderSettings::default());
+	let acc_provider = AccountProvider::new(secret_store, AccountProviderSettings::default())
+		.map_err(|e| format!("Could not open accounts: {}", e))?;
 	let accounts = acc_provider.accounts().map_err(|e| format!("{}", e))?;
 	let result = accounts.into_iter()
 		.map(|a| format!("0x{:x}", a))
@@ -128,6 +162,39 @@ fn import(i: ImportAccounts) -> Result<String, String> {
 	Ok(format!("{} account(s) imported", imported))
 }
 
+fn backup(b: BackupAccounts) -> Result<String, String> {
+	let password: String = match b.password_file {
+		Some(file) => password_from_file(file)?,
+		None => password_prompt()?,
+	};
+
+	let path = keys_path(b.path, b.spec)?;
+	let backup = backup_directory(&path, &password, &ScryptParams::default())
+		.map_err(|e| format!("Could not backup accounts from {:?}: {}", path, e))?;
+
+	let to = Path::new(&b.to);
+	let mut file = create_owner_only(to).map_err(|e| format!("Could not create {:?}: {}", to, e))?;
+	backup.write(&mut file).map_err(|e| format!("Could not write {:?}: {}", to, e))?;
+
+	Ok(format!("Accounts backed up to {}", b.to))
+}
+
+fn restore(r: RestoreAccounts) -> Result<String, String> {
+	let password: String = match r.password_file {
+		Some(file) => password_from_file(file)?,
+		None => existing_password_prompt()?,
+	};
+
+	let file = fs::File::open(&r.from).map_err(|e| format!("Could not open {}: {}", r.from, e))?;
+	let backup = BackupFile::load(file).map_err(|e| format!("Invalid backup file {}: {}", r.from, e))?;
+
+	let path = keys_path(r.path, r.spec)?;
+	let restored = restore_directory(backup, &password, &path)
+		.map_err(|e| format!("Could not restore accounts: {}", e))?;
+
+	Ok(format!("{} file(s) restored", restored))
+}
+
 fn import_geth(i: ImportFromGethAccounts) -> Result<String, String> {
 	use std::io::ErrorKind;
 	use ethcore::ethstore::Error;
@@ -64,6 +64,24 @@ usage! {
 				"<PATH>...",
 				"Path to the accounts",
 			}
+
+			CMD cmd_account_backup
+			{
+				"Backup all accounts, vaults, address book and dapps settings to an encrypted archive",
+
+				ARG arg_account_backup_to: (Option<String>) = None,
+				"--to=[FILE]",
+				"Path to the archive",
+			}
+
+			CMD cmd_account_restore
+			{
+				"Restore accounts, vaults, address book and dapps settings from an encrypted archive",
+
+				ARG arg_account_restore_from: (Option<String>) = None,
+				"--from=[FILE]",
+				"Path to the archive",
+			}
 		}
 
 		CMD cmd_wallet
@@ -322,10 +340,26 @@ usage! {
 			"--no-hardware-wallets",
 			"Disables hardware wallet support.",
 
+			ARG arg_external_signer: (Option<String>) = None, or |c: &Config| c.account.as_ref()?.external_signer.clone(),
+			"--external-signer=[ENDPOINT]",
+			"Use accounts held by an external signing service. ENDPOINT is either an HTTP(S) URL or a path to a Unix domain socket.",
+
+			ARG arg_external_signer_protocol: (String) = "parity", or |c: &Config| c.account.as_ref()?.external_signer_protocol.clone(),
+			"--external-signer-protocol=[PROTOCOL]",
+			"JSON-RPC API spoken by the external signing service. PROTOCOL may be one of: parity - signer_* methods; clef - account_* methods, which can sign only transactions.",
+
+			FLAG flag_standalone_signer: (bool) = false, or |c: &Config| c.account.as_ref()?.standalone_signer.clone(),
+			"--standalone-signer",
+			"Run as a standalone signer without syncing any chain. Only the Clef-compatible account API is exposed over IPC, requests of locked accounts have to be confirmed over the authenticated WebSockets interface.",
+
 			FLAG flag_fast_unlock: (bool) = false, or |c: &Config| c.account.as_ref()?.fast_unlock.clone(),
 			"--fast-unlock",
 			"Use drasticly faster unlocking mode. This setting causes raw secrets to be stored unprotected in memory, so use with care.",
 
+			FLAG flag_accounts_audit_log: (bool) = false, or |c: &Config| c.account.as_ref()?.audit_log.clone(),
+			"--accounts-audit-log",
+			"Record unlocks, signing, decryption, exports, password changes and vault operations of local accounts in an append-only audit.log in the keys directory.",
+
 			ARG arg_keys_iterations: (u32) = 10240u32, or |c: &Config| c.account.as_ref()?.keys_iterations.clone(),
 			"--keys-iterations=[NUM]",
 			"Specify the number of iterations to use when deriving key from the password (bigger is more secure)",
@@ -376,6 +410,14 @@ usage! {
 			"--ui-path=[PATH]",
 			"Specify directory where Trusted UIs tokens should be stored.",
 
+			ARG arg_ui_confirmation_delay: (u64) = 0u64, or |c: &Config| c.ui.as_ref()?.confirmation_delay.clone(),
+			"--ui-confirmation-delay=[SECS]",
+			"Number of seconds high-value transactions have to wait in the Trusted UI queue before they can be confirmed. 0 disables the delay.",
+
+			ARG arg_ui_delay_min_value: (Option<String>) = None, or |c: &Config| c.ui.as_ref()?.delay_min_value.clone(),
+			"--ui-delay-min-value=[WEI]",
+			"Minimal value (in wei) of transactions affected by --ui-confirmation-delay. All transactions are delayed if not specified.",
+
 		["Networking options"]
 			FLAG flag_no_warp: (bool) = false, or |c: &Config| c.network.as_ref()?.warp.clone().map(|w| !w),
 			"--no-warp",
@@ -464,7 +506,7 @@ usage! {
 
 			ARG arg_jsonrpc_apis: (String) = "web3,eth,pubsub,net,parity,private,parity_pubsub,traces,rpc,shh,shh_pubsub", or |c: &Config| c.rpc.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
 			"--jsonrpc-apis=[APIS]",
-			"Specify the APIs available through the JSONRPC interface using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, shh, shh_pubsub. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains following apis: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
+			"Specify the APIs available through the JSONRPC interface using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, shh, shh_pubsub, account. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains following apis: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
 
 			ARG arg_jsonrpc_hosts: (String) = "none", or |c: &Config| c.rpc.as_ref()?.hosts.as_ref().map(|vec| vec.join(",")),
 			"--jsonrpc-hosts=[HOSTS]",
@@ -497,7 +539,7 @@ usage! {
 
 			ARG arg_ws_apis: (String) = "web3,eth,pubsub,net,parity,parity_pubsub,private,traces,rpc,shh,shh_pubsub", or |c: &Config| c.websockets.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
 			"--ws-apis=[APIS]",
-			"Specify the APIs available through the WebSockets interface using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, shh, shh_pubsub. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains following apis: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
+			"Specify the APIs available through the WebSockets interface using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, shh, shh_pubsub, account. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains following apis: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
 
 			ARG arg_ws_origins: (String) = "parity://*,chrome-extension://*,moz-extension://*", or |c: &Config| c.websockets.as_ref()?.origins.as_ref().map(|vec| vec.join(",")),
 			"--ws-origins=[URL]",
@@ -520,9 +562,9 @@ usage! {
 			"--ipc-path=[PATH]",
 			"Specify custom path for JSON-RPC over IPC service.",
 
-			ARG arg_ipc_apis: (String) = "web3,eth,pubsub,net,parity,parity_pubsub,parity_accounts,private,traces,rpc,shh,shh_pubsub", or |c: &Config| c.ipc.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
+			ARG arg_ipc_apis: (String) = "web3,eth,pubsub,net,parity,parity_pubsub,parity_accounts,account,private,traces,rpc,shh,shh_pubsub", or |c: &Config| c.ipc.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
 			"--ipc-apis=[APIS]",
-			"Specify custom API set available via JSON-RPC over IPC using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, shh, shh_pubsub. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
+			"Specify custom API set available via JSON-RPC over IPC using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, shh, shh_pubsub, account. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
 
 		["API and console options – Dapps"]
 			FLAG flag_no_dapps: (bool) = false, or |c: &Config| c.dapps.as_ref()?.disable.clone(),
@@ -648,6 +690,10 @@ usage! {
 			"--no-persistent-txqueue",
 			"Don't save pending local transactions to disk to be restored whenever the node restarts.",
 
+			FLAG flag_persistent_txqueue_all: (bool) = false, or |c: &Config| c.parity.as_ref()?.persistent_txqueue_all,
+			"--persistent-txqueue-all",
+			"Save all verified transactions in the queue, not only local ones, to be re-verified and restored whenever the node restarts. Has no effect with --no-persistent-txqueue.",
+
 			FLAG flag_stratum: (bool) = false, or |c: &Config| Some(c.stratum.is_some()),
 			"--stratum",
 			"Run Stratum server for miner push notification.",
@@ -704,13 +750,21 @@ usage! {
 			"--tx-queue-per-sender=[LIMIT]",
 			"Maximum number of transactions per sender in the queue. By default it's 1% of the entire queue, but not less than 16.",
 
+			ARG arg_tx_queue_peer_rate: (Option<usize>) = None, or |c: &Config| c.mining.as_ref()?.tx_queue_peer_rate.clone(),
+			"--tx-queue-peer-rate=[LIMIT]",
+			"Maximum number of transactions per second accepted from a single peer. Transactions over the limit are dropped. Unlimited by default.",
+
+			ARG arg_tx_queue_rate: (Option<usize>) = None, or |c: &Config| c.mining.as_ref()?.tx_queue_rate.clone(),
+			"--tx-queue-rate=[LIMIT]",
+			"Maximum number of transactions per second accepted from all peers. Transactions over the limit are dropped. Unlimited by default.",
+
 			ARG arg_tx_queue_gas: (String) = "off", or |c: &Config| c.mining.as_ref()?.tx_queue_gas.clone(),
 			"--tx-queue-gas=[LIMIT]",
 			"Maximum amount of total gas for external transactions in the queue. LIMIT can be either an amount of gas or 'auto' or 'off'. 'auto' sets the limit to be 20x the current block gas limit.",
 
 			ARG arg_tx_queue_strategy: (String) = "gas_price", or |c: &Config| c.mining.as_ref()?.tx_queue_strategy.clone(),
 			"--tx-queue-strategy=[S]",
-			"Prioritization strategy used to order transactions in the queue. S may be: gas_price - Prioritize txs with high gas price",
+			"Prioritization strategy used to order transactions in the queue. S may be: gas_price - Prioritize txs with high gas price; profit - Fill blocks by the simulated fee paid per gas used",
 
 			ARG arg_stratum_interface: (String) = "local", or |c: &Config| c.stratum.as_ref()?.interface.clone(),
 			"--stratum-interface=[IP]",
@@ -1074,6 +1128,7 @@ struct Operating {
 	identity: Option<String>,
 	light: Option<bool>,
 	no_persistent_txqueue: Option<bool>,
+	persistent_txqueue_all: Option<bool>,
 	no_hardcoded_sync: Option<bool>,
 
 	#[serde(rename="public_node")]
@@ -1088,7 +1143,11 @@ struct Account {
 	keys_iterations: Option<u32>,
 	refresh_time: Option<u64>,
 	disable_hardware: Option<bool>,
+	external_signer: Option<String>,
+	external_signer_protocol: Option<String>,
+	standalone_signer: Option<bool>,
 	fast_unlock: Option<bool>,
+	audit_log: Option<bool>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1107,6 +1166,8 @@ struct PrivateTransactions {
 #[serde(deny_unknown_fields)]
 struct Ui {
 	path: Option<String>,
+	confirmation_delay: Option<u64>,
+	delay_min_value: Option<String>,
 
 	#[serde(rename="force")]
 	_legacy_force: Option<bool>,
@@ -1244,6 +1305,8 @@ struct Mining {
 	extra_data: Option<String>,
 	tx_queue_size: Option<usize>,
 	tx_queue_per_sender: Option<usize>,
+	tx_queue_peer_rate: Option<usize>,
+	tx_queue_rate: Option<usize>,
 	tx_queue_mem_limit: Option<u32>,
 	tx_queue_gas: Option<String>,
 	tx_queue_strategy: Option<String>,
@@ -1481,6 +1544,8 @@ mod tests {
 			cmd_account_new: false,
 			cmd_account_list: false,
 			cmd_account_import: false,
+			cmd_account_backup: false,
+			cmd_account_restore: false,
 			cmd_wallet: false,
 			cmd_wallet_import: false,
 			cmd_import: false,
@@ -1516,6 +1581,8 @@ mod tests {
 			arg_signer_reject_id: None,
 			arg_dapp_path: None,
 			arg_account_import_path: None,
+			arg_account_backup_to: None,
+			arg_account_restore_from: None,
 			arg_wallet_import_path: None,
 
 			// -- Operating Options
@@ -1537,6 +1604,7 @@ mod tests {
 			flag_light: false,
 			flag_no_hardcoded_sync: false,
 			flag_no_persistent_txqueue: false,
+			flag_persistent_txqueue_all: false,
 			flag_force_direct: false,
 
 			// -- Convenience Options
@@ -1550,7 +1618,11 @@ mod tests {
 			arg_keys_iterations: 10240u32,
 			arg_accounts_refresh: 5u64,
 			flag_no_hardware_wallets: false,
+			arg_external_signer: None,
+			arg_external_signer_protocol: "parity".into(),
+			flag_standalone_signer: false,
 			flag_fast_unlock: false,
+			flag_accounts_audit_log: false,
 
 			// -- Private Transactions Options
 			flag_private_enabled: true,
@@ -1567,6 +1639,8 @@ mod tests {
 			arg_ui_interface: "local".into(),
 			arg_ui_hosts: "none".into(),
 			arg_ui_path: "$HOME/.parity/signer".into(),
+			arg_ui_confirmation_delay: 0u64,
+			arg_ui_delay_min_value: None,
 			flag_ui_no_validation: false,
 
 			// -- Networking Options
@@ -1665,6 +1739,8 @@ mod tests {
 			arg_extra_data: Some("Parity".into()),
 			arg_tx_queue_size: 8192usize,
 			arg_tx_queue_per_sender: None,
+			arg_tx_queue_peer_rate: None,
+			arg_tx_queue_rate: None,
 			arg_tx_queue_mem_limit: 4u32,
 			arg_tx_queue_gas: "off".into(),
 			arg_tx_queue_strategy: "gas_factor".into(),
@@ -1807,6 +1883,7 @@ mod tests {
 				light: None,
 				no_hardcoded_sync: None,
 				no_persistent_txqueue: None,
+				persistent_txqueue_all: None,
 				_legacy_public_node: None,
 			}),
 			account: Some(Account {
@@ -1815,10 +1892,16 @@ mod tests {
 				keys_iterations: None,
 				refresh_time: None,
 				disable_hardware: None,
+				external_signer: None,
+				external_signer_protocol: None,
+				standalone_signer: None,
 				fast_unlock: None,
+				audit_log: None,
 			}),
 			ui: Some(Ui {
 				path: None,
+				confirmation_delay: None,
+				delay_min_value: None,
 				_legacy_force: None,
 				_legacy_disable: Some(true),
 				_legacy_port: None,
@@ -1924,6 +2007,8 @@ mod tests {
 				gas_cap: None,
 				tx_queue_size: Some(8192),
 				tx_queue_per_sender: None,
+				tx_queue_peer_rate: None,
+				tx_queue_rate: None,
 				tx_queue_mem_limit: None,
 				tx_queue_gas: Some("off".into()),
 				tx_queue_strategy: None,
@@ -35,6 +35,7 @@ use miner::pool;
 
 use rpc::{IpcConfiguration, HttpConfiguration, WsConfiguration};
 use parity_rpc::NetworkSettings;
+use parity_rpc::signer::ConfirmationDelay;
 use cache::CacheConfig;
 use helpers::{to_duration, to_mode, to_block_id, to_u256, to_pending_set, to_price, geth_ipc_path, parity_ipc_path, to_bootnodes, to_addresses, to_address, to_queue_strategy, to_queue_penalization, passwords_from_files};
 use dir::helpers::{replace_home, replace_home_and_local};
@@ -50,7 +51,7 @@ use run::RunCmd;
 use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, KillBlockchain, ExportState, DataFormat};
 use export_hardcoded_sync::ExportHsyncCmd;
 use presale::ImportWallet;
-use account::{AccountCmd, NewAccount, ListAccounts, ImportAccounts, ImportFromGethAccounts};
+use account::{AccountCmd, NewAccount, ListAccounts, ImportAccounts, ImportFromGethAccounts, BackupAccounts, RestoreAccounts};
 use snapshot::{self, SnapshotCommand};
 use network::{IpFilter};
 
@@ -203,6 +204,22 @@ impl Configuration {
 					spec: spec,
 				};
 				AccountCmd::Import(import_acc)
+			} else if self.args.cmd_account_backup {
+				let backup_acc = BackupAccounts {
+					path: dirs.keys,
+					spec: spec,
+					to: self.args.arg_account_backup_to.clone().ok_or("Path to the archive is required (--to=FILE).")?,
+					password_file: self.accounts_config()?.password_files.first().map(|x| x.to_owned()),
+				};
+				AccountCmd::Backup(backup_acc)
+			} else if self.args.cmd_account_restore {
+				let restore_acc = RestoreAccounts {
+					path: dirs.keys,
+					spec: spec,
+					from: self.args.arg_account_restore_from.clone().ok_or("Path to the archive is required (--from=FILE).")?,
+					password_file: self.accounts_config()?.password_files.first().map(|x| x.to_owned()),
+				};
+				AccountCmd::Restore(restore_acc)
 			} else {
 				unreachable!();
 			};
@@ -392,7 +409,11 @@ impl Configuration {
 				verifier_settings: verifier_settings,
 				serve_light: !self.args.flag_no_serve_light,
 				light: self.args.flag_light,
+				standalone_signer: self.args.flag_standalone_signer,
 				no_persistent_txqueue: self.args.flag_no_persistent_txqueue,
+				persistent_txqueue_all: self.args.flag_persistent_txqueue_all,
+				tx_queue_peer_rate: self.args.arg_tx_queue_peer_rate,
+				tx_queue_rate: self.args.arg_tx_queue_rate,
 				whisper: whisper_config,
 				no_hardcoded_sync: self.args.flag_no_hardcoded_sync,
 			};
@@ -509,7 +530,10 @@ impl Configuration {
 			password_files: self.args.arg_password.iter().map(|s| replace_home(&self.directories().base, s)).collect(),
 			unlocked_accounts: to_addresses(&self.args.arg_unlock)?,
 			enable_hardware_wallets: !self.args.flag_no_hardware_wallets,
+			external_signer: self.args.arg_external_signer.as_ref().map(|s| replace_home(&self.directories().base, s)),
+			external_signer_protocol: self.args.arg_external_signer_protocol.parse()?,
 			enable_fast_unlock: self.args.flag_fast_unlock,
+			enable_audit_log: self.args.flag_accounts_audit_log,
 		};
 
 		Ok(cfg)
@@ -891,11 +915,26 @@ impl Configuration {
 			support_token_api,
 			dapps_address: http.address(),
 			max_connections: self.args.arg_ws_max_connections,
+			confirmation_delay: self.confirmation_delay()?,
 		};
 
 		Ok(conf)
 	}
 
+	fn confirmation_delay(&self) -> Result<Option<ConfirmationDelay>, String> {
+		if self.args.arg_ui_confirmation_delay == 0 {
+			return Ok(None);
+		}
+
+		Ok(Some(ConfirmationDelay {
+			min_value: match self.args.arg_ui_delay_min_value {
+				Some(ref value) => to_u256(value)?,
+				None => 0.into(),
+			},
+			delay: Duration::from_secs(self.args.arg_ui_confirmation_delay),
+		}))
+	}
+
 	fn private_provider_config(&self) -> Result<(ProviderConfig, EncryptorConfig, bool), String> {
 		let provider_conf = ProviderConfig {
 			validator_accounts: to_addresses(&self.args.arg_private_validators)?,
@@ -1166,7 +1205,7 @@ mod tests {
 	use parity_rpc::NetworkSettings;
 	use updater::{UpdatePolicy, UpdateFilter, ReleaseTrack};
 
-	use account::{AccountCmd, NewAccount, ImportAccounts, ListAccounts};
+	use account::{AccountCmd, NewAccount, ImportAccounts, ListAccounts, BackupAccounts, RestoreAccounts};
 	use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, DataFormat, ExportState};
 	use cli::Args;
 	use dir::{Directories, default_hypervisor_path};
@@ -1235,6 +1274,30 @@ mod tests {
 		})));
 	}
 
+	#[test]
+	fn test_command_account_backup() {
+		let args = vec!["parity", "account", "backup", "--to", "accounts.backup", "--password", "pwd"];
+		let conf = parse(&args);
+		assert_eq!(conf.into_command().unwrap().cmd, Cmd::Account(AccountCmd::Backup(BackupAccounts {
+			path: Directories::default().keys,
+			spec: SpecType::default(),
+			to: "accounts.backup".into(),
+			password_file: Some("pwd".into()),
+		})));
+	}
+
+	#[test]
+	fn test_command_account_restore() {
+		let args = vec!["parity", "account", "restore", "--from", "accounts.backup"];
+		let conf = parse(&args);
+		assert_eq!(conf.into_command().unwrap().cmd, Cmd::Account(AccountCmd::Restore(RestoreAccounts {
+			path: Directories::default().keys,
+			spec: SpecType::default(),
+			from: "accounts.backup".into(),
+			password_file: None,
+		})));
+	}
+
 	#[test]
 	fn test_command_wallet_import() {
 		let args = vec!["parity", "wallet", "import", "my_wallet.json", "--password", "pwd"];
@@ -1360,6 +1423,7 @@ mod tests {
 			dapps_address: Some("127.0.0.1:8545".into()),
 			support_token_api: true,
 			max_connections: 100,
+			confirmation_delay: None,
 		}, LogConfig {
             color: true,
             mode: None,
@@ -1378,6 +1442,20 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_ui_confirmation_delay() {
+		let args = vec!["parity", "--ui-confirmation-delay", "600", "--ui-delay-min-value", "0xde0b6b3a7640000"];
+		let conf = parse(&args);
+
+		assert_eq!(conf.ws_config().unwrap(), WsConfiguration {
+			confirmation_delay: Some(ConfirmationDelay {
+				min_value: U256::from_dec_str("1000000000000000000").unwrap(),
+				delay: Duration::from_secs(600),
+			}),
+			..Default::default()
+		});
+	}
+
 	#[test]
 	fn test_run_cmd() {
 		let args = vec!["parity"];
@@ -1443,8 +1521,12 @@ mod tests {
 			verifier_settings: Default::default(),
 			serve_light: true,
 			light: false,
+			standalone_signer: false,
 			no_hardcoded_sync: false,
 			no_persistent_txqueue: false,
+			persistent_txqueue_all: false,
+			tx_queue_peer_rate: None,
+			tx_queue_rate: None,
 			whisper: Default::default(),
 		};
 		expected.secretstore_conf.enabled = cfg!(feature = "secretstore");
@@ -133,6 +133,7 @@ impl<T: LightChainClient + 'static> RegistrarClient for LightRegistrar<T> {
 					header: header.into(),
 					env_info: env_info,
 					engine: self.client.engine().clone(),
+					overrides: Default::default(),
 				})
 				.expect("No back-references; therefore all back-refs valid; qed")
 				.then(|res| match res {
@@ -99,6 +99,7 @@ pub fn to_pending_set(s: &str) -> Result<PendingSet, String> {
 pub fn to_queue_strategy(s: &str) -> Result<PrioritizationStrategy, String> {
 	match s {
 		"gas_price" => Ok(PrioritizationStrategy::GasPriceOnly),
+		"profit" => Ok(PrioritizationStrategy::Profit),
 		other => Err(format!("Invalid queue strategy: {}", other)),
 	}
 }
@@ -299,6 +300,17 @@ pub fn password_prompt() -> Result<String, String> {
 	Ok(password)
 }
 
+/// Ask the user for an already existing password.
+pub fn existing_password_prompt() -> Result<String, String> {
+	use rpassword::read_password;
+	const STDIN_ERROR: &'static str = "Unable to ask for password on non-interactive terminal.";
+
+	print!("Type password: ");
+	flush_stdout();
+
+	read_password().map_err(|_| STDIN_ERROR.to_owned())
+}
+
 /// Read a password from password file.
 pub fn password_from_file(path: String) -> Result<String, String> {
 	let passwords = passwords_from_files(&[path])?;
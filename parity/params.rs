@@ -17,6 +17,7 @@
 use std::{str, fs, fmt};
 use std::time::Duration;
 
+use ethcore::account_provider::ExternalSignerProtocol;
 use ethcore::client::Mode;
 use ethcore::ethereum;
 use ethcore::spec::{Spec, SpecParams};
@@ -203,7 +204,10 @@ pub struct AccountsConfig {
 	pub password_files: Vec<String>,
 	pub unlocked_accounts: Vec<Address>,
 	pub enable_hardware_wallets: bool,
+	pub external_signer: Option<String>,
+	pub external_signer_protocol: ExternalSignerProtocol,
 	pub enable_fast_unlock: bool,
+	pub enable_audit_log: bool,
 }
 
 impl Default for AccountsConfig {
@@ -215,7 +219,10 @@ impl Default for AccountsConfig {
 			password_files: Vec::new(),
 			unlocked_accounts: Vec::new(),
 			enable_hardware_wallets: true,
+			external_signer: None,
+			external_signer_protocol: ExternalSignerProtocol::default(),
 			enable_fast_unlock: false,
+			enable_audit_log: false,
 		}
 	}
 }
@@ -102,6 +102,7 @@ pub struct WsConfiguration {
 	pub signer_path: PathBuf,
 	pub support_token_api: bool,
 	pub dapps_address: Option<rpc::Host>,
+	pub confirmation_delay: Option<rpc::signer::ConfirmationDelay>,
 }
 
 impl Default for WsConfiguration {
@@ -118,6 +119,7 @@ impl Default for WsConfiguration {
 			signer_path: replace_home(&data_dir, "$BASE/signer").into(),
 			support_token_api: true,
 			dapps_address: Some("127.0.0.1:8545".into()),
+			confirmation_delay: None,
 		}
 	}
 }
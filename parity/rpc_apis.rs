@@ -37,7 +37,7 @@ use light::{TransactionQueue as LightTransactionQueue, Cache as LightDataCache};
 use miner::external::ExternalMiner;
 use node_health::NodeHealth;
 use parity_reactor;
-use parity_rpc::dispatch::{FullDispatcher, LightDispatcher};
+use parity_rpc::dispatch::{FullDispatcher, LightDispatcher, StandaloneDispatcher};
 use parity_rpc::informant::{ActivityNotifier, ClientNotifier};
 use parity_rpc::{Metadata, NetworkSettings, Host};
 use parking_lot::{Mutex, RwLock};
@@ -80,6 +80,8 @@ pub enum Api {
 	Whisper,
 	/// Whisper Pub-Sub (Safe but same concerns as above).
 	WhisperPubSub,
+	/// Clef-compatible external signer API (Safe: requests of locked accounts need confirmation in Signer)
+	Account,
 }
 
 impl FromStr for Api {
@@ -105,6 +107,7 @@ impl FromStr for Api {
 			"private" => Ok(Private),
 			"shh" => Ok(Whisper),
 			"shh_pubsub" => Ok(WhisperPubSub),
+			"account" => Ok(Account),
 			api => Err(format!("Unknown api: {}", api))
 		}
 	}
@@ -189,6 +192,7 @@ fn to_modules(apis: &HashSet<Api>) -> BTreeMap<String, String> {
 			Api::Private => ("private", "1.0"),
 			Api::Whisper => ("shh", "1.0"),
 			Api::WhisperPubSub => ("shh_pubsub", "1.0"),
+			Api::Account => ("account", "1.0"),
 		};
 		modules.insert(name.into(), version.into());
 	}
@@ -290,7 +294,7 @@ impl FullDependencies {
 							gas_price_percentile: self.gas_price_percentile,
 						}
 					);
-					handler.extend_with(client.to_delegate());
+					handler.extend_with(client.to_call_delegate());
 
 					if !for_generic_pubsub {
 						let filter_client = EthFilterClient::new(self.client.clone(), self.miner.clone());
@@ -348,7 +352,32 @@ impl FullDependencies {
 						let mut rpc = MetaIoHandler::default();
 						let apis = ApiSet::List(apis.clone()).retain(ApiSet::PubSub).list_apis();
 						self.extend_api(&mut rpc, &apis, true);
-						handler.extend_with(PubSubClient::new(rpc, self.remote.clone()).to_delegate());
+						use ethcore::client::BlockChainClient;
+
+						let client = PubSubClient::new(rpc, self.remote.clone());
+						let h = client.transaction_pool_events_handler();
+						let chain = Arc::downgrade(&self.client);
+						let eip86_transition = self.client.eip86_transition();
+						let remote = self.remote.clone();
+						self.miner.add_transaction_events_listener(Box::new(move |events| {
+							// Listeners run with the pool locked, so events are converted on the event loop.
+							let events = events.to_vec();
+							let (h, chain) = (h.clone(), chain.clone());
+							remote.spawn_fn(move |_| {
+								use ethcore::client::ChainInfo;
+								use parity_rpc::v1::TransactionPoolEvent;
+
+								if let (Some(h), Some(chain)) = (h.upgrade(), chain.upgrade()) {
+									let block_number = chain.chain_info().best_block_number;
+									let events = events.into_iter()
+										.map(|event| TransactionPoolEvent::from(event, block_number, eip86_transition))
+										.collect::<Vec<_>>();
+									h.notify(&events);
+								}
+								Ok(())
+							});
+						}));
+						handler.extend_with(client.to_delegate());
 					}
 				},
 				Api::ParityAccounts => {
@@ -366,7 +395,7 @@ impl FullDependencies {
 					).to_delegate())
 				},
 				Api::Traces => {
-					handler.extend_with(TracesClient::new(&self.client).to_delegate())
+					handler.extend_with(TracesClient::new(&self.client).to_call_delegate())
 				},
 				Api::Rpc => {
 					let modules = to_modules(&apis);
@@ -394,6 +423,9 @@ impl FullDependencies {
 				Api::Private => {
 					handler.extend_with(PrivateClient::new(self.private_tx_service.as_ref().map(|p| p.provider())).to_delegate());
 				},
+				Api::Account => {
+					add_signing_methods!(Account, handler, self, nonces.clone());
+				},
 			}
 		}
 	}
@@ -505,7 +537,7 @@ impl<C: LightChainClient + 'static> LightDependencies<C> {
 						self.cache.clone(),
 						self.gas_price_percentile,
 					);
-					handler.extend_with(Eth::to_delegate(client.clone()));
+					handler.extend_with(EthCall::to_call_delegate(client.clone()));
 
 					if !for_generic_pubsub {
 						handler.extend_with(EthFilter::to_delegate(client));
@@ -578,7 +610,7 @@ impl<C: LightChainClient + 'static> LightDependencies<C> {
 					).to_delegate())
 				},
 				Api::Traces => {
-					handler.extend_with(light::TracesClient.to_delegate())
+					handler.extend_with(light::TracesClient.to_call_delegate())
 				},
 				Api::Rpc => {
 					let modules = to_modules(&apis);
@@ -604,7 +636,10 @@ impl<C: LightChainClient + 'static> LightDependencies<C> {
 						let private_tx_service = Some(tx_manager.clone());
 						handler.extend_with(PrivateClient::new(private_tx_service).to_delegate());
 					}
-				}
+				},
+				Api::Account => {
+					add_signing_methods!(Account, handler, self);
+				},
 			}
 		}
 	}
@@ -624,6 +659,62 @@ impl<T: LightChainClient + 'static> Dependencies for LightDependencies<T> {
 	}
 }
 
+/// Standalone signer notifier. There is no client to keep awake.
+pub struct SignerNotifier;
+
+impl ActivityNotifier for SignerNotifier {
+	fn active(&self) {}
+}
+
+/// RPC dependencies for a standalone signer, running without any chain.
+pub struct SignerDependencies {
+	pub signer_service: Arc<SignerService>,
+	pub secret_store: Arc<AccountProvider>,
+	pub remote: parity_reactor::Remote,
+	pub chain_id: Option<u64>,
+}
+
+impl Dependencies for SignerDependencies {
+	type Notifier = SignerNotifier;
+
+	fn activity_notifier(&self) -> Self::Notifier { SignerNotifier }
+
+	fn extend_with_set<S>(
+		&self,
+		handler: &mut MetaIoHandler<Metadata, S>,
+		apis: &HashSet<Api>,
+	) where S: core::Middleware<Metadata> {
+		use parity_rpc::v1::*;
+
+		let dispatcher = StandaloneDispatcher::new(self.chain_id);
+		for api in apis {
+			match *api {
+				Api::Account => {
+					if self.signer_service.is_enabled() {
+						handler.extend_with(Account::to_delegate(
+							SigningQueueClient::new(&self.signer_service, dispatcher.clone(), self.remote.clone(), &self.secret_store)
+						))
+					} else {
+						handler.extend_with(Account::to_delegate(SigningUnsafeClient::new(&self.secret_store, dispatcher.clone())))
+					}
+				},
+				Api::Signer => {
+					handler.extend_with(SignerClient::new(&self.secret_store, dispatcher.clone(), &self.signer_service, self.remote.clone()).to_delegate());
+				},
+				Api::ParityAccounts => {
+					handler.extend_with(ParityAccountsClient::new(&self.secret_store).to_delegate());
+				},
+				Api::Rpc => {
+					let modules = to_modules(&apis);
+					handler.extend_with(RpcClient::new(modules).to_delegate());
+				},
+				// all other APIs require a chain
+				_ => {},
+			}
+		}
+	}
+}
+
 impl ApiSet {
 	/// Retains only APIs in given set.
 	pub fn retain(self, set: Self) -> Self {
@@ -654,6 +745,7 @@ impl ApiSet {
 				public_list.insert(Api::Traces);
 				public_list.insert(Api::ParityPubSub);
 				public_list.insert(Api::ParityAccounts);
+				public_list.insert(Api::Account);
 				public_list
 			},
 			ApiSet::SafeContext => {
@@ -663,6 +755,7 @@ impl ApiSet {
 				public_list.insert(Api::ParitySet);
 				public_list.insert(Api::Signer);
 				public_list.insert(Api::SecretStore);
+				public_list.insert(Api::Account);
 				public_list
 			},
 			ApiSet::All => {
@@ -673,6 +766,7 @@ impl ApiSet {
 				public_list.insert(Api::Signer);
 				public_list.insert(Api::Personal);
 				public_list.insert(Api::SecretStore);
+				public_list.insert(Api::Account);
 				public_list
 			},
 			ApiSet::PubSub => [
@@ -707,6 +801,7 @@ mod test {
 		assert_eq!(Api::Private, "private".parse().unwrap());
 		assert_eq!(Api::Whisper, "shh".parse().unwrap());
 		assert_eq!(Api::WhisperPubSub, "shh_pubsub".parse().unwrap());
+		assert_eq!(Api::Account, "account".parse().unwrap());
 		assert!("rp".parse::<Api>().is_err());
 	}
 
@@ -735,7 +830,7 @@ mod test {
 			// safe
 			Api::Web3, Api::Net, Api::Eth, Api::EthPubSub, Api::Parity, Api::ParityPubSub, Api::Traces, Api::Rpc, Api::Whisper, Api::WhisperPubSub, Api::Private,
 			// semi-safe
-			Api::ParityAccounts
+			Api::ParityAccounts, Api::Account,
 		].into_iter().collect();
 		assert_eq!(ApiSet::IpcContext.list_apis(), expected);
 	}
@@ -746,7 +841,7 @@ mod test {
 			// safe
 			Api::Web3, Api::Net, Api::Eth, Api::EthPubSub, Api::Parity, Api::ParityPubSub, Api::Traces, Api::Rpc, Api::SecretStore, Api::Whisper, Api::WhisperPubSub, Api::Private,
 			// semi-safe
-			Api::ParityAccounts,
+			Api::ParityAccounts, Api::Account,
 			// Unsafe
 			Api::ParitySet, Api::Signer,
 		].into_iter().collect();
@@ -757,7 +852,7 @@ mod test {
 	fn test_all_apis() {
 		assert_eq!("all".parse::<ApiSet>().unwrap(), ApiSet::List(vec![
 			Api::Web3, Api::Net, Api::Eth, Api::EthPubSub, Api::Parity, Api::ParityPubSub, Api::Traces, Api::Rpc, Api::SecretStore, Api::Whisper, Api::WhisperPubSub,
-			Api::ParityAccounts,
+			Api::ParityAccounts, Api::Account,
 			Api::ParitySet, Api::Signer,
 			Api::Personal,
 			Api::Private,
@@ -768,7 +863,7 @@ mod test {
 	fn test_all_without_personal_apis() {
 		assert_eq!("personal,all,-personal".parse::<ApiSet>().unwrap(), ApiSet::List(vec![
 			Api::Web3, Api::Net, Api::Eth, Api::EthPubSub, Api::Parity, Api::ParityPubSub, Api::Traces, Api::Rpc, Api::SecretStore, Api::Whisper, Api::WhisperPubSub,
-			Api::ParityAccounts,
+			Api::ParityAccounts, Api::Account,
 			Api::ParitySet, Api::Signer,
 			Api::Private
 		].into_iter().collect()));
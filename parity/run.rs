@@ -15,6 +15,7 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::any::Any;
+use std::collections::HashSet;
 use std::fmt;
 use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
@@ -126,7 +127,11 @@ pub struct RunCmd {
 	pub verifier_settings: VerifierSettings,
 	pub serve_light: bool,
 	pub light: bool,
+	pub standalone_signer: bool,
 	pub no_persistent_txqueue: bool,
+	pub persistent_txqueue_all: bool,
+	pub tx_queue_peer_rate: Option<usize>,
+	pub tx_queue_rate: Option<usize>,
 	pub whisper: ::whisper::Config,
 	pub no_hardcoded_sync: bool,
 }
@@ -134,6 +139,7 @@ pub struct RunCmd {
 // node info fetcher for the local store.
 struct FullNodeInfo {
 	miner: Option<Arc<Miner>>, // TODO: only TXQ needed, just use that after decoupling.
+	persist_all: bool,
 }
 
 impl ::local_store::NodeInfo for FullNodeInfo {
@@ -151,6 +157,28 @@ impl ::local_store::NodeInfo for FullNodeInfo {
 			})
 			.collect()
 	}
+
+	fn queued_transactions(&self) -> Vec<::local_store::QueuedTransaction> {
+		use miner::pool::Priority;
+
+		let miner = match self.miner.as_ref() {
+			Some(m) if self.persist_all => m,
+			_ => return Vec::new(),
+		};
+
+		let mut queued = miner.queued_transactions();
+		queued.sort_by_key(|tx| tx.insertion_id());
+		queued.into_iter()
+			.map(|tx| ::local_store::QueuedTransaction {
+				transaction: tx.pending().clone(),
+				priority: match tx.priority() {
+					Priority::Local => ::local_store::Priority::Local,
+					Priority::Retracted => ::local_store::Priority::Retracted,
+					Priority::Regular => ::local_store::Priority::Regular,
+				},
+			})
+			.collect()
+	}
 }
 
 type LightClient = ::light::client::Client<::light_helpers::EpochFetch>;
@@ -393,6 +421,60 @@ fn execute_light_impl(cmd: RunCmd, logger: Arc<RotatingLogger>) -> Result<Runnin
 	})
 }
 
+fn execute_signer_impl(cmd: RunCmd) -> Result<RunningClient, String> {
+	// load spec
+	let spec = cmd.spec.spec(SpecParams::new(cmd.dirs.cache.as_ref(), OptimizeFor::Memory))?;
+	let chain_id = spec.chain_id();
+
+	// create dirs used by parity
+	cmd.dirs.create_dirs(false, cmd.ws_conf.support_token_api, false)?;
+
+	info!("Starting {}", Colour::White.bold().paint(version()));
+	info!("Keys path {}", Colour::White.bold().paint(cmd.dirs.keys_path(&spec.data_dir).to_string_lossy().into_owned()));
+	info!("Running as a {} for chain id {}.", Colour::Blue.bold().paint("Standalone Signer"), chain_id);
+
+	let passwords = passwords_from_files(&cmd.acc_conf.password_files)?;
+
+	// prepare account provider
+	let account_provider = Arc::new(prepare_account_provider(&cmd.spec, &cmd.dirs, &spec.data_dir, cmd.acc_conf, &passwords)?);
+	let signer_service = Arc::new(signer::new_service(&cmd.ws_conf, &cmd.logger_config));
+
+	// spin up event loop
+	let event_loop = EventLoop::spawn();
+
+	let deps_for_rpc_apis = Arc::new(rpc_apis::SignerDependencies {
+		signer_service,
+		secret_store: account_provider,
+		remote: event_loop.remote(),
+		chain_id: Some(chain_id),
+	});
+
+	let dependencies = rpc::Dependencies {
+		apis: deps_for_rpc_apis.clone(),
+		remote: event_loop.raw_remote(),
+		stats: Arc::new(informant::RpcStats::default()),
+		pool: None,
+	};
+
+	// only the account API is exposed over IPC, confirmations go through authenticated WebSockets sessions
+	let mut ipc_conf = cmd.ipc_conf;
+	ipc_conf.apis = rpc_apis::ApiSet::List(vec![rpc_apis::Api::Account].into_iter().collect());
+	let mut ws_conf = cmd.ws_conf;
+	ws_conf.apis = rpc_apis::ApiSet::List(Default::default());
+
+	// start rpc servers
+	let rpc_direct = rpc::setup_apis(rpc_apis::ApiSet::All, &dependencies);
+	let ws_server = rpc::new_ws(ws_conf, &dependencies)?;
+	let ipc_server = rpc::new_ipc(ipc_conf, &dependencies)?;
+
+	Ok(RunningClient {
+		inner: RunningClientInner::Signer {
+			rpc: rpc_direct,
+			keep_alive: Box::new((event_loop, ws_server, ipc_server)),
+		}
+	})
+}
+
 fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq: Cr,
 						on_updater_rq: Rr) -> Result<RunningClient, String>
 	where Cr: Fn(String) + 'static + Send,
@@ -501,6 +583,8 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 	};
 	sync_config.download_old_blocks = cmd.download_old_blocks;
 	sync_config.serve_light = cmd.serve_light;
+	sync_config.max_peer_transactions_per_second = cmd.tx_queue_peer_rate;
+	sync_config.max_transactions_per_second = cmd.tx_queue_rate;
 
 	let passwords = passwords_from_files(&cmd.acc_conf.password_files)?;
 
@@ -537,13 +621,14 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 			return Err(format!("Consensus signer account not found for the current chain. {}", build_create_account_hint(&cmd.spec, &cmd.dirs.keys)));
 		}
 
-		// Check if any passwords have been read from the password file(s)
-		if passwords.is_empty() {
+		if account_provider.is_external_address(&engine_signer) {
+			// Keys in the external signer are not protected by a password.
+			miner.set_author(engine_signer, Some(String::new()))
+				.map_err(|e| format!("Consensus signer {} rejected by the external signer: {}", engine_signer, e))?;
+		} else if passwords.is_empty() {
+			// Check if any passwords have been read from the password file(s)
 			return Err(format!("No password found for the consensus signer {}. {}", engine_signer, VERIFY_PASSWORD_HINT));
-		}
-
-		// Attempt to sign in the engine signer.
-		if !passwords.iter().any(|p| miner.set_author(engine_signer, Some(p.to_owned())).is_ok()) {
+		} else if !passwords.iter().any(|p| miner.set_author(engine_signer, Some(p.to_owned())).is_ok()) {
 			return Err(format!("No valid password for the consensus signer {}. {}", engine_signer, VERIFY_PASSWORD_HINT));
 		}
 	}
@@ -620,7 +705,8 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 			miner: match cmd.no_persistent_txqueue {
 				true => None,
 				false => Some(miner.clone()),
-			}
+			},
+			persist_all: cmd.persistent_txqueue_all,
 		};
 
 		let store = ::local_store::create(db, ::ethcore::db::COL_NODE_INFO, node_info);
@@ -633,10 +719,37 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 			}
 		}
 
+		// re-queue the whole transaction queue, preserving insertion order.
+		let mut restored = HashSet::new();
+		match store.queued_transactions() {
+			Ok(ref queued) if queued.is_empty() => {},
+			Ok(queued) => {
+				use miner::pool::verifier::Transaction;
+
+				let transactions = queued.into_iter()
+					.map(|queued| {
+						restored.insert(queued.transaction.hash());
+						match queued.priority {
+							::local_store::Priority::Local => Transaction::Local(queued.transaction),
+							::local_store::Priority::Retracted => Transaction::Retracted(queued.transaction.transaction.into()),
+							::local_store::Priority::Regular => Transaction::Unverified(queued.transaction.transaction.into()),
+						}
+					})
+					.collect::<Vec<_>>();
+				let total = transactions.len();
+				let imported = miner.import_restored_transactions(&*client, transactions)
+					.into_iter()
+					.filter(|result| result.is_ok())
+					.count();
+				info!("Restored {} out of {} queued transactions.", imported, total);
+			},
+			Err(e) => warn!("Error loading cached queued transactions from disk: {}", e),
+		}
+
 		// re-queue pending transactions.
 		match store.pending_transactions() {
 			Ok(pending) => {
-				for pending_tx in pending {
+				for pending_tx in pending.into_iter().filter(|tx| !restored.contains(&tx.hash())) {
 					if let Err(e) = miner.import_own_transaction(&*client, pending_tx) {
 						warn!("Error importing saved transaction: {}", e)
 					}
@@ -897,6 +1010,10 @@ enum RunningClientInner {
 		client_service: Arc<ClientService>,
 		keep_alive: Box<Any>,
 	},
+	Signer {
+		rpc: jsonrpc_core::MetaIoHandler<Metadata, informant::Middleware<rpc_apis::SignerNotifier>>,
+		keep_alive: Box<Any>,
+	},
 }
 
 impl RunningClient {
@@ -915,6 +1032,9 @@ impl RunningClient {
 			RunningClientInner::Full { ref rpc, .. } => {
 				rpc.handle_request_sync(request, metadata)
 			},
+			RunningClientInner::Signer { ref rpc, .. } => {
+				rpc.handle_request_sync(request, metadata)
+			},
 		}
 	}
 
@@ -949,7 +1069,11 @@ impl RunningClient {
 				drop(informant);
 				drop(client);
 				wait_for_drop(weak_client);
-			}
+			},
+			RunningClientInner::Signer { rpc, keep_alive } => {
+				drop(rpc);
+				drop(keep_alive);
+			},
 		}
 	}
 }
@@ -967,7 +1091,9 @@ pub fn execute<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>,
 	where Cr: Fn(String) + 'static + Send,
 		  Rr: Fn() + 'static + Send
 {
-	if cmd.light {
+	if cmd.standalone_signer {
+		execute_signer_impl(cmd)
+	} else if cmd.light {
 		execute_light_impl(cmd, logger)
 	} else {
 		execute_impl(cmd, logger, on_client_rq, on_updater_rq)
@@ -1008,7 +1134,10 @@ fn prepare_account_provider(spec: &SpecType, dirs: &Directories, data_dir: &str,
 	let account_settings = AccountProviderSettings {
 		enable_hardware_wallets: cfg.enable_hardware_wallets,
 		hardware_wallet_classic_key: spec == &SpecType::Classic,
+		external_signer: cfg.external_signer,
+		external_signer_protocol: cfg.external_signer_protocol,
 		unlock_keep_secret: cfg.enable_fast_unlock,
+		audit_log: cfg.enable_audit_log,
 		blacklisted_accounts: 	match *spec {
 			SpecType::Morden | SpecType::Ropsten | SpecType::Kovan | SpecType::Dev => vec![],
 			_ => vec![
@@ -1024,7 +1153,7 @@ fn prepare_account_provider(spec: &SpecType, dirs: &Directories, data_dir: &str,
 	let account_provider = AccountProvider::new(
 		Box::new(ethstore),
 		account_settings,
-	);
+	).map_err(|e| format!("Could not open accounts: {}", e))?;
 
 	for a in cfg.unlocked_accounts {
 		// Check if the account exists
@@ -38,7 +38,7 @@ pub fn new_service(ws_conf: &rpc::WsConfiguration, logger_config: &LogConfig) ->
 
 	rpc_apis::SignerService::new(move || {
 		generate_new_token(&signer_path, logger_config_color).map_err(|e| format!("{:?}", e))
-	}, signer_enabled)
+	}, signer_enabled).with_confirmation_delay(ws_conf.confirmation_delay.clone())
 }
 
 pub fn codes_path(path: &Path) -> PathBuf {
@@ -0,0 +1,97 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parameters of calls executed with state overrides.
+//!
+//! `build_rpc_trait!` supports a single optional trailing parameter only, so methods taking
+//! an optional block number followed by optional state overrides parse their parameters here.
+
+use ethcore::state::StateOverride as EthStateOverride;
+use jsonrpc_core::{Error, Params, Result, Value};
+use serde::Serialize;
+use serde_json;
+
+use v1::helpers::errors;
+use v1::types::{BlockNumber, StateOverride};
+
+/// Splits positional `params` into the `required` leading parameters,
+/// an optional block number and optional state overrides.
+pub fn split(params: Params, required: usize) -> Result<(Params, BlockNumber, StateOverride)> {
+	let mut values = match params {
+		Params::Array(values) => values,
+		Params::None => Vec::new(),
+		Params::Map(_) => return Err(errors::invalid_params("params", "expected an array")),
+	};
+
+	if values.len() < required || values.len() > required + 2 {
+		return Err(errors::invalid_params("params", format!("expected {} to {} parameters, got {}", required, required + 2, values.len())));
+	}
+
+	let mut trailing = values.split_off(required).into_iter();
+	let num = match trailing.next() {
+		Some(num) => serde_json::from_value(num).map_err(|e| errors::invalid_params("block number", e))?,
+		None => BlockNumber::default(),
+	};
+	let overrides = match trailing.next() {
+		Some(overrides) => serde_json::from_value(overrides).map_err(|e| errors::invalid_params("overrides", e))?,
+		None => StateOverride::default(),
+	};
+
+	Ok((Params::Array(values), num, overrides))
+}
+
+/// Converts state overrides into their ethcore representation.
+pub fn state_override(overrides: StateOverride) -> Result<EthStateOverride> {
+	overrides.into_overrides()
+		.map_err(|address| errors::invalid_params("overrides", format!("Both state and stateDiff given for {:?}", address)))
+}
+
+/// Serializes the result of a method registered by hand.
+pub fn to_value<T: Serialize>(value: T) -> ::std::result::Result<Value, Error> {
+	serde_json::to_value(value).map_err(|e| errors::internal("Could not serialize the result", e))
+}
+
+#[cfg(test)]
+mod tests {
+	use jsonrpc_core::Params;
+	use serde_json;
+	use v1::types::{BlockNumber, StateOverride};
+	use super::split;
+
+	fn params(s: &str) -> Params {
+		serde_json::from_str(s).unwrap()
+	}
+
+	#[test]
+	fn split_call_params() {
+		let (leading, num, overrides) = split(params(r#"[{}]"#), 1).unwrap();
+		assert_eq!(leading, params(r#"[{}]"#));
+		assert_eq!(num, BlockNumber::Latest);
+		assert_eq!(overrides, StateOverride::default());
+
+		let (leading, num, overrides) = split(params(r#"[{}, "pending", {"0x0000000000000000000000000000000000000001": {}}]"#), 1).unwrap();
+		assert_eq!(leading, params(r#"[{}]"#));
+		assert_eq!(num, BlockNumber::Pending);
+		assert_eq!(overrides, serde_json::from_str(r#"{"0x0000000000000000000000000000000000000001": {}}"#).unwrap());
+	}
+
+	#[test]
+	fn split_rejects_wrong_number_of_params() {
+		assert!(split(params(r#"[]"#), 1).is_err());
+		assert!(split(params(r#"[{}, "latest", {}, {}]"#), 1).is_err());
+		assert!(split(params(r#"[{}, "latest", {"foo": 1}]"#), 1).is_err());
+	}
+}
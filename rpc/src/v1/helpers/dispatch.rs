@@ -36,25 +36,32 @@ use sync::LightSync;
 use ethcore::ids::BlockId;
 use ethcore::client::BlockChainClient;
 use ethcore::miner::{self, MinerService};
-use ethcore::account_provider::AccountProvider;
+use ethcore::account_provider::{AccountProvider, PolicyApproval, SafeTransaction, with_audit_origin};
 use crypto::DEFAULT_MAC;
 use transaction::{Action, SignedTransaction, PendingTransaction, Transaction};
 
 use jsonrpc_core::{BoxFuture, Result, Error};
 use jsonrpc_core::futures::{future, Future, Poll, Async};
 use jsonrpc_core::futures::future::Either;
-use v1::helpers::{errors, nonce, TransactionRequest, FilledTransactionRequest, ConfirmationPayload};
+use v1::helpers::{eip712, errors, nonce, TransactionRequest, FilledTransactionRequest, ConfirmationPayload};
 use v1::types::{
 	H256 as RpcH256, H520 as RpcH520, Bytes as RpcBytes,
 	RichRawTransaction as RpcRichRawTransaction,
 	ConfirmationPayload as RpcConfirmationPayload,
 	ConfirmationResponse,
+	PolicyApproval as RpcPolicyApproval,
 	SignRequest as RpcSignRequest,
+	SignTypedDataRequest as RpcSignTypedDataRequest,
 	DecryptRequest as RpcDecryptRequest,
+	Origin,
 };
 
 pub use self::nonce::Reservations;
 
+/// Gas added on top of the inner call for executing a policy account transaction,
+/// covering signature verification by the Safe contract.
+const POLICY_GAS_OVERHEAD: u64 = 100_000;
+
 /// Has the capability to dispatch, sign, and decrypt.
 ///
 /// Requires a clone implementation, with the implication that it be cheap;
@@ -68,7 +75,8 @@ pub trait Dispatcher: Send + Sync + Clone {
 		-> BoxFuture<FilledTransactionRequest>;
 
 	/// Sign the given transaction request without dispatching, fetching appropriate nonce.
-	fn sign(&self, accounts: Arc<AccountProvider>, filled: FilledTransactionRequest, password: SignWith)
+	/// The signature is audited as requested from given origin.
+	fn sign(&self, accounts: Arc<AccountProvider>, filled: FilledTransactionRequest, password: SignWith, origin: Origin)
 		-> BoxFuture<WithToken<SignedTransaction>>;
 
 	/// Converts a `SignedTransaction` into `RichRawTransaction`
@@ -77,6 +85,9 @@ pub trait Dispatcher: Send + Sync + Clone {
 	/// "Dispatch" a local transaction.
 	fn dispatch_transaction(&self, signed_transaction: PendingTransaction)
 		-> Result<H256>;
+
+	/// Execute a read-only contract call against the latest state.
+	fn call_contract(&self, address: Address, data: Bytes) -> BoxFuture<Bytes>;
 }
 
 /// A dispatcher which uses references to a client and miner in order to sign
@@ -159,19 +170,20 @@ impl<C: miner::BlockChainClient + BlockChainClient, M: MinerService> Dispatcher
 		}))
 	}
 
-	fn sign(&self, accounts: Arc<AccountProvider>, filled: FilledTransactionRequest, password: SignWith)
+	fn sign(&self, accounts: Arc<AccountProvider>, filled: FilledTransactionRequest, password: SignWith, origin: Origin)
 		-> BoxFuture<WithToken<SignedTransaction>>
 	{
 		let chain_id = self.client.signing_chain_id();
+		try_bf!(authorize_spending(&*accounts, &filled, &password));
 
 		if let Some(nonce) = filled.nonce {
-			return Box::new(future::done(sign_transaction(&*accounts, filled, chain_id, nonce, password)));
+			return Box::new(future::done(with_origin(&origin, || sign_transaction(&*accounts, filled, chain_id, nonce, password))));
 		}
 
 		let state = self.state_nonce(&filled.from);
 		let reserved = self.nonces.lock().reserve(filled.from, state);
 
-		Box::new(ProspectiveSigner::new(accounts, filled, chain_id, reserved, password))
+		Box::new(ProspectiveSigner::new(accounts, filled, chain_id, reserved, password, origin))
 	}
 
 	fn enrich(&self, signed_transaction: SignedTransaction) -> RpcRichRawTransaction {
@@ -182,6 +194,12 @@ impl<C: miner::BlockChainClient + BlockChainClient, M: MinerService> Dispatcher
 	fn dispatch_transaction(&self, signed_transaction: PendingTransaction) -> Result<H256> {
 		Self::dispatch_transaction(&*self.client, &*self.miner, signed_transaction)
 	}
+
+	fn call_contract(&self, address: Address, data: Bytes) -> BoxFuture<Bytes> {
+		Box::new(future::done(
+			self.client.call_contract(BlockId::Latest, address, data).map_err(errors::execution)
+		))
+	}
 }
 
 /// Get a recent gas price corpus.
@@ -381,14 +399,15 @@ impl Dispatcher for LightDispatcher {
 		}
 	}
 
-	fn sign(&self, accounts: Arc<AccountProvider>, filled: FilledTransactionRequest, password: SignWith)
+	fn sign(&self, accounts: Arc<AccountProvider>, filled: FilledTransactionRequest, password: SignWith, origin: Origin)
 		-> BoxFuture<WithToken<SignedTransaction>>
 	{
 		let chain_id = self.client.signing_chain_id();
+		try_bf!(authorize_spending(&*accounts, &filled, &password));
 
 		// fast path for pre-filled nonce.
 		if let Some(nonce) = filled.nonce {
-			return Box::new(future::done(sign_transaction(&*accounts, filled, chain_id, nonce, password)))
+			return Box::new(future::done(with_origin(&origin, || sign_transaction(&*accounts, filled, chain_id, nonce, password))))
 		}
 
 		let nonces = self.nonces.clone();
@@ -397,7 +416,7 @@ impl Dispatcher for LightDispatcher {
 			.and_then(move |nonce| {
 				let reserved = nonces.lock().reserve(filled.from, nonce);
 
-				ProspectiveSigner::new(accounts, filled, chain_id, reserved, password)
+				ProspectiveSigner::new(accounts, filled, chain_id, reserved, password, origin)
 			}))
 	}
 
@@ -413,6 +432,86 @@ impl Dispatcher for LightDispatcher {
 			.map_err(errors::transaction)
 			.map(|_| hash)
 	}
+
+	fn call_contract(&self, _address: Address, _data: Bytes) -> BoxFuture<Bytes> {
+		Box::new(future::err(errors::light_unimplemented(None)))
+	}
+}
+
+/// A dispatcher for standalone signers which have no access to any chain.
+///
+/// Transactions are only signed, so nonce, gas and gas price have to be provided by the caller.
+#[derive(Debug, Clone)]
+pub struct StandaloneDispatcher {
+	chain_id: Option<u64>,
+}
+
+impl StandaloneDispatcher {
+	/// Create a `StandaloneDispatcher` signing transactions for given chain id.
+	pub fn new(chain_id: Option<u64>) -> Self {
+		StandaloneDispatcher { chain_id }
+	}
+}
+
+impl Dispatcher for StandaloneDispatcher {
+	fn fill_optional_fields(&self, request: TransactionRequest, default_sender: Address, _force_nonce: bool)
+		-> BoxFuture<FilledTransactionRequest>
+	{
+		let (gas, gas_price) = match (request.gas, request.gas_price) {
+			(Some(gas), Some(gas_price)) => (gas, gas_price),
+			_ => return Box::new(future::err(errors::invalid_params("request", "Gas and gas price are required by standalone signer."))),
+		};
+
+		Box::new(future::ok(FilledTransactionRequest {
+			from: request.from.unwrap_or(default_sender),
+			used_default_from: request.from.is_none(),
+			to: request.to,
+			nonce: request.nonce,
+			gas_price,
+			gas,
+			value: request.value.unwrap_or_else(|| 0.into()),
+			data: request.data.unwrap_or_else(Vec::new),
+			condition: request.condition,
+		}))
+	}
+
+	fn sign(&self, accounts: Arc<AccountProvider>, filled: FilledTransactionRequest, password: SignWith, origin: Origin)
+		-> BoxFuture<WithToken<SignedTransaction>>
+	{
+		let nonce = match filled.nonce {
+			Some(nonce) => nonce,
+			None => return Box::new(future::err(errors::invalid_params("request", "Nonce is required by standalone signer."))),
+		};
+
+		try_bf!(authorize_spending(&*accounts, &filled, &password));
+		Box::new(future::done(with_origin(&origin, || sign_transaction(&*accounts, filled, self.chain_id, nonce, password))))
+	}
+
+	fn enrich(&self, signed_transaction: SignedTransaction) -> RpcRichRawTransaction {
+		// there is no chain, so EIP-86 is never active.
+		RpcRichRawTransaction::from_signed(signed_transaction, 0, u64::max_value())
+	}
+
+	fn dispatch_transaction(&self, _signed_transaction: PendingTransaction) -> Result<H256> {
+		Err(errors::unsupported("Standalone signer can't dispatch transactions.", None))
+	}
+
+	fn call_contract(&self, _address: Address, _data: Bytes) -> BoxFuture<Bytes> {
+		Box::new(future::err(errors::unsupported("Standalone signer can't call contracts.", None)))
+	}
+}
+
+/// Checks the transaction against the spending limit of its sender and records the spent value.
+///
+/// Has to be called exactly once per request, before any signing attempt.
+fn authorize_spending(accounts: &AccountProvider, filled: &FilledTransactionRequest, password: &SignWith) -> Result<()> {
+	// transactions confirmed with a password are not restricted, only the ones relying on an unlocked account
+	if let SignWith::Nothing = *password {
+		accounts.authorize_spending(&filled.from, filled.to.as_ref(), &filled.value, &filled.data)
+			.map_err(errors::spending_limit)?;
+	}
+
+	Ok(())
 }
 
 fn sign_transaction(
@@ -435,6 +534,14 @@ fn sign_transaction(
 		return hardware_signature(accounts, filled.from, t, chain_id).map(WithToken::No)
 	}
 
+	if accounts.is_external_address(&filled.from) {
+		return external_signature(accounts, filled.from, t, chain_id).map(WithToken::No)
+	}
+
+	if accounts.is_policy_address(&filled.from) {
+		return Err(errors::unsupported("Transactions of policy accounts have to be approved by their owners in the signer.", None))
+	}
+
 	let hash = t.hash(chain_id);
 	let signature = signature(accounts, filled.from, hash, password)?;
 
@@ -460,6 +567,7 @@ struct ProspectiveSigner {
 	state: ProspectiveSignerState,
 	prospective: Option<Result<WithToken<SignedTransaction>>>,
 	ready: Option<nonce::Ready>,
+	origin: Origin,
 }
 
 impl ProspectiveSigner {
@@ -469,6 +577,7 @@ impl ProspectiveSigner {
 		chain_id: Option<u64>,
 		reserved: nonce::Reserved,
 		password: SignWith,
+		origin: Origin,
 	) -> Self {
 		// If the account is permanently unlocked we can try to sign
 		// using prospective nonce. This should speed up sending
@@ -489,17 +598,18 @@ impl ProspectiveSigner {
 			},
 			prospective: None,
 			ready: None,
+			origin,
 		}
 	}
 
 	fn sign(&self, nonce: &U256) -> Result<WithToken<SignedTransaction>> {
-		sign_transaction(
+		with_origin(&self.origin, || sign_transaction(
 			&*self.accounts,
 			self.filled.clone(),
 			self.chain_id,
 			*nonce,
 			self.password.clone()
-		)
+		))
 	}
 
 	fn poll_reserved(&mut self) -> Poll<nonce::Ready, Error> {
@@ -644,17 +754,28 @@ impl<T: Debug> From<(T, Option<AccountToken>)> for WithToken<T> {
 	}
 }
 
+/// Runs `f` with the account operations it performs audited as requested from given origin.
+///
+/// Has to wrap the account operation itself, futures may be polled outside of the request.
+pub fn with_origin<T, F: FnOnce() -> T>(origin: &Origin, f: F) -> T {
+	with_audit_origin(Some(origin.to_string()), f)
+}
+
 /// Execute a confirmation payload.
 pub fn execute<D: Dispatcher + 'static>(
 	dispatcher: D,
 	accounts: Arc<AccountProvider>,
 	payload: ConfirmationPayload,
-	pass: SignWith
+	pass: SignWith,
+	origin: Origin,
 ) -> BoxFuture<WithToken<ConfirmationResponse>> {
 	match payload {
+		ConfirmationPayload::SendTransaction(ref request) if accounts.is_policy_address(&request.from) => {
+			execute_policy_transaction(dispatcher, accounts, request.clone(), pass, origin)
+		},
 		ConfirmationPayload::SendTransaction(request) => {
 			let condition = request.condition.clone().map(Into::into);
-			Box::new(dispatcher.sign(accounts, request, pass)
+			Box::new(dispatcher.sign(accounts, request, pass, origin)
 				.map(move |v| v.map(move |tx| PendingTransaction::new(tx, condition)))
 				.map(WithToken::into_tuple)
 				.map(|(tx, token)| (tx, token, dispatcher))
@@ -666,7 +787,7 @@ pub fn execute<D: Dispatcher + 'static>(
 				}))
 		},
 		ConfirmationPayload::SignTransaction(request) => {
-			Box::new(dispatcher.sign(accounts, request, pass)
+			Box::new(dispatcher.sign(accounts, request, pass, origin)
 				.map(move |result| result
 					.map(move |tx| dispatcher.enrich(tx))
 					.map(ConfirmationResponse::SignTransaction)
@@ -674,11 +795,42 @@ pub fn execute<D: Dispatcher + 'static>(
 		},
 		ConfirmationPayload::EthSignMessage(address, data) => {
 			if accounts.is_hardware_address(&address) {
-				return Box::new(future::err(errors::unsupported("Signing via hardware wallets is not supported.", None)));
+				let res = with_origin(&origin, || accounts.sign_message_with_hardware(address, &data))
+					.map(|rsv| H520(rsv.into_electrum()))
+					.map(RpcH520::from)
+					.map(ConfirmationResponse::Signature)
+					.map(WithToken::No)
+					.map_err(|e| errors::account("Error signing message with hardware wallet", e));
+				return Box::new(future::done(res));
 			}
 
 			let hash = eth_data_hash(data);
-			let res = signature(&accounts, address, hash, pass)
+			let res = with_origin(&origin, || signature(&accounts, address, hash, pass))
+				.map(|result| result
+					.map(|rsv| H520(rsv.into_electrum()))
+					.map(RpcH520::from)
+					.map(ConfirmationResponse::Signature)
+				);
+			Box::new(future::done(res))
+		},
+		ConfirmationPayload::SignTypedData(address, data) => {
+			let (domain, message) = match eip712::hashes(&data) {
+				Ok(hashes) => hashes,
+				Err(e) => return Box::new(future::err(errors::invalid_params("Typed data", e.to_string()))),
+			};
+
+			if accounts.is_hardware_address(&address) {
+				let res = with_origin(&origin, || accounts.sign_typed_data_with_hardware(address, &domain, &message))
+					.map(|rsv| H520(rsv.into_electrum()))
+					.map(RpcH520::from)
+					.map(ConfirmationResponse::Signature)
+					.map(WithToken::No)
+					.map_err(|e| errors::account("Error signing typed data with hardware wallet", e));
+				return Box::new(future::done(res));
+			}
+
+			let hash = eip712::signing_hash(&domain, &message);
+			let res = with_origin(&origin, || signature(&accounts, address, hash, pass))
 				.map(|result| result
 					.map(|rsv| H520(rsv.into_electrum()))
 					.map(RpcH520::from)
@@ -691,7 +843,7 @@ pub fn execute<D: Dispatcher + 'static>(
 				return Box::new(future::err(errors::unsupported("Decrypting via hardware wallets is not supported.", None)));
 			}
 
-			let res = decrypt(&accounts, address, data, pass)
+			let res = with_origin(&origin, || decrypt(&accounts, address, data, pass))
 				.map(|result| result
 					.map(RpcBytes)
 					.map(ConfirmationResponse::Decrypt)
@@ -701,6 +853,80 @@ pub fn execute<D: Dispatcher + 'static>(
 	}
 }
 
+/// Record an owner's approval of a policy account transaction.
+/// Once enough owners approve it, the last one submits it to the Safe contract.
+fn execute_policy_transaction<D: Dispatcher + 'static>(
+	dispatcher: D,
+	accounts: Arc<AccountProvider>,
+	request: FilledTransactionRequest,
+	pass: SignWith,
+	origin: Origin,
+) -> BoxFuture<WithToken<ConfirmationResponse>> {
+	let policy = request.from;
+	let to = match request.to {
+		Some(to) => to,
+		None => return Box::new(future::err(errors::invalid_params("to", "Policy accounts can't create contracts."))),
+	};
+	let password = match pass {
+		SignWith::Nothing => None,
+		SignWith::Password(ref password) => Some(password.clone()),
+		SignWith::Token(_) => return Box::new(future::err(errors::unsupported("Policy account transactions can't be approved with a token.", None))),
+	};
+
+	let safe_transaction = SafeTransaction::new(to, request.value, request.data.clone());
+	let hash_input = safe_transaction.clone();
+	let call_dispatcher = dispatcher.clone();
+	Box::new(dispatcher.call_contract(policy, SafeTransaction::nonce_input())
+		.and_then(|output| SafeTransaction::decode_nonce(&output).map_err(errors::encoding))
+		.and_then(move |nonce| call_dispatcher.call_contract(policy, hash_input.hash_input(nonce)))
+		.and_then(|output| SafeTransaction::decode_hash(&output).map_err(errors::encoding))
+		.and_then(move |hash| {
+			let approval = with_origin(&origin, || accounts.approve_policy_transaction(policy, hash, password))
+				.map_err(|e| match pass {
+					SignWith::Nothing => errors::signing(e),
+					_ => errors::password(e),
+				});
+
+			let (executor, signatures) = match approval {
+				Ok(PolicyApproval::Pending { approvals, threshold }) => {
+					let response = ConfirmationResponse::PolicyApproval(RpcPolicyApproval {
+						approvals: approvals as u64,
+						threshold: threshold as u64,
+					});
+					return Either::A(future::ok(WithToken::No(response)));
+				},
+				Ok(PolicyApproval::Complete { executor, signatures }) => (executor, signatures),
+				Err(e) => return Either::A(future::err(e)),
+			};
+
+			let condition = request.condition.clone().map(Into::into);
+			let execution = FilledTransactionRequest {
+				from: executor,
+				used_default_from: false,
+				to: Some(policy),
+				nonce: None,
+				gas_price: request.gas_price,
+				gas: request.gas + POLICY_GAS_OVERHEAD.into(),
+				value: 0.into(),
+				data: safe_transaction.exec_input(&signatures),
+				condition: request.condition,
+			};
+
+			// approvals are kept until the execution is dispatched, so it can be retried if it fails
+			let approvals = accounts.clone();
+			Either::B(dispatcher.sign(accounts, execution, pass, origin)
+				.map(move |tx| PendingTransaction::new(tx.into_value(), condition))
+				.and_then(move |tx| dispatcher.dispatch_transaction(tx))
+				.map(move |tx_hash| {
+					approvals.discard_policy_approvals(&policy, &hash);
+					tx_hash
+				})
+				.map(RpcH256::from)
+				.map(ConfirmationResponse::SendTransaction)
+				.map(WithToken::No))
+		}))
+}
+
 fn signature(accounts: &AccountProvider, address: Address, hash: H256, password: SignWith) -> Result<WithToken<Signature>> {
 	match password.clone() {
 		SignWith::Nothing => accounts.sign(address, None, hash).map(WithToken::No),
@@ -733,6 +959,22 @@ fn hardware_signature(accounts: &AccountProvider, address: Address, t: Transacti
 		})
 }
 
+fn external_signature(accounts: &AccountProvider, address: Address, t: Transaction, chain_id: Option<u64>)
+	-> Result<SignedTransaction>
+{
+	let signature = accounts.sign_transaction_with_external(address, &t, chain_id)
+		.map_err(|e| {
+			debug!(target: "miner", "Error signing transaction with external signer: {}", e);
+			errors::account("Error signing transaction with external signer", e)
+		})?;
+
+	SignedTransaction::new(t.with_signature(signature, chain_id))
+		.map_err(|e| {
+			debug!(target: "miner", "External signer has produced invalid signature: {}", e);
+			errors::account("Invalid signature generated", e)
+		})
+}
+
 fn decrypt(accounts: &AccountProvider, address: Address, msg: Bytes, password: SignWith) -> Result<WithToken<Bytes>> {
 	match password.clone() {
 		SignWith::Nothing => accounts.decrypt(address, None, &DEFAULT_MAC, &msg).map(WithToken::No),
@@ -772,5 +1014,11 @@ pub fn from_rpc<D>(payload: RpcConfirmationPayload, default_account: Address, di
 		RpcConfirmationPayload::EthSignMessage(RpcSignRequest { address, data }) => {
 			Box::new(future::ok(ConfirmationPayload::EthSignMessage(address.into(), data.into())))
 		},
+		RpcConfirmationPayload::SignTypedData(RpcSignTypedDataRequest { address, data }) => {
+			let res = eip712::hash_structured_data(&data)
+				.map(|_| ConfirmationPayload::SignTypedData(address.into(), data))
+				.map_err(|e| errors::invalid_params("Typed data", e.to_string()));
+			Box::new(future::done(res))
+		},
 	}
 }
@@ -0,0 +1,441 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! EIP-712 encoding and hashing of typed structured data.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+use ethereum_types::{H160, H256, U256};
+use hash::keccak;
+use rustc_hex::FromHex;
+use serde_json::Value;
+
+use v1::types::{MemberType, TypedData};
+
+/// Name of the struct type describing the signing domain.
+pub const DOMAIN_TYPE: &str = "EIP712Domain";
+
+type Types = BTreeMap<String, Vec<MemberType>>;
+
+/// Errors encountered while encoding typed data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+	/// Type is neither an atomic, dynamic nor a declared struct type.
+	UnknownType(String),
+	/// Struct type declares the same member twice.
+	DuplicateMember {
+		/// Struct type.
+		type_: String,
+		/// Member name.
+		member: String,
+	},
+	/// Value of a struct type is not an object.
+	NotAnObject(String),
+	/// Value lacks a member declared by its struct type.
+	MissingMember {
+		/// Struct type.
+		type_: String,
+		/// Member name.
+		member: String,
+	},
+	/// Value contains a member not declared by its struct type.
+	UnexpectedMember {
+		/// Struct type.
+		type_: String,
+		/// Member name.
+		member: String,
+	},
+	/// Value can't be encoded as given type.
+	InvalidValue {
+		/// Expected type.
+		type_: String,
+		/// Offending value.
+		value: Value,
+	},
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Error::UnknownType(ref ty) => write!(f, "Unknown type: {}", ty),
+			Error::DuplicateMember { ref type_, ref member } => write!(f, "Type {} declares member {} twice", type_, member),
+			Error::NotAnObject(ref ty) => write!(f, "Value of type {} is not an object", ty),
+			Error::MissingMember { ref type_, ref member } => write!(f, "Value of type {} is missing member {}", type_, member),
+			Error::UnexpectedMember { ref type_, ref member } => write!(f, "Type {} has no member {}", type_, member),
+			Error::InvalidValue { ref type_, ref value } => write!(f, "Invalid value of type {}: {}", type_, value),
+		}
+	}
+}
+
+/// Parsed member type.
+#[derive(Debug, PartialEq)]
+enum FieldType<'a> {
+	Address,
+	Bool,
+	String,
+	Bytes,
+	FixedBytes(usize),
+	Uint(usize),
+	Int(usize),
+	Array(Box<FieldType<'a>>, Option<usize>),
+	Struct(&'a str),
+}
+
+fn parse_type<'a>(ty: &'a str, types: &Types) -> Result<FieldType<'a>, Error> {
+	let unknown = || Error::UnknownType(ty.into());
+
+	if ty.ends_with(']') {
+		let open = ty.rfind('[').ok_or_else(unknown)?;
+		let inner = parse_type(&ty[..open], types)?;
+		let len = match &ty[open + 1..ty.len() - 1] {
+			"" => None,
+			len => Some(len.parse().map_err(|_| unknown())?),
+		};
+		return Ok(FieldType::Array(Box::new(inner), len));
+	}
+
+	let sized = |prefix: &str| -> Option<usize> {
+		if ty.starts_with(prefix) {
+			ty[prefix.len()..].parse().ok()
+		} else {
+			None
+		}
+	};
+
+	Ok(match ty {
+		"address" => FieldType::Address,
+		"bool" => FieldType::Bool,
+		"string" => FieldType::String,
+		"bytes" => FieldType::Bytes,
+		_ if types.contains_key(ty) => FieldType::Struct(ty),
+		_ => if let Some(size) = sized("bytes") {
+			if size < 1 || size > 32 {
+				return Err(unknown());
+			}
+			FieldType::FixedBytes(size)
+		} else if let Some(bits) = sized("uint") {
+			if bits < 8 || bits > 256 || bits % 8 != 0 {
+				return Err(unknown());
+			}
+			FieldType::Uint(bits)
+		} else if let Some(bits) = sized("int") {
+			if bits < 8 || bits > 256 || bits % 8 != 0 {
+				return Err(unknown());
+			}
+			FieldType::Int(bits)
+		} else {
+			return Err(unknown());
+		},
+	})
+}
+
+/// Checks that all struct types are well-formed and that the domain and primary types are declared.
+fn validate(data: &TypedData) -> Result<(), Error> {
+	for (name, members) in &data.types {
+		let mut names = BTreeSet::new();
+		for member in members {
+			if !names.insert(&member.name) {
+				return Err(Error::DuplicateMember { type_: name.clone(), member: member.name.clone() });
+			}
+			parse_type(&member.type_, &data.types)?;
+		}
+	}
+
+	for ty in &[DOMAIN_TYPE, &*data.primary_type] {
+		if !data.types.contains_key(*ty) {
+			return Err(Error::UnknownType(ty.to_string()));
+		}
+	}
+
+	Ok(())
+}
+
+fn dependencies<'a>(name: &'a str, types: &'a Types, deps: &mut BTreeSet<&'a str>) {
+	let members = match types.get(name) {
+		Some(members) => members,
+		None => return,
+	};
+
+	if !deps.insert(name) {
+		return;
+	}
+
+	for member in members {
+		let base = member.type_.split('[').next().expect("split always yields at least one item; qed");
+		dependencies(base, types, deps);
+	}
+}
+
+/// Encodes the type as `Name(type1 name1,...)` followed by all referenced struct types sorted by name.
+fn encode_type(name: &str, types: &Types) -> String {
+	let format = |name: &str| {
+		let members: Vec<_> = types[name].iter().map(|m| format!("{} {}", m.type_, m.name)).collect();
+		format!("{}({})", name, members.join(","))
+	};
+
+	let mut deps = BTreeSet::new();
+	dependencies(name, types, &mut deps);
+	deps.remove(name);
+
+	let mut encoded = format(name);
+	for dep in deps {
+		encoded.push_str(&format(dep));
+	}
+	encoded
+}
+
+fn type_hash(name: &str, types: &Types) -> H256 {
+	keccak(encode_type(name, types))
+}
+
+fn parse_bytes(value: &Value) -> Option<Vec<u8>> {
+	let s = value.as_str()?;
+	if !s.starts_with("0x") {
+		return None;
+	}
+	s[2..].from_hex().ok()
+}
+
+fn parse_uint(value: &Value) -> Option<U256> {
+	match *value {
+		Value::Number(ref n) => n.as_u64().map(Into::into),
+		Value::String(ref s) if s.starts_with("0x") => {
+			let hex = &s[2..];
+			if hex.is_empty() || hex.len() > 64 {
+				return None;
+			}
+			U256::from_str(hex).ok()
+		},
+		Value::String(ref s) => U256::from_dec_str(s).ok(),
+		_ => None,
+	}
+}
+
+/// Returns the magnitude of the value and whether it is negative.
+fn parse_int(value: &Value) -> Option<(U256, bool)> {
+	match *value {
+		Value::Number(ref n) => match n.as_i64() {
+			Some(n) if n < 0 => Some(((n.wrapping_neg() as u64).into(), true)),
+			_ => n.as_u64().map(|n| (n.into(), false)),
+		},
+		Value::String(ref s) if s.starts_with('-') => parse_uint(&Value::String(s[1..].into())).map(|n| (n, true)),
+		_ => parse_uint(value).map(|n| (n, false)),
+	}
+}
+
+fn encode_value(ty: &FieldType, type_name: &str, value: &Value, types: &Types) -> Result<H256, Error> {
+	let invalid = || Error::InvalidValue { type_: type_name.into(), value: value.clone() };
+
+	Ok(match *ty {
+		FieldType::Address => {
+			let address = value.as_str()
+				.and_then(|s| if s.starts_with("0x") { H160::from_str(&s[2..]).ok() } else { None })
+				.ok_or_else(invalid)?;
+			let mut encoded = H256::zero();
+			encoded[12..].copy_from_slice(&address);
+			encoded
+		},
+		FieldType::Bool => H256::from(U256::from(value.as_bool().ok_or_else(invalid)? as u8)),
+		FieldType::String => keccak(value.as_str().ok_or_else(invalid)?),
+		FieldType::Bytes => keccak(parse_bytes(value).ok_or_else(invalid)?),
+		FieldType::FixedBytes(size) => {
+			let bytes = parse_bytes(value).ok_or_else(invalid)?;
+			if bytes.len() > size {
+				return Err(invalid());
+			}
+			let mut encoded = H256::zero();
+			encoded[..bytes.len()].copy_from_slice(&bytes);
+			encoded
+		},
+		FieldType::Uint(bits) => {
+			let n = parse_uint(value).ok_or_else(invalid)?;
+			if n.bits() > bits {
+				return Err(invalid());
+			}
+			H256::from(n)
+		},
+		FieldType::Int(bits) => {
+			let (n, negative) = parse_int(value).ok_or_else(invalid)?;
+			let min = U256::one() << (bits - 1);
+			if (negative && n > min) || (!negative && n >= min) {
+				return Err(invalid());
+			}
+			match negative {
+				true => H256::from((!n).overflowing_add(U256::one()).0),
+				false => H256::from(n),
+			}
+		},
+		FieldType::Array(ref inner, len) => {
+			let items = value.as_array().ok_or_else(invalid)?;
+			if len.map_or(false, |len| len != items.len()) {
+				return Err(invalid());
+			}
+			let inner_name = &type_name[..type_name.rfind('[').expect("array type names contain '['; qed")];
+			let mut encoded = Vec::with_capacity(items.len() * 32);
+			for item in items {
+				encoded.extend_from_slice(&encode_value(inner, inner_name, item, types)?);
+			}
+			keccak(encoded)
+		},
+		FieldType::Struct(name) => hash_struct(name, value, types)?,
+	})
+}
+
+/// Encodes the value of a struct type as `typeHash ‖ encodeData(member1) ‖ ...`.
+fn encode_data(name: &str, value: &Value, types: &Types) -> Result<Vec<u8>, Error> {
+	let members = types.get(name).ok_or_else(|| Error::UnknownType(name.into()))?;
+	let object = value.as_object().ok_or_else(|| Error::NotAnObject(name.into()))?;
+
+	if let Some(key) = object.keys().find(|key| !members.iter().any(|m| &m.name == *key)) {
+		return Err(Error::UnexpectedMember { type_: name.into(), member: key.clone() });
+	}
+
+	let mut encoded = type_hash(name, types).to_vec();
+	for member in members {
+		let value = object.get(&member.name)
+			.ok_or_else(|| Error::MissingMember { type_: name.into(), member: member.name.clone() })?;
+		let ty = parse_type(&member.type_, types)?;
+		encoded.extend_from_slice(&encode_value(&ty, &member.type_, value, types)?);
+	}
+	Ok(encoded)
+}
+
+fn hash_struct(name: &str, value: &Value, types: &Types) -> Result<H256, Error> {
+	encode_data(name, value, types).map(keccak)
+}
+
+/// Validates the typed data against its types and returns its domain separator and the struct hash of its message.
+pub fn hashes(data: &TypedData) -> Result<(H256, H256), Error> {
+	validate(data)?;
+	let domain_separator = hash_struct(DOMAIN_TYPE, &data.domain, &data.types)?;
+	let message_hash = hash_struct(&data.primary_type, &data.message, &data.types)?;
+	Ok((domain_separator, message_hash))
+}
+
+/// Combines the domain separator and the message hash into the hash to sign:
+/// `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+pub fn signing_hash(domain_separator: &H256, message_hash: &H256) -> H256 {
+	let mut encoded = vec![0x19, 0x01];
+	encoded.extend_from_slice(domain_separator);
+	encoded.extend_from_slice(message_hash);
+	keccak(encoded)
+}
+
+/// Validates the typed data against its types and returns the hash to sign.
+pub fn hash_structured_data(data: &TypedData) -> Result<H256, Error> {
+	let (domain_separator, message_hash) = hashes(data)?;
+	Ok(signing_hash(&domain_separator, &message_hash))
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use super::*;
+
+	const MAIL: &str = r#"{
+		"types": {
+			"EIP712Domain": [
+				{"name": "name", "type": "string"},
+				{"name": "version", "type": "string"},
+				{"name": "chainId", "type": "uint256"},
+				{"name": "verifyingContract", "type": "address"}
+			],
+			"Person": [
+				{"name": "name", "type": "string"},
+				{"name": "wallet", "type": "address"}
+			],
+			"Mail": [
+				{"name": "from", "type": "Person"},
+				{"name": "to", "type": "Person"},
+				{"name": "contents", "type": "string"}
+			]
+		},
+		"primaryType": "Mail",
+		"domain": {
+			"name": "Ether Mail",
+			"version": "1",
+			"chainId": 1,
+			"verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+		},
+		"message": {
+			"from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+			"to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+			"contents": "Hello, Bob!"
+		}
+	}"#;
+
+	fn mail() -> TypedData {
+		serde_json::from_str(MAIL).unwrap()
+	}
+
+	#[test]
+	fn should_encode_type_with_dependencies() {
+		assert_eq!(
+			encode_type("Mail", &mail().types),
+			"Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+		);
+	}
+
+	#[test]
+	fn should_hash_structured_data() {
+		let data = mail();
+
+		let (domain_separator, message_hash) = hashes(&data).unwrap();
+		assert_eq!(domain_separator, "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f".into());
+		assert_eq!(message_hash, "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e".into());
+		assert_eq!(hash_structured_data(&data).unwrap(), "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2".into());
+	}
+
+	#[test]
+	fn should_reject_data_not_matching_types() {
+		let mut data = mail();
+		data.message["to"]["wallet"] = "0x01".into();
+		assert_eq!(hash_structured_data(&data), Err(Error::InvalidValue { type_: "address".into(), value: "0x01".into() }));
+
+		let mut data = mail();
+		data.message["cc"] = "Alice".into();
+		assert_eq!(hash_structured_data(&data), Err(Error::UnexpectedMember { type_: "Mail".into(), member: "cc".into() }));
+
+		let mut data = mail();
+		data.message.as_object_mut().unwrap().remove("contents");
+		assert_eq!(hash_structured_data(&data), Err(Error::MissingMember { type_: "Mail".into(), member: "contents".into() }));
+
+		let mut data = mail();
+		data.primary_type = "Letter".into();
+		assert_eq!(hash_structured_data(&data), Err(Error::UnknownType("Letter".into())));
+
+		let mut data = mail();
+		data.types.get_mut("Mail").unwrap()[2].type_ = "uint7".into();
+		assert_eq!(hash_structured_data(&data), Err(Error::UnknownType("uint7".into())));
+	}
+
+	#[test]
+	fn should_encode_numbers_and_arrays() {
+		let types = Types::new();
+		let encode = |ty: &str, value: Value| encode_value(&parse_type(ty, &types).unwrap(), ty, &value, &types);
+
+		assert_eq!(encode("uint8", 255.into()), Ok(H256::from(U256::from(255))));
+		assert!(encode("uint8", 256.into()).is_err());
+		assert_eq!(encode("uint256", "0x10".into()), Ok(H256::from(U256::from(16))));
+		assert_eq!(encode("int8", (-128).into()), Ok(H256::from(U256::max_value() - U256::from(127))));
+		assert!(encode("int8", 128.into()).is_err());
+		assert_eq!(encode("bytes2", "0x0102".into()), Ok("0102000000000000000000000000000000000000000000000000000000000000".into()));
+		assert_eq!(encode("bool[2]", serde_json::from_str("[true, false]").unwrap()), Ok(keccak(&[H256::from(U256::one()).to_vec(), vec![0u8; 32]].concat())));
+		assert!(encode("bool[2]", serde_json::from_str("[true]").unwrap()).is_err());
+	}
+}
@@ -18,8 +18,10 @@
 
 use std::fmt;
 
-use ethcore::account_provider::{SignError as AccountError};
-use ethcore::error::{Error as EthcoreError, ErrorKind, CallError};
+use ethcore::account_provider::{SignError as AccountError, SpendingError};
+use ethcore::error::{Error as EthcoreError, ErrorKind, CallError, ExecutionError};
+use ethcore::miner::BundleError;
+use ethereum_types::U256;
 use jsonrpc_core::{futures, Error, ErrorCode, Value};
 use rlp::DecoderError;
 use transaction::Error as TransactionError;
@@ -42,9 +44,12 @@ mod codes {
 	pub const PASSWORD_INVALID: i64 = -32021;
 	pub const ACCOUNT_ERROR: i64 = -32023;
 	pub const PRIVATE_ERROR: i64 = -32024;
+	pub const SPENDING_LIMIT: i64 = -32025;
+	pub const SECOND_FACTOR: i64 = -32026;
 	pub const REQUEST_REJECTED: i64 = -32040;
 	pub const REQUEST_REJECTED_LIMIT: i64 = -32041;
 	pub const REQUEST_NOT_FOUND: i64 = -32042;
+	pub const REQUEST_DELAYED: i64 = -32043;
 	pub const ENCRYPTION_ERROR: i64 = -32055;
 	pub const ENCODING_ERROR: i64 = -32058;
 	pub const FETCH_ERROR: i64 = -32060;
@@ -100,6 +105,14 @@ pub fn request_rejected_limit() -> Error {
 	}
 }
 
+pub fn request_delayed(remaining_secs: u64) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::REQUEST_DELAYED),
+		message: format!("Request can not be confirmed yet. Try again in {} seconds.", remaining_secs),
+		data: Some(Value::Number(remaining_secs.into())),
+	}
+}
+
 pub fn account<T: fmt::Debug>(error: &str, details: T) -> Error {
 	Error {
 		code: ErrorCode::ServerError(codes::ACCOUNT_ERROR),
@@ -283,6 +296,22 @@ pub fn password(error: AccountError) -> Error {
 	}
 }
 
+pub fn spending_limit(error: SpendingError) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::SPENDING_LIMIT),
+		message: format!("Transaction violates the spending limit of the account: {}.", error),
+		data: None,
+	}
+}
+
+pub fn second_factor(details: &str) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::SECOND_FACTOR),
+		message: format!("Second factor verification failed: {}.", details),
+		data: None,
+	}
+}
+
 pub fn private_message(error: PrivateTransactionError) -> Error {
 	Error {
 		code: ErrorCode::ServerError(codes::PRIVATE_ERROR),
@@ -352,6 +381,21 @@ pub fn transaction<T: Into<EthcoreError>>(error: T) -> Error {
 	}
 }
 
+pub fn bundle(error: BundleError) -> Error {
+	match error {
+		BundleError::Transaction(ref e) => Error {
+			code: ErrorCode::ServerError(codes::TRANSACTION_ERROR),
+			message: transaction_message(e),
+			data: None,
+		},
+		e => Error {
+			code: ErrorCode::InvalidParams,
+			message: format!("Invalid bundle: {}", e),
+			data: None,
+		},
+	}
+}
+
 pub fn decode<T: Into<EthcoreError>>(error: T) -> Error {
 	let error = error.into();
 	match *error.kind() {
@@ -378,11 +422,66 @@ pub fn call(error: CallError) -> Error {
 		CallError::StatePruned => state_pruned(),
 		CallError::StateCorrupt => state_corrupt(),
 		CallError::Exceptional => exceptional(),
+		CallError::OutOfGas(limit) => out_of_gas(limit),
+		CallError::Reverted(output) => reverted(&output),
+		CallError::Execution(ExecutionError::NotEnoughCash { required, got }) => Error {
+			code: ErrorCode::ServerError(codes::TRANSACTION_ERROR),
+			message: format!("Insufficient funds. The account you tried to send transaction from does not have enough funds. Required {} and got: {}.", required, got),
+			data: None,
+		},
+		CallError::Execution(ExecutionError::InvalidNonce { expected, got }) => Error {
+			code: ErrorCode::ServerError(codes::TRANSACTION_ERROR),
+			message: format!("Invalid transaction nonce. Expected {} and got: {}.", expected, got),
+			data: None,
+		},
 		CallError::Execution(e) => execution(e),
 		CallError::TransactionNotFound => internal("{}, this should not be the case with eth_call, most likely a bug.", CallError::TransactionNotFound),
 	}
 }
 
+pub fn out_of_gas(limit: U256) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::EXECUTION_ERROR),
+		message: format!("Transaction ran out of gas. Gas required exceeds the limit of {}.", limit),
+		data: None,
+	}
+}
+
+pub fn reverted(output: &[u8]) -> Error {
+	use rustc_hex::ToHex;
+
+	let message = match revert_reason(output) {
+		Some(reason) => format!("Execution reverted: {}", reason),
+		None => "Execution reverted.".into(),
+	};
+
+	Error {
+		code: ErrorCode::ServerError(codes::EXECUTION_ERROR),
+		message,
+		data: Some(Value::String(format!("0x{}", output.to_hex()))),
+	}
+}
+
+/// Decodes the reason given to `revert` encoded as a call to `Error(string)`.
+fn revert_reason(output: &[u8]) -> Option<String> {
+	const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+	if output.len() < 4 + 64 || output[..4] != ERROR_SELECTOR {
+		return None;
+	}
+
+	let data = &output[4..];
+	let word = |pos: usize| -> Option<usize> {
+		let word = U256::from(data.get(pos..pos + 32)?);
+		if word > U256::from(data.len()) { None } else { Some(word.low_u64() as usize) }
+	};
+	let offset = word(0)?;
+	let len = word(offset)?;
+	let reason = data.get(offset + 32..offset + 32 + len)?;
+
+	String::from_utf8(reason.to_vec()).ok()
+}
+
 pub fn vm(error: &VMError, output: &[u8]) -> Error {
 	use rustc_hex::ToHex;
 
@@ -24,11 +24,11 @@ use ethcore::executed::{Executed, ExecutionError};
 use ethcore::ids::BlockId;
 use ethcore::filter::Filter as EthcoreFilter;
 use ethcore::receipt::Receipt;
+use ethcore::state::StateOverride;
 
 use jsonrpc_core::{Result, Error};
 use jsonrpc_core::futures::{future, Future};
 use jsonrpc_core::futures::future::Either;
-use jsonrpc_macros::Trailing;
 
 use light::cache::Cache;
 use light::client::LightChainClient;
@@ -187,7 +187,8 @@ impl LightFetch {
 	}
 
 	/// Helper for getting proved execution.
-	pub fn proved_execution(&self, req: CallRequest, num: Trailing<BlockNumber>) -> impl Future<Item = ExecutionResult, Error = Error> + Send {
+	/// The execution is proved by a peer without `overrides`, which are applied when checking the proof.
+	pub fn proved_execution(&self, req: CallRequest, num: BlockNumber, overrides: StateOverride) -> impl Future<Item = ExecutionResult, Error = Error> + Send {
 		const DEFAULT_GAS_PRICE: u64 = 21_000;
 		// starting gas when gas not provided.
 		const START_GAS: u64 = 50_000;
@@ -198,7 +199,7 @@ impl LightFetch {
 		// Note: Here we treat `Pending` as `Latest`.
 		//       Since light clients don't produce pending blocks
 		//       (they don't have state) we can safely fallback to `Latest`.
-		let id = match num.unwrap_or_default() {
+		let id = match num {
 			BlockNumber::Num(n) => BlockId::Number(n),
 			BlockNumber::Earliest => BlockId::Earliest,
 			BlockNumber::Latest => BlockId::Latest,
@@ -209,7 +210,7 @@ impl LightFetch {
 		};
 
 		let from = req.from.unwrap_or(Address::zero());
-		let nonce_fut = match req.nonce {
+		let nonce_fut = match req.nonce.or_else(|| overrides.get(&from).and_then(|account| account.nonce)) {
 			Some(nonce) => Either::A(future::ok(Some(nonce))),
 			None => Either::B(self.account(from, id).map(|acc| acc.map(|a| a.nonce))),
 		};
@@ -270,6 +271,7 @@ impl LightFetch {
 				hdr: hdr,
 				env_info: env_info,
 				engine: client.engine().clone(),
+				overrides: overrides,
 				on_demand: on_demand,
 				sync: sync,
 			}))
@@ -448,6 +450,7 @@ struct ExecuteParams {
 	hdr: encoded::Header,
 	env_info: ::vm::EnvInfo,
 	engine: Arc<::ethcore::engines::EthEngine>,
+	overrides: StateOverride,
 	on_demand: Arc<OnDemand>,
 	sync: Arc<LightSync>,
 }
@@ -487,6 +490,7 @@ fn execute_tx(gas_known: bool, params: ExecuteParams) -> impl Future<Item = Exec
 			header: params.hdr.into(),
 			env_info: params.env_info,
 			engine: params.engine,
+			overrides: params.overrides,
 		};
 
 		let on_demand = params.on_demand;
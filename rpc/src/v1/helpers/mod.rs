@@ -18,14 +18,17 @@
 pub mod errors;
 
 pub mod block_import;
+pub mod call_params;
 pub mod dapps;
 pub mod dispatch;
+pub mod eip712;
 pub mod fake_sign;
 pub mod ipfs;
 pub mod light_fetch;
 pub mod nonce;
 pub mod oneshot;
 pub mod secretstore;
+pub mod totp;
 
 mod network_settings;
 mod poll_filter;
@@ -36,7 +39,7 @@ mod signing_queue;
 mod subscribers;
 mod subscription_manager;
 
-pub use self::dispatch::{Dispatcher, FullDispatcher};
+pub use self::dispatch::{Dispatcher, FullDispatcher, StandaloneDispatcher};
 pub use self::network_settings::NetworkSettings;
 pub use self::poll_manager::PollManager;
 pub use self::poll_filter::{PollFilter, limit_logs};
@@ -48,7 +51,7 @@ pub use self::signing_queue::{
 	SigningQueue, QueueEvent, DefaultAccount,
 	QUEUE_LIMIT as SIGNING_QUEUE_LIMIT,
 };
-pub use self::signer::SignerService;
+pub use self::signer::{SignerService, ConfirmationDelay};
 pub use self::subscribers::Subscribers;
 pub use self::subscription_manager::GenericPollManager;
 
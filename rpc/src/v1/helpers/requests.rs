@@ -17,7 +17,7 @@
 use ethereum_types::{U256, Address};
 use bytes::Bytes;
 
-use v1::types::{Origin, TransactionCondition};
+use v1::types::{Origin, TransactionCondition, TypedData};
 
 /// Transaction request coming from RPC
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
@@ -117,6 +117,8 @@ pub enum ConfirmationPayload {
 	SignTransaction(FilledTransactionRequest),
 	/// Sign a message with an Ethereum specific security prefix.
 	EthSignMessage(Address, Bytes),
+	/// Sign typed structured data (EIP-712).
+	SignTypedData(Address, TypedData),
 	/// Decrypt request
 	Decrypt(Address, Bytes),
 }
@@ -127,6 +129,7 @@ impl ConfirmationPayload {
 			ConfirmationPayload::SendTransaction(ref request) => request.from,
 			ConfirmationPayload::SignTransaction(ref request) => request.from,
 			ConfirmationPayload::EthSignMessage(ref address, _) => *address,
+			ConfirmationPayload::SignTypedData(ref address, _) => *address,
 			ConfirmationPayload::Decrypt(ref address, _) => *address,
 		}
 	}
@@ -16,19 +16,43 @@
 
 use std::sync::Arc;
 use std::ops::Deref;
+use std::time::Duration;
+use ethereum_types::U256;
 use http::Origin;
 use parking_lot::Mutex;
 use transient_hashmap::TransientHashMap;
 
 use ethstore::random_string;
 
+use v1::helpers::ConfirmationPayload;
 use v1::helpers::signing_queue::{ConfirmationsQueue};
 
 const TOKEN_LIFETIME_SECS: u32 = 3600;
 
+/// Delay applied to high-value transactions before they can be confirmed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmationDelay {
+	/// Transactions transferring at least this much wei are delayed.
+	pub min_value: U256,
+	/// Time the request has to spend in the queue.
+	pub delay: Duration,
+}
+
+impl ConfirmationDelay {
+	/// Returns the delay that applies to given payload (if any).
+	pub fn delay_for(&self, payload: &ConfirmationPayload) -> Option<Duration> {
+		match *payload {
+			ConfirmationPayload::SendTransaction(ref request) |
+			ConfirmationPayload::SignTransaction(ref request) if request.value >= self.min_value => Some(self.delay),
+			_ => None,
+		}
+	}
+}
+
 /// Manages communication with Signer crate
 pub struct SignerService {
 	is_enabled: bool,
+	confirmation_delay: Option<ConfirmationDelay>,
 	queue: Arc<ConfirmationsQueue>,
 	web_proxy_tokens: Mutex<TransientHashMap<String, Origin>>,
 	generate_new_token: Box<Fn() -> Result<String, String> + Send + Sync + 'static>,
@@ -43,9 +67,21 @@ impl SignerService {
 			web_proxy_tokens: Mutex::new(TransientHashMap::new(TOKEN_LIFETIME_SECS)),
 			generate_new_token: Box::new(new_token),
 			is_enabled: is_enabled,
+			confirmation_delay: None,
 		}
 	}
 
+	/// Delays confirmation of high-value transactions.
+	pub fn with_confirmation_delay(mut self, delay: Option<ConfirmationDelay>) -> Self {
+		self.confirmation_delay = delay;
+		self
+	}
+
+	/// Returns the delay of high-value transactions.
+	pub fn confirmation_delay(&self) -> Option<&ConfirmationDelay> {
+		self.confirmation_delay.as_ref()
+	}
+
 	/// Checks if the token is valid web proxy access token.
 	pub fn web_proxy_access_token_domain(&self, token: &String) -> Option<Origin> {
 		self.web_proxy_tokens.lock().get(token).cloned()
@@ -15,6 +15,7 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 use ethereum_types::{U256, Address};
 use parking_lot::{Mutex, RwLock};
 use ethcore::account_provider::DappId;
@@ -99,6 +100,7 @@ pub trait SigningQueue: Send + Sync {
 struct ConfirmationSender {
 	sender: oneshot::Sender<ConfirmationResult>,
 	request: ConfirmationRequest,
+	created: Instant,
 }
 
 /// Receiving end of the Confirmation channel; can be used as a `Future` to await for `ConfirmationRequest`
@@ -133,6 +135,11 @@ impl ConfirmationsQueue {
 		}
 	}
 
+	/// Returns time elapsed since given request was added to the queue.
+	pub fn request_age(&self, id: &U256) -> Option<Duration> {
+		self.queue.read().get(id).map(|sender| sender.created.elapsed())
+	}
+
 	/// Removes requests from this queue and notifies `ConfirmationReceiver` holder about the result.
 	/// Notifies also a receiver about that event.
 	fn remove(&self, id: U256, result: Option<ConfirmationResult>) -> Option<ConfirmationRequest> {
@@ -189,6 +196,7 @@ impl SigningQueue for ConfirmationsQueue {
 					payload: request,
 					origin,
 				},
+				created: Instant::now(),
 			});
 			(id, receiver)
 		};
@@ -0,0 +1,214 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Time-based one-time passwords (RFC 6238) used as a second factor
+//! when confirming requests of selected accounts in the signer queue.
+
+use crypto::hmac;
+use serde_json;
+
+/// Key in the account meta holding a base32 encoded TOTP secret.
+pub const META_KEY: &'static str = "totpSecret";
+/// Length of a single time step in seconds.
+pub const STEP_SECS: u64 = 30;
+/// Number of steps before and after current one that are still accepted.
+const WINDOW: u64 = 1;
+const DIGITS: u32 = 6;
+
+/// Extracts TOTP secret from account's meta (a JSON object).
+/// Returns `Ok(None)` if the account has no second factor configured.
+pub fn secret_from_meta(meta: &str) -> Result<Option<Vec<u8>>, String> {
+	let meta: serde_json::Value = match serde_json::from_str(meta) {
+		Ok(meta) => meta,
+		Err(_) => return Ok(None),
+	};
+
+	match meta.get(META_KEY) {
+		None | Some(&serde_json::Value::Null) => Ok(None),
+		Some(&serde_json::Value::String(ref secret)) => decode_base32(secret)
+			.map(Some)
+			.ok_or_else(|| "invalid TOTP secret in account meta".into()),
+		Some(_) => Err("invalid TOTP secret in account meta".into()),
+	}
+}
+
+/// Removes the TOTP secret from account's meta, so that it's never returned over RPC.
+/// Meta without the secret is returned as is.
+pub fn strip_secret(meta: &str) -> String {
+	match serde_json::from_str(meta) {
+		Ok(serde_json::Value::Object(mut object)) => match object.remove(META_KEY) {
+			Some(_) => serde_json::Value::Object(object).to_string(),
+			None => meta.to_owned(),
+		},
+		_ => meta.to_owned(),
+	}
+}
+
+/// Applies an update of account's meta to the current one.
+/// The update can't see the secret, so it's kept unless the update sets it (`null` removes it).
+/// Returns the meta to store and whether the secret is added, changed or removed.
+pub fn update_meta(current: &str, update: &str) -> (String, bool) {
+	let secret = match serde_json::from_str(current) {
+		Ok(serde_json::Value::Object(mut current)) => current.remove(META_KEY).and_then(|secret| match secret {
+			serde_json::Value::Null => None,
+			secret => Some(secret),
+		}),
+		_ => None,
+	};
+
+	let mut update_meta = match serde_json::from_str(update) {
+		Ok(serde_json::Value::Object(update_meta)) => update_meta,
+		// the whole meta is replaced
+		_ => return (update.to_owned(), secret.is_some()),
+	};
+
+	match update_meta.remove(META_KEY) {
+		None => match secret {
+			Some(secret) => {
+				update_meta.insert(META_KEY.into(), secret);
+				(serde_json::Value::Object(update_meta).to_string(), false)
+			},
+			None => (update.to_owned(), false),
+		},
+		Some(serde_json::Value::Null) => (serde_json::Value::Object(update_meta).to_string(), secret.is_some()),
+		Some(new_secret) => {
+			let changed = secret.as_ref() != Some(&new_secret);
+			update_meta.insert(META_KEY.into(), new_secret);
+			(serde_json::Value::Object(update_meta).to_string(), changed)
+		},
+	}
+}
+
+/// Decodes RFC 4648 base32 string (case insensitive, padding and whitespace are ignored).
+pub fn decode_base32(input: &str) -> Option<Vec<u8>> {
+	let mut result = Vec::with_capacity(input.len() * 5 / 8);
+	let mut buffer = 0u64;
+	let mut bits = 0;
+
+	for c in input.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+		let value = match c.to_ascii_uppercase() {
+			c @ 'A'...'Z' => c as u64 - 'A' as u64,
+			c @ '2'...'7' => c as u64 - '2' as u64 + 26,
+			_ => return None,
+		};
+		buffer = (buffer << 5) | value;
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			result.push((buffer >> bits) as u8);
+			buffer &= (1 << bits) - 1;
+		}
+	}
+
+	if result.is_empty() {
+		None
+	} else {
+		Some(result)
+	}
+}
+
+/// Computes HOTP value (RFC 4226) for given counter.
+pub fn hotp(secret: &[u8], counter: u64, digits: u32) -> u32 {
+	let mut message = [0u8; 8];
+	for (i, byte) in message.iter_mut().enumerate() {
+		*byte = (counter >> (8 * (7 - i))) as u8;
+	}
+
+	let signature = hmac::sign(&hmac::SigKey::sha1(secret), &message);
+	let offset = (signature[signature.len() - 1] & 0x0f) as usize;
+	let binary = ((signature[offset] as u32 & 0x7f) << 24)
+		| ((signature[offset + 1] as u32) << 16)
+		| ((signature[offset + 2] as u32) << 8)
+		| (signature[offset + 3] as u32);
+
+	binary % 10u32.pow(digits)
+}
+
+/// Verifies given code against the secret at `unix_time`.
+/// Returns the time step that matched (to prevent replaying the same code).
+pub fn verify(secret: &[u8], code: &str, unix_time: u64) -> Option<u64> {
+	let code = code.trim();
+	if code.len() != DIGITS as usize {
+		return None;
+	}
+	let code: u32 = code.parse().ok()?;
+	let step = unix_time / STEP_SECS;
+
+	(step.saturating_sub(WINDOW)..step + WINDOW + 1)
+		.find(|step| hotp(secret, *step, DIGITS) == code)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// RFC 6238, Appendix B (SHA1)
+	const SECRET: &'static [u8] = b"12345678901234567890";
+
+	#[test]
+	fn should_compute_rfc_test_vectors() {
+		assert_eq!(hotp(SECRET, 59 / STEP_SECS, 8), 94287082);
+		assert_eq!(hotp(SECRET, 1111111109 / STEP_SECS, 8), 7081804);
+		assert_eq!(hotp(SECRET, 1234567890 / STEP_SECS, 8), 89005924);
+		assert_eq!(hotp(SECRET, 20000000000 / STEP_SECS, 8), 65353130);
+	}
+
+	#[test]
+	fn should_decode_base32() {
+		assert_eq!(decode_base32("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"), Some(SECRET.to_vec()));
+		assert_eq!(decode_base32("gezd gnbv gy3t qojq gezd gnbv gy3t qojq"), Some(SECRET.to_vec()));
+		assert_eq!(decode_base32("MZXW6==="), Some(b"foo".to_vec()));
+		assert_eq!(decode_base32("GEZ1"), None);
+		assert_eq!(decode_base32(""), None);
+	}
+
+	#[test]
+	fn should_verify_within_window() {
+		assert_eq!(verify(SECRET, "081804", 1111111109), Some(1111111109 / STEP_SECS));
+		assert_eq!(verify(SECRET, "081804", 1111111109 + STEP_SECS), Some(1111111109 / STEP_SECS));
+		assert_eq!(verify(SECRET, "081804", 1111111109 + 3 * STEP_SECS), None);
+		assert_eq!(verify(SECRET, "81804", 1111111109), None);
+		assert_eq!(verify(SECRET, "081805", 1111111109), None);
+	}
+
+	#[test]
+	fn should_read_secret_from_meta() {
+		assert_eq!(secret_from_meta("{}"), Ok(None));
+		assert_eq!(secret_from_meta("not json"), Ok(None));
+		assert_eq!(secret_from_meta(r#"{"totpSecret":"MZXW6==="}"#), Ok(Some(b"foo".to_vec())));
+		assert!(secret_from_meta(r#"{"totpSecret":"!!"}"#).is_err());
+		assert!(secret_from_meta(r#"{"totpSecret":5}"#).is_err());
+	}
+
+	#[test]
+	fn should_strip_secret_from_meta() {
+		assert_eq!(strip_secret(r#"{"a":1,"totpSecret":"MZXW6==="}"#), r#"{"a":1}"#);
+		assert_eq!(strip_secret(r#"{ "a": 1 }"#), r#"{ "a": 1 }"#);
+		assert_eq!(strip_secret("{foo: 69}"), "{foo: 69}");
+	}
+
+	#[test]
+	fn should_keep_secret_unless_updated() {
+		let current = r#"{"a":1,"totpSecret":"MZXW6==="}"#;
+		assert_eq!(update_meta(current, r#"{"a":2}"#), (r#"{"a":2,"totpSecret":"MZXW6==="}"#.into(), false));
+		assert_eq!(update_meta(current, r#"{"totpSecret":"MZXW6==="}"#), (r#"{"totpSecret":"MZXW6==="}"#.into(), false));
+		assert_eq!(update_meta(current, r#"{"totpSecret":"GEZDGNBV"}"#), (r#"{"totpSecret":"GEZDGNBV"}"#.into(), true));
+		assert_eq!(update_meta(current, r#"{"a":1,"totpSecret":null}"#), (r#"{"a":1}"#.into(), true));
+		assert_eq!(update_meta(current, "{foo: 69}"), ("{foo: 69}".into(), true));
+		assert_eq!(update_meta("{}", "{foo: 69}"), ("{foo: 69}".into(), false));
+		assert_eq!(update_meta("{}", r#"{"totpSecret":"MZXW6==="}"#), (r#"{"totpSecret":"MZXW6==="}"#.into(), true));
+	}
+}
@@ -0,0 +1,81 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Clef-compatible account RPC implementation shared by the signing clients.
+
+use ethcore::account_provider::{AccountProvider, DappId};
+
+use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_core::futures::future;
+use jsonrpc_macros::Trailing;
+use v1::helpers::errors;
+use v1::metadata::Metadata;
+use v1::traits::{Account, EthSigning};
+use v1::types::{
+	H160 as RpcH160, H520 as RpcH520, Bytes as RpcBytes,
+	RichRawTransaction as RpcRichRawTransaction,
+	TransactionRequest as RpcTransactionRequest,
+	TypedData as RpcTypedData,
+};
+
+/// Version of the Clef external API implemented by the `account_*` methods.
+pub const ACCOUNT_API_VERSION: &'static str = "6.0.0";
+
+/// The only content type of `account_signData`, signed as a message prefixed with `\x19Ethereum Signed Message:\n`.
+pub const TEXT_PLAIN: &'static str = "text/plain";
+
+/// Signing client exposing its account provider to the `account_*` methods.
+pub trait SigningAccounts {
+	/// Account provider used for signing.
+	fn account_provider(&self) -> &AccountProvider;
+}
+
+impl<T> Account for T where
+	T: EthSigning<Metadata = Metadata> + SigningAccounts + Send + Sync + 'static,
+{
+	type Metadata = Metadata;
+
+	fn version(&self) -> Result<String> {
+		Ok(ACCOUNT_API_VERSION.into())
+	}
+
+	fn list(&self, meta: Metadata) -> Result<Vec<RpcH160>> {
+		let dapp: DappId = meta.dapp_id().into();
+		let accounts = self.account_provider();
+
+		accounts
+			.note_dapp_used(dapp.clone())
+			.and_then(|_| accounts.dapp_addresses(dapp))
+			.map(|accounts| accounts.into_iter().map(Into::into).collect())
+			.map_err(|e| errors::account("Could not fetch accounts.", e))
+	}
+
+	fn sign_transaction(&self, meta: Metadata, request: RpcTransactionRequest, _method: Trailing<String>) -> BoxFuture<RpcRichRawTransaction> {
+		EthSigning::sign_transaction(self, meta, request)
+	}
+
+	fn sign_data(&self, meta: Metadata, content_type: String, address: RpcH160, data: RpcBytes) -> BoxFuture<RpcH520> {
+		if content_type != TEXT_PLAIN {
+			return Box::new(future::err(errors::unsupported(format!("Unsupported content type: {}", content_type), None)));
+		}
+
+		EthSigning::sign(self, meta, address, data)
+	}
+
+	fn sign_typed_data(&self, meta: Metadata, address: RpcH160, data: RpcTypedData) -> BoxFuture<RpcH520> {
+		EthSigning::sign_typed_data(self, meta, address, data)
+	}
+}
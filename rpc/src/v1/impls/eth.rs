@@ -29,7 +29,7 @@ use ethcore::account_provider::{AccountProvider, DappId};
 use ethcore::client::{BlockChainClient, BlockId, TransactionId, UncleId, StateOrBlock, StateClient, StateInfo, Call, EngineInfo};
 use ethcore::ethereum::Ethash;
 use ethcore::filter::Filter as EthcoreFilter;
-use ethcore::header::{BlockNumber as EthBlockNumber};
+use ethcore::header::{BlockNumber as EthBlockNumber, Header};
 use ethcore::log_entry::LogEntry;
 use ethcore::miner::{self, MinerService};
 use ethcore::snapshot::SnapshotService;
@@ -42,13 +42,13 @@ use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_core::futures::future;
 use jsonrpc_macros::Trailing;
 
-use v1::helpers::{errors, limit_logs, fake_sign};
+use v1::helpers::{call_params, errors, limit_logs, fake_sign};
 use v1::helpers::dispatch::{FullDispatcher, default_gas_price};
 use v1::helpers::block_import::is_major_importing;
-use v1::traits::Eth;
+use v1::traits::{Eth, EthCall};
 use v1::types::{
 	RichBlock, Block, BlockTransactions, BlockNumber, Bytes, SyncStatus, SyncInfo,
-	Transaction, CallRequest, Index, Filter, Log, Receipt, Work,
+	Transaction, CallRequest, Index, Filter, Log, Receipt, Work, StateOverride,
 	H64 as RpcH64, H256 as RpcH256, H160 as RpcH160, U256 as RpcU256, block_number_to_id,
 };
 use v1::metadata::Metadata;
@@ -404,6 +404,26 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> EthClient<C, SN, S
 			.map_err(|e| errors::account("Could not fetch accounts.", e))
 	}
 
+	/// Pending state and header. When there is no pending block,
+	/// transactions ready in the queue are applied on top of the latest state.
+	fn pending_state_and_header(&self) -> (T, Header) {
+		let info = self.client.chain_info();
+		let pending = self.miner.pending_state(info.best_block_number)
+			.and_then(|state| self.miner.pending_block_header(info.best_block_number).map(|header| (state, header)));
+		if let Some(pending) = pending {
+			return pending;
+		}
+
+		let mut state = self.client.latest_state();
+		let header = self.client.best_block_header();
+		for tx in self.miner.ready_transactions(&*self.client) {
+			// transactions failing on top of the latest state are skipped
+			let _ = self.client.call(tx.signed(), Default::default(), &Default::default(), &mut state, &header);
+		}
+
+		(state, header)
+	}
+
 	fn get_state(&self, number: BlockNumber) -> StateOrBlock {
 		match number {
 			BlockNumber::Num(num) => BlockId::Number(num).into(),
@@ -834,11 +854,30 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 		self.send_raw_transaction(raw)
 	}
 
-	fn call(&self, meta: Self::Metadata, request: CallRequest, num: Trailing<BlockNumber>) -> BoxFuture<Bytes> {
+	fn compile_lll(&self, _: String) -> Result<Bytes> {
+		Err(errors::deprecated("Compilation of LLL via RPC is deprecated".to_string()))
+	}
+
+	fn compile_serpent(&self, _: String) -> Result<Bytes> {
+		Err(errors::deprecated("Compilation of Serpent via RPC is deprecated".to_string()))
+	}
+
+	fn compile_solidity(&self, _: String) -> Result<Bytes> {
+		Err(errors::deprecated("Compilation of Solidity via RPC is deprecated".to_string()))
+	}
+}
+
+impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> EthCall for EthClient<C, SN, S, M, EM> where
+	C: miner::BlockChainClient + BlockChainClient + StateClient<State=T> + Call<State=T> + EngineInfo + 'static,
+	SN: SnapshotService + 'static,
+	S: SyncProvider + 'static,
+	M: MinerService<State=T> + 'static,
+	EM: ExternalMinerService + 'static,
+{
+	fn call(&self, meta: Self::Metadata, request: CallRequest, num: BlockNumber, overrides: StateOverride) -> BoxFuture<Bytes> {
 		let request = CallRequest::into(request);
 		let signed = try_bf!(fake_sign::sign_call(request, meta.is_dapp()));
-
-		let num = num.unwrap_or_default();
+		let overrides = try_bf!(call_params::state_override(overrides));
 
 		let (mut state, header) = if num == BlockNumber::Pending {
 			let info = self.client.chain_info();
@@ -860,7 +899,7 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 			(state, header)
 		};
 
-		let result = self.client.call(&signed, Default::default(), &mut state, &header);
+		let result = self.client.call(&signed, Default::default(), &overrides, &mut state, &header);
 
 		Box::new(future::done(result
 			.map_err(errors::call)
@@ -874,17 +913,17 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 		))
 	}
 
-	fn estimate_gas(&self, meta: Self::Metadata, request: CallRequest, num: Trailing<BlockNumber>) -> BoxFuture<RpcU256> {
-		let request = CallRequest::into(request);
-		let signed = try_bf!(fake_sign::sign_call(request, meta.is_dapp()));
-		let num = num.unwrap_or_default();
+	fn estimate_gas(&self, meta: Self::Metadata, request: CallRequest, num: BlockNumber, overrides: StateOverride) -> BoxFuture<RpcU256> {
+		let mut request = CallRequest::into(request);
+		let overrides = try_bf!(call_params::state_override(overrides));
+		let from = request.from.unwrap_or_default();
 
-		let (state, header) = if num == BlockNumber::Pending {
-			let info = self.client.chain_info();
-			let state = try_bf!(self.miner.pending_state(info.best_block_number).ok_or(errors::state_pruned()));
-			let header = try_bf!(self.miner.pending_block_header(info.best_block_number).ok_or(errors::state_pruned()));
+		// only nonces given explicitly can be invalid, others are read from the state the call is executed against
+		let (state, header, nonce) = if num == BlockNumber::Pending {
+			let (state, header) = self.pending_state_and_header();
+			let nonce = try_bf!(state.nonce(&from).map_err(errors::database));
 
-			(state, header)
+			(state, header, nonce)
 		} else {
 			let id = match num {
 				BlockNumber::Num(num) => BlockId::Number(num),
@@ -895,25 +934,18 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 
 			let state = try_bf!(self.client.state_at(id).ok_or(errors::state_pruned()));
 			let header = try_bf!(self.client.block_header(id).ok_or(errors::state_pruned()).and_then(|h| h.decode().map_err(errors::decode)));
+			let nonce = try_bf!(self.client.nonce(&from, id).ok_or(errors::state_pruned()));
 
-			(state, header)
+			(state, header, nonce)
 		};
 
-		Box::new(future::done(self.client.estimate_gas(&signed, &state, &header)
+		let nonce = overrides.get(&from).and_then(|account| account.nonce).unwrap_or(nonce);
+		request.nonce = request.nonce.or(Some(nonce));
+		let signed = try_bf!(fake_sign::sign_call(request, meta.is_dapp()));
+
+		Box::new(future::done(self.client.estimate_gas(&signed, &overrides, &state, &header)
 			.map(Into::into)
 			.map_err(errors::call)
 		))
 	}
-
-	fn compile_lll(&self, _: String) -> Result<Bytes> {
-		Err(errors::deprecated("Compilation of LLL via RPC is deprecated".to_string()))
-	}
-
-	fn compile_serpent(&self, _: String) -> Result<Bytes> {
-		Err(errors::deprecated("Compilation of Serpent via RPC is deprecated".to_string()))
-	}
-
-	fn compile_solidity(&self, _: String) -> Result<Bytes> {
-		Err(errors::deprecated("Compilation of Solidity via RPC is deprecated".to_string()))
-	}
 }
@@ -40,13 +40,13 @@ use rlp::Rlp;
 use transaction::SignedTransaction;
 
 use v1::impls::eth_filter::Filterable;
-use v1::helpers::{errors, limit_logs};
+use v1::helpers::{call_params, errors, limit_logs};
 use v1::helpers::{PollFilter, PollManager};
 use v1::helpers::light_fetch::{self, LightFetch};
-use v1::traits::Eth;
+use v1::traits::{Eth, EthCall};
 use v1::types::{
 	RichBlock, Block, BlockTransactions, BlockNumber, Bytes, SyncStatus, SyncInfo,
-	Transaction, CallRequest, Index, Filter, Log, Receipt, Work,
+	Transaction, CallRequest, Index, Filter, Log, Receipt, Work, StateOverride,
 	H64 as RpcH64, H256 as RpcH256, H160 as RpcH160, U256 as RpcU256,
 };
 use v1::metadata::Metadata;
@@ -393,25 +393,6 @@ impl<T: LightChainClient + 'static> Eth for EthClient<T> {
 		self.send_raw_transaction(raw)
 	}
 
-	fn call(&self, _meta: Self::Metadata, req: CallRequest, num: Trailing<BlockNumber>) -> BoxFuture<Bytes> {
-		Box::new(self.fetcher().proved_execution(req, num).and_then(|res| {
-			match res {
-				Ok(exec) => Ok(exec.output.into()),
-				Err(e) => Err(errors::execution(e)),
-			}
-		}))
-	}
-
-	fn estimate_gas(&self, _meta: Self::Metadata, req: CallRequest, num: Trailing<BlockNumber>) -> BoxFuture<RpcU256> {
-		// TODO: binary chop for more accurate estimates.
-		Box::new(self.fetcher().proved_execution(req, num).and_then(|res| {
-			match res {
-				Ok(exec) => Ok((exec.refunded + exec.gas_used).into()),
-				Err(e) => Err(errors::execution(e)),
-			}
-		}))
-	}
-
 	fn transaction_by_hash(&self, hash: RpcH256) -> BoxFuture<Option<Transaction>> {
 		let hash = hash.into();
 		let eip86 = self.client.eip86_transition();
@@ -526,6 +507,29 @@ impl<T: LightChainClient + 'static> Eth for EthClient<T> {
 }
 
 // This trait implementation triggers a blanked impl of `EthFilter`.
+impl<T: LightChainClient + 'static> EthCall for EthClient<T> {
+	fn call(&self, _meta: Self::Metadata, req: CallRequest, num: BlockNumber, overrides: StateOverride) -> BoxFuture<Bytes> {
+		let overrides = try_bf!(call_params::state_override(overrides));
+		Box::new(self.fetcher().proved_execution(req, num, overrides).and_then(|res| {
+			match res {
+				Ok(exec) => Ok(exec.output.into()),
+				Err(e) => Err(errors::execution(e)),
+			}
+		}))
+	}
+
+	fn estimate_gas(&self, _meta: Self::Metadata, req: CallRequest, num: BlockNumber, overrides: StateOverride) -> BoxFuture<RpcU256> {
+		let overrides = try_bf!(call_params::state_override(overrides));
+		// TODO: binary chop for more accurate estimates.
+		Box::new(self.fetcher().proved_execution(req, num, overrides).and_then(|res| {
+			match res {
+				Ok(exec) => Ok((exec.refunded + exec.gas_used).into()),
+				Err(e) => Err(errors::execution(e)),
+			}
+		}))
+	}
+}
+
 impl<T: LightChainClient + 'static> Filterable for EthClient<T> {
 	fn best_block_number(&self) -> u64 { self.client.chain_info().best_block_number }
 
@@ -41,8 +41,9 @@ use v1::metadata::Metadata;
 use v1::traits::Parity;
 use v1::types::{
 	Bytes, U256, U64, H160, H256, H512, CallRequest,
+	SimulationResults, StateOverride,
 	Peers, Transaction, RpcSettings, Histogram,
-	TransactionStats, LocalTransactionStatus,
+	TransactionStats, LocalTransactionStatus, ProfitStats,
 	BlockNumber, ConsensusCapability, VersionInfo,
 	OperationsInfo, DappId, ChainStatus,
 	AccountInfo, HwAccountInfo, Header, RichHeader,
@@ -328,6 +329,10 @@ impl Parity for ParityClient {
 		Ok(map)
 	}
 
+	fn profit_stats(&self) -> Result<ProfitStats> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn dapps_url(&self) -> Result<String> {
 		helpers::to_url(&self.dapps_address)
 			.ok_or_else(|| errors::dapps_disabled())
@@ -440,6 +445,10 @@ impl Parity for ParityClient {
 		Err(errors::light_unimplemented(None))
 	}
 
+	fn simulate_calls(&self, _meta: Self::Metadata, _requests: Vec<CallRequest>, _block: BlockNumber, _overrides: Trailing<StateOverride>) -> Result<SimulationResults> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn node_health(&self) -> BoxFuture<Health> {
 		Box::new(self.health.health()
 			.map_err(|err| errors::internal("Health API failure.", err)))
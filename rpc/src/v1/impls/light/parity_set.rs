@@ -27,10 +27,11 @@ use hash::keccak_buffer;
 
 use jsonrpc_core::{Result, BoxFuture};
 use jsonrpc_core::futures::Future;
+use jsonrpc_macros::Trailing;
 use v1::helpers::dapps::DappsService;
 use v1::helpers::errors;
 use v1::traits::ParitySet;
-use v1::types::{Bytes, H160, H256, U256, ReleaseInfo, Transaction, LocalDapp};
+use v1::types::{Bytes, H160, H256, U256, U64, ReleaseInfo, Transaction, LocalDapp};
 
 /// Parity-specific rpc interface for operations altering the settings.
 pub struct ParitySetClient<F> {
@@ -159,4 +160,8 @@ impl<F: Fetch> ParitySet for ParitySetClient<F> {
 	fn remove_transaction(&self, _hash: H256) -> Result<Option<Transaction>> {
 		Err(errors::light_unimplemented(None))
 	}
+
+	fn send_bundle(&self, _raw_transactions: Vec<Bytes>, _max_block: U64, _min_block: Trailing<U64>) -> Result<H256> {
+		Err(errors::light_unimplemented(None))
+	}
 }
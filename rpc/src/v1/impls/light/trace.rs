@@ -19,9 +19,9 @@
 use jsonrpc_core::Result;
 use jsonrpc_macros::Trailing;
 use v1::Metadata;
-use v1::traits::Traces;
+use v1::traits::{Traces, TracesCall};
 use v1::helpers::errors;
-use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, TraceOptions, H256};
+use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, TraceOptions, H256, StateOverride};
 
 /// Traces api implementation.
 // TODO: all calling APIs should be possible w. proved remote TX execution.
@@ -46,10 +46,6 @@ impl Traces for TracesClient {
 		Err(errors::light_unimplemented(None))
 	}
 
-	fn call(&self, _meta: Self::Metadata, _request: CallRequest, _flags: TraceOptions, _block: Trailing<BlockNumber>) -> Result<TraceResults> {
-		Err(errors::light_unimplemented(None))
-	}
-
 	fn call_many(&self, _meta: Self::Metadata, _request: Vec<(CallRequest, TraceOptions)>, _block: Trailing<BlockNumber>) -> Result<Vec<TraceResults>> {
 		Err(errors::light_unimplemented(None))
 	}
@@ -66,3 +62,9 @@ impl Traces for TracesClient {
 		Err(errors::light_unimplemented(None))
 	}
 }
+
+impl TracesCall for TracesClient {
+	fn call(&self, _meta: Self::Metadata, _request: CallRequest, _flags: TraceOptions, _block: BlockNumber, _overrides: StateOverride) -> Result<TraceResults> {
+		Err(errors::light_unimplemented(None))
+	}
+}
@@ -16,6 +16,7 @@
 
 //! Ethereum rpc interface implementation.
 
+mod account;
 mod eth;
 mod eth_filter;
 mod eth_pubsub;
@@ -44,7 +45,7 @@ pub use self::parity::ParityClient;
 pub use self::parity_accounts::ParityAccountsClient;
 pub use self::parity_set::ParitySetClient;
 pub use self::personal::PersonalClient;
-pub use self::pubsub::PubSubClient;
+pub use self::pubsub::{PubSubClient, TransactionPoolEventsHandler};
 pub use self::signer::SignerClient;
 pub use self::signing::SigningQueueClient;
 pub use self::signing_unsafe::SigningUnsafeClient;
@@ -28,6 +28,7 @@ use ethstore::random_phrase;
 use sync::{SyncProvider, ManageNetwork};
 use ethcore::account_provider::AccountProvider;
 use ethcore::client::{BlockChainClient, StateClient, Call};
+use ethcore::header::Header;
 use ethcore::ids::BlockId;
 use ethcore::miner::{self, MinerService};
 use ethcore::state::StateInfo;
@@ -38,13 +39,14 @@ use updater::{Service as UpdateService};
 use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_core::futures::{future, Future};
 use jsonrpc_macros::Trailing;
-use v1::helpers::{self, errors, fake_sign, ipfs, SigningQueue, SignerService, NetworkSettings};
+use v1::helpers::{self, call_params, errors, fake_sign, ipfs, SigningQueue, SignerService, NetworkSettings};
 use v1::metadata::Metadata;
 use v1::traits::Parity;
 use v1::types::{
 	Bytes, U256, U64, H160, H256, H512, CallRequest,
+	SimulationResults, StateOverride,
 	Peers, Transaction, RpcSettings, Histogram,
-	TransactionStats, LocalTransactionStatus,
+	TransactionStats, LocalTransactionStatus, ProfitStats,
 	BlockNumber, ConsensusCapability, VersionInfo,
 	OperationsInfo, DappId, ChainStatus,
 	AccountInfo, HwAccountInfo, RichHeader,
@@ -106,6 +108,34 @@ impl<C, M, U> ParityClient<C, M, U> where
 	}
 }
 
+impl<C, M, U, S> ParityClient<C, M, U> where
+	S: StateInfo + 'static,
+	C: miner::BlockChainClient + BlockChainClient + StateClient<State=S> + Call<State=S> + 'static,
+	M: MinerService<State=S> + 'static,
+{
+	fn state_and_header(&self, num: BlockNumber) -> Result<(S, Header)> {
+		if num == BlockNumber::Pending {
+			let info = self.client.chain_info();
+			let state = self.miner.pending_state(info.best_block_number).ok_or(errors::state_pruned())?;
+			let header = self.miner.pending_block_header(info.best_block_number).ok_or(errors::state_pruned())?;
+
+			Ok((state, header))
+		} else {
+			let id = match num {
+				BlockNumber::Num(num) => BlockId::Number(num),
+				BlockNumber::Earliest => BlockId::Earliest,
+				BlockNumber::Latest => BlockId::Latest,
+				BlockNumber::Pending => unreachable!(), // Already covered
+			};
+
+			let state = self.client.state_at(id).ok_or(errors::state_pruned())?;
+			let header = self.client.block_header(id).ok_or(errors::state_pruned())?.decode().map_err(errors::decode)?;
+
+			Ok((state, header))
+		}
+	}
+}
+
 impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 	S: StateInfo + 'static,
 	C: miner::BlockChainClient + BlockChainClient + StateClient<State=S> + Call<State=S> + 'static,
@@ -347,6 +377,10 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 		)
 	}
 
+	fn profit_stats(&self) -> Result<ProfitStats> {
+		Ok(self.miner.profit_stats().into())
+	}
+
 	fn dapps_url(&self) -> Result<String> {
 		helpers::to_url(&self.dapps_address)
 			.ok_or_else(|| errors::dapps_disabled())
@@ -445,30 +479,27 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 			)))
 			.collect::<Result<Vec<_>>>()?;
 
-		let num = num.unwrap_or_default();
-
-		let (mut state, header) = if num == BlockNumber::Pending {
-			let info = self.client.chain_info();
-			let state = self.miner.pending_state(info.best_block_number).ok_or(errors::state_pruned())?;
-			let header = self.miner.pending_block_header(info.best_block_number).ok_or(errors::state_pruned())?;
+		let (mut state, header) = self.state_and_header(num.unwrap_or_default())?;
 
-			(state, header)
-		} else {
-			let id = match num {
-				BlockNumber::Num(num) => BlockId::Number(num),
-				BlockNumber::Earliest => BlockId::Earliest,
-				BlockNumber::Latest => BlockId::Latest,
-				BlockNumber::Pending => unreachable!(), // Already covered
-			};
+		self.client.call_many(&requests, &mut state, &header)
+				.map(|res| res.into_iter().map(|res| res.output.into()).collect())
+				.map_err(errors::call)
+	}
 
-			let state = self.client.state_at(id).ok_or(errors::state_pruned())?;
-			let header = self.client.block_header(id).ok_or(errors::state_pruned())?.decode().map_err(errors::decode)?;
+	fn simulate_calls(&self, meta: Self::Metadata, requests: Vec<CallRequest>, num: BlockNumber, overrides: Trailing<StateOverride>) -> Result<SimulationResults> {
+		let requests = requests
+			.into_iter()
+			.map(|request| Ok((
+				fake_sign::sign_call(request.into(), meta.is_dapp())?,
+				Default::default()
+			)))
+			.collect::<Result<Vec<_>>>()?;
 
-			(state, header)
-		};
+		let overrides = call_params::state_override(overrides.unwrap_or_default())?;
+		let (mut state, header) = self.state_and_header(num)?;
 
-		self.client.call_many(&requests, &mut state, &header)
-				.map(|res| res.into_iter().map(|res| res.output.into()).collect())
+		self.client.simulate(&requests, &overrides, &mut state, &header)
+				.map(Into::into)
 				.map_err(errors::call)
 	}
 
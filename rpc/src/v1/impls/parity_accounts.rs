@@ -17,16 +17,22 @@
 //! Account management (personal) rpc implementation
 use std::sync::Arc;
 use std::collections::btree_map::{BTreeMap, Entry};
-use ethereum_types::Address;
+use std::time::{SystemTime, UNIX_EPOCH};
+use ethereum_types::{Address, H256};
 
-use ethkey::{Brain, Generator, Secret};
+use ethkey::{Brain, Generator, Mnemonic, Secret};
 use ethstore::KeyFile;
-use ethcore::account_provider::AccountProvider;
+use ethcore::account_provider::{AccountProvider, SpendingLimit as EthSpendingLimit};
 
 use jsonrpc_core::Result;
-use v1::helpers::errors;
+use jsonrpc_macros::Trailing;
+use v1::helpers::{dispatch, errors, totp};
+use v1::metadata::Metadata;
 use v1::traits::ParityAccounts;
-use v1::types::{H160 as RpcH160, H256 as RpcH256, H520 as RpcH520, DappId, Derive, DeriveHierarchical, DeriveHash, ExtAccountInfo};
+use v1::types::{H160 as RpcH160, H256 as RpcH256, H520 as RpcH520, AuditEntry, DappId, Derive, DeriveHierarchical, DeriveHash, ExtAccountInfo, PolicyAccountInfo, SpendingLimit};
+
+/// Maximal number of accounts created with a single `parity_newAccountsFromMnemonic` call.
+const MAX_MNEMONIC_ACCOUNTS: u32 = 100;
 
 /// Account management (personal) rpc implementation.
 pub struct ParityAccountsClient {
@@ -40,9 +46,28 @@ impl ParityAccountsClient {
 			accounts: store.clone(),
 		}
 	}
+
+	/// Checks that the caller knows either the current TOTP code of the account or its password.
+	fn authorize_second_factor_change(&self, address: &Address, meta: &str, authorization: Option<String>) -> Result<()> {
+		let authorization = authorization.ok_or_else(|| errors::second_factor("changing the TOTP secret requires the current TOTP code or the account password"))?;
+
+		if let Ok(Some(secret)) = totp::secret_from_meta(meta) {
+			let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+			if totp::verify(&secret, &authorization, now).is_some() {
+				return Ok(());
+			}
+		}
+
+		match self.accounts.test_password(address, &authorization) {
+			Ok(true) => Ok(()),
+			_ => Err(errors::second_factor("invalid TOTP code or password")),
+		}
+	}
 }
 
 impl ParityAccounts for ParityAccountsClient {
+	type Metadata = Metadata;
+
 	fn all_accounts_info(&self) -> Result<BTreeMap<RpcH160, ExtAccountInfo>> {
 		let info = self.accounts.accounts_info().map_err(|e| errors::account("Could not fetch account info.", e))?;
 		let other = self.accounts.addresses_info();
@@ -52,8 +77,9 @@ impl ParityAccounts for ParityAccountsClient {
 			.chain(other.into_iter())
 			.map(|(address, v)| (address.into(), ExtAccountInfo {
 				name: v.name,
-				meta: v.meta,
-				uuid: v.uuid.map(|uuid| uuid.to_string())
+				meta: totp::strip_secret(&v.meta),
+				uuid: v.uuid.map(|uuid| uuid.to_string()),
+				policy: None,
 			}));
 
 		let mut accounts: BTreeMap<RpcH160, ExtAccountInfo> = BTreeMap::new();
@@ -71,6 +97,19 @@ impl ParityAccounts for ParityAccountsClient {
 			};
 		}
 
+		// Policy accounts are never local, so they only shadow address book entries
+		for (address, policy) in self.accounts.policy_accounts_info() {
+			accounts.insert(address.into(), ExtAccountInfo {
+				name: policy.name,
+				meta: "{}".into(),
+				uuid: None,
+				policy: Some(PolicyAccountInfo {
+					owners: into_vec(policy.owners),
+					threshold: policy.threshold as u64,
+				}),
+			});
+		}
+
 		Ok(accounts)
 	}
 
@@ -81,6 +120,18 @@ impl ParityAccounts for ParityAccountsClient {
 			.map_err(|e| errors::account("Could not create account.", e))
 	}
 
+	fn new_accounts_from_mnemonic(&self, phrase: String, passphrase: String, count: u32, pass: String) -> Result<Vec<RpcH160>> {
+		if count > MAX_MNEMONIC_ACCOUNTS {
+			return Err(errors::invalid_params("count", format!("At most {} accounts can be created at once", MAX_MNEMONIC_ACCOUNTS)));
+		}
+
+		let mnemonic = Mnemonic::from_phrase(&phrase)
+			.map_err(|e| errors::invalid_params("mnemonic", e.to_string()))?;
+		self.accounts.insert_mnemonic_accounts(&mnemonic, &passphrase, count, &pass)
+			.map(into_vec)
+			.map_err(|e| errors::account("Could not create accounts.", e))
+	}
+
 	fn new_account_from_wallet(&self, json: String, pass: String) -> Result<RpcH160> {
 		self.accounts.import_presale(json.as_bytes(), &pass)
 			.or_else(|_| self.accounts.import_wallet(json.as_bytes(), &pass, true))
@@ -104,22 +155,40 @@ impl ParityAccounts for ParityAccountsClient {
 			.map_err(|e| errors::account("Could not fetch account info.", e))
 	}
 
-	fn change_password(&self, account: RpcH160, password: String, new_password: String) -> Result<bool> {
+	fn change_password(&self, meta: Metadata, account: RpcH160, password: String, new_password: String) -> Result<bool> {
 		let account: Address = account.into();
-		self.accounts
-			.change_password(&account, password, new_password)
+		dispatch::with_origin(&meta.origin, || self.accounts.change_password(&account, password, new_password))
 			.map(|_| true)
 			.map_err(|e| errors::account("Could not fetch account info.", e))
 	}
 
-	fn kill_account(&self, account: RpcH160, password: String) -> Result<bool> {
+	fn reencrypt_account(&self, meta: Metadata, account: RpcH160, password: String) -> Result<bool> {
 		let account: Address = account.into();
-		self.accounts
-			.kill_account(&account, &password)
+		dispatch::with_origin(&meta.origin, || self.accounts.reencrypt_account(&account, &password))
+			.map(|_| true)
+			.map_err(|e| errors::account("Could not re-encrypt account.", e))
+	}
+
+	fn kill_account(&self, meta: Metadata, account: RpcH160, password: String) -> Result<bool> {
+		let account: Address = account.into();
+		dispatch::with_origin(&meta.origin, || self.accounts.kill_account(&account, &password))
 			.map(|_| true)
 			.map_err(|e| errors::account("Could not delete account.", e))
 	}
 
+	fn new_policy_account(&self, address: RpcH160, owners: Vec<RpcH160>, threshold: u64, name: String) -> Result<bool> {
+		self.accounts.add_policy_account(address.into(), name, into_vec(owners), threshold as usize)
+			.map(|_| true)
+			.map_err(|e| errors::account("Could not add policy account.", e))
+	}
+
+	fn remove_policy_account(&self, address: RpcH160) -> Result<bool> {
+		let address: Address = address.into();
+
+		self.accounts.remove_policy_account(&address);
+		Ok(true)
+	}
+
 	fn remove_address(&self, addr: RpcH160) -> Result<bool> {
 		let addr: Address = addr.into();
 
@@ -135,14 +204,73 @@ impl ParityAccounts for ParityAccountsClient {
 		Ok(true)
 	}
 
-	fn set_account_meta(&self, addr: RpcH160, meta: String) -> Result<bool> {
+	fn set_account_meta(&self, addr: RpcH160, meta: String, authorization: Trailing<String>) -> Result<bool> {
 		let addr: Address = addr.into();
 
+		// the second factor of local accounts can't be changed without the first one or itself
+		let meta = match self.accounts.account_meta(addr) {
+			Ok(current) => {
+				let (meta, secret_changed) = totp::update_meta(&current.meta, &meta);
+				if secret_changed {
+					let authorization: Option<String> = authorization.into();
+					self.authorize_second_factor_change(&addr, &current.meta, authorization)?;
+				}
+				meta
+			},
+			Err(_) => meta,
+		};
+
 		self.accounts.set_account_meta(addr.clone(), meta.clone())
 			.unwrap_or_else(|_| self.accounts.set_address_meta(addr, meta));
 		Ok(true)
 	}
 
+	fn set_spending_limit(&self, addr: RpcH160, limit: SpendingLimit) -> Result<bool> {
+		let allowed_methods = match limit.allowed_methods {
+			Some(methods) => Some(methods.into_iter()
+				.map(|method| match method.0.len() {
+					4 => Ok([method.0[0], method.0[1], method.0[2], method.0[3]]),
+					_ => Err(errors::invalid_params("allowedMethods", "Method selectors have to be 4 bytes long.")),
+				})
+				.collect::<Result<Vec<_>>>()?),
+			None => None,
+		};
+		let limit = EthSpendingLimit {
+			max_value: limit.max_value.map(Into::into),
+			daily_limit: limit.daily_limit.map(Into::into),
+			allowed_recipients: limit.allowed_recipients.map(into_vec),
+			allowed_methods,
+		};
+
+		self.accounts.set_spending_limit(addr.into(), limit)
+			.map(|_| true)
+			.map_err(|e| errors::account("Could not set spending limit.", e))
+	}
+
+	fn remove_spending_limit(&self, addr: RpcH160) -> Result<bool> {
+		let addr: Address = addr.into();
+
+		self.accounts.remove_spending_limit(&addr)
+			.map(|_| true)
+			.map_err(|e| errors::account("Could not remove spending limit.", e))
+	}
+
+	fn spending_limits(&self) -> Result<BTreeMap<RpcH160, SpendingLimit>> {
+		Ok(self.accounts.spending_limits()
+			.into_iter()
+			.map(|(address, limit)| (address.into(), limit.into()))
+			.collect())
+	}
+
+	fn audit_log(&self, limit: u64, account: Trailing<RpcH160>) -> Result<Vec<AuditEntry>> {
+		let account: Option<RpcH160> = account.into();
+		let account: Option<Address> = account.map(Into::into);
+
+		self.accounts.audit_log(account.as_ref(), limit as usize)
+			.map(into_vec)
+			.map_err(|e| errors::account("Could not read audit log.", e))
+	}
+
 	fn set_dapp_addresses(&self, dapp: DappId, addresses: Option<Vec<RpcH160>>) -> Result<bool> {
 		self.accounts.set_dapp_addresses(dapp.into(), addresses.map(into_vec))
 			.map_err(|e| errors::account("Couldn't set dapp addresses.", e))
@@ -209,23 +337,20 @@ impl ParityAccounts for ParityAccountsClient {
 		Ok(into_vec(self.accounts.list_geth_accounts(false)))
 	}
 
-	fn create_vault(&self, name: String, password: String) -> Result<bool> {
-		self.accounts
-			.create_vault(&name, &password)
+	fn create_vault(&self, meta: Metadata, name: String, password: String) -> Result<bool> {
+		dispatch::with_origin(&meta.origin, || self.accounts.create_vault(&name, &password))
 			.map_err(|e| errors::account("Could not create vault.", e))
 			.map(|_| true)
 	}
 
-	fn open_vault(&self, name: String, password: String) -> Result<bool> {
-		self.accounts
-			.open_vault(&name, &password)
+	fn open_vault(&self, meta: Metadata, name: String, password: String) -> Result<bool> {
+		dispatch::with_origin(&meta.origin, || self.accounts.open_vault(&name, &password))
 			.map_err(|e| errors::account("Could not open vault.", e))
 			.map(|_| true)
 	}
 
-	fn close_vault(&self, name: String) -> Result<bool> {
-		self.accounts
-			.close_vault(&name)
+	fn close_vault(&self, meta: Metadata, name: String) -> Result<bool> {
+		dispatch::with_origin(&meta.origin, || self.accounts.close_vault(&name))
 			.map_err(|e| errors::account("Could not close vault.", e))
 			.map(|_| true)
 	}
@@ -242,16 +367,20 @@ impl ParityAccounts for ParityAccountsClient {
 			.map_err(|e| errors::account("Could not list vaults.", e))
 	}
 
-	fn change_vault_password(&self, name: String, new_password: String) -> Result<bool> {
-		self.accounts
-			.change_vault_password(&name, &new_password)
+	fn change_vault_password(&self, meta: Metadata, name: String, new_password: String) -> Result<bool> {
+		dispatch::with_origin(&meta.origin, || self.accounts.change_vault_password(&name, &new_password))
 			.map_err(|e| errors::account("Could not change vault password.", e))
 			.map(|_| true)
 	}
 
-	fn change_vault(&self, address: RpcH160, new_vault: String) -> Result<bool> {
-		self.accounts
-			.change_vault(address.into(), &new_vault)
+	fn reencrypt_vault(&self, meta: Metadata, name: String) -> Result<bool> {
+		dispatch::with_origin(&meta.origin, || self.accounts.reencrypt_vault(&name))
+			.map_err(|e| errors::account("Could not re-encrypt vault.", e))
+			.map(|_| true)
+	}
+
+	fn change_vault(&self, meta: Metadata, address: RpcH160, new_vault: String) -> Result<bool> {
+		dispatch::with_origin(&meta.origin, || self.accounts.change_vault(address.into(), &new_vault))
 			.map_err(|e| errors::account("Could not change vault.", e))
 			.map(|_| true)
 	}
@@ -295,24 +424,24 @@ impl ParityAccounts for ParityAccountsClient {
 			.map_err(|e| errors::account("Could not derive account.", e))
 	}
 
-	fn export_account(&self, addr: RpcH160, password: String) -> Result<KeyFile> {
+	fn export_account(&self, meta: Metadata, addr: RpcH160, password: String) -> Result<KeyFile> {
 		let addr = addr.into();
-		self.accounts
-			.export_account(
-				&addr,
-				password,
-			)
+		dispatch::with_origin(&meta.origin, || self.accounts.export_account(&addr, password))
 			.map(Into::into)
 			.map_err(|e| errors::account("Could not export account.", e))
 	}
 
-	fn sign_message(&self, addr: RpcH160, password: String, message: RpcH256) -> Result<RpcH520> {
-		self.accounts
-			.sign(
-				addr.into(),
-				Some(password),
-				message.into()
-			)
+	fn sign_message(&self, meta: Metadata, addr: RpcH160, password: String, message: RpcH256) -> Result<RpcH520> {
+		let addr = addr.into();
+		// hardware wallets never sign raw hashes, the hash is signed as a prefixed message instead
+		if self.accounts.is_hardware_address(&addr) {
+			let message: H256 = message.into();
+			return dispatch::with_origin(&meta.origin, || self.accounts.sign_message_with_hardware(addr, &*message))
+				.map(Into::into)
+				.map_err(|e| errors::account("Could not sign message with hardware wallet.", e));
+		}
+
+		dispatch::with_origin(&meta.origin, || self.accounts.sign(addr, Some(password), message.into()))
 			.map(Into::into)
 			.map_err(|e| errors::account("Could not sign message.", e))
 	}
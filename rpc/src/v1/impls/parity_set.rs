@@ -20,19 +20,21 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use ethcore::client::{BlockChainClient, Mode};
-use ethcore::miner::MinerService;
+use ethcore::miner::{self, MinerService};
 use sync::ManageNetwork;
 use fetch::{self, Fetch};
 use futures_cpupool::CpuPool;
 use hash::keccak_buffer;
+use rlp::Rlp;
 use updater::{Service as UpdateService};
 
 use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_core::futures::Future;
+use jsonrpc_macros::Trailing;
 use v1::helpers::dapps::DappsService;
 use v1::helpers::errors;
 use v1::traits::ParitySet;
-use v1::types::{Bytes, H160, H256, U256, ReleaseInfo, Transaction, LocalDapp};
+use v1::types::{Bytes, H160, H256, U256, U64, ReleaseInfo, Transaction, LocalDapp};
 
 /// Parity-specific rpc interface for operations altering the settings.
 pub struct ParitySetClient<C, M, U, F = fetch::Client> {
@@ -73,7 +75,7 @@ impl<C, M, U, F> ParitySetClient<C, M, U, F>
 }
 
 impl<C, M, U, F> ParitySet for ParitySetClient<C, M, U, F> where
-	C: BlockChainClient + 'static,
+	C: BlockChainClient + miner::BlockChainClient + 'static,
 	M: MinerService + 'static,
 	U: UpdateService + 'static,
 	F: Fetch + 'static,
@@ -210,4 +212,15 @@ impl<C, M, U, F> ParitySet for ParitySetClient<C, M, U, F> where
 		   .map(|t| Transaction::from_pending(t.pending().clone(), block_number + 1, self.eip86_transition))
 		)
 	}
+
+	fn send_bundle(&self, raw_transactions: Vec<Bytes>, max_block: U64, min_block: Trailing<U64>) -> Result<H256> {
+		let transactions = raw_transactions.into_iter()
+			.map(|raw| Rlp::new(&raw.into_vec()).as_val().map_err(errors::rlp))
+			.collect::<Result<Vec<_>>>()?;
+		let min_block: Option<U64> = min_block.into();
+
+		self.miner.submit_bundle(&*self.client, transactions, min_block.map(Into::into), max_block.into())
+			.map(Into::into)
+			.map_err(errors::bundle)
+	}
 }
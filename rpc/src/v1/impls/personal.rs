@@ -35,6 +35,7 @@ use v1::types::{
 	ConfirmationPayload as RpcConfirmationPayload,
 	ConfirmationResponse as RpcConfirmationResponse,
 	TransactionRequest,
+	TypedData as RpcTypedData,
 	RichRawTransaction as RpcRichRawTransaction,
 };
 use v1::metadata::Metadata;
@@ -77,7 +78,7 @@ impl<D: Dispatcher + 'static> PersonalClient<D> {
 		Box::new(dispatcher.fill_optional_fields(request.into(), default, false)
 			.and_then(move |filled| {
 				let condition = filled.condition.clone().map(Into::into);
-				dispatcher.sign(accounts, filled, SignWith::Password(password))
+				dispatcher.sign(accounts, filled, SignWith::Password(password), meta.origin)
 					.map(|tx| tx.into_value())
 					.map(move |tx| PendingTransaction::new(tx, condition))
 					.map(move |tx| (tx, dispatcher))
@@ -100,7 +101,7 @@ impl<D: Dispatcher + 'static> Personal for PersonalClient<D> {
 			.map_err(|e| errors::account("Could not create account.", e))
 	}
 
-	fn unlock_account(&self, account: RpcH160, account_pass: String, duration: Option<RpcU128>) -> Result<bool> {
+	fn unlock_account(&self, meta: Metadata, account: RpcH160, account_pass: String, duration: Option<RpcU128>) -> Result<bool> {
 		let account: Address = account.into();
 		let store = self.accounts.clone();
 		let duration = match duration {
@@ -116,23 +117,23 @@ impl<D: Dispatcher + 'static> Personal for PersonalClient<D> {
 			},
 		};
 
-		let r = match (self.allow_perm_unlock, duration) {
-			(false, None) => store.unlock_account_temporarily(account, account_pass),
-			(false, _) => return Err(errors::unsupported(
+		let r = dispatch::with_origin(&meta.origin, || match (self.allow_perm_unlock, duration) {
+			(false, None) => Ok(store.unlock_account_temporarily(account, account_pass)),
+			(false, _) => Err(errors::unsupported(
 				"Time-unlocking is only supported in --geth compatibility mode.",
 				Some("Restart your client with --geth flag or use personal_sendTransaction instead."),
 			)),
-			(true, Some(0)) => store.unlock_account_permanently(account, account_pass),
-			(true, Some(d)) => store.unlock_account_timed(account, account_pass, Duration::from_secs(d.into())),
-			(true, None) => store.unlock_account_timed(account, account_pass, Duration::from_secs(300)),
-		};
+			(true, Some(0)) => Ok(store.unlock_account_permanently(account, account_pass)),
+			(true, Some(d)) => Ok(store.unlock_account_timed(account, account_pass, Duration::from_secs(d.into()))),
+			(true, None) => Ok(store.unlock_account_timed(account, account_pass, Duration::from_secs(300))),
+		})?;
 		match r {
 			Ok(_) => Ok(true),
 			Err(err) => Err(errors::account("Unable to unlock the account.", err)),
 		}
 	}
 
-	fn sign(&self, data: RpcBytes, account: RpcH160, password: String) -> BoxFuture<RpcH520> {
+	fn sign(&self, meta: Metadata, data: RpcBytes, account: RpcH160, password: String) -> BoxFuture<RpcH520> {
 		let dispatcher = self.dispatcher.clone();
 		let accounts = self.accounts.clone();
 
@@ -140,7 +141,25 @@ impl<D: Dispatcher + 'static> Personal for PersonalClient<D> {
 
 		Box::new(dispatch::from_rpc(payload, account.into(), &dispatcher)
 				 .and_then(|payload| {
-					 dispatch::execute(dispatcher, accounts, payload, dispatch::SignWith::Password(password))
+					 dispatch::execute(dispatcher, accounts, payload, dispatch::SignWith::Password(password), meta.origin)
+				 })
+				 .map(|v| v.into_value())
+				 .then(|res| match res {
+					 Ok(RpcConfirmationResponse::Signature(signature)) => Ok(signature),
+					 Err(e) => Err(e),
+					 e => Err(errors::internal("Unexpected result", e)),
+				 }))
+	}
+
+	fn sign_typed_data(&self, meta: Metadata, data: RpcTypedData, account: RpcH160, password: String) -> BoxFuture<RpcH520> {
+		let dispatcher = self.dispatcher.clone();
+		let accounts = self.accounts.clone();
+
+		let payload = RpcConfirmationPayload::SignTypedData((account.clone(), data).into());
+
+		Box::new(dispatch::from_rpc(payload, account.into(), &dispatcher)
+				 .and_then(|payload| {
+					 dispatch::execute(dispatcher, accounts, payload, dispatch::SignWith::Password(password), meta.origin)
 				 })
 				 .map(|v| v.into_value())
 				 .then(|res| match res {
@@ -16,26 +16,61 @@
 
 //! Parity-specific PUB-SUB rpc implementation.
 
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use std::time::Duration;
 use parking_lot::RwLock;
+use serde_json;
 
 use jsonrpc_core::{self as core, Result, MetaIoHandler};
 use jsonrpc_core::futures::{Future, Stream, Sink};
 use jsonrpc_macros::Trailing;
-use jsonrpc_macros::pubsub::Subscriber;
+use jsonrpc_macros::pubsub::{self, Subscriber};
 use jsonrpc_pubsub::SubscriptionId;
 use tokio_timer;
 
 use parity_reactor::Remote;
-use v1::helpers::GenericPollManager;
+use v1::helpers::{errors, GenericPollManager, Subscribers};
 use v1::metadata::Metadata;
 use v1::traits::PubSub;
+use v1::types::TransactionPoolEvent;
+
+/// Subscription streaming transaction pool events instead of polling a method.
+const TRANSACTION_POOL_EVENTS: &'static str = "parity_transactionPoolEvents";
 
 /// Parity PubSub implementation.
 pub struct PubSubClient<S: core::Middleware<Metadata>> {
 	poll_manager: Arc<RwLock<GenericPollManager<S>>>,
+	pool_events: Arc<TransactionPoolEventsHandler>,
+	remote: Remote,
+}
+
+/// Transaction pool events notification handler.
+pub struct TransactionPoolEventsHandler {
 	remote: Remote,
+	subscribers: RwLock<Subscribers<pubsub::Sink<core::Value>>>,
+}
+
+impl TransactionPoolEventsHandler {
+	/// Notify all subscribers about transaction pool events.
+	pub fn notify(&self, events: &[TransactionPoolEvent]) {
+		for subscriber in self.subscribers.read().values() {
+			for event in events {
+				let value = match serde_json::to_value(event) {
+					Ok(value) => value,
+					Err(e) => {
+						warn!(target: "rpc", "Unable to serialize transaction pool event: {}", e);
+						continue;
+					},
+				};
+
+				self.remote.spawn(subscriber
+					.notify(Ok(value))
+					.map(|_| ())
+					.map_err(|e| warn!(target: "rpc", "Unable to send notification: {}", e))
+				);
+			}
+		}
+	}
 }
 
 impl<S: core::Middleware<Metadata>> PubSubClient<S> {
@@ -57,9 +92,18 @@ impl<S: core::Middleware<Metadata>> PubSubClient<S> {
 
 		PubSubClient {
 			poll_manager,
+			pool_events: Arc::new(TransactionPoolEventsHandler {
+				remote: remote.clone(),
+				subscribers: RwLock::new(Subscribers::default()),
+			}),
 			remote,
 		}
 	}
+
+	/// Returns a handler for `parity_transactionPoolEvents` subscriptions.
+	pub fn transaction_pool_events_handler(&self) -> Weak<TransactionPoolEventsHandler> {
+		Arc::downgrade(&self.pool_events)
+	}
 }
 
 impl PubSubClient<core::NoopMiddleware> {
@@ -68,6 +112,7 @@ impl PubSubClient<core::NoopMiddleware> {
 	pub fn new_test(rpc: MetaIoHandler<Metadata, core::NoopMiddleware>, remote: Remote) -> Self {
 		let client = Self::new(MetaIoHandler::with_middleware(Default::default()), remote);
 		*client.poll_manager.write() = GenericPollManager::new_test(rpc);
+		*client.pool_events.subscribers.write() = Subscribers::new_test();
 		client
 	}
 }
@@ -77,6 +122,20 @@ impl<S: core::Middleware<Metadata>> PubSub for PubSubClient<S> {
 
 	fn parity_subscribe(&self, mut meta: Metadata, subscriber: Subscriber<core::Value>, method: String, params: Trailing<core::Params>) {
 		let params = params.unwrap_or(core::Params::Array(vec![]));
+		if method == TRANSACTION_POOL_EVENTS {
+			match params {
+				core::Params::None => {},
+				core::Params::Array(ref params) if params.is_empty() => {},
+				_ => {
+					let _ = subscriber.reject(errors::invalid_params(TRANSACTION_POOL_EVENTS, "Expected no parameters."));
+					return;
+				},
+			}
+
+			self.pool_events.subscribers.write().push(subscriber);
+			return;
+		}
+
 		// Make sure to get rid of PubSub session otherwise it will never be dropped.
 		meta.session = None;
 
@@ -96,6 +155,7 @@ impl<S: core::Middleware<Metadata>> PubSub for PubSubClient<S> {
 
 	fn parity_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
 		let res = self.poll_manager.write().unsubscribe(&id);
-		Ok(res)
+		let res2 = self.pool_events.subscribers.write().remove(&id).is_some();
+		Ok(res || res2)
 	}
 }
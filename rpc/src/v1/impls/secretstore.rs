@@ -24,11 +24,12 @@ use ethkey::Secret;
 use ethcore::account_provider::AccountProvider;
 
 use jsonrpc_core::Result;
-use v1::helpers::errors;
+use v1::helpers::{dispatch, errors};
 use v1::helpers::secretstore::{generate_document_key, encrypt_document,
 	decrypt_document, decrypt_document_with_shadow, ordered_servers_keccak};
+use v1::metadata::Metadata;
 use v1::traits::SecretStore;
-use v1::types::{H160, H256, H512, Bytes, EncryptedDocumentKey};
+use v1::types::{H160, H256, H512, Bytes, EncryptedDocumentKey, Origin};
 
 /// Parity implementation.
 pub struct SecretStoreClient {
@@ -44,39 +45,41 @@ impl SecretStoreClient {
 	}
 
 	/// Decrypt public key using account' private key
-	fn decrypt_key(&self, address: H160, password: String, key: Bytes) -> Result<Vec<u8>> {
-		self.accounts.decrypt(address.into(), Some(password), &DEFAULT_MAC, &key.0)
+	fn decrypt_key(&self, address: H160, password: String, key: Bytes, origin: &Origin) -> Result<Vec<u8>> {
+		dispatch::with_origin(origin, || self.accounts.decrypt(address.into(), Some(password), &DEFAULT_MAC, &key.0))
 			.map_err(|e| errors::account("Could not decrypt key.", e))
 	}
 
 	/// Decrypt secret key using account' private key
-	fn decrypt_secret(&self, address: H160, password: String, key: Bytes) -> Result<Secret> {
-		self.decrypt_key(address, password, key)
+	fn decrypt_secret(&self, address: H160, password: String, key: Bytes, origin: &Origin) -> Result<Secret> {
+		self.decrypt_key(address, password, key, origin)
 			.and_then(|s| Secret::from_unsafe_slice(&s).map_err(|e| errors::account("invalid secret", e)))
 	}
 }
 
 impl SecretStore for SecretStoreClient {
+	type Metadata = Metadata;
+
 	fn generate_document_key(&self, address: H160, password: String, server_key_public: H512) -> Result<EncryptedDocumentKey> {
 		let account_public = self.accounts.account_public(address.into(), &password)
 			.map_err(|e| errors::account("Could not read account public.", e))?;
 		generate_document_key(account_public, server_key_public.into())
 	}
 
-	fn encrypt(&self, address: H160, password: String, key: Bytes, data: Bytes) -> Result<Bytes> {
-		encrypt_document(self.decrypt_key(address, password, key)?, data.0)
+	fn encrypt(&self, meta: Metadata, address: H160, password: String, key: Bytes, data: Bytes) -> Result<Bytes> {
+		encrypt_document(self.decrypt_key(address, password, key, &meta.origin)?, data.0)
 			.map(Into::into)
 	}
 
-	fn decrypt(&self, address: H160, password: String, key: Bytes, data: Bytes) -> Result<Bytes> {
-		decrypt_document(self.decrypt_key(address, password, key)?, data.0)
+	fn decrypt(&self, meta: Metadata, address: H160, password: String, key: Bytes, data: Bytes) -> Result<Bytes> {
+		decrypt_document(self.decrypt_key(address, password, key, &meta.origin)?, data.0)
 			.map(Into::into)
 	}
 
-	fn shadow_decrypt(&self, address: H160, password: String, decrypted_secret: H512, common_point: H512, decrypt_shadows: Vec<Bytes>, data: Bytes) -> Result<Bytes> {
+	fn shadow_decrypt(&self, meta: Metadata, address: H160, password: String, decrypted_secret: H512, common_point: H512, decrypt_shadows: Vec<Bytes>, data: Bytes) -> Result<Bytes> {
 		let mut shadows = Vec::with_capacity(decrypt_shadows.len());
 		for decrypt_shadow in decrypt_shadows {
-			shadows.push(self.decrypt_secret(address.clone(), password.clone(), decrypt_shadow)?);
+			shadows.push(self.decrypt_secret(address.clone(), password.clone(), decrypt_shadow, &meta.origin)?);
 		}
 
 		decrypt_document_with_shadow(decrypted_secret.into(), common_point.into(), shadows, data.0)
@@ -87,9 +90,8 @@ impl SecretStore for SecretStoreClient {
 		Ok(ordered_servers_keccak(servers_set))
 	}
 
-	fn sign_raw_hash(&self, address: H160, password: String, raw_hash: H256) -> Result<Bytes> {
-		self.accounts
-			.sign(address.into(), Some(password), raw_hash.into())
+	fn sign_raw_hash(&self, meta: Metadata, address: H160, password: String, raw_hash: H256) -> Result<Bytes> {
+		dispatch::with_origin(&meta.origin, || self.accounts.sign(address.into(), Some(password), raw_hash.into()))
 			.map(|s| Bytes::new((*s).to_vec()))
 			.map_err(|e| errors::account("Could not sign raw hash.", e))
 	}
@@ -16,9 +16,12 @@
 
 //! Transactions Confirmations rpc implementation
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use ethcore::account_provider::AccountProvider;
+use ethereum_types::{Address, U256 as EthU256};
 use ethkey;
 use parity_reactor::Remote;
 use parking_lot::Mutex;
@@ -29,12 +32,13 @@ use jsonrpc_core::{Result, BoxFuture, Error};
 use jsonrpc_core::futures::{future, Future, IntoFuture};
 use jsonrpc_core::futures::future::Either;
 use jsonrpc_pubsub::SubscriptionId;
+use jsonrpc_macros::Trailing;
 use jsonrpc_macros::pubsub::{Sink, Subscriber};
 use v1::helpers::dispatch::{self, Dispatcher, WithToken, eth_data_hash};
-use v1::helpers::{errors, SignerService, SigningQueue, ConfirmationPayload, FilledTransactionRequest, Subscribers};
+use v1::helpers::{eip712, errors, totp, SignerService, SigningQueue, ConfirmationPayload, FilledTransactionRequest, Subscribers};
 use v1::metadata::Metadata;
 use v1::traits::Signer;
-use v1::types::{TransactionModification, ConfirmationRequest, ConfirmationResponse, ConfirmationResponseWithToken, U256, Bytes};
+use v1::types::{TransactionModification, ConfirmationRequest, ConfirmationResponse, ConfirmationResponseWithToken, Origin, U256, Bytes};
 
 /// Transactions confirmation (personal) rpc implementation.
 pub struct SignerClient<D: Dispatcher> {
@@ -42,6 +46,8 @@ pub struct SignerClient<D: Dispatcher> {
 	accounts: Arc<AccountProvider>,
 	dispatcher: D,
 	subscribers: Arc<Mutex<Subscribers<Sink<Vec<ConfirmationRequest>>>>>,
+	/// Last TOTP time step used by each account, so that codes can't be replayed.
+	used_totp_steps: Mutex<HashMap<Address, u64>>,
 }
 
 impl<D: Dispatcher + 'static> SignerClient<D> {
@@ -74,11 +80,47 @@ impl<D: Dispatcher + 'static> SignerClient<D> {
 			accounts: store.clone(),
 			dispatcher,
 			subscribers,
+			used_totp_steps: Mutex::new(HashMap::new()),
 		}
 	}
 
-	fn confirm_internal<F, T>(&self, id: U256, modification: TransactionModification, f: F) -> BoxFuture<WithToken<ConfirmationResponse>> where
-		F: FnOnce(D, Arc<AccountProvider>, ConfirmationPayload) -> T,
+	/// Checks whether given request can be confirmed now:
+	/// high-value transactions need to wait in the queue for the configured delay and
+	/// accounts with a TOTP secret in their meta require a valid second factor code.
+	fn check_confirmation(&self, id: &EthU256, payload: &ConfirmationPayload, totp_code: Option<String>) -> Result<()> {
+		if let Some(delay) = self.signer.confirmation_delay().and_then(|delay| delay.delay_for(payload)) {
+			let age = self.signer.request_age(id).unwrap_or_default();
+			if age < delay {
+				let remaining = delay - age;
+				let remaining_secs = remaining.as_secs() + if remaining.subsec_nanos() > 0 { 1 } else { 0 };
+				return Err(errors::request_delayed(remaining_secs));
+			}
+		}
+
+		let address = payload.sender();
+		let meta = match self.accounts.account_meta(address) {
+			Ok(meta) => meta.meta,
+			Err(_) => return Ok(()),
+		};
+		let secret = match totp::secret_from_meta(&meta).map_err(|e| errors::second_factor(&e))? {
+			Some(secret) => secret,
+			None => return Ok(()),
+		};
+
+		let code = totp_code.ok_or_else(|| errors::second_factor("TOTP code is required for this account"))?;
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+		let step = totp::verify(&secret, &code, now).ok_or_else(|| errors::second_factor("invalid TOTP code"))?;
+
+		let mut used = self.used_totp_steps.lock();
+		if used.get(&address).map_or(false, |last| *last >= step) {
+			return Err(errors::second_factor("TOTP code has already been used"));
+		}
+		used.insert(address, step);
+		Ok(())
+	}
+
+	fn confirm_internal<F, T>(&self, id: U256, modification: TransactionModification, totp_code: Option<String>, f: F) -> BoxFuture<WithToken<ConfirmationResponse>> where
+		F: FnOnce(D, Arc<AccountProvider>, ConfirmationPayload, Origin) -> T,
 		T: IntoFuture<Item=WithToken<ConfirmationResponse>, Error=Error>,
 		T::Future: Send + 'static
 	{
@@ -105,11 +147,18 @@ impl<D: Dispatcher + 'static> SignerClient<D> {
 					request.condition = condition.clone().map(Into::into);
 				}
 			}
-			let fut = f(dispatcher, self.accounts.clone(), payload);
+			if let Err(e) = self.check_confirmation(&id, &payload, totp_code) {
+				return Either::B(future::err(e));
+			}
+			// account operations are audited with the origin of the confirmed request
+			let fut = f(dispatcher, self.accounts.clone(), payload, confirmation.origin.clone());
 			Either::A(fut.into_future().then(move |result| {
 				// Execute
-				if let Ok(ref response) = result {
-					signer.request_confirmed(id, Ok((*response).clone()));
+				match result {
+					// requests of policy accounts stay in the queue until enough owners approve them
+					Ok(ref response) if is_pending_approval(response) => {},
+					Ok(ref response) => signer.request_confirmed(id, Ok((*response).clone())),
+					Err(_) => {},
 				}
 
 				result
@@ -150,6 +199,13 @@ impl<D: Dispatcher + 'static> SignerClient<D> {
 	}
 }
 
+fn is_pending_approval(response: &ConfirmationResponse) -> bool {
+	match *response {
+		ConfirmationResponse::PolicyApproval(_) => true,
+		_ => false,
+	}
+}
+
 impl<D: Dispatcher + 'static> Signer for SignerClient<D> {
 	type Metadata = Metadata;
 
@@ -163,19 +219,19 @@ impl<D: Dispatcher + 'static> Signer for SignerClient<D> {
 
 	// TODO [ToDr] TransactionModification is redundant for some calls
 	// might be better to replace it in future
-	fn confirm_request(&self, id: U256, modification: TransactionModification, pass: String)
+	fn confirm_request(&self, id: U256, modification: TransactionModification, pass: String, totp_code: Trailing<String>)
 		-> BoxFuture<ConfirmationResponse>
 	{
-		Box::new(self.confirm_internal(id, modification, move |dis, accounts, payload| {
-			dispatch::execute(dis, accounts, payload, dispatch::SignWith::Password(pass))
+		Box::new(self.confirm_internal(id, modification, totp_code.into(), move |dis, accounts, payload, origin| {
+			dispatch::execute(dis, accounts, payload, dispatch::SignWith::Password(pass), origin)
 		}).map(|v| v.into_value()))
 	}
 
-	fn confirm_request_with_token(&self, id: U256, modification: TransactionModification, token: String)
+	fn confirm_request_with_token(&self, id: U256, modification: TransactionModification, token: String, totp_code: Trailing<String>)
 		-> BoxFuture<ConfirmationResponseWithToken>
 	{
-		Box::new(self.confirm_internal(id, modification, move |dis, accounts, payload| {
-			dispatch::execute(dis, accounts, payload, dispatch::SignWith::Token(token))
+		Box::new(self.confirm_internal(id, modification, totp_code.into(), move |dis, accounts, payload, origin| {
+			dispatch::execute(dis, accounts, payload, dispatch::SignWith::Token(token), origin)
 		}).and_then(|v| match v {
 			WithToken::No(_) => Err(errors::internal("Unexpected response without token.", "")),
 			WithToken::Yes(response, token) => Ok(ConfirmationResponseWithToken {
@@ -185,10 +241,11 @@ impl<D: Dispatcher + 'static> Signer for SignerClient<D> {
 		}))
 	}
 
-	fn confirm_request_raw(&self, id: U256, bytes: Bytes) -> Result<ConfirmationResponse> {
+	fn confirm_request_raw(&self, id: U256, bytes: Bytes, totp_code: Trailing<String>) -> Result<ConfirmationResponse> {
 		let id = id.into();
 
 		self.signer.peek(&id).map(|confirmation| {
+			self.check_confirmation(&id, &confirmation.payload, totp_code.into())?;
 			let result = match confirmation.payload {
 				ConfirmationPayload::SendTransaction(request) => {
 					Self::verify_transaction(bytes, request, |pending_transaction| {
@@ -212,6 +269,16 @@ impl<D: Dispatcher + 'static> Signer for SignerClient<D> {
 						Err(err) => Err(errors::invalid_params("Invalid signature received.", err)),
 					}
 				},
+				ConfirmationPayload::SignTypedData(address, data) => {
+					let expected_hash = eip712::hash_structured_data(&data)
+						.map_err(|e| errors::invalid_params("Typed data", e.to_string()))?;
+					let signature = ethkey::Signature::from_electrum(&bytes.0);
+					match ethkey::verify_address(&address, &signature, &expected_hash) {
+						Ok(true) => Ok(ConfirmationResponse::Signature(bytes.0.as_slice().into())),
+						Ok(false) => Err(errors::invalid_params("Sender address does not match the signature.", ())),
+						Err(err) => Err(errors::invalid_params("Invalid signature received.", err)),
+					}
+				},
 				ConfirmationPayload::Decrypt(_address, _data) => {
 					// TODO [ToDr]: Decrypt can we verify if the answer is correct?
 					Ok(ConfirmationResponse::Decrypt(bytes))
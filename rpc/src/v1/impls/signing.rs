@@ -27,11 +27,12 @@ use jsonrpc_core::{BoxFuture, Result, Error};
 use jsonrpc_core::futures::{future, Future, Poll, Async};
 use jsonrpc_core::futures::future::Either;
 use v1::helpers::{
-	errors, DefaultAccount, SignerService, SigningQueue,
+	eip712, errors, DefaultAccount, SignerService, SigningQueue,
 	ConfirmationReceiver as RpcConfirmationReceiver,
 	ConfirmationResult as RpcConfirmationResult,
 };
 use v1::helpers::dispatch::{self, Dispatcher};
+use v1::impls::account::SigningAccounts;
 use v1::metadata::Metadata;
 use v1::traits::{EthSigning, ParitySigning};
 use v1::types::{
@@ -39,6 +40,7 @@ use v1::types::{
 	Either as RpcEither,
 	RichRawTransaction as RpcRichRawTransaction,
 	TransactionRequest as RpcTransactionRequest,
+	TypedData as RpcTypedData,
 	ConfirmationPayload as RpcConfirmationPayload,
 	ConfirmationResponse as RpcConfirmationResponse,
 	Origin,
@@ -121,7 +123,7 @@ impl<D: Dispatcher + 'static> SigningQueueClient<D> {
 			.and_then(move |payload| {
 				let sender = payload.sender();
 				if accounts.is_unlocked(&sender) {
-					Either::A(dispatch::execute(dispatcher, accounts, payload, dispatch::SignWith::Nothing)
+					Either::A(dispatch::execute(dispatcher, accounts, payload, dispatch::SignWith::Nothing, origin)
 						.map(|v| v.into_value())
 						.map(DispatchResult::Value))
 				} else {
@@ -218,6 +220,26 @@ impl<D: Dispatcher + 'static> EthSigning for SigningQueueClient<D> {
 		}))
 	}
 
+	fn sign_typed_data(&self, meta: Metadata, address: RpcH160, data: RpcTypedData) -> BoxFuture<RpcH520> {
+		// malformed data is rejected before the request reaches the signer queue
+		if let Err(e) = eip712::hash_structured_data(&data) {
+			return Box::new(future::err(errors::invalid_params("Typed data", e.to_string())));
+		}
+
+		let res = self.dispatch(
+			RpcConfirmationPayload::SignTypedData((address.clone(), data).into()),
+			address.into(),
+			meta.origin,
+		);
+
+		Box::new(res.flatten().and_then(move |response| {
+			match response {
+				RpcConfirmationResponse::Signature(sig) => Ok(sig),
+				e => Err(errors::internal("Unexpected result.", e)),
+			}
+		}))
+	}
+
 	fn send_transaction(&self, meta: Metadata, request: RpcTransactionRequest) -> BoxFuture<RpcH256> {
 		let res = self.dispatch(
 			RpcConfirmationPayload::SendTransaction(request),
@@ -248,3 +270,9 @@ impl<D: Dispatcher + 'static> EthSigning for SigningQueueClient<D> {
 		}))
 	}
 }
+
+impl<D> SigningAccounts for SigningQueueClient<D> {
+	fn account_provider(&self) -> &AccountProvider {
+		&self.accounts
+	}
+}
@@ -25,15 +25,16 @@ use transaction::SignedTransaction;
 use jsonrpc_core::Result;
 use jsonrpc_macros::Trailing;
 use v1::Metadata;
-use v1::traits::Traces;
-use v1::helpers::{errors, fake_sign};
-use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, TraceOptions, H256, block_number_to_id};
+use v1::traits::{Traces, TracesCall};
+use v1::helpers::{call_params, errors, fake_sign};
+use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, TraceOptions, H256, StateOverride, block_number_to_id};
 
 fn to_call_analytics(flags: TraceOptions) -> CallAnalytics {
 	CallAnalytics {
 		transaction_tracing: flags.contains(&("trace".to_owned())),
 		vm_tracing: flags.contains(&("vmTrace".to_owned())),
 		state_diffing: flags.contains(&("stateDiff".to_owned())),
+		vm_profiling: flags.contains(&("vmProfile".to_owned())),
 	}
 }
 
@@ -87,28 +88,6 @@ impl<C, S> Traces for TracesClient<C> where
 			.map(LocalizedTrace::from))
 	}
 
-	fn call(&self, meta: Self::Metadata, request: CallRequest, flags: TraceOptions, block: Trailing<BlockNumber>) -> Result<TraceResults> {
-		let block = block.unwrap_or_default();
-
-		let request = CallRequest::into(request);
-		let signed = fake_sign::sign_call(request, meta.is_dapp())?;
-
-		let id = match block {
-			BlockNumber::Num(num) => BlockId::Number(num),
-			BlockNumber::Earliest => BlockId::Earliest,
-			BlockNumber::Latest => BlockId::Latest,
-
-			BlockNumber::Pending => return Err(errors::invalid_params("`BlockNumber::Pending` is not supported", ())),
-		};
-
-		let mut state = self.client.state_at(id).ok_or(errors::state_pruned())?;
-		let header = self.client.block_header(id).ok_or(errors::state_pruned())?;
-
-		self.client.call(&signed, to_call_analytics(flags), &mut state, &header.decode().map_err(errors::decode)?)
-			.map(TraceResults::from)
-			.map_err(errors::call)
-	}
-
 	fn call_many(&self, meta: Self::Metadata, requests: Vec<(CallRequest, TraceOptions)>, block: Trailing<BlockNumber>) -> Result<Vec<TraceResults>> {
 		let block = block.unwrap_or_default();
 
@@ -153,7 +132,7 @@ impl<C, S> Traces for TracesClient<C> where
 		let mut state = self.client.state_at(id).ok_or(errors::state_pruned())?;
 		let header = self.client.block_header(id).ok_or(errors::state_pruned())?;
 
-		self.client.call(&signed, to_call_analytics(flags), &mut state, &header.decode().map_err(errors::decode)?)
+		self.client.call(&signed, to_call_analytics(flags), &Default::default(), &mut state, &header.decode().map_err(errors::decode)?)
 			.map(TraceResults::from)
 			.map_err(errors::call)
 	}
@@ -178,3 +157,29 @@ impl<C, S> Traces for TracesClient<C> where
 			.map_err(errors::call)
 	}
 }
+
+impl<C, S> TracesCall for TracesClient<C> where
+	S: StateInfo + 'static,
+	C: BlockChainClient + StateClient<State=S> + Call<State=S> + 'static
+{
+	fn call(&self, meta: Self::Metadata, request: CallRequest, flags: TraceOptions, block: BlockNumber, overrides: StateOverride) -> Result<TraceResults> {
+		let request = CallRequest::into(request);
+		let signed = fake_sign::sign_call(request, meta.is_dapp())?;
+		let overrides = call_params::state_override(overrides)?;
+
+		let id = match block {
+			BlockNumber::Num(num) => BlockId::Number(num),
+			BlockNumber::Earliest => BlockId::Earliest,
+			BlockNumber::Latest => BlockId::Latest,
+
+			BlockNumber::Pending => return Err(errors::invalid_params("`BlockNumber::Pending` is not supported", ())),
+		};
+
+		let mut state = self.client.state_at(id).ok_or(errors::state_pruned())?;
+		let header = self.client.block_header(id).ok_or(errors::state_pruned())?;
+
+		self.client.call(&signed, to_call_analytics(flags), &overrides, &mut state, &header.decode().map_err(errors::decode)?)
+			.map(TraceResults::from)
+			.map_err(errors::call)
+	}
+}
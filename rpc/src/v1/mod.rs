@@ -41,16 +41,16 @@ pub mod informant;
 pub mod metadata;
 pub mod traits;
 
-pub use self::traits::{Web3, Eth, EthFilter, EthPubSub, EthSigning, Net, Parity, ParityAccounts, ParitySet, ParitySigning, PubSub, Signer, Personal, Traces, Rpc, SecretStore, Private};
+pub use self::traits::{Account, Web3, Eth, EthCall, EthFilter, EthPubSub, EthSigning, Net, Parity, ParityAccounts, ParitySet, ParitySigning, PubSub, Signer, Personal, Traces, TracesCall, Rpc, SecretStore, Private};
 pub use self::impls::*;
 pub use self::helpers::{NetworkSettings, block_import, dispatch};
 pub use self::metadata::Metadata;
-pub use self::types::Origin;
+pub use self::types::{Origin, TransactionPoolEvent};
 pub use self::extractors::{RpcExtractor, WsExtractor, WsStats, WsDispatcher};
 
 /// Signer utilities
 pub mod signer {
-	pub use super::helpers::{SigningQueue, SignerService, ConfirmationsQueue};
+	pub use super::helpers::{SigningQueue, SignerService, ConfirmationsQueue, ConfirmationDelay};
 	pub use super::types::{ConfirmationRequest, TransactionModification, U256, TransactionCondition};
 }
 
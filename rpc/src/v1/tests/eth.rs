@@ -40,7 +40,7 @@ use v1::helpers::nonce;
 use v1::impls::{EthClient, SigningUnsafeClient};
 use v1::metadata::Metadata;
 use v1::tests::helpers::{TestSnapshotService, TestSyncProvider, Config};
-use v1::traits::eth::Eth;
+use v1::traits::eth::EthCall;
 use v1::traits::eth_signing::EthSigning;
 use v1::types::U256 as NU256;
 
@@ -134,7 +134,7 @@ impl EthTester {
 		);
 
 		let mut handler = IoHandler::default();
-		handler.extend_with(eth_client.to_delegate());
+		handler.extend_with(eth_client.to_call_delegate());
 		handler.extend_with(eth_sign.to_delegate());
 
 		EthTester {
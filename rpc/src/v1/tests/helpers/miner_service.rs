@@ -27,7 +27,7 @@ use ethcore::engines::EthEngine;
 use ethcore::error::Error;
 use ethcore::header::{BlockNumber, Header};
 use ethcore::ids::BlockId;
-use ethcore::miner::{MinerService, AuthoringParams};
+use ethcore::miner::{MinerService, AuthoringParams, Bundle, BundleError, ProfitStats};
 use ethcore::receipt::{Receipt, RichReceipt};
 use ethereum_types::{H256, U256, Address};
 use miner::pool::local_transactions::Status as LocalTransactionStatus;
@@ -50,6 +50,8 @@ pub struct TestMinerService {
 	pub next_nonces: RwLock<HashMap<Address, U256>>,
 	/// Password held by Engine.
 	pub password: RwLock<String>,
+	/// Submitted bundles.
+	pub bundles: Mutex<Vec<Bundle>>,
 
 	authoring_params: RwLock<AuthoringParams>,
 }
@@ -63,6 +65,7 @@ impl Default for TestMinerService {
 			pending_receipts: Mutex::new(BTreeMap::new()),
 			next_nonces: RwLock::new(HashMap::new()),
 			password: RwLock::new(String::new()),
+			bundles: Mutex::new(Vec::new()),
 			authoring_params: RwLock::new(AuthoringParams {
 				author: Address::zero(),
 				gas_range_target: (12345.into(), 54321.into()),
@@ -194,6 +197,17 @@ impl MinerService for TestMinerService {
 		})
 	}
 
+	fn submit_bundle<C>(&self, _chain: &C, transactions: Vec<UnverifiedTransaction>, min_block: Option<BlockNumber>, max_block: BlockNumber)
+		-> Result<H256, BundleError>
+	{
+		// lets assume that all txs are valid
+		let transactions = transactions.into_iter().map(|tx| SignedTransaction::new(tx).unwrap()).collect();
+		let bundle = Bundle::new(transactions, min_block.unwrap_or(0), max_block);
+		let hash = bundle.hash;
+		self.bundles.lock().push(bundle);
+		Ok(hash)
+	}
+
 	fn remove_transaction(&self, hash: &H256) -> Option<Arc<VerifiedTransaction>> {
 		self.pending_transactions.lock().remove(hash).map(|tx| {
 			Arc::new(VerifiedTransaction::from_pending_block_transaction(tx))
@@ -266,6 +280,14 @@ impl MinerService for TestMinerService {
 		}
 	}
 
+	fn profit_stats(&self) -> ProfitStats {
+		ProfitStats {
+			blocks: 2,
+			profit: 3_000.into(),
+			baseline_profit: 2_000.into(),
+		}
+	}
+
 	/// Submit `seal` as a valid solution for the header of `pow_hash`.
 	/// Will check the seal, but not actually insert the block into the chain.
 	fn submit_seal(&self, _pow_hash: H256, _seal: Vec<Bytes>) -> Result<SealedBlock, Error> {
@@ -0,0 +1,192 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ethcore::account_provider::AccountProvider;
+use ethereum_types::{U256, Address};
+use ethkey::Secret;
+use bytes::ToPretty;
+use rlp;
+use serde_json;
+use transaction::{Transaction, Action};
+
+use jsonrpc_core::IoHandler;
+use v1::{Account, Metadata, SigningUnsafeClient};
+use v1::helpers::StandaloneDispatcher;
+use v1::types::Origin;
+
+const CHAIN_ID: u64 = 1;
+
+struct AccountTester {
+	accounts: Arc<AccountProvider>,
+	io: IoHandler<Metadata>,
+}
+
+fn account_tester() -> AccountTester {
+	let accounts = Arc::new(AccountProvider::transient_provider());
+	let dispatcher = StandaloneDispatcher::new(Some(CHAIN_ID));
+	let mut io = IoHandler::default();
+	io.extend_with(Account::to_delegate(SigningUnsafeClient::new(&accounts, dispatcher)));
+
+	AccountTester {
+		accounts,
+		io,
+	}
+}
+
+#[test]
+fn rpc_account_version() {
+	let tester = account_tester();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "account_version", "params": [], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"6.0.0","id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_account_list() {
+	let tester = account_tester();
+	let address = tester.accounts.new_account("test").unwrap();
+	tester.accounts.set_new_dapps_addresses(None).unwrap();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "account_list", "params": [], "id": 1}"#;
+	let response = format!(r#"{{"jsonrpc":"2.0","result":["0x{:x}"],"id":1}}"#, address);
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response));
+}
+
+#[test]
+fn rpc_account_list_respects_dapp_visibility() {
+	let tester = account_tester();
+	tester.accounts.new_account("test").unwrap();
+	tester.accounts.set_address_name(10.into(), "10".into());
+	tester.accounts.set_dapp_addresses("app1".into(), Some(vec![10.into()])).unwrap();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "account_list", "params": [], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":["0x000000000000000000000000000000000000000a"],"id":1}"#;
+	let mut meta = Metadata::default();
+	meta.origin = Origin::Dapps("app1".into());
+
+	assert_eq!((*tester.io).handle_request_sync(request, meta), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_account_sign_data_as_message() {
+	let tester = account_tester();
+	let data = vec![5u8];
+	let acc = tester.accounts.insert_account(Secret::from([69u8; 32]), "test").unwrap();
+	tester.accounts.unlock_account_permanently(acc, "test".into()).unwrap();
+
+	// same signature as `eth_sign` of the same data
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "account_signData",
+		"params": [
+			"text/plain",
+			""#.to_owned() + format!("0x{:x}", acc).as_ref() + r#"",
+			""# + format!("0x{}", data.to_hex()).as_ref() + r#""
+		],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0xdb53b32e56cf3e9735377b7664d6de5a03e125b1bf8ec55715d253668b4238503b4ac931fe6af90add73e72a585e952665376b2b9afc5b6b239b7df74c734e121b","id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_account_sign_data_rejects_unknown_content_type() {
+	let tester = account_tester();
+	let acc = tester.accounts.insert_account(Secret::from([69u8; 32]), "test").unwrap();
+	tester.accounts.unlock_account_permanently(acc, "test".into()).unwrap();
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "account_signData",
+		"params": [
+			"application/clique",
+			""#.to_owned() + format!("0x{:x}", acc).as_ref() + r#"",
+			"0x05"
+		],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"Unsupported content type: application/clique"},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_account_sign_transaction() {
+	let tester = account_tester();
+	let secret = Secret::from([69u8; 32]);
+	let acc = tester.accounts.insert_account(secret.clone(), "test").unwrap();
+	tester.accounts.unlock_account_permanently(acc, "test".into()).unwrap();
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "account_signTransaction",
+		"params": [{
+			"from": ""#.to_owned() + format!("0x{:x}", acc).as_ref() + r#"",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567",
+			"gas": "0x76c0",
+			"gasPrice": "0x9184e72a000",
+			"value": "0x9184e72a",
+			"nonce": "0x7"
+		}],
+		"id": 1
+	}"#;
+
+	let t = Transaction {
+		nonce: U256::from(7),
+		gas_price: U256::from(0x9184e72a000u64),
+		gas: U256::from(0x76c0),
+		action: Action::Call(Address::from_str("d46e8dd67c5d32be8058bb8eb970870f07244567").unwrap()),
+		value: U256::from(0x9184e72au64),
+		data: vec![]
+	}.sign(&secret, Some(CHAIN_ID));
+
+	let response = tester.io.handle_request_sync(&request).unwrap();
+	let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+	assert_eq!(response["result"]["raw"], format!("0x{}", rlp::encode(&t).to_hex()));
+	assert_eq!(response["result"]["tx"]["chainId"], CHAIN_ID);
+}
+
+#[test]
+fn rpc_account_sign_transaction_requires_nonce() {
+	let tester = account_tester();
+	let acc = tester.accounts.insert_account(Secret::from([69u8; 32]), "test").unwrap();
+	tester.accounts.unlock_account_permanently(acc, "test".into()).unwrap();
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "account_signTransaction",
+		"params": [{
+			"from": ""#.to_owned() + format!("0x{:x}", acc).as_ref() + r#"",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567",
+			"gas": "0x76c0",
+			"gasPrice": "0x9184e72a000"
+		}],
+		"id": 1
+	}"#;
+
+	let response = tester.io.handle_request_sync(&request).unwrap();
+	let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+	assert_eq!(response["error"]["code"], -32602);
+}
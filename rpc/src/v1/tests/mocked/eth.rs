@@ -21,7 +21,7 @@ use std::time::{Instant, Duration, SystemTime, UNIX_EPOCH};
 
 use ethereum_types::{H160, H256, U256, Address};
 use parking_lot::Mutex;
-use ethcore::account_provider::AccountProvider;
+use ethcore::account_provider::{AccountProvider, SpendingLimit};
 use ethcore::client::{BlockChainClient, BlockId, EachBlockWith, Executed, TestBlockChainClient, TransactionId};
 use ethcore::log_entry::{LocalizedLogEntry, LogEntry};
 use ethcore::miner::MinerService;
@@ -34,7 +34,7 @@ use rustc_hex::{FromHex, ToHex};
 use transaction::{Transaction, Action};
 
 use jsonrpc_core::IoHandler;
-use v1::{Eth, EthClient, EthClientOptions, EthFilter, EthFilterClient, EthSigning, SigningUnsafeClient};
+use v1::{EthCall, EthClient, EthClientOptions, EthFilter, EthFilterClient, EthSigning, SigningUnsafeClient};
 use v1::helpers::nonce;
 use v1::helpers::dispatch::FullDispatcher;
 use v1::tests::helpers::{TestSyncProvider, Config, TestMinerService, TestSnapshotService};
@@ -92,7 +92,7 @@ impl EthTester {
 		let hashrates = Arc::new(Mutex::new(HashMap::new()));
 		let external_miner = Arc::new(ExternalMiner::new(hashrates.clone()));
 		let gas_price_percentile = options.gas_price_percentile;
-		let eth = EthClient::new(&client, &snapshot, &sync, &opt_ap, &miner, &external_miner, options).to_delegate();
+		let eth = EthClient::new(&client, &snapshot, &sync, &opt_ap, &miner, &external_miner, options).to_call_delegate();
 		let filter = EthFilterClient::new(client.clone(), miner.clone()).to_delegate();
 		let reservations = Arc::new(Mutex::new(nonce::Reservations::new()));
 
@@ -637,6 +637,7 @@ fn rpc_eth_call_latest() {
 		trace: vec![],
 		vm_trace: None,
 		state_diff: None,
+		vm_profile: None,
 	}));
 
 	let request = r#"{
@@ -673,6 +674,7 @@ fn rpc_eth_call() {
 		trace: vec![],
 		vm_trace: None,
 		state_diff: None,
+		vm_profile: None,
 	}));
 
 	let request = r#"{
@@ -709,6 +711,7 @@ fn rpc_eth_call_default_block() {
 		trace: vec![],
 		vm_trace: None,
 		state_diff: None,
+		vm_profile: None,
 	}));
 
 	let request = r#"{
@@ -729,6 +732,66 @@ fn rpc_eth_call_default_block() {
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_call_with_state_overrides() {
+	let tester = EthTester::default();
+	tester.client.set_execution_result(Ok(Executed {
+		exception: None,
+		gas: U256::zero(),
+		gas_used: U256::from(0xff30),
+		refunded: U256::from(0x5),
+		cumulative_gas_used: U256::zero(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![0x12, 0x34, 0xff],
+		trace: vec![],
+		vm_trace: None,
+		state_diff: None,
+		vm_profile: None,
+	}));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_call",
+		"params": [{
+			"from": "0xb60e8dd61c5d32be8058bb8eb970870f07233155",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567"
+		},
+		"latest",
+		{
+			"0xd46e8dd67c5d32be8058bb8eb970870f07244567": {
+				"balance": "0x1",
+				"code": "0x600160005260206000f3"
+			}
+		}],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x1234ff","id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_eth_call_rejects_state_with_state_diff() {
+	let tester = EthTester::default();
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_call",
+		"params": [{
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567"
+		},
+		"latest",
+		{
+			"0xd46e8dd67c5d32be8058bb8eb970870f07244567": { "state": {}, "stateDiff": {} }
+		}],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: overrides","data":"\"Both state and stateDiff given for d46e8dd67c5d32be8058bb8eb970870f07244567\""},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_eth_estimate_gas() {
 	let tester = EthTester::default();
@@ -744,6 +807,7 @@ fn rpc_eth_estimate_gas() {
 		trace: vec![],
 		vm_trace: None,
 		state_diff: None,
+		vm_profile: None,
 	}));
 
 	let request = r#"{
@@ -780,6 +844,7 @@ fn rpc_eth_estimate_gas_default_block() {
 		trace: vec![],
 		vm_trace: None,
 		state_diff: None,
+		vm_profile: None,
 	}));
 
 	let request = r#"{
@@ -851,6 +916,33 @@ fn rpc_eth_send_transaction() {
 	assert_eq!(tester.io.handle_request_sync(&request), Some(response));
 }
 
+#[test]
+fn rpc_eth_send_transaction_over_spending_limit() {
+	let tester = EthTester::default();
+	let address = tester.accounts_provider.new_account("").unwrap();
+	tester.accounts_provider.unlock_account_permanently(address, "".into()).unwrap();
+	tester.accounts_provider.set_spending_limit(address, SpendingLimit {
+		max_value: Some(0x1000.into()),
+		..Default::default()
+	}).unwrap();
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_sendTransaction",
+		"params": [{
+			"from": ""#.to_owned() + format!("0x{:x}", address).as_ref() + r#"",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567",
+			"gas": "0x76c0",
+			"gasPrice": "0x9184e72a000",
+			"value": "0x9184e72a"
+		}],
+		"id": 1
+	}"#;
+
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32025,"message":"Transaction violates the spending limit of the account: Transaction value 2441406250 exceeds the limit of 4096 per transaction."},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.into()));
+}
+
 #[test]
 fn rpc_eth_sign_transaction() {
 	let tester = EthTester::default();
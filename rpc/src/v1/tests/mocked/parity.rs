@@ -503,6 +503,17 @@ fn rpc_parity_local_transactions() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_profit_stats() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_profitStats", "params":[], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"baselineProfit":"0x7d0","blocks":2,"gain":"0x3e8","profit":"0xbb8"},"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_chain_status() {
 	let deps = Dependencies::new();
@@ -554,6 +565,7 @@ fn rpc_parity_call() {
 		trace: vec![],
 		vm_trace: None,
 		state_diff: None,
+		vm_profile: None,
 	}));
 	let io = deps.default_client();
 
@@ -576,6 +588,48 @@ fn rpc_parity_call() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_simulate_calls() {
+	let deps = Dependencies::new();
+	deps.client.set_execution_result(Ok(Executed {
+		exception: None,
+		gas: U256::zero(),
+		gas_used: U256::from(0xff30),
+		refunded: U256::from(0x5),
+		cumulative_gas_used: U256::zero(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![0x12, 0x34, 0xff],
+		trace: vec![],
+		vm_trace: None,
+		state_diff: None,
+		vm_profile: None,
+	}));
+	let io = deps.default_client();
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "parity_simulateCalls",
+		"params": [[{
+			"from": "0xb60e8dd61c5d32be8058bb8eb970870f07233155",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567",
+			"data": "0xd46e8dd6"
+		}, {
+			"from": "0xb60e8dd61c5d32be8058bb8eb970870f07233155",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567"
+		}],
+		"latest",
+		{
+			"0xb60e8dd61c5d32be8058bb8eb970870f07233155": { "balance": "0xde0b6b3a7640000" }
+		}],
+		"id": 1
+	}"#;
+	let call = r#"{"output":"0x1234ff","gasUsed":"0xff30","logs":[],"error":null}"#;
+	let response = format!(r#"{{"jsonrpc":"2.0","result":{{"calls":[{},{}],"stateDiff":{{}}}},"id":1}}"#, call, call);
+
+	assert_eq!(io.handle_request_sync(request), Some(response));
+}
+
 #[test]
 fn rpc_parity_node_health() {
 	let deps = Dependencies::new();
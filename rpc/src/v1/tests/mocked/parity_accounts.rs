@@ -15,18 +15,22 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use ethereum_types::Address;
 
 use ethcore::account_provider::{AccountProvider, AccountProviderSettings};
 use ethstore::EthStore;
 use ethstore::accounts_dir::RootDiskDirectory;
+use serde_json;
 use tempdir::TempDir;
 
 use jsonrpc_core::IoHandler;
-use v1::{ParityAccounts, ParityAccountsClient};
+use v1::{Metadata, ParityAccounts, ParityAccountsClient};
+use v1::helpers::totp;
 
 struct ParityAccountsTester {
 	accounts: Arc<AccountProvider>,
-	io: IoHandler,
+	io: IoHandler<Metadata>,
 }
 
 fn accounts_provider() -> Arc<AccountProvider> {
@@ -36,7 +40,7 @@ fn accounts_provider() -> Arc<AccountProvider> {
 fn accounts_provider_with_vaults_support(temp_path: &str) -> Arc<AccountProvider> {
 	let root_keys_dir = RootDiskDirectory::create(temp_path).unwrap();
 	let secret_store = EthStore::open(Box::new(root_keys_dir)).unwrap();
-	Arc::new(AccountProvider::new(Box::new(secret_store), AccountProviderSettings::default()))
+	Arc::new(AccountProvider::new(Box::new(secret_store), AccountProviderSettings::default()).unwrap())
 }
 
 fn setup_with_accounts_provider(accounts_provider: Arc<AccountProvider>) -> ParityAccountsTester {
@@ -121,6 +125,59 @@ fn should_be_able_to_set_meta() {
 	assert_eq!(res, Some(response));
 }
 
+fn set_totp_secret_request(address: &Address, meta: &str, authorization: Option<&str>) -> String {
+	let meta = serde_json::to_string(meta).unwrap();
+	match authorization {
+		Some(authorization) => format!(r#"{{"jsonrpc": "2.0", "method": "parity_setAccountMeta", "params": ["0x{:x}", {}, "{}"], "id": 1}}"#, address, meta, authorization),
+		None => format!(r#"{{"jsonrpc": "2.0", "method": "parity_setAccountMeta", "params": ["0x{:x}", {}], "id": 1}}"#, address, meta),
+	}
+}
+
+#[test]
+fn should_require_password_to_add_totp_secret() {
+	let tester = setup();
+	let address = tester.accounts.new_account("password").unwrap();
+	// RFC 6238 test secret ("12345678901234567890")
+	let meta = r#"{"totpSecret":"GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"}"#;
+
+	let res = tester.io.handle_request_sync(&set_totp_secret_request(&address, meta, None)).unwrap();
+	assert!(res.contains("Second factor verification failed"), "{}", res);
+	let res = tester.io.handle_request_sync(&set_totp_secret_request(&address, meta, Some("wrong"))).unwrap();
+	assert!(res.contains("Second factor verification failed"), "{}", res);
+	assert_eq!(tester.accounts.account_meta(address).unwrap().meta, "{}");
+
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(&set_totp_secret_request(&address, meta, Some("password"))), Some(response.into()));
+	assert_eq!(tester.accounts.account_meta(address).unwrap().meta, meta);
+
+	// the secret is never returned and kept by updates which don't set it
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_allAccountsInfo", "params": [], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request).unwrap();
+	assert!(!res.contains("totpSecret"), "{}", res);
+	assert_eq!(tester.io.handle_request_sync(&set_totp_secret_request(&address, r#"{"a":1}"#, None)), Some(response.into()));
+	assert_eq!(tester.accounts.account_meta(address).unwrap().meta, r#"{"a":1,"totpSecret":"GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"}"#);
+}
+
+#[test]
+fn should_require_totp_code_or_password_to_remove_totp_secret() {
+	let tester = setup();
+	let address = tester.accounts.new_account("password").unwrap();
+	let meta = r#"{"totpSecret":"GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"}"#;
+	tester.accounts.set_account_meta(address, meta.into()).unwrap();
+
+	let res = tester.io.handle_request_sync(&set_totp_secret_request(&address, r#"{"totpSecret":null}"#, None)).unwrap();
+	assert!(res.contains("Second factor verification failed"), "{}", res);
+	let res = tester.io.handle_request_sync(&set_totp_secret_request(&address, r#"{"totpSecret":null}"#, Some("000000x"))).unwrap();
+	assert!(res.contains("Second factor verification failed"), "{}", res);
+	assert_eq!(tester.accounts.account_meta(address).unwrap().meta, meta);
+
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+	let code = format!("{:06}", totp::hotp(b"12345678901234567890", now / totp::STEP_SECS, 6));
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(&set_totp_secret_request(&address, r#"{"totpSecret":null}"#, Some(&code))), Some(response.into()));
+	assert_eq!(tester.accounts.account_meta(address).unwrap().meta, "{}");
+}
+
 #[test]
 fn rpc_parity_set_and_get_dapps_accounts() {
 	// given
@@ -325,6 +382,116 @@ fn rpc_parity_change_vault_password() {
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_reencrypt_account() {
+	let tempdir = TempDir::new("").unwrap();
+	let tester = setup_with_vaults_support(tempdir.path().to_str().unwrap());
+
+	let (address, _) = tester.accounts.new_account_and_public("password").unwrap();
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_reencryptAccount", "params":["0x{:x}", "wrong"], "id": 1}}"#, address);
+	assert!(tester.io.handle_request_sync(&request).unwrap().contains("Could not re-encrypt account."));
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_reencryptAccount", "params":["0x{:x}", "password"], "id": 1}}"#, address);
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.to_owned()));
+	assert!(tester.accounts.test_password(&address, "password").unwrap());
+}
+
+#[test]
+fn rpc_parity_new_policy_account() {
+	let tester = setup();
+	let owner = tester.accounts.new_account("").unwrap();
+	let policy = Address::from(5);
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_newPolicyAccount", "params":["0x{:x}", ["0x{:x}"], 2, "Safe"], "id": 1}}"#, policy, owner);
+	assert!(tester.io.handle_request_sync(&request).unwrap().contains("Could not add policy account."));
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_newPolicyAccount", "params":["0x{:x}", ["0x{:x}"], 1, "Safe"], "id": 1}}"#, policy, owner);
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.to_owned()));
+
+	let uuid = tester.accounts.accounts_info().unwrap().get(&owner).unwrap().uuid.as_ref().unwrap().clone();
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_allAccountsInfo", "params": [], "id": 1}"#;
+	let response = format!(
+		"{{\"jsonrpc\":\"2.0\",\"result\":{{\"0x{:x}\":{{\"meta\":\"{{}}\",\"name\":\"Safe\",\"policy\":{{\"owners\":[\"0x{:x}\"],\"threshold\":1}}}},\"0x{:x}\":{{\"meta\":\"{{}}\",\"name\":\"\",\"uuid\":\"{}\"}}}},\"id\":1}}",
+		policy, owner, owner, uuid
+	);
+	assert_eq!(tester.io.handle_request_sync(request), Some(response));
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_removePolicyAccount", "params":["0x{:x}"], "id": 1}}"#, policy);
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.to_owned()));
+	assert!(tester.accounts.policy_accounts_info().is_empty());
+}
+
+#[test]
+fn rpc_parity_audit_log() {
+	let tempdir = TempDir::new("").unwrap();
+	let root_keys_dir = RootDiskDirectory::create(tempdir.path()).unwrap();
+	let secret_store = EthStore::open(Box::new(root_keys_dir)).unwrap();
+	let settings = AccountProviderSettings { audit_log: true, ..Default::default() };
+	let tester = setup_with_accounts_provider(Arc::new(AccountProvider::new(Box::new(secret_store), settings).unwrap()));
+
+	let address = tester.accounts.new_account("password").unwrap();
+	tester.accounts.unlock_account_permanently(address, "wrong".into()).unwrap_err();
+	tester.accounts.sign(address, Some("password".into()), 5.into()).unwrap();
+	tester.accounts.create_vault("vault1", "password1").unwrap();
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_auditLog", "params":[10, "0x{:x}"], "id": 1}}"#, address);
+	let response: serde_json::Value = serde_json::from_str(&tester.io.handle_request_sync(&request).unwrap()).unwrap();
+	let entries = response["result"].as_array().unwrap();
+
+	assert_eq!(entries.len(), 2);
+	assert_eq!(entries[0]["operation"], "sign");
+	assert_eq!(entries[0]["hash"], "0x0000000000000000000000000000000000000000000000000000000000000005");
+	assert_eq!(entries[0]["success"], true);
+	assert_eq!(entries[1]["operation"], "unlock");
+	assert_eq!(entries[1]["success"], false);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_auditLog", "params":[1], "id": 1}"#;
+	let response: serde_json::Value = serde_json::from_str(&tester.io.handle_request_sync(request).unwrap()).unwrap();
+	assert_eq!(response["result"][0]["operation"], "createVault");
+	assert_eq!(response["result"][0]["vault"], "vault1");
+	assert_eq!(response["result"][0]["account"], serde_json::Value::Null);
+}
+
+#[test]
+fn rpc_parity_audit_log_disabled() {
+	let tester = setup();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_auditLog", "params":[10], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32023,"message":"Could not read audit log.","data":"Custom(\"Audit log is disabled.\")"},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_set_spending_limit() {
+	let tester = setup();
+	let address = tester.accounts.new_account("").unwrap();
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_setSpendingLimit", "params":["0x{:x}", {{"allowedMethods": ["0xa9059c"]}}], "id": 1}}"#, address);
+	assert!(tester.io.handle_request_sync(&request).unwrap().contains("Method selectors have to be 4 bytes long."));
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_setSpendingLimit", "params":["0x{:x}", {{"dailyLimit": "0x10", "allowedMethods": ["0xa9059cbb"]}}], "id": 1}}"#, address);
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_spendingLimits", "params": [], "id": 1}"#;
+	let response = format!(
+		r#"{{"jsonrpc":"2.0","result":{{"0x{:x}":{{"allowedMethods":["0xa9059cbb"],"allowedRecipients":null,"dailyLimit":"0x10","maxValue":null}}}},"id":1}}"#,
+		address
+	);
+	assert_eq!(tester.io.handle_request_sync(request), Some(response));
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_removeSpendingLimit", "params":["0x{:x}"], "id": 1}}"#, address);
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.to_owned()));
+	assert!(tester.accounts.spending_limits().is_empty());
+}
+
 #[test]
 fn rpc_parity_change_vault() {
 	let tempdir = TempDir::new("").unwrap();
@@ -519,6 +686,25 @@ fn should_import_wallet() {
 	assert!(account_uuid != id);
 }
 
+#[test]
+fn should_import_accounts_from_mnemonic() {
+	let tester = setup();
+
+	let request = r#"{"jsonrpc":"2.0","method":"parity_newAccountsFromMnemonic","params":["abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", "", 2, "password"],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":["0x9858effd232b4033e47d90003d41ec34ecaeda94","0x6fac4d18c912343bf86fa7049364dd4e424ab9c0"],"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.into()));
+	assert_eq!(tester.accounts.accounts().unwrap().len(), 2);
+
+	let request = r#"{"jsonrpc":"2.0","method":"parity_newAccountsFromMnemonic","params":["abandon abandon abandon", "", 1, "password"],"id":2}"#;
+	let res = tester.io.handle_request_sync(request).unwrap();
+	assert!(res.contains("Invalid number of words: 3"), "{}", res);
+
+	let request = r#"{"jsonrpc":"2.0","method":"parity_newAccountsFromMnemonic","params":["abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", "", 101, "password"],"id":3}"#;
+	let res = tester.io.handle_request_sync(request).unwrap();
+	assert!(res.contains("At most 100 accounts can be created at once"), "{}", res);
+	assert_eq!(tester.accounts.accounts().unwrap().len(), 2);
+}
+
 #[test]
 fn should_sign_message() {
 	let tester = setup();
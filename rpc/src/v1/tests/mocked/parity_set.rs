@@ -16,7 +16,7 @@
 
 use std::sync::Arc;
 use std::str::FromStr;
-use rustc_hex::FromHex;
+use rustc_hex::{FromHex, ToHex};
 use ethereum_types::{U256, Address};
 
 use ethcore::miner::MinerService;
@@ -240,6 +240,41 @@ fn rpc_parity_remove_transaction() {
 	assert_eq!(io.handle_request_sync(&request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_send_bundle() {
+	use ethkey::{Generator, Random};
+	use rlp;
+	use transaction::{Transaction, Action};
+
+	let miner = miner_service();
+	let client = client_service();
+	let network = network_service();
+	let updater = updater_service();
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client, &miner, &updater, &network).to_delegate());
+
+	let keypair = Random.generate().unwrap();
+	let tx = Transaction {
+		nonce: 0.into(),
+		gas_price: 0x9184e72a000u64.into(),
+		gas: 0x76c0.into(),
+		action: Action::Call(5.into()),
+		value: 0x9184e72au64.into(),
+		data: vec![]
+	}.sign(keypair.secret(), None);
+	let raw = rlp::encode(&tx);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_sendBundle", "params":[[""#.to_owned()
+		+ &format!("0x{}", raw.to_hex()) + r#""], "0x5", "0x2"], "id": 1}"#;
+	let result = io.handle_request_sync(&request).unwrap();
+
+	let bundles = miner.bundles.lock();
+	assert_eq!(bundles.len(), 1);
+	assert_eq!(bundles[0].transactions, vec![tx]);
+	assert_eq!((bundles[0].min_block, bundles[0].max_block), (2, 5));
+	assert_eq!(result, format!(r#"{{"jsonrpc":"2.0","result":"0x{:x}","id":1}}"#, bundles[0].hash));
+}
+
 #[test]
 fn rpc_parity_set_dapps_list() {
 	let miner = miner_service();
@@ -19,17 +19,21 @@ use std::str::FromStr;
 
 use bytes::ToPretty;
 use ethereum_types::{U256, Address};
-use ethcore::account_provider::AccountProvider;
+use ethcore::account_provider::{AccountProvider, AccountProviderSettings};
 use ethcore::client::TestBlockChainClient;
+use ethstore::EthStore;
+use ethstore::accounts_dir::RootDiskDirectory;
 use jsonrpc_core::IoHandler;
 use parking_lot::Mutex;
+use serde_json;
+use tempdir::TempDir;
 use transaction::{Action, Transaction};
 
 use v1::{PersonalClient, Personal, Metadata};
-use v1::helpers::nonce;
+use v1::helpers::{eip712, nonce};
 use v1::helpers::dispatch::{eth_data_hash, FullDispatcher};
 use v1::tests::helpers::TestMinerService;
-use v1::types::H520;
+use v1::types::{H520, Origin};
 
 struct PersonalTester {
 	accounts: Arc<AccountProvider>,
@@ -51,7 +55,10 @@ fn miner_service() -> Arc<TestMinerService> {
 }
 
 fn setup() -> PersonalTester {
-	let accounts = accounts_provider();
+	setup_with_accounts_provider(accounts_provider())
+}
+
+fn setup_with_accounts_provider(accounts: Arc<AccountProvider>) -> PersonalTester {
 	let client = blockchain_client();
 	let miner = miner_service();
 	let reservations = Arc::new(Mutex::new(nonce::Reservations::new()));
@@ -145,6 +152,57 @@ fn sign() {
 	assert_eq!(tester.io.handle_request_sync(request.as_ref()), Some(response));
 }
 
+#[test]
+fn sign_should_audit_request_origin() {
+	let tempdir = TempDir::new("").unwrap();
+	let secret_store = EthStore::open(Box::new(RootDiskDirectory::create(tempdir.path()).unwrap())).unwrap();
+	let settings = AccountProviderSettings { audit_log: true, ..Default::default() };
+	let tester = setup_with_accounts_provider(Arc::new(AccountProvider::new(Box::new(secret_store), settings).unwrap()));
+	let address = tester.accounts.new_account("password123").unwrap();
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "personal_sign", "params": ["0x05", "0x{:x}", "password123"], "id": 1}}"#, address);
+	let meta = Metadata { origin: Origin::Rpc("test".into()), ..Default::default() };
+	let response = (*tester.io).handle_request_sync(&request, meta).unwrap();
+	assert!(response.contains("result"), "Unexpected response: {}", response);
+
+	let entries = tester.accounts.audit_log(Some(&address), 1).unwrap();
+	assert_eq!(entries[0].origin, Some("test via RPC".into()));
+}
+
+#[test]
+fn sign_typed_data() {
+	let tester = setup();
+	let address = tester.accounts.new_account("password123").unwrap();
+	let data = r#"{
+		"types": {
+			"EIP712Domain": [{"name": "name", "type": "string"}],
+			"Mail": [{"name": "contents", "type": "string"}]
+		},
+		"primaryType": "Mail",
+		"domain": {"name": "Ether Mail"},
+		"message": {"contents": "Hello, Bob!"}
+	}"#;
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "personal_signTypedData",
+		"params": [
+			"#.to_owned() + data + r#",
+			""# + format!("0x{:x}", address).as_ref() + r#"",
+			"password123"
+		],
+		"id": 1
+	}"#;
+
+	let hash = eip712::hash_structured_data(&serde_json::from_str(data).unwrap()).unwrap();
+	let signature = H520(tester.accounts.sign(address, Some("password123".into()), hash).unwrap().into_electrum());
+	let signature = format!("0x{:?}", signature);
+
+	let response = r#"{"jsonrpc":"2.0","result":""#.to_owned() + &signature + r#"","id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request.as_ref()), Some(response));
+}
+
 #[test]
 fn sign_with_invalid_password() {
 	let tester = setup();
@@ -75,3 +75,64 @@ fn should_subscribe_to_a_method() {
 	let (res, _receiver) = receiver.into_future().wait().unwrap();
 	assert_eq!(res, None);
 }
+
+#[test]
+fn should_subscribe_to_transaction_pool_events() {
+	use serde_json;
+	use v1::types::{Transaction, TransactionPoolEvent};
+
+	// given
+	let el = EventLoop::spawn();
+	let pubsub = PubSubClient::new_test(rpc(), el.remote());
+	let handler = pubsub.transaction_pool_events_handler().upgrade().unwrap();
+	let pubsub = pubsub.to_delegate();
+
+	let mut io = MetaIoHandler::default();
+	io.extend_with(pubsub);
+
+	let mut metadata = Metadata::default();
+	let (sender, receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	// Subscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_subscribe", "params": ["parity_transactionPoolEvents", []], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x416d77337e24399d","id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
+
+	// Check notifications
+	handler.notify(&[TransactionPoolEvent::Invalid(Transaction::default())]);
+	let (res, receiver) = receiver.into_future().wait().unwrap();
+	let response = r#"{"jsonrpc":"2.0","method":"parity_subscription","params":{"result":{"status":"invalid","transaction":"#.to_owned() +
+		&serde_json::to_string(&Transaction::default()).unwrap() +
+		r#"},"subscription":"0x416d77337e24399d"}}"#;
+	assert_eq!(res, Some(response));
+
+	// And unsubscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_unsubscribe", "params": ["0x416d77337e24399d"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata), Some(response.to_owned()));
+
+	let (res, _receiver) = receiver.into_future().wait().unwrap();
+	assert_eq!(res, None);
+}
+
+#[test]
+fn should_reject_transaction_pool_events_with_params() {
+	// given
+	let el = EventLoop::spawn();
+	let pubsub = PubSubClient::new_test(rpc(), el.remote()).to_delegate();
+
+	let mut io = MetaIoHandler::default();
+	io.extend_with(pubsub);
+
+	let mut metadata = Metadata::default();
+	let (sender, _receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	// when
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_subscribe", "params": ["parity_transactionPoolEvents", [1]], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: parity_transactionPoolEvents","data":"\"Expected no parameters.\""},"id":1}"#;
+
+	// then
+	assert_eq!(io.handle_request_sync(request, metadata), Some(response.to_owned()));
+}
@@ -16,6 +16,7 @@
 
 use std::sync::Arc;
 use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use ethereum_types::{U256, Address};
 use bytes::ToPretty;
 
@@ -32,7 +33,7 @@ use v1::{SignerClient, Signer, Origin};
 use v1::metadata::Metadata;
 use v1::tests::helpers::TestMinerService;
 use v1::types::{Bytes as RpcBytes, H520};
-use v1::helpers::{nonce, SigningQueue, SignerService, FilledTransactionRequest, ConfirmationPayload};
+use v1::helpers::{nonce, totp, SigningQueue, SignerService, ConfirmationDelay, FilledTransactionRequest, ConfirmationPayload};
 use v1::helpers::dispatch::{FullDispatcher, eth_data_hash};
 
 struct SignerTester {
@@ -56,7 +57,11 @@ fn miner_service() -> Arc<TestMinerService> {
 }
 
 fn signer_tester() -> SignerTester {
-	let signer = Arc::new(SignerService::new_test(false));
+	signer_tester_with(SignerService::new_test(false))
+}
+
+fn signer_tester_with(signer: SignerService) -> SignerTester {
+	let signer = Arc::new(signer);
 	let accounts = accounts_provider();
 	let client = blockchain_client();
 	let miner = miner_service();
@@ -536,6 +541,105 @@ fn should_confirm_decrypt_with_phrase() {
 	assert_eq!(tester.miner.imported_transactions.lock().len(), 0);
 }
 
+// RFC 6238 test secret ("12345678901234567890")
+const TOTP_SECRET: &'static str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+fn current_totp_code() -> String {
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+	format!("{:06}", totp::hotp(b"12345678901234567890", now / totp::STEP_SECS, 6))
+}
+
+fn confirm_decrypt_request(id: &str, totp_code: Option<&str>) -> String {
+	let params = match totp_code {
+		Some(code) => format!(r#"["{}", "0x01", "{}"]"#, id, code),
+		None => format!(r#"["{}", "0x01"]"#, id),
+	};
+	format!(r#"{{"jsonrpc":"2.0","method":"signer_confirmRequestRaw","params":{},"id":1}}"#, params)
+}
+
+#[test]
+fn should_require_totp_code_for_accounts_with_second_factor() {
+	// given
+	let tester = signer_tester();
+	let address = tester.accounts.new_account("test").unwrap();
+	tester.accounts.set_account_meta(address, format!(r#"{{"totpSecret":"{}"}}"#, TOTP_SECRET)).unwrap();
+	let _confirmation_future = tester.signer.add_request(ConfirmationPayload::Decrypt(
+		address,
+		vec![1, 2, 3, 4].into(),
+	), Origin::Unknown).unwrap();
+
+	// when
+	let missing = tester.io.handle_request_sync(&confirm_decrypt_request("0x1", None));
+	let invalid = tester.io.handle_request_sync(&confirm_decrypt_request("0x1", Some("12345")));
+	let valid = tester.io.handle_request_sync(&confirm_decrypt_request("0x1", Some(&current_totp_code())));
+
+	// then
+	assert_eq!(missing, Some(r#"{"jsonrpc":"2.0","error":{"code":-32026,"message":"Second factor verification failed: TOTP code is required for this account."},"id":1}"#.into()));
+	assert_eq!(invalid, Some(r#"{"jsonrpc":"2.0","error":{"code":-32026,"message":"Second factor verification failed: invalid TOTP code."},"id":1}"#.into()));
+	assert_eq!(valid, Some(r#"{"jsonrpc":"2.0","result":"0x01","id":1}"#.into()));
+	assert_eq!(tester.signer.requests().len(), 0);
+}
+
+#[test]
+fn should_not_accept_the_same_totp_code_twice() {
+	// given
+	let tester = signer_tester();
+	let address = tester.accounts.new_account("test").unwrap();
+	tester.accounts.set_account_meta(address, format!(r#"{{"totpSecret":"{}"}}"#, TOTP_SECRET)).unwrap();
+	for _ in 0..2 {
+		let _confirmation_future = tester.signer.add_request(ConfirmationPayload::Decrypt(
+			address,
+			vec![1, 2, 3, 4].into(),
+		), Origin::Unknown).unwrap();
+	}
+	let code = current_totp_code();
+
+	// when
+	let first = tester.io.handle_request_sync(&confirm_decrypt_request("0x1", Some(&code)));
+	let second = tester.io.handle_request_sync(&confirm_decrypt_request("0x2", Some(&code)));
+
+	// then
+	assert_eq!(first, Some(r#"{"jsonrpc":"2.0","result":"0x01","id":1}"#.into()));
+	assert_eq!(second, Some(r#"{"jsonrpc":"2.0","error":{"code":-32026,"message":"Second factor verification failed: TOTP code has already been used."},"id":1}"#.into()));
+	assert_eq!(tester.signer.requests().len(), 1);
+}
+
+#[test]
+fn should_delay_confirmation_of_high_value_transactions() {
+	// given
+	let tester = signer_tester_with(SignerService::new_test(false).with_confirmation_delay(Some(ConfirmationDelay {
+		min_value: U256::from(100),
+		delay: Duration::from_secs(3600),
+	})));
+	let address = tester.accounts.new_account("test").unwrap();
+	let request = |value: u64| ConfirmationPayload::SendTransaction(FilledTransactionRequest {
+		from: address,
+		used_default_from: false,
+		to: Some(Address::from_str("d46e8dd67c5d32be8058bb8eb970870f07244567").unwrap()),
+		gas_price: U256::from(10_000),
+		gas: U256::from(10_000_000),
+		value: U256::from(value),
+		data: vec![],
+		nonce: None,
+		condition: None,
+	});
+	let _high_value_future = tester.signer.add_request(request(1000), Origin::Unknown).unwrap();
+	let _low_value_future = tester.signer.add_request(request(1), Origin::Unknown).unwrap();
+
+	// when
+	let high_value = r#"{"jsonrpc":"2.0","method":"signer_confirmRequest","params":["0x1",{},"test"],"id":1}"#;
+	let low_value = r#"{"jsonrpc":"2.0","method":"signer_confirmRequest","params":["0x2",{},"test"],"id":1}"#;
+
+	// then
+	assert_eq!(
+		tester.io.handle_request_sync(high_value),
+		Some(r#"{"jsonrpc":"2.0","error":{"code":-32043,"message":"Request can not be confirmed yet. Try again in 3600 seconds.","data":3600},"id":1}"#.into())
+	);
+	assert!(tester.io.handle_request_sync(low_value).unwrap().contains("result"));
+	assert_eq!(tester.signer.requests().len(), 1);
+	assert_eq!(tester.miner.imported_transactions.lock().len(), 1);
+}
+
 #[test]
 fn should_generate_new_token() {
 	// given
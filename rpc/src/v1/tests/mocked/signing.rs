@@ -25,8 +25,8 @@ use jsonrpc_core::futures::Future;
 use v1::impls::SigningQueueClient;
 use v1::metadata::Metadata;
 use v1::traits::{EthSigning, ParitySigning, Parity};
-use v1::helpers::{nonce, SignerService, SigningQueue, FullDispatcher};
-use v1::types::{ConfirmationResponse, RichRawTransaction};
+use v1::helpers::{eip712, nonce, SignerService, SigningQueue, FullDispatcher, ConfirmationPayload};
+use v1::types::{ConfirmationResponse, RichRawTransaction, H520};
 use v1::tests::helpers::TestMinerService;
 use v1::tests::mocked::parity;
 
@@ -228,6 +228,104 @@ fn should_sign_if_account_is_unlocked() {
 	assert_eq!(tester.signer.requests().len(), 0);
 }
 
+const TYPED_DATA: &str = r#"{
+	"types": {
+		"EIP712Domain": [{"name": "name", "type": "string"}, {"name": "chainId", "type": "uint256"}],
+		"Mail": [{"name": "to", "type": "address"}, {"name": "contents", "type": "string"}]
+	},
+	"primaryType": "Mail",
+	"domain": {"name": "Ether Mail", "chainId": 1},
+	"message": {"to": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB", "contents": "Hello, Bob!"}
+}"#;
+
+#[test]
+fn should_add_sign_typed_data_to_queue() {
+	// given
+	let tester = eth_signing();
+	let address = Address::random();
+	assert_eq!(tester.signer.requests().len(), 0);
+
+	// when
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_signTypedData",
+		"params": [
+			""#.to_owned() + format!("0x{:x}", address).as_ref() + r#"",
+			"# + TYPED_DATA + r#"
+		],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","id":1}"#;
+
+	// then
+	let promise = tester.io.handle_request(&request);
+
+	// the future must be polled at least once before request is queued.
+	let signer = tester.signer.clone();
+	::std::thread::spawn(move || loop {
+		if signer.requests().len() == 1 {
+			let data = serde_json::from_str(TYPED_DATA).unwrap();
+			assert_eq!(signer.requests()[0].payload, ConfirmationPayload::SignTypedData(address, data));
+			// respond
+			signer.request_confirmed(1.into(), Ok(ConfirmationResponse::Signature(0.into())));
+			break
+		}
+		::std::thread::sleep(Duration::from_millis(100))
+	});
+
+	let res = promise.wait().unwrap();
+	assert_eq!(res, Some(response.to_owned()));
+}
+
+#[test]
+fn should_sign_typed_data_if_account_is_unlocked() {
+	// given
+	let tester = eth_signing();
+	let acc = tester.accounts.insert_account(Secret::from([69u8; 32]), "test").unwrap();
+	tester.accounts.unlock_account_permanently(acc, "test".into()).unwrap();
+
+	// when
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_signTypedData",
+		"params": [
+			""#.to_owned() + format!("0x{:x}", acc).as_ref() + r#"",
+			"# + TYPED_DATA + r#"
+		],
+		"id": 1
+	}"#;
+
+	// then
+	let hash = eip712::hash_structured_data(&serde_json::from_str(TYPED_DATA).unwrap()).unwrap();
+	let signature = H520(tester.accounts.sign(acc, None, hash).unwrap().into_electrum());
+	let response = r#"{"jsonrpc":"2.0","result":""#.to_owned() + &format!("0x{:?}", signature) + r#"","id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response));
+	assert_eq!(tester.signer.requests().len(), 0);
+}
+
+#[test]
+fn should_reject_typed_data_not_matching_its_types() {
+	// given
+	let tester = eth_signing();
+	let address = Address::random();
+
+	// when
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_signTypedData",
+		"params": [
+			""#.to_owned() + format!("0x{:x}", address).as_ref() + r#"",
+			"# + &TYPED_DATA.replace("\"chainId\": 1", "\"chainId\": true") + r#"
+		],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: Typed data","data":"\"Invalid value of type uint256: true\""},"id":1}"#;
+
+	// then
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.to_owned()));
+	assert_eq!(tester.signer.requests().len(), 0);
+}
+
 #[test]
 fn should_add_transaction_to_queue() {
 	// given
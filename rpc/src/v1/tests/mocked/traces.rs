@@ -25,7 +25,7 @@ use vm::CallType;
 
 use jsonrpc_core::IoHandler;
 use v1::tests::helpers::{TestMinerService};
-use v1::{Metadata, Traces, TracesClient};
+use v1::{Metadata, TracesCall, TracesClient};
 
 struct Tester {
 	client: Arc<TestBlockChainClient>,
@@ -64,11 +64,12 @@ fn io() -> Tester {
 		trace: vec![],
 		vm_trace: None,
 		state_diff: None,
+		vm_profile: None,
 	}));
 	let miner = Arc::new(TestMinerService::default());
 	let traces = TracesClient::new(&client);
 	let mut io = IoHandler::default();
-	io.extend_with(traces.to_delegate());
+	io.extend_with(traces.to_call_delegate());
 
 	Tester {
 		client: client,
@@ -0,0 +1,53 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Clef-compatible external signer rpc interface.
+
+use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_macros::Trailing;
+
+use v1::types::{Bytes, H160, H520, TransactionRequest, RichRawTransaction, TypedData};
+
+build_rpc_trait! {
+	/// Signing methods of standalone signers, compatible with Clef.
+	/// Requests of locked accounts have to be confirmed in the Trusted Signer.
+	pub trait Account {
+		type Metadata;
+
+		/// Returns the version of the signer API.
+		#[rpc(name = "account_version")]
+		fn version(&self) -> Result<String>;
+
+		/// Lists accounts available for signing, visible to the requesting dapp.
+		#[rpc(meta, name = "account_list")]
+		fn list(&self, Self::Metadata) -> Result<Vec<H160>>;
+
+		/// Signs a transaction without dispatching it to the network.
+		/// Nonce, gas and gas price can't be omitted without a chain.
+		/// The optional method signature is accepted for compatibility and ignored.
+		#[rpc(meta, name = "account_signTransaction")]
+		fn sign_transaction(&self, Self::Metadata, TransactionRequest, Trailing<String>) -> BoxFuture<RichRawTransaction>;
+
+		/// Signs data of given content type with the account.
+		/// Only `text/plain` is supported, signed as `eth_sign` does.
+		#[rpc(meta, name = "account_signData")]
+		fn sign_data(&self, Self::Metadata, String, H160, Bytes) -> BoxFuture<H520>;
+
+		/// Signs typed structured data (EIP-712) with the account.
+		#[rpc(meta, name = "account_signTypedData")]
+		fn sign_typed_data(&self, Self::Metadata, H160, TypedData) -> BoxFuture<H520>;
+	}
+}
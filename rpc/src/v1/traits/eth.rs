@@ -15,10 +15,12 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 //! Eth rpc interface.
-use jsonrpc_core::{Result, BoxFuture};
-use jsonrpc_macros::Trailing;
+use jsonrpc_core::{Result, BoxFuture, Value};
+use jsonrpc_core::futures::{future, Future};
+use jsonrpc_macros::{IoDelegate, Trailing};
 
-use v1::types::{RichBlock, BlockNumber, Bytes, CallRequest, Filter, FilterChanges, Index};
+use v1::helpers::call_params;
+use v1::types::{RichBlock, BlockNumber, Bytes, CallRequest, Filter, FilterChanges, Index, StateOverride};
 use v1::types::{Log, Receipt, SyncStatus, Transaction, Work};
 use v1::types::{H64, H160, H256, U256};
 
@@ -107,14 +109,6 @@ build_rpc_trait! {
 		#[rpc(name = "eth_submitTransaction")]
 		fn submit_transaction(&self, Bytes) -> Result<H256>;
 
-		/// Call contract, returning the output data.
-		#[rpc(meta, name = "eth_call")]
-		fn call(&self, Self::Metadata, CallRequest, Trailing<BlockNumber>) -> BoxFuture<Bytes>;
-
-		/// Estimate gas needed for execution of given contract.
-		#[rpc(meta, name = "eth_estimateGas")]
-		fn estimate_gas(&self, Self::Metadata, CallRequest, Trailing<BlockNumber>) -> BoxFuture<U256>;
-
 		/// Get transaction by its hash.
 		#[rpc(name = "eth_getTransactionByHash")]
 		fn transaction_by_hash(&self, H256) -> BoxFuture<Option<Transaction>>;
@@ -177,6 +171,42 @@ build_rpc_trait! {
 	}
 }
 
+/// Eth rpc methods executing calls on top of state overrides.
+///
+/// They take an optional block number followed by optional state overrides and are
+/// registered by `to_call_delegate`, since `build_rpc_trait!` supports one optional parameter only.
+pub trait EthCall: Eth {
+	/// Call contract, returning the output data.
+	fn call(&self, Self::Metadata, CallRequest, BlockNumber, StateOverride) -> BoxFuture<Bytes>;
+
+	/// Estimate gas needed for execution of given contract.
+	fn estimate_gas(&self, Self::Metadata, CallRequest, BlockNumber, StateOverride) -> BoxFuture<U256>;
+
+	/// Transform into an `IoDelegate` handling `Eth` methods as well as `eth_call` and `eth_estimateGas`.
+	fn to_call_delegate(self) -> IoDelegate<Self, Self::Metadata> {
+		let mut delegate = Eth::to_delegate(self);
+		delegate.add_method_with_meta("eth_call", |eth, params, meta| {
+			let params = call_params::split(params, 1)
+				.and_then(|(params, num, overrides)| params.parse::<(CallRequest, )>().map(|(request, )| (request, num, overrides)));
+			match params {
+				Ok((request, num, overrides)) => Box::new(EthCall::call(eth, meta, request, num, overrides)
+					.and_then(call_params::to_value)) as BoxFuture<Value>,
+				Err(e) => Box::new(future::err(e)),
+			}
+		});
+		delegate.add_method_with_meta("eth_estimateGas", |eth, params, meta| {
+			let params = call_params::split(params, 1)
+				.and_then(|(params, num, overrides)| params.parse::<(CallRequest, )>().map(|(request, )| (request, num, overrides)));
+			match params {
+				Ok((request, num, overrides)) => Box::new(EthCall::estimate_gas(eth, meta, request, num, overrides)
+					.and_then(call_params::to_value)) as BoxFuture<Value>,
+				Err(e) => Box::new(future::err(e)),
+			}
+		});
+		delegate
+	}
+}
+
 build_rpc_trait! {
 	/// Eth filters rpc api (polling).
 	// TODO: do filters api properly
@@ -16,6 +16,7 @@
 
 //! Ethereum rpc interfaces.
 
+pub mod account;
 pub mod web3;
 pub mod eth;
 pub mod eth_pubsub;
@@ -33,8 +34,9 @@ pub mod rpc;
 pub mod secretstore;
 pub mod private;
 
+pub use self::account::Account;
 pub use self::web3::Web3;
-pub use self::eth::{Eth, EthFilter};
+pub use self::eth::{Eth, EthCall, EthFilter};
 pub use self::eth_pubsub::EthPubSub;
 pub use self::eth_signing::EthSigning;
 pub use self::net::Net;
@@ -45,7 +47,7 @@ pub use self::parity_signing::ParitySigning;
 pub use self::personal::Personal;
 pub use self::pubsub::PubSub;
 pub use self::signer::Signer;
-pub use self::traces::Traces;
+pub use self::traces::{Traces, TracesCall};
 pub use self::rpc::Rpc;
 pub use self::secretstore::SecretStore;
 pub use self::private::Private;
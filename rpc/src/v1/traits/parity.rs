@@ -24,8 +24,9 @@ use jsonrpc_macros::Trailing;
 use node_health::Health;
 use v1::types::{
 	H160, H256, H512, U256, U64, Bytes, CallRequest,
+	SimulationResults, StateOverride,
 	Peers, Transaction, RpcSettings, Histogram,
-	TransactionStats, LocalTransactionStatus,
+	TransactionStats, LocalTransactionStatus, ProfitStats,
 	BlockNumber, ConsensusCapability, VersionInfo,
 	OperationsInfo, DappId, ChainStatus,
 	AccountInfo, HwAccountInfo, RichHeader,
@@ -161,6 +162,10 @@ build_rpc_trait! {
 		#[rpc(name = "parity_localTransactions")]
 		fn local_transactions(&self) -> Result<BTreeMap<H256, LocalTransactionStatus>>;
 
+		/// Returns fees of blocks sealed with the profit-based block building strategy.
+		#[rpc(name = "parity_profitStats")]
+		fn profit_stats(&self) -> Result<ProfitStats>;
+
 		/// Returns current Dapps Server interface and port or an error if dapps server is disabled.
 		#[rpc(name = "parity_dappsUrl")]
 		fn dapps_url(&self) -> Result<String>;
@@ -224,6 +229,12 @@ build_rpc_trait! {
 		#[rpc(meta, name = "parity_call")]
 		fn call(&self, Self::Metadata, Vec<CallRequest>, Trailing<BlockNumber>) -> Result<Vec<Bytes>>;
 
+		/// Executes a sequence of dependent calls on top of given block (or the pending block)
+		/// with optional account overrides, without persisting any changes.
+		/// Returns output, gas used and logs of every call along with the combined state diff.
+		#[rpc(meta, name = "parity_simulateCalls")]
+		fn simulate_calls(&self, Self::Metadata, Vec<CallRequest>, BlockNumber, Trailing<StateOverride>) -> Result<SimulationResults>;
+
 		/// Returns node's health report.
 		#[rpc(name = "parity_nodeHealth")]
 		fn node_health(&self) -> BoxFuture<Health>;
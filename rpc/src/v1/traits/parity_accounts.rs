@@ -18,12 +18,15 @@
 use std::collections::BTreeMap;
 
 use jsonrpc_core::Result;
+use jsonrpc_macros::Trailing;
 use ethstore::KeyFile;
-use v1::types::{H160, H256, H520, DappId, DeriveHash, DeriveHierarchical, ExtAccountInfo};
+use v1::types::{H160, H256, H520, AuditEntry, DappId, DeriveHash, DeriveHierarchical, ExtAccountInfo, SpendingLimit};
 
 build_rpc_trait! {
 	/// Personal Parity rpc interface.
 	pub trait ParityAccounts {
+		type Metadata;
+
 		/// Returns accounts information.
 		#[rpc(name = "parity_allAccountsInfo")]
 		fn all_accounts_info(&self) -> Result<BTreeMap<H160, ExtAccountInfo>>;
@@ -33,6 +36,11 @@ build_rpc_trait! {
 		#[rpc(name = "parity_newAccountFromPhrase")]
 		fn new_account_from_phrase(&self, String, String) -> Result<H160>;
 
+		/// Creates accounts derived from the given BIP-39 mnemonic along BIP-44 paths `m/44'/60'/0'/0/i`.
+		/// Arguments: `mnemonic`, `passphrase` (may be empty), number of accounts (at most 100) and password for the new accounts.
+		#[rpc(name = "parity_newAccountsFromMnemonic")]
+		fn new_accounts_from_mnemonic(&self, String, String, u32, String) -> Result<Vec<H160>>;
+
 		/// Creates new account from the given JSON wallet.
 		/// Second parameter is password for the wallet and the new account.
 		#[rpc(name = "parity_newAccountFromWallet")]
@@ -50,13 +58,29 @@ build_rpc_trait! {
 
 		/// Changes an account's password.
 		/// Arguments: `account`, `password`, `new_password`.
-		#[rpc(name = "parity_changePassword")]
-		fn change_password(&self, H160, String, String) -> Result<bool>;
+		#[rpc(meta, name = "parity_changePassword")]
+		fn change_password(&self, Self::Metadata, H160, String, String) -> Result<bool>;
+
+		/// Encrypts an account again using scrypt with strong parameters.
+		/// Arguments: `account`, `password`.
+		#[rpc(meta, name = "parity_reencryptAccount")]
+		fn reencrypt_account(&self, Self::Metadata, H160, String) -> Result<bool>;
 
 		/// Permanently deletes an account.
 		/// Arguments: `account`, `password`.
-		#[rpc(name = "parity_killAccount")]
-		fn kill_account(&self, H160, String) -> Result<bool>;
+		#[rpc(meta, name = "parity_killAccount")]
+		fn kill_account(&self, Self::Metadata, H160, String) -> Result<bool>;
+
+		/// Adds an account controlled by a Gnosis Safe contract deployed at given address.
+		/// Its transactions are confirmed in the signer once by each of `threshold` owners.
+		/// Arguments: `address`, `owners` (local accounts), `threshold`, `name`.
+		#[rpc(name = "parity_newPolicyAccount")]
+		fn new_policy_account(&self, H160, Vec<H160>, u64, String) -> Result<bool>;
+
+		/// Removes a policy account. The Safe contract and owner accounts are not affected.
+		/// Arguments: `address`.
+		#[rpc(name = "parity_removePolicyAccount")]
+		fn remove_policy_account(&self, H160) -> Result<bool>;
 
 		/// Permanently deletes an address from the addressbook
 		/// Arguments: `address`
@@ -68,8 +92,30 @@ build_rpc_trait! {
 		fn set_account_name(&self, H160, String) -> Result<bool>;
 
 		/// Set an account's metadata string.
+		/// Adding, changing or removing its TOTP secret requires the current TOTP code or the account password.
 		#[rpc(name = "parity_setAccountMeta")]
-		fn set_account_meta(&self, H160, String) -> Result<bool>;
+		fn set_account_meta(&self, H160, String, Trailing<String>) -> Result<bool>;
+
+		/// Restricts transactions sent from an account while it's unlocked.
+		/// Transactions confirmed with a password are not affected.
+		/// Arguments: `account`, `limit`.
+		#[rpc(name = "parity_setSpendingLimit")]
+		fn set_spending_limit(&self, H160, SpendingLimit) -> Result<bool>;
+
+		/// Removes restrictions of transactions sent from an account.
+		/// Arguments: `account`.
+		#[rpc(name = "parity_removeSpendingLimit")]
+		fn remove_spending_limit(&self, H160) -> Result<bool>;
+
+		/// Returns spending limits of all restricted accounts.
+		#[rpc(name = "parity_spendingLimits")]
+		fn spending_limits(&self) -> Result<BTreeMap<H160, SpendingLimit>>;
+
+		/// Returns the most recent entries of the audit log of account operations, starting with the latest one.
+		/// Requires `--accounts-audit-log`.
+		/// Arguments: `limit`, `account` (optional).
+		#[rpc(name = "parity_auditLog")]
+		fn audit_log(&self, u64, Trailing<H160>) -> Result<Vec<AuditEntry>>;
 
 		/// Sets addresses exposed for particular dapp.
 		/// Setting a non-empty list will also override default account.
@@ -131,16 +177,16 @@ build_rpc_trait! {
 		fn geth_accounts(&self) -> Result<Vec<H160>>;
 
 		/// Create new vault.
-		#[rpc(name = "parity_newVault")]
-		fn create_vault(&self, String, String) -> Result<bool>;
+		#[rpc(meta, name = "parity_newVault")]
+		fn create_vault(&self, Self::Metadata, String, String) -> Result<bool>;
 
 		/// Open existing vault.
-		#[rpc(name = "parity_openVault")]
-		fn open_vault(&self, String, String) -> Result<bool>;
+		#[rpc(meta, name = "parity_openVault")]
+		fn open_vault(&self, Self::Metadata, String, String) -> Result<bool>;
 
 		/// Close previously opened vault.
-		#[rpc(name = "parity_closeVault")]
-		fn close_vault(&self, String) -> Result<bool>;
+		#[rpc(meta, name = "parity_closeVault")]
+		fn close_vault(&self, Self::Metadata, String) -> Result<bool>;
 
 		/// List all vaults.
 		#[rpc(name = "parity_listVaults")]
@@ -151,12 +197,16 @@ build_rpc_trait! {
 		fn list_opened_vaults(&self) -> Result<Vec<String>>;
 
 		/// Change vault password.
-		#[rpc(name = "parity_changeVaultPassword")]
-		fn change_vault_password(&self, String, String) -> Result<bool>;
+		#[rpc(meta, name = "parity_changeVaultPassword")]
+		fn change_vault_password(&self, Self::Metadata, String, String) -> Result<bool>;
+
+		/// Encrypt vault file and metadata of vault accounts again using scrypt with strong parameters.
+		#[rpc(meta, name = "parity_reencryptVault")]
+		fn reencrypt_vault(&self, Self::Metadata, String) -> Result<bool>;
 
 		/// Change vault of the given address.
-		#[rpc(name = "parity_changeVault")]
-		fn change_vault(&self, H160, String) -> Result<bool>;
+		#[rpc(meta, name = "parity_changeVault")]
+		fn change_vault(&self, Self::Metadata, H160, String) -> Result<bool>;
 
 		/// Get vault metadata string.
 		#[rpc(name = "parity_getVaultMeta")]
@@ -178,12 +228,14 @@ build_rpc_trait! {
 		fn derive_key_index(&self, H160, String, DeriveHierarchical, bool) -> Result<H160>;
 
 		/// Exports an account with given address if provided password matches.
-		#[rpc(name = "parity_exportAccount")]
-		fn export_account(&self, H160, String) -> Result<KeyFile>;
+		#[rpc(meta, name = "parity_exportAccount")]
+		fn export_account(&self, Self::Metadata, H160, String) -> Result<KeyFile>;
 
 		/// Sign raw hash with the key corresponding to address and password.
-		#[rpc(name = "parity_signMessage")]
-		fn sign_message(&self, H160, String, H256) -> Result<H520>;
+		/// Hardware wallets can't sign raw hashes, so for them the password is ignored and the signature
+		/// is the one of `\x19Ethereum Signed Message:\n32` followed by the hash, as `eth_sign` of the hash would give.
+		#[rpc(meta, name = "parity_signMessage")]
+		fn sign_message(&self, Self::Metadata, H160, String, H256) -> Result<H520>;
 
 		/// Send a PinMatrixAck to a hardware wallet, unlocking it
 		#[rpc(name = "parity_hardwarePinMatrixAck")]
@@ -17,8 +17,9 @@
 //! Parity-specific rpc interface for operations altering the settings.
 
 use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_macros::Trailing;
 
-use v1::types::{Bytes, H160, H256, U256, ReleaseInfo, Transaction, LocalDapp};
+use v1::types::{Bytes, H160, H256, U256, U64, ReleaseInfo, Transaction, LocalDapp};
 
 build_rpc_trait! {
 	/// Parity-specific rpc interface for operations altering the settings.
@@ -119,5 +120,13 @@ build_rpc_trait! {
 		/// Returns `true` when transaction was removed, `false` if it was not found.
 		#[rpc(name = "parity_removeTransaction")]
 		fn remove_transaction(&self, H256) -> Result<Option<Transaction>>;
+
+		/// Submits a bundle of signed transactions to be included back-to-back at the top of a block.
+		/// The bundle is included in one of the blocks up to the given maximal block
+		/// (starting from the optional minimal block) only if every transaction succeeds.
+		/// Bundles are not propagated and don't enter the transaction queue.
+		/// Returns the hash of the bundle.
+		#[rpc(name = "parity_sendBundle")]
+		fn send_bundle(&self, Vec<Bytes>, U64, Trailing<U64>) -> Result<H256>;
 	}
 }
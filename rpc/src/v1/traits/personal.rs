@@ -17,7 +17,7 @@
 //! Personal rpc interface.
 use jsonrpc_core::{BoxFuture, Result};
 
-use v1::types::{Bytes, U128, H160, H256, H520, TransactionRequest, RichRawTransaction as RpcRichRawTransaction};
+use v1::types::{Bytes, U128, H160, H256, H520, TransactionRequest, TypedData, RichRawTransaction as RpcRichRawTransaction};
 
 build_rpc_trait! {
 	/// Personal rpc interface. Safe (read-only) functions.
@@ -34,13 +34,18 @@ build_rpc_trait! {
 		fn new_account(&self, String) -> Result<H160>;
 
 		/// Unlocks specified account for use (can only be one unlocked account at one moment)
-		#[rpc(name = "personal_unlockAccount")]
-		fn unlock_account(&self, H160, String, Option<U128>) -> Result<bool>;
+		#[rpc(meta, name = "personal_unlockAccount")]
+		fn unlock_account(&self, Self::Metadata, H160, String, Option<U128>) -> Result<bool>;
 
 		/// Signs the hash of data with given account signature using the given password to unlock the account during
 		/// the request.
-		#[rpc(name = "personal_sign")]
-		fn sign(&self, Bytes, H160, String) -> BoxFuture<H520>;
+		#[rpc(meta, name = "personal_sign")]
+		fn sign(&self, Self::Metadata, Bytes, H160, String) -> BoxFuture<H520>;
+
+		/// Signs typed structured data (EIP-712) with given account using the given password to unlock the account
+		/// during the request.
+		#[rpc(meta, name = "personal_signTypedData")]
+		fn sign_typed_data(&self, Self::Metadata, TypedData, H160, String) -> BoxFuture<H520>;
 
 		/// Returns the account associated with the private key that was used to calculate the signature in
 		/// `personal_sign`.
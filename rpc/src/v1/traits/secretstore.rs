@@ -24,6 +24,8 @@ use v1::types::{H160, H256, H512, Bytes, EncryptedDocumentKey};
 build_rpc_trait! {
 	/// Parity-specific rpc interface.
 	pub trait SecretStore {
+		type Metadata;
+
 		/// Generate document key to store in secret store.
 		/// Arguments: `account`, `password`, `server_key_public`.
 		#[rpc(name = "secretstore_generateDocumentKey")]
@@ -31,18 +33,18 @@ build_rpc_trait! {
 
 		/// Encrypt data with key, received from secret store.
 		/// Arguments: `account`, `password`, `key`, `data`.
-		#[rpc(name = "secretstore_encrypt")]
-		fn encrypt(&self, H160, String, Bytes, Bytes) -> Result<Bytes>;
+		#[rpc(meta, name = "secretstore_encrypt")]
+		fn encrypt(&self, Self::Metadata, H160, String, Bytes, Bytes) -> Result<Bytes>;
 
 		/// Decrypt data with key, received from secret store.
 		/// Arguments: `account`, `password`, `key`, `data`.
-		#[rpc(name = "secretstore_decrypt")]
-		fn decrypt(&self, H160, String, Bytes, Bytes) -> Result<Bytes>;
+		#[rpc(meta, name = "secretstore_decrypt")]
+		fn decrypt(&self, Self::Metadata, H160, String, Bytes, Bytes) -> Result<Bytes>;
 
 		/// Decrypt data with shadow key, received from secret store.
 		/// Arguments: `account`, `password`, `decrypted_secret`, `common_point`, `decrypt_shadows`, `data`.
-		#[rpc(name = "secretstore_shadowDecrypt")]
-		fn shadow_decrypt(&self, H160, String, H512, H512, Vec<Bytes>, Bytes) -> Result<Bytes>;
+		#[rpc(meta, name = "secretstore_shadowDecrypt")]
+		fn shadow_decrypt(&self, Self::Metadata, H160, String, H512, H512, Vec<Bytes>, Bytes) -> Result<Bytes>;
 
 		/// Calculates the hash (keccak256) of servers set for using in ServersSetChange session.
 		/// Returned hash must be signed later by using `secretstore_signRawHash` method.
@@ -53,7 +55,7 @@ build_rpc_trait! {
 		/// Generate recoverable ECDSA signature of raw hash.
 		/// Passed hash is treated as an input to the `sign` function (no prefixes added, no hash function is applied).
 		/// Arguments: `account`, `password`, `raw_hash`.
-		#[rpc(name = "secretstore_signRawHash")]
-		fn sign_raw_hash(&self, H160, String, H256) -> Result<Bytes>;
+		#[rpc(meta, name = "secretstore_signRawHash")]
+		fn sign_raw_hash(&self, Self::Metadata, H160, String, H256) -> Result<Bytes>;
 	}
 }
@@ -17,6 +17,7 @@
 //! Parity Signer-related rpc interface.
 use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_pubsub::SubscriptionId;
+use jsonrpc_macros::Trailing;
 use jsonrpc_macros::pubsub::Subscriber;
 
 use v1::types::{U256, Bytes, TransactionModification, ConfirmationRequest, ConfirmationResponse, ConfirmationResponseWithToken};
@@ -31,16 +32,17 @@ build_rpc_trait! {
 		fn requests_to_confirm(&self) -> Result<Vec<ConfirmationRequest>>;
 
 		/// Confirm specific request.
+		/// Accounts with a second factor configured require a TOTP code as the last parameter.
 		#[rpc(name = "signer_confirmRequest")]
-		fn confirm_request(&self, U256, TransactionModification, String) -> BoxFuture<ConfirmationResponse>;
+		fn confirm_request(&self, U256, TransactionModification, String, Trailing<String>) -> BoxFuture<ConfirmationResponse>;
 
 		/// Confirm specific request with token.
 		#[rpc(name = "signer_confirmRequestWithToken")]
-		fn confirm_request_with_token(&self, U256, TransactionModification, String) -> BoxFuture<ConfirmationResponseWithToken>;
+		fn confirm_request_with_token(&self, U256, TransactionModification, String, Trailing<String>) -> BoxFuture<ConfirmationResponseWithToken>;
 
 		/// Confirm specific request with already signed data.
 		#[rpc(name = "signer_confirmRequestRaw")]
-		fn confirm_request_raw(&self, U256, Bytes) -> Result<ConfirmationResponse>;
+		fn confirm_request_raw(&self, U256, Bytes, Trailing<String>) -> Result<ConfirmationResponse>;
 
 		/// Reject the confirmation request.
 		#[rpc(name = "signer_rejectRequest")]
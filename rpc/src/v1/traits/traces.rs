@@ -16,9 +16,11 @@
 
 //! Traces specific rpc interface.
 
-use jsonrpc_core::Result;
-use jsonrpc_macros::Trailing;
-use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, H256, TraceOptions};
+use jsonrpc_core::{Result, BoxFuture, Value};
+use jsonrpc_core::futures::future;
+use jsonrpc_macros::{IoDelegate, Trailing};
+use v1::helpers::call_params;
+use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, H256, TraceOptions, StateOverride};
 
 build_rpc_trait! {
 	/// Traces specific rpc interface.
@@ -41,10 +43,6 @@ build_rpc_trait! {
 		#[rpc(name = "trace_block")]
 		fn block_traces(&self, BlockNumber) -> Result<Option<Vec<LocalizedTrace>>>;
 
-		/// Executes the given call and returns a number of possible traces for it.
-		#[rpc(meta, name = "trace_call")]
-		fn call(&self, Self::Metadata, CallRequest, TraceOptions, Trailing<BlockNumber>) -> Result<TraceResults>;
-
 		/// Executes all given calls and returns a number of possible traces for each of it.
 		#[rpc(meta, name = "trace_callMany")]
 		fn call_many(&self, Self::Metadata, Vec<(CallRequest, TraceOptions)>, Trailing<BlockNumber>) -> Result<Vec<TraceResults>>;
@@ -62,3 +60,25 @@ build_rpc_trait! {
 		fn replay_block_transactions(&self, BlockNumber, TraceOptions) ->  Result<Vec<TraceResults>>;
 	}
 }
+
+/// Traces rpc methods executing calls on top of state overrides.
+///
+/// They take an optional block number followed by optional state overrides and are
+/// registered by `to_call_delegate`, since `build_rpc_trait!` supports one optional parameter only.
+pub trait TracesCall: Traces {
+	/// Executes the given call and returns a number of possible traces for it.
+	fn call(&self, Self::Metadata, CallRequest, TraceOptions, BlockNumber, StateOverride) -> Result<TraceResults>;
+
+	/// Transform into an `IoDelegate` handling `Traces` methods as well as `trace_call`.
+	fn to_call_delegate(self) -> IoDelegate<Self, Self::Metadata> {
+		let mut delegate = Traces::to_delegate(self);
+		delegate.add_method_with_meta("trace_call", |traces, params, meta| {
+			let result = call_params::split(params, 2)
+				.and_then(|(params, num, overrides)| params.parse::<(CallRequest, TraceOptions)>().map(|(request, options)| (request, options, num, overrides)))
+				.and_then(|(request, options, num, overrides)| TracesCall::call(traces, meta, request, options, num, overrides))
+				.and_then(call_params::to_value);
+			Box::new(future::done(result)) as BoxFuture<Value>
+		});
+		delegate
+	}
+}
@@ -31,6 +31,18 @@ pub struct ExtAccountInfo {
 	/// Account UUID (`None` for address book entries)
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub uuid: Option<String>,
+	/// Owners of a policy account
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub policy: Option<PolicyAccountInfo>,
+}
+
+/// Policy account information.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct PolicyAccountInfo {
+	/// Local accounts owning the policy account
+	pub owners: Vec<H160>,
+	/// Number of owners required to approve a transaction
+	pub threshold: u64,
 }
 
 /// Hardware wallet information.
@@ -0,0 +1,54 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Audit log of account operations.
+
+use ethcore::account_provider::AuditEntry as EthAuditEntry;
+use v1::types::{H160, H256};
+
+/// Entry of the audit log of account operations.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+	/// Unix timestamp of the operation
+	pub timestamp: u64,
+	/// Performed operation
+	pub operation: String,
+	/// Account the operation was performed with
+	pub account: Option<H160>,
+	/// Vault the operation was performed with
+	pub vault: Option<String>,
+	/// Origin of the request, `null` for operations of the node itself
+	pub origin: Option<String>,
+	/// Hash of the signed or decrypted message
+	pub hash: Option<H256>,
+	/// Whether the operation succeeded
+	pub success: bool,
+}
+
+impl From<EthAuditEntry> for AuditEntry {
+	fn from(e: EthAuditEntry) -> Self {
+		AuditEntry {
+			timestamp: e.timestamp,
+			operation: e.operation.to_string(),
+			account: e.account.map(Into::into),
+			vault: e.vault,
+			origin: e.origin,
+			hash: e.hash.map(Into::into),
+			success: e.success,
+		}
+	}
+}
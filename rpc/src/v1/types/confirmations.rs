@@ -21,7 +21,7 @@ use serde::{Serialize, Serializer};
 use ansi_term::Colour;
 use bytes::ToPretty;
 
-use v1::types::{U256, TransactionRequest, RichRawTransaction, H160, H256, H520, Bytes, TransactionCondition, Origin};
+use v1::types::{U256, TransactionRequest, RichRawTransaction, H160, H256, H520, Bytes, TransactionCondition, Origin, TypedData};
 use v1::helpers;
 
 /// Confirmation waiting in a queue
@@ -58,6 +58,7 @@ impl fmt::Display for ConfirmationPayload {
 			ConfirmationPayload::SendTransaction(ref transaction) => write!(f, "{}", transaction),
 			ConfirmationPayload::SignTransaction(ref transaction) => write!(f, "(Sign only) {}", transaction),
 			ConfirmationPayload::EthSignMessage(ref sign) => write!(f, "{}", sign),
+			ConfirmationPayload::SignTypedData(ref sign) => write!(f, "{}", sign),
 			ConfirmationPayload::Decrypt(ref decrypt) => write!(f, "{}", decrypt),
 		}
 	}
@@ -93,6 +94,37 @@ impl fmt::Display for SignRequest {
 	}
 }
 
+/// Typed structured data sign request (EIP-712)
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SignTypedDataRequest {
+	/// Address
+	pub address: H160,
+	/// Typed data to sign
+	pub data: TypedData,
+}
+
+impl From<(H160, TypedData)> for SignTypedDataRequest {
+	fn from(tuple: (H160, TypedData)) -> Self {
+		SignTypedDataRequest {
+			address: tuple.0,
+			data: tuple.1,
+		}
+	}
+}
+
+impl fmt::Display for SignTypedDataRequest {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"sign typed data {} {} with {}",
+			self.data.primary_type,
+			self.data.message,
+			Colour::White.bold().paint(format!("0x{:?}", self.address)),
+		)
+	}
+}
+
 /// Decrypt request
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -133,6 +165,17 @@ pub enum ConfirmationResponse {
 	Signature(H520),
 	/// Decrypted data
 	Decrypt(Bytes),
+	/// Transaction of a policy account approved by an owner, waiting for more approvals
+	PolicyApproval(PolicyApproval),
+}
+
+/// Approvals of a policy account transaction
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PolicyApproval {
+	/// Number of owners who approved the transaction
+	pub approvals: u64,
+	/// Number of approvals required
+	pub threshold: u64,
 }
 
 impl Serialize for ConfirmationResponse {
@@ -144,6 +187,7 @@ impl Serialize for ConfirmationResponse {
 			ConfirmationResponse::SignTransaction(ref rlp) => rlp.serialize(serializer),
 			ConfirmationResponse::Signature(ref signature) => signature.serialize(serializer),
 			ConfirmationResponse::Decrypt(ref data) => data.serialize(serializer),
+			ConfirmationResponse::PolicyApproval(ref approval) => approval.serialize(serializer),
 		}
 	}
 }
@@ -170,6 +214,9 @@ pub enum ConfirmationPayload {
 	/// Signature
 	#[serde(rename="sign")]
 	EthSignMessage(SignRequest),
+	/// Typed structured data signature
+	#[serde(rename="signTypedData")]
+	SignTypedData(SignTypedDataRequest),
 	/// Decryption
 	#[serde(rename="decrypt")]
 	Decrypt(DecryptRequest),
@@ -184,6 +231,10 @@ impl From<helpers::ConfirmationPayload> for ConfirmationPayload {
 				address: address.into(),
 				data: data.into(),
 			}),
+			helpers::ConfirmationPayload::SignTypedData(address, data) => ConfirmationPayload::SignTypedData(SignTypedDataRequest {
+				address: address.into(),
+				data,
+			}),
 			helpers::ConfirmationPayload::Decrypt(address, msg) => ConfirmationPayload::Decrypt(DecryptRequest {
 				address: address.into(),
 				msg: msg.into(),
@@ -267,6 +318,24 @@ mod tests {
 		assert_eq!(res.unwrap(), expected.to_owned());
 	}
 
+	#[test]
+	fn should_serialize_sign_typed_data_confirmation() {
+		// given
+		let data: TypedData = serde_json::from_str(r#"{"types":{"EIP712Domain":[]},"primaryType":"EIP712Domain","domain":{},"message":{}}"#).unwrap();
+		let request = helpers::ConfirmationRequest {
+			id: 15.into(),
+			payload: helpers::ConfirmationPayload::SignTypedData(1.into(), data),
+			origin: Origin::Rpc("test service".into()),
+		};
+
+		// when
+		let res = serde_json::to_string(&ConfirmationRequest::from(request));
+		let expected = r#"{"id":"0xf","payload":{"signTypedData":{"address":"0x0000000000000000000000000000000000000001","data":{"types":{"EIP712Domain":[]},"primaryType":"EIP712Domain","domain":{},"message":{}}}},"origin":{"rpc":"test service"}}"#;
+
+		// then
+		assert_eq!(res.unwrap(), expected.to_owned());
+	}
+
 	#[test]
 	fn should_serialize_transaction_confirmation() {
 		// given
@@ -395,4 +464,20 @@ mod tests {
 		// then
 		assert_eq!(res.unwrap(), expected.to_owned());
 	}
+
+	#[test]
+	fn should_serialize_policy_approval() {
+		// given
+		let response = ConfirmationResponse::PolicyApproval(PolicyApproval {
+			approvals: 1,
+			threshold: 2,
+		});
+
+		// when
+		let res = serde_json::to_string(&response);
+		let expected = r#"{"approvals":1,"threshold":2}"#;
+
+		// then
+		assert_eq!(res.unwrap(), expected.to_owned());
+	}
 }
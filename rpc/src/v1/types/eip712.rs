@@ -0,0 +1,82 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! EIP-712 typed structured data.
+
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+use serde_json::{self, Value};
+
+/// Member of a struct type.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MemberType {
+	/// Name of the member
+	pub name: String,
+	/// Type of the member
+	#[serde(rename="type")]
+	pub type_: String,
+}
+
+/// Typed structured data to sign, as described in EIP-712.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TypedData {
+	/// Struct types, including `EIP712Domain`
+	pub types: BTreeMap<String, Vec<MemberType>>,
+	/// Type of the message
+	#[serde(rename="primaryType")]
+	pub primary_type: String,
+	/// Signing domain, an instance of `EIP712Domain`
+	pub domain: Value,
+	/// Message, an instance of `primaryType`
+	pub message: Value,
+}
+
+impl Hash for TypedData {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		// `Value` is not `Hash`, the JSON representation is canonical though.
+		self.types.hash(state);
+		self.primary_type.hash(state);
+		serde_json::to_string(&self.domain).expect("Value serialization is infallible; qed").hash(state);
+		serde_json::to_string(&self.message).expect("Value serialization is infallible; qed").hash(state);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use super::*;
+
+	#[test]
+	fn should_deserialize_typed_data() {
+		let s = r#"{
+			"types": {
+				"EIP712Domain": [{"name": "name", "type": "string"}],
+				"Mail": [{"name": "contents", "type": "string"}]
+			},
+			"primaryType": "Mail",
+			"domain": {"name": "Ether Mail"},
+			"message": {"contents": "Hello, Bob!"}
+		}"#;
+		let deserialized: TypedData = serde_json::from_str(s).unwrap();
+
+		assert_eq!(deserialized.primary_type, "Mail");
+		assert_eq!(deserialized.types["Mail"], vec![MemberType { name: "contents".into(), type_: "string".into() }]);
+		assert_eq!(deserialized.message["contents"], "Hello, Bob!");
+	}
+}
@@ -17,6 +17,7 @@
 //! RPC types
 
 mod account_info;
+mod audit_entry;
 mod block;
 mod block_number;
 mod bytes;
@@ -25,16 +26,21 @@ mod confirmations;
 mod consensus_status;
 mod dapps;
 mod derivation;
+mod eip712;
 mod filter;
 mod hash;
 mod histogram;
 mod index;
 mod log;
 mod node_kind;
+mod profit_stats;
 mod provenance;
 mod receipt;
 mod rpc_settings;
 mod secretstore;
+mod simulation;
+mod spending_limit;
+mod state_override;
 mod sync;
 mod trace;
 mod trace_filter;
@@ -47,35 +53,41 @@ mod private_receipt;
 
 pub mod pubsub;
 
-pub use self::account_info::{AccountInfo, ExtAccountInfo, HwAccountInfo};
+pub use self::account_info::{AccountInfo, ExtAccountInfo, HwAccountInfo, PolicyAccountInfo};
+pub use self::audit_entry::AuditEntry;
 pub use self::bytes::Bytes;
 pub use self::block::{RichBlock, Block, BlockTransactions, Header, RichHeader, Rich};
 pub use self::block_number::{BlockNumber, block_number_to_id};
 pub use self::call_request::CallRequest;
 pub use self::confirmations::{
 	ConfirmationPayload, ConfirmationRequest, ConfirmationResponse, ConfirmationResponseWithToken,
-	TransactionModification, SignRequest, DecryptRequest, Either
+	TransactionModification, SignRequest, SignTypedDataRequest, DecryptRequest, PolicyApproval, Either
 };
 pub use self::consensus_status::*;
 pub use self::dapps::LocalDapp;
 pub use self::derivation::{DeriveHash, DeriveHierarchical, Derive};
+pub use self::eip712::{MemberType, TypedData};
 pub use self::filter::{Filter, FilterChanges};
 pub use self::hash::{H64, H160, H256, H512, H520, H2048};
 pub use self::histogram::Histogram;
 pub use self::index::Index;
 pub use self::log::Log;
 pub use self::node_kind::{NodeKind, Availability, Capability};
+pub use self::profit_stats::ProfitStats;
 pub use self::provenance::{Origin, DappId};
 pub use self::receipt::Receipt;
 pub use self::rpc_settings::RpcSettings;
 pub use self::secretstore::EncryptedDocumentKey;
+pub use self::simulation::{SimulatedCall, SimulationResults};
+pub use self::spending_limit::SpendingLimit;
+pub use self::state_override::{AccountOverride, StateOverride};
 pub use self::sync::{
 	SyncStatus, SyncInfo, Peers, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
 	TransactionStats, ChainStatus, EthProtocolInfo, PipProtocolInfo,
 };
-pub use self::trace::{LocalizedTrace, TraceResults};
+pub use self::trace::{LocalizedTrace, StateDiff, TraceResults};
 pub use self::trace_filter::TraceFilter;
-pub use self::transaction::{Transaction, RichRawTransaction, LocalTransactionStatus};
+pub use self::transaction::{Transaction, RichRawTransaction, LocalTransactionStatus, TransactionPoolEvent};
 pub use self::transaction_request::TransactionRequest;
 pub use self::transaction_condition::TransactionCondition;
 pub use self::uint::{U128, U256, U64};
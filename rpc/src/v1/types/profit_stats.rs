@@ -0,0 +1,43 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use ethcore::miner;
+use v1::types::U256;
+
+/// Fees of blocks sealed with the profit-based block building strategy.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ProfitStats {
+	/// Number of sealed blocks.
+	pub blocks: u64,
+	/// Total fees expected from the sealed blocks.
+	pub profit: U256,
+	/// Total fees expected from the same blocks when filled in pool order.
+	#[serde(rename="baselineProfit")]
+	pub baseline_profit: U256,
+	/// Fees earned over the plain gas price strategy.
+	pub gain: U256,
+}
+
+impl From<miner::ProfitStats> for ProfitStats {
+	fn from(stats: miner::ProfitStats) -> Self {
+		ProfitStats {
+			blocks: stats.blocks,
+			profit: stats.profit.into(),
+			baseline_profit: stats.baseline_profit.into(),
+			gain: stats.gain().into(),
+		}
+	}
+}
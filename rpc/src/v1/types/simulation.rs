@@ -0,0 +1,86 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use ethcore::client::Executed;
+use ethcore::state_diff;
+use v1::types::{Bytes, Log, StateDiff, U256};
+
+/// Result of a single simulated call.
+#[derive(Debug, Serialize)]
+pub struct SimulatedCall {
+	/// Output of the call
+	pub output: Bytes,
+	/// Gas used by the call
+	#[serde(rename="gasUsed")]
+	pub gas_used: U256,
+	/// Logs emitted by the call
+	pub logs: Vec<Log>,
+	/// Error the call failed with, if any
+	pub error: Option<String>,
+}
+
+impl From<Executed> for SimulatedCall {
+	fn from(e: Executed) -> Self {
+		SimulatedCall {
+			output: e.output.into(),
+			gas_used: e.gas_used.into(),
+			logs: e.logs.into_iter().map(Into::into).collect(),
+			error: e.exception.map(|e| e.to_string()),
+		}
+	}
+}
+
+/// Results of a sequence of simulated calls.
+#[derive(Debug, Serialize)]
+pub struct SimulationResults {
+	/// Results of every call, in order
+	pub calls: Vec<SimulatedCall>,
+	/// Combined state changes made by all calls
+	#[serde(rename="stateDiff")]
+	pub state_diff: StateDiff,
+}
+
+impl From<(Vec<Executed>, state_diff::StateDiff)> for SimulationResults {
+	fn from((calls, state_diff): (Vec<Executed>, state_diff::StateDiff)) -> Self {
+		SimulationResults {
+			calls: calls.into_iter().map(Into::into).collect(),
+			state_diff: state_diff.into(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use std::collections::BTreeMap;
+	use ethcore::state_diff;
+	use super::*;
+
+	#[test]
+	fn should_serialize_simulation_results() {
+		let r = SimulationResults {
+			calls: vec![SimulatedCall {
+				output: vec![0x60].into(),
+				gas_used: 21_000.into(),
+				logs: vec![],
+				error: Some("Out of gas".into()),
+			}],
+			state_diff: state_diff::StateDiff { raw: BTreeMap::new() }.into(),
+		};
+		let serialized = serde_json::to_string(&r).unwrap();
+		assert_eq!(serialized, r#"{"calls":[{"output":"0x60","gasUsed":"0x5208","logs":[],"error":"Out of gas"}],"stateDiff":{}}"#);
+	}
+}
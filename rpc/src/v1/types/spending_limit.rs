@@ -0,0 +1,65 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Spending limits of unlocked accounts.
+
+use ethcore::account_provider::SpendingLimit as EthSpendingLimit;
+use v1::types::{Bytes, H160, U256};
+
+/// Restrictions of transactions sent from an unlocked account. Unset fields don't restrict anything.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct SpendingLimit {
+	/// Maximal value of a single transaction
+	pub max_value: Option<U256>,
+	/// Maximal value of all transactions within a day (UTC)
+	pub daily_limit: Option<U256>,
+	/// Addresses the account is allowed to send transactions to
+	pub allowed_recipients: Option<Vec<H160>>,
+	/// 4-byte selectors of the contract methods the account is allowed to call
+	pub allowed_methods: Option<Vec<Bytes>>,
+}
+
+impl From<EthSpendingLimit> for SpendingLimit {
+	fn from(l: EthSpendingLimit) -> Self {
+		SpendingLimit {
+			max_value: l.max_value.map(Into::into),
+			daily_limit: l.daily_limit.map(Into::into),
+			allowed_recipients: l.allowed_recipients.map(|r| r.into_iter().map(Into::into).collect()),
+			allowed_methods: l.allowed_methods.map(|m| m.iter().map(|method| Bytes::new(method.to_vec())).collect()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use super::*;
+
+	#[test]
+	fn should_deserialize_spending_limit() {
+		let s = r#"{"maxValue":"0xde0b6b3a7640000","allowedMethods":["0xa9059cbb"]}"#;
+		let limit: SpendingLimit = serde_json::from_str(s).unwrap();
+
+		assert_eq!(limit, SpendingLimit {
+			max_value: Some(1_000_000_000_000_000_000u64.into()),
+			daily_limit: None,
+			allowed_recipients: None,
+			allowed_methods: Some(vec![Bytes::new(vec![0xa9, 0x05, 0x9c, 0xbb])]),
+		});
+		assert!(serde_json::from_str::<SpendingLimit>(r#"{"maxValu":"0x1"}"#).is_err());
+	}
+}
@@ -0,0 +1,136 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use ethcore::state::{AccountOverride as EthAccountOverride, StateOverride as EthStateOverride};
+use v1::types::{Bytes, H160, H256, U256};
+
+/// Account fields replaced before executing calls.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountOverride {
+	/// Balance
+	pub balance: Option<U256>,
+	/// Nonce
+	pub nonce: Option<U256>,
+	/// Code
+	pub code: Option<Bytes>,
+	/// Storage to replace the whole storage of the account with
+	pub state: Option<BTreeMap<H256, H256>>,
+	/// Storage slots to replace, other slots are left untouched
+	#[serde(rename="stateDiff")]
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+impl AccountOverride {
+	/// Convert into ethcore override. Fails if both `state` and `stateDiff` are given.
+	pub fn into_override(self) -> Option<EthAccountOverride> {
+		let (storage, reset_storage) = match (self.state, self.state_diff) {
+			(Some(_), Some(_)) => return None,
+			(Some(state), None) => (state, true),
+			(None, state_diff) => (state_diff.unwrap_or_default(), false),
+		};
+
+		Some(EthAccountOverride {
+			balance: self.balance.map(Into::into),
+			nonce: self.nonce.map(Into::into),
+			code: self.code.map(Into::into),
+			storage: storage.into_iter().map(|(k, v)| (k.into(), v.into())).collect(),
+			reset_storage,
+		})
+	}
+}
+
+/// Accounts replaced before executing calls.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct StateOverride(BTreeMap<H160, AccountOverride>);
+
+impl StateOverride {
+	/// Convert into ethcore overrides.
+	/// Returns the address of the first account with both `state` and `stateDiff` given as an error.
+	pub fn into_overrides(self) -> Result<EthStateOverride, H160> {
+		self.0.into_iter()
+			.map(|(address, account)| match account.into_override() {
+				Some(account) => Ok((address.into(), account)),
+				None => Err(address),
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use ethcore::state::StateOverride as EthStateOverride;
+	use super::*;
+
+	#[test]
+	fn state_override_deserialize() {
+		let s = r#"{
+			"0x0000000000000000000000000000000000000001": {
+				"balance": "0x10",
+				"nonce": "0x2",
+				"code": "0x6000",
+				"stateDiff": {
+					"0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002"
+				}
+			},
+			"0x0000000000000000000000000000000000000002": {}
+		}"#;
+		let deserialized: StateOverride = serde_json::from_str(s).unwrap();
+		let overrides: EthStateOverride = deserialized.into_overrides().unwrap();
+
+		assert_eq!(overrides.len(), 2);
+		let account = &overrides[&1.into()];
+		assert_eq!(account.balance, Some(16.into()));
+		assert_eq!(account.nonce, Some(2.into()));
+		assert_eq!(account.code, Some(vec![0x60, 0x00]));
+		assert_eq!(account.storage, vec![(1.into(), 2.into())].into_iter().collect());
+		assert!(!account.reset_storage);
+		assert_eq!(overrides[&2.into()], Default::default());
+	}
+
+	#[test]
+	fn state_override_replacing_storage() {
+		let s = r#"{
+			"0x0000000000000000000000000000000000000001": {
+				"state": {
+					"0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002"
+				}
+			}
+		}"#;
+		let deserialized: StateOverride = serde_json::from_str(s).unwrap();
+		let overrides: EthStateOverride = deserialized.into_overrides().unwrap();
+
+		let account = &overrides[&1.into()];
+		assert_eq!(account.storage, vec![(1.into(), 2.into())].into_iter().collect());
+		assert!(account.reset_storage);
+	}
+
+	#[test]
+	fn state_override_rejects_state_with_state_diff() {
+		let s = r#"{"0x0000000000000000000000000000000000000001": {"state": {}, "stateDiff": {}}}"#;
+		let deserialized: StateOverride = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.into_overrides(), Err(1.into()));
+	}
+
+	#[test]
+	fn state_override_rejects_unknown_fields() {
+		let s = r#"{"0x0000000000000000000000000000000000000001": {"storage": {}}}"#;
+		assert!(serde_json::from_str::<StateOverride>(s).is_err());
+	}
+}
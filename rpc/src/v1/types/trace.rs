@@ -605,6 +605,39 @@ impl From<FlatTrace> for Trace {
 	}
 }
 
+#[derive(Debug, Serialize)]
+/// Number of executions and gas consumed by a single instruction.
+pub struct InstructionProfile {
+	/// How many times the instruction was executed.
+	pub count: u64,
+	/// Gas consumed by the instruction, excluding gas consumed by the calls it made.
+	#[serde(rename="gasUsed")]
+	pub gas_used: U256,
+}
+
+impl From<et::InstructionProfile> for InstructionProfile {
+	fn from(p: et::InstructionProfile) -> Self {
+		InstructionProfile {
+			count: p.count,
+			gas_used: p.gas_used.into(),
+		}
+	}
+}
+
+#[derive(Debug, Serialize)]
+/// Instruction coverage and gas profile, keyed by code hash and program counter.
+pub struct VMProfile(BTreeMap<H256, BTreeMap<usize, InstructionProfile>>);
+
+impl From<et::VMProfile> for VMProfile {
+	fn from(p: et::VMProfile) -> Self {
+		VMProfile(p.codes.into_iter()
+			.map(|(code_hash, instructions)| (code_hash.into(), instructions.into_iter()
+				.map(|(pc, profile)| (pc, profile.into()))
+				.collect()))
+			.collect())
+	}
+}
+
 #[derive(Debug, Serialize)]
 /// A diff of some chunk of memory.
 pub struct TraceResults {
@@ -618,6 +651,9 @@ pub struct TraceResults {
 	/// The transaction trace.
 	#[serde(rename="stateDiff")]
 	pub state_diff: Option<StateDiff>,
+	/// The instruction coverage and gas profile.
+	#[serde(rename="vmProfile", skip_serializing_if="Option::is_none")]
+	pub vm_profile: Option<VMProfile>,
 }
 
 impl From<Executed> for TraceResults {
@@ -627,6 +663,7 @@ impl From<Executed> for TraceResults {
 			trace: t.trace.into_iter().map(Into::into).collect(),
 			vm_trace: t.vm_trace.map(Into::into),
 			state_diff: t.state_diff.map(Into::into),
+			vm_profile: t.vm_profile.map(Into::into),
 		}
 	}
 }
@@ -646,11 +683,28 @@ mod tests {
 			trace: vec![],
 			vm_trace: None,
 			state_diff: None,
+			vm_profile: None,
 		};
 		let serialized = serde_json::to_string(&r).unwrap();
 		assert_eq!(serialized, r#"{"output":"0x60","trace":[],"vmTrace":null,"stateDiff":null}"#);
 	}
 
+	#[test]
+	fn should_serialize_vm_profile() {
+		let mut profile = et::VMProfile::default();
+		profile.record(1.into(), 4, 3.into());
+		profile.record(1.into(), 4, 3.into());
+		let r = TraceResults {
+			output: vec![].into(),
+			trace: vec![],
+			vm_trace: None,
+			state_diff: None,
+			vm_profile: Some(profile.into()),
+		};
+		let serialized = serde_json::to_string(&r).unwrap();
+		assert_eq!(serialized, r#"{"output":"0x","trace":[],"vmTrace":null,"stateDiff":null,"vmProfile":{"0x0000000000000000000000000000000000000000000000000000000000000001":{"4":{"count":2,"gasUsed":"0x6"}}}}"#);
+	}
+
 	#[test]
 	fn test_trace_call_serialize() {
 		let t = LocalizedTrace {
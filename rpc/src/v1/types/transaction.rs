@@ -149,6 +149,82 @@ impl Serialize for LocalTransactionStatus {
 	}
 }
 
+/// Transaction pool event
+#[derive(Debug)]
+pub enum TransactionPoolEvent {
+	/// Transaction was added to the pool.
+	Added(Transaction),
+	/// Transaction was replaced by transaction with higher gas price.
+	Replaced(Transaction, U256, H256),
+	/// Transaction was rejected from the pool.
+	Rejected(Transaction, String),
+	/// Transaction was dropped because of limit, optionally pushed out by another transaction.
+	Dropped(Transaction, Option<H256>),
+	/// Transaction is invalid.
+	Invalid(Transaction),
+	/// Transaction was canceled.
+	Canceled(Transaction),
+	/// Transaction was mined or became stale.
+	Mined(Transaction),
+}
+
+impl Serialize for TransactionPoolEvent {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where S: Serializer
+	{
+		use self::TransactionPoolEvent::*;
+
+		let elems = match *self {
+			Added(..) | Invalid(..) | Canceled(..) | Mined(..) | Dropped(_, None) => 2,
+			Rejected(..) | Dropped(_, Some(_)) => 3,
+			Replaced(..) => 4,
+		};
+
+		let status = "status";
+		let transaction = "transaction";
+
+		let mut struc = serializer.serialize_struct("TransactionPoolEvent", elems)?;
+		match *self {
+			Added(ref tx) => {
+				struc.serialize_field(status, "added")?;
+				struc.serialize_field(transaction, tx)?;
+			},
+			Replaced(ref tx, ref gas_price, ref hash) => {
+				struc.serialize_field(status, "replaced")?;
+				struc.serialize_field(transaction, tx)?;
+				struc.serialize_field("hash", hash)?;
+				struc.serialize_field("gasPrice", gas_price)?;
+			},
+			Rejected(ref tx, ref reason) => {
+				struc.serialize_field(status, "rejected")?;
+				struc.serialize_field(transaction, tx)?;
+				struc.serialize_field("error", reason)?;
+			},
+			Dropped(ref tx, ref by) => {
+				struc.serialize_field(status, "dropped")?;
+				struc.serialize_field(transaction, tx)?;
+				if let Some(ref hash) = *by {
+					struc.serialize_field("hash", hash)?;
+				}
+			},
+			Invalid(ref tx) => {
+				struc.serialize_field(status, "invalid")?;
+				struc.serialize_field(transaction, tx)?;
+			},
+			Canceled(ref tx) => {
+				struc.serialize_field(status, "canceled")?;
+				struc.serialize_field(transaction, tx)?;
+			},
+			Mined(ref tx) => {
+				struc.serialize_field(status, "mined")?;
+				struc.serialize_field(transaction, tx)?;
+			},
+		}
+
+		struc.end()
+	}
+}
+
 /// Geth-compatible output for eth_signTransaction method
 #[derive(Debug, Default, Clone, PartialEq, Serialize)]
 pub struct RichRawTransaction {
@@ -270,9 +346,32 @@ impl LocalTransactionStatus {
 	}
 }
 
+impl TransactionPoolEvent {
+	/// Convert pool `TransactionEvent` into RPC `TransactionPoolEvent`.
+	pub fn from(e: miner::pool::TransactionEvent, block_number: u64, eip86_transition: u64) -> Self {
+		let convert = |tx: Arc<miner::pool::VerifiedTransaction>| {
+			Transaction::from_pending(tx.pending().clone(), block_number, eip86_transition)
+		};
+		use miner::pool::TransactionEvent::*;
+		match e {
+			Added(tx) => TransactionPoolEvent::Added(convert(tx)),
+			Replaced { old, new } => TransactionPoolEvent::Replaced(
+				convert(old),
+				new.signed().gas_price.into(),
+				new.signed().hash().into(),
+			),
+			Rejected(tx, reason) => TransactionPoolEvent::Rejected(convert(tx), reason),
+			Dropped(tx, by) => TransactionPoolEvent::Dropped(convert(tx), by.map(Into::into)),
+			Invalid(tx) => TransactionPoolEvent::Invalid(convert(tx)),
+			Canceled(tx) => TransactionPoolEvent::Canceled(convert(tx)),
+			Mined(tx) => TransactionPoolEvent::Mined(convert(tx)),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
-	use super::{Transaction, LocalTransactionStatus};
+	use super::{Transaction, LocalTransactionStatus, TransactionPoolEvent};
 	use serde_json;
 
 	#[test]
@@ -326,4 +425,34 @@ mod tests {
 			r#","hash":"0x000000000000000000000000000000000000000000000000000000000000000a","gasPrice":"0x5"}"#
 		);
 	}
+
+	#[test]
+	fn test_transaction_pool_event_serialize() {
+		let tx_ser = serde_json::to_string(&Transaction::default()).unwrap();
+		let event1 = TransactionPoolEvent::Added(Transaction::default());
+		let event2 = TransactionPoolEvent::Dropped(Transaction::default(), None);
+		let event3 = TransactionPoolEvent::Dropped(Transaction::default(), Some(10.into()));
+		let event4 = TransactionPoolEvent::Replaced(Transaction::default(), 5.into(), 10.into());
+
+		assert_eq!(
+			serde_json::to_string(&event1).unwrap(),
+			r#"{"status":"added","transaction":"#.to_owned() + &format!("{}", tx_ser) + r#"}"#
+		);
+		assert_eq!(
+			serde_json::to_string(&event2).unwrap(),
+			r#"{"status":"dropped","transaction":"#.to_owned() + &format!("{}", tx_ser) + r#"}"#
+		);
+		assert_eq!(
+			serde_json::to_string(&event3).unwrap(),
+			r#"{"status":"dropped","transaction":"#.to_owned() +
+			&format!("{}", tx_ser) +
+			r#","hash":"0x000000000000000000000000000000000000000000000000000000000000000a"}"#
+		);
+		assert_eq!(
+			serde_json::to_string(&event4).unwrap(),
+			r#"{"status":"replaced","transaction":"#.to_owned() +
+			&format!("{}", tx_ser) +
+			r#","hash":"0x000000000000000000000000000000000000000000000000000000000000000a","gasPrice":"0x5"}"#
+		);
+	}
 }
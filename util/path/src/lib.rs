@@ -98,3 +98,22 @@ pub fn restrict_permissions_owner(_file_path: &Path, _write: bool, _executable:
 	//TODO: implement me
 	Ok(())
 }
+
+/// Creates a file (truncating an existing one) readable and writable only by the owner.
+#[cfg(unix)]
+pub fn create_owner_only(file_path: &Path) -> ::std::io::Result<::std::fs::File> {
+	use std::fs::{OpenOptions, Permissions};
+	use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+	let file = OpenOptions::new().write(true).create(true).truncate(true).mode(0o600).open(file_path)?;
+	// the mode is only applied to newly created files
+	file.set_permissions(Permissions::from_mode(0o600))?;
+	Ok(file)
+}
+
+/// Creates a file (truncating an existing one) readable and writable only by the owner.
+#[cfg(not(unix))]
+pub fn create_owner_only(file_path: &Path) -> ::std::io::Result<::std::fs::File> {
+	//TODO: implement me
+	::std::fs::File::create(file_path)
+}